@@ -3,7 +3,7 @@
 use std::hint::black_box;
 
 use criterion::{BenchmarkId, Criterion};
-use saavy_dsp::dsp::delay::DelayLine;
+use saavy_dsp::dsp::delay::{DelayLine, Interpolation};
 
 use crate::BLOCK_SIZES;
 
@@ -62,6 +62,24 @@ pub fn bench_delay(c: &mut Criterion) {
                 })
             },
         );
+
+        // Cubic Hermite read (chorus default) vs linear, same modulation
+        for (name, interpolation) in [("read_linear", Interpolation::Linear), ("read_hermite", Interpolation::Hermite)] {
+            group.bench_with_input(
+                BenchmarkId::new(name, size),
+                &size,
+                |b, _| {
+                    b.iter(|| {
+                        let mut sum = 0.0f32;
+                        for i in 0..size {
+                            let delay_time = 480.0 + (i as f32 * 0.1).sin() * 48.0;
+                            sum += delay.read_with(black_box(delay_time), interpolation);
+                        }
+                        sum
+                    })
+                },
+            );
+        }
     }
 
     group.finish();
@@ -8,6 +8,7 @@ mod filter;
 mod mix;
 mod oscillator;
 mod reverb;
+mod simd;
 
 pub use amplify::bench_amplify;
 pub use delay::bench_delay;
@@ -17,3 +18,4 @@ pub use filter::bench_filter;
 pub use mix::bench_mix;
 pub use oscillator::bench_oscillator;
 pub use reverb::bench_reverb;
+pub use simd::bench_simd;
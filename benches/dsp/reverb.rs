@@ -3,7 +3,7 @@
 use std::hint::black_box;
 
 use criterion::{BenchmarkId, Criterion};
-use saavy_dsp::dsp::reverb::SchroederReverb;
+use saavy_dsp::dsp::{denormal::DenormalGuard, reverb::SchroederReverb};
 
 use crate::BLOCK_SIZES;
 
@@ -67,5 +67,29 @@ pub fn bench_reverb(c: &mut Criterion) {
         });
     }
 
+    // Decaying tail: an impulse followed by minutes of silence, so recursive
+    // state would sit in the denormal range without protection. Compare the
+    // guarded run against the plain one (the anti-denormal offset is always on).
+    let silence = vec![0.0f32; 512];
+    for (name, guarded) in [("tail_silence", false), ("tail_silence_ftz", true)] {
+        let mut reverb = SchroederReverb::new(sample_rate);
+        reverb.set_room_size(0.9);
+        reverb.process(1.0);
+        for _ in 0..(sample_rate as usize * 120) {
+            reverb.process(0.0);
+        }
+
+        group.bench_function(BenchmarkId::new(name, silence.len()), |b| {
+            let _guard = guarded.then(DenormalGuard::new);
+            b.iter(|| {
+                let mut sum = 0.0f32;
+                for &sample in &silence {
+                    sum += reverb.process(black_box(sample));
+                }
+                sum
+            })
+        });
+    }
+
     group.finish();
 }
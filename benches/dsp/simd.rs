@@ -0,0 +1,72 @@
+//! Benchmarks comparing SIMD kernels against the scalar primitives.
+//!
+//! Run with `cargo bench --features simd -- dsp/simd` to compare the SSE
+//! paths; without the feature both sides measure the scalar code.
+
+use std::f32::consts::TAU;
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion};
+use saavy_dsp::dsp::{amplify, distortion, mix, simd};
+
+use crate::BLOCK_SIZES;
+
+pub fn bench_simd(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dsp/simd");
+
+    for &size in BLOCK_SIZES {
+        let signal: Vec<f32> = (0..size).map(|i| (i as f32 * 0.1).sin()).collect();
+        let other: Vec<f32> = (0..size).map(|i| (i as f32 * 0.15).cos()).collect();
+        let mut buffer = signal.clone();
+
+        group.bench_with_input(BenchmarkId::new("gain_scalar", size), &size, |b, _| {
+            b.iter(|| amplify::apply_gain(black_box(&mut buffer), black_box(0.5)))
+        });
+        group.bench_with_input(BenchmarkId::new("gain_simd", size), &size, |b, _| {
+            b.iter(|| simd::apply_gain(black_box(&mut buffer), black_box(0.5)))
+        });
+
+        group.bench_with_input(BenchmarkId::new("mix_scalar", size), &size, |b, _| {
+            b.iter(|| {
+                buffer.copy_from_slice(&signal);
+                mix::mix_in_place(black_box(&mut buffer), black_box(&other), black_box(0.3));
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("mix_simd", size), &size, |b, _| {
+            b.iter(|| {
+                buffer.copy_from_slice(&signal);
+                simd::mix_in_place(black_box(&mut buffer), black_box(&other), black_box(0.3));
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("soft_clip_scalar", size), &size, |b, _| {
+            b.iter(|| {
+                buffer.copy_from_slice(&signal);
+                distortion::soft_clip_buffer(black_box(&mut buffer), black_box(4.0));
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("soft_clip_simd", size), &size, |b, _| {
+            b.iter(|| {
+                buffer.copy_from_slice(&signal);
+                simd::soft_clip_buffer(black_box(&mut buffer), black_box(4.0));
+            })
+        });
+
+        let phase_inc = TAU * 440.0 / 48_000.0;
+        group.bench_with_input(BenchmarkId::new("sawtooth_scalar", size), &size, |b, _| {
+            let mut phase: f32 = 0.0;
+            b.iter(|| {
+                for sample in black_box(&mut buffer).iter_mut() {
+                    *sample = 2.0 * (phase / TAU) - 1.0;
+                    phase = (phase + black_box(phase_inc)).rem_euclid(TAU);
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("sawtooth_simd", size), &size, |b, _| {
+            let mut phase = 0.0;
+            b.iter(|| phase = simd::sawtooth(black_box(&mut buffer), phase, black_box(phase_inc)))
+        });
+    }
+
+    group.finish();
+}
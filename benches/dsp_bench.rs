@@ -13,7 +13,8 @@
 //!
 //! Benchmark groups:
 //!   - dsp/*        Low-level primitives (oscillator, filter, envelope, etc.)
-//!   - scenarios/*  Real-world voice chains and multi-track mixes
+//!   - scenarios/*  Real-world voice chains, multi-track mixes, and worst-case
+//!     voice stress (scenarios/stress)
 
 use criterion::{criterion_group, criterion_main};
 
@@ -34,8 +35,10 @@ criterion_group!(
     dsp::bench_delay,
     dsp::bench_mix,
     dsp::bench_reverb,
+    dsp::bench_simd,
     // Real-world scenarios
     scenarios::bench_voices,
     scenarios::bench_mix,
+    scenarios::bench_stress,
 );
 criterion_main!(benches);
@@ -4,7 +4,9 @@
 //! testing complete voice chains and multi-track rendering.
 
 mod mix;
+mod stress;
 mod voices;
 
 pub use mix::bench_mix;
+pub use stress::bench_stress;
 pub use voices::bench_voices;
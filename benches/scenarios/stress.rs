@@ -0,0 +1,126 @@
+//! Voice stress scenarios measuring worst-case block time.
+//!
+//! Average block time hides the spikes that cause audible dropouts, so these
+//! benchmarks report the SLOWEST block seen in each sample instead of the
+//! mean. Scenarios:
+//!   - 64_voices       64 sustained voices rendered and summed
+//!   - note_storm      every voice retriggered every block
+//!   - steal_heavy     a 16-voice pool receiving 4 new notes per block,
+//!     always stealing (retriggering) the oldest voice
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use criterion::{BenchmarkId, Criterion};
+use saavy_dsp::graph::node::{GraphNode, RenderCtx};
+use saavy_dsp::voices;
+
+use crate::BLOCK_SIZES;
+
+/// Sample rate used for all stress scenarios
+const SAMPLE_RATE: f32 = 48_000.0;
+
+/// Build `count` voices cycling through the melodic and drum presets
+fn voice_pool(count: usize) -> Vec<Box<dyn GraphNode>> {
+    (0..count)
+        .map(|i| -> Box<dyn GraphNode> {
+            match i % 6 {
+                0 => Box::new(voices::lead()),
+                1 => Box::new(voices::pad()),
+                2 => Box::new(voices::pluck()),
+                3 => Box::new(voices::bass()),
+                4 => Box::new(voices::hihat()),
+                _ => Box::new(voices::snare()),
+            }
+        })
+        .collect()
+}
+
+/// Render every voice into `scratch` and sum into `buffer`
+fn render_pool(voices: &mut [Box<dyn GraphNode>], buffer: &mut [f32], scratch: &mut [f32], ctx: &RenderCtx) {
+    buffer.fill(0.0);
+    for voice in voices.iter_mut() {
+        scratch.fill(0.0);
+        voice.render_block(scratch, ctx);
+        for (out, &sample) in buffer.iter_mut().zip(scratch.iter()) {
+            *out += sample;
+        }
+    }
+}
+
+/// Time `iters` blocks and report the worst one as the per-iteration time
+///
+/// Criterion divides the returned duration by `iters`, so returning
+/// `worst * iters` makes the reported figure the worst-case block.
+fn worst_case(iters: u64, mut block: impl FnMut()) -> Duration {
+    let mut worst = Duration::ZERO;
+    for _ in 0..iters {
+        let start = Instant::now();
+        block();
+        worst = worst.max(start.elapsed());
+    }
+    worst.mul_f64(iters as f64)
+}
+
+pub fn bench_stress(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scenarios/stress");
+
+    for &size in BLOCK_SIZES {
+        let mut buffer = vec![0.0f32; size];
+        let mut scratch = vec![0.0f32; size];
+
+        // === 64 SUSTAINED VOICES ===
+        let mut pool = voice_pool(64);
+        for (i, voice) in pool.iter_mut().enumerate() {
+            voice.note_on(&RenderCtx::from_note(SAMPLE_RATE, 36 + i as u8, 100.0));
+        }
+        let ctx = RenderCtx::from_note(SAMPLE_RATE, 60, 100.0);
+
+        group.bench_with_input(BenchmarkId::new("64_voices", size), &size, |b, _| {
+            b.iter_custom(|iters| {
+                worst_case(iters, || {
+                    render_pool(black_box(&mut pool), &mut buffer, &mut scratch, &ctx);
+                })
+            })
+        });
+
+        // === NOTE STORM: retrigger every voice every block ===
+        let mut pool = voice_pool(64);
+        let mut note = 36u8;
+
+        group.bench_with_input(BenchmarkId::new("note_storm", size), &size, |b, _| {
+            b.iter_custom(|iters| {
+                worst_case(iters, || {
+                    for voice in pool.iter_mut() {
+                        note = 36 + (note - 35) % 48;
+                        voice.note_on(&RenderCtx::from_note(SAMPLE_RATE, note, 100.0));
+                    }
+                    render_pool(black_box(&mut pool), &mut buffer, &mut scratch, &ctx);
+                })
+            })
+        });
+
+        // === STEAL-HEAVY: 4 new notes per block into a 16-voice pool ===
+        let mut pool = voice_pool(16);
+        let mut oldest = 0usize;
+        let mut note = 48u8;
+
+        group.bench_with_input(BenchmarkId::new("steal_heavy", size), &size, |b, _| {
+            b.iter_custom(|iters| {
+                worst_case(iters, || {
+                    for _ in 0..4 {
+                        // Steal the oldest voice: release, then retrigger
+                        let voice = &mut pool[oldest];
+                        voice.note_off(&RenderCtx::from_note(SAMPLE_RATE, note, 0.0));
+                        note = 48 + (note - 47) % 24;
+                        voice.note_on(&RenderCtx::from_note(SAMPLE_RATE, note, 100.0));
+                        oldest = (oldest + 1) % 16;
+                    }
+                    render_pool(black_box(&mut pool), &mut buffer, &mut scratch, &ctx);
+                })
+            })
+        });
+    }
+
+    group.finish();
+}
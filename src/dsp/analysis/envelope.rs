@@ -0,0 +1,182 @@
+//! Envelope timing measurement for verifying ADSR stage lengths.
+
+/*
+Measuring an Envelope
+=====================
+
+An ADSR configured as (10ms, 100ms, 0.6, 300ms) should produce exactly
+that shape - but timing bugs hide easily. A stage that only advances
+once per BLOCK instead of once per sample, or a note_off that lands at
+the start of the next block, stretches a 10ms attack to 10.7ms at a
+512-sample block. Nobody hears that in isolation; everybody hears drums
+that feel late.
+
+So we render the envelope (as a control signal or as the gain on a
+constant input) and read the stage boundaries back off the waveform:
+
+  level
+    1 ┤   ●                                 ● = detected boundaries
+      │  ╱ ╲
+    S ┤ ╱   ●────────────────●
+      │╱                      ╲
+    0 ●────────────────────────────●
+      ├───┼─┼────────────────┼─────┤
+       A   D                  R
+      0    peak   settle    gate   silent
+
+  attack    start → first sample at the peak
+  decay     peak  → first sample within `epsilon` of the sustain level
+  sustain   level just before the gate closes
+  release   gate  → first sample at (or below) `epsilon`
+
+Times are counted in whole samples, so a correct envelope measures within
+a sample or two of its settings regardless of block size.
+*/
+
+use crate::graph::{GraphNode, RenderCtx};
+
+/// How close to a target level counts as "arrived"
+const EPSILON: f32 = 1e-4;
+
+/// Stage timings read back from a rendered envelope
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnvelopeTiming {
+    pub attack_secs: f32,
+    pub decay_secs: f32,
+    pub sustain_level: f32,
+    pub release_secs: f32,
+}
+
+impl EnvelopeTiming {
+    /// Measure a rendered envelope whose gate closed at sample `note_off_at`
+    ///
+    /// Returns `None` if the buffer never rises, or never falls silent after the gate.
+    pub fn measure(buffer: &[f32], sample_rate: f32, note_off_at: usize) -> Option<Self> {
+        let held = &buffer[..note_off_at.min(buffer.len())];
+        let peak = held.iter().copied().fold(0.0f32, f32::max);
+        if peak <= EPSILON {
+            return None;
+        }
+
+        let peak_at = held.iter().position(|&x| x >= peak - EPSILON)?;
+        let sustain_level = *held.last()?;
+        let settle_at = held[peak_at..]
+            .iter()
+            .position(|&x| (x - sustain_level).abs() <= EPSILON)
+            .map_or(held.len(), |i| peak_at + i);
+        let silent_at = buffer[note_off_at..].iter().position(|&x| x <= EPSILON)?;
+
+        let secs = |samples: usize| samples as f32 / sample_rate;
+        Some(Self {
+            attack_secs: secs(peak_at + 1),
+            decay_secs: secs(settle_at - peak_at),
+            sustain_level,
+            release_secs: secs(silent_at + 1),
+        })
+    }
+
+    /// Check every stage against configured values
+    ///
+    /// `tolerance_secs` applies to times; the sustain level must match within 1%.
+    pub fn compare(&self, attack: f32, decay: f32, sustain: f32, release: f32, tolerance_secs: f32) -> Result<(), String> {
+        let mut errors = Vec::new();
+        for (name, measured, expected) in [
+            ("attack", self.attack_secs, attack),
+            ("decay", self.decay_secs, decay),
+            ("release", self.release_secs, release),
+        ] {
+            if (measured - expected).abs() > tolerance_secs {
+                errors.push(format!("{name}: measured {:.2}ms, expected {:.2}ms", measured * 1e3, expected * 1e3));
+            }
+        }
+        if (self.sustain_level - sustain).abs() > 0.01 {
+            errors.push(format!("sustain: measured {:.3}, expected {:.3}", self.sustain_level, sustain));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+/// Render an envelope node in `block_size` blocks: gate held for `gate_secs`, then `tail_secs`
+///
+/// Returns the buffer and the sample index where note_off took effect. The
+/// gate closes at the first block boundary at or after `gate_secs`, the
+/// way the runtime delivers it.
+pub fn render_envelope<N: GraphNode>(
+    node: &mut N,
+    sample_rate: f32,
+    block_size: usize,
+    gate_secs: f32,
+    tail_secs: f32,
+) -> (Vec<f32>, usize) {
+    let ctx = RenderCtx::from_freq(sample_rate, 440.0, 1.0);
+    let gate = (gate_secs * sample_rate) as usize;
+    let total = gate + (tail_secs * sample_rate) as usize;
+    let block_size = block_size.max(1);
+
+    let mut out = vec![0.0; total];
+    let mut note_off_at = None;
+    node.prepare(sample_rate, block_size);
+    node.note_on(&ctx);
+    for (i, block) in out.chunks_mut(block_size).enumerate() {
+        let start = i * block_size;
+        if note_off_at.is_none() && start >= gate {
+            node.note_off(&ctx);
+            note_off_at = Some(start);
+        }
+        node.render_block(block, &ctx);
+    }
+    (out, note_off_at.unwrap_or(total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::envelope::EnvNode;
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+    const ONE_SAMPLE: f32 = 1.0 / SAMPLE_RATE;
+
+    #[test]
+    fn measures_configured_stages() {
+        let mut env = EnvNode::adsr(0.01, 0.1, 0.6, 0.3);
+        let (buffer, off) = render_envelope(&mut env, SAMPLE_RATE, 1, 0.5, 0.5);
+        let timing = EnvelopeTiming::measure(&buffer, SAMPLE_RATE, off).unwrap();
+
+        timing.compare(0.01, 0.1, 0.6, 0.3, 2.0 * ONE_SAMPLE).unwrap();
+    }
+
+    #[test]
+    fn timing_is_independent_of_block_size() {
+        for block in [1, 7, 64, 512] {
+            let mut env = EnvNode::adsr(0.005, 0.05, 0.4, 0.2);
+            let (buffer, off) = render_envelope(&mut env, SAMPLE_RATE, block, 0.25, 0.4);
+            let timing = EnvelopeTiming::measure(&buffer, SAMPLE_RATE, off).unwrap();
+
+            if let Err(report) = timing.compare(0.005, 0.05, 0.4, 0.2, 2.0 * ONE_SAMPLE) {
+                panic!("block size {block}: {report}");
+            }
+        }
+    }
+
+    #[test]
+    fn detects_stretched_attack() {
+        // A synthetic envelope whose attack ran one 64-sample block long
+        let attack = 480 + 64;
+        let mut buffer: Vec<f32> = (1..=attack).map(|i| i as f32 / attack as f32).collect();
+        buffer.extend(std::iter::repeat_n(1.0, 1000));
+        buffer.extend(std::iter::repeat_n(0.0, 10));
+        let timing = EnvelopeTiming::measure(&buffer, SAMPLE_RATE, attack + 1000).unwrap();
+
+        assert!(timing.compare(0.01, 0.0, 1.0, 0.0, 2.0 * ONE_SAMPLE).is_err());
+    }
+
+    #[test]
+    fn silent_buffer_has_no_timing() {
+        assert!(EnvelopeTiming::measure(&[0.0; 100], SAMPLE_RATE, 50).is_none());
+    }
+}
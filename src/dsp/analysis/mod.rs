@@ -0,0 +1,23 @@
+//! Offline and visualization analysis tools.
+//!
+//! Unlike the rest of `dsp`, nothing here runs inside the audio callback:
+//! these helpers inspect signals after the fact (UI meters, examples, tests).
+
+/// ADSR stage timing measurement.
+pub mod envelope;
+/// THD, THD+N and aliasing measurement.
+pub mod harmonics;
+/// Null tests and gain-staging assertions for effect nodes.
+pub mod null_test;
+/// Frequency-response measurement by sine probing.
+pub mod response;
+/// Golden-audio fingerprints for snapshot tests.
+pub mod snapshot;
+/// FFT magnitude spectrum with log-spaced display bins.
+#[cfg(feature = "rustfft")]
+pub mod spectrum;
+/// Rendering effect tails until they fall silent.
+pub mod tail;
+
+#[cfg(feature = "rustfft")]
+pub use spectrum::Spectrum;
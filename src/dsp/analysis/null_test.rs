@@ -0,0 +1,133 @@
+//! Null tests and gain-staging checks for effect nodes.
+
+/*
+Null Testing
+============
+
+The oldest trick in audio engineering: to prove two signal paths are the
+same, flip one's polarity and sum them. If they're identical, the result
+is silence - a "null". Any residue is exactly the difference.
+
+We use the digital version: render the same input through an effect and
+compare the output against the input sample by sample.
+
+  mix = 0      A fully dry effect must be TRANSPARENT - bit-identical to
+               its input. Not "close": identical. Otherwise a user who
+               turns an effect off still hears it (a stray gain, a DC
+               offset, a one-sample shift).
+
+  unity        An effect at neutral settings (filter wide open, clip
+               threshold above the signal, wet-only delay without
+               feedback) should keep roughly the same LEVEL. Comparing
+               RMS before and after catches accidental gain staging bugs:
+               a forgotten 0.5 in a mix law, a doubled summing bus.
+
+                 change (dB) = 20 × log10(RMS out / RMS in)
+
+The probe signal is a chord of three sines with different levels - enough
+content to expose phase or filtering mistakes, deterministic, and well
+below full scale so nothing clips.
+*/
+
+use crate::dsp::meter;
+use crate::graph::{GraphNode, RenderCtx};
+use std::f32::consts::TAU;
+
+/// Block size used to render probes
+const PROBE_BLOCK: usize = 256;
+
+/// Deterministic multi-tone probe signal (peak below 0.7)
+pub fn probe_signal(sample_rate: f32, len: usize) -> Vec<f32> {
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / sample_rate;
+            0.3 * (TAU * 220.0 * t).sin() + 0.2 * (TAU * 1_000.0 * t).sin() + 0.1 * (TAU * 3_300.0 * t).sin()
+        })
+        .collect()
+}
+
+/// Render `input` through `node` in blocks, returning the output
+pub fn process<N: GraphNode>(node: &mut N, input: &[f32], sample_rate: f32) -> Vec<f32> {
+    let ctx = RenderCtx::from_freq(sample_rate, 440.0, 1.0);
+    node.prepare(sample_rate, PROBE_BLOCK);
+    node.note_on(&ctx);
+
+    let mut out = input.to_vec();
+    for block in out.chunks_mut(PROBE_BLOCK) {
+        node.render_block(block, &ctx);
+    }
+    out
+}
+
+/// Assert that `node` passes the probe signal through bit-for-bit
+pub fn assert_null<N: GraphNode>(mut node: N, sample_rate: f32) {
+    let input = probe_signal(sample_rate, sample_rate as usize / 2);
+    let output = process(&mut node, &input, sample_rate);
+
+    if let Some(i) = input.iter().zip(&output).position(|(a, b)| a.to_bits() != b.to_bits()) {
+        panic!(
+            "null test failed at sample {i}: input {}, output {} (residue {:e})",
+            input[i],
+            output[i],
+            output[i] - input[i]
+        );
+    }
+}
+
+/// RMS change (dB) from input to output once the node has settled
+///
+/// The first `settle_secs` are skipped on both sides so delay lines
+/// filling up or filter transients don't count.
+pub fn rms_change_db<N: GraphNode>(mut node: N, sample_rate: f32, settle_secs: f32) -> f32 {
+    let input = probe_signal(sample_rate, sample_rate as usize);
+    let output = process(&mut node, &input, sample_rate);
+
+    let skip = ((settle_secs * sample_rate) as usize).min(input.len() - 1);
+    let rms_in = meter::rms(&input[skip..]);
+    let rms_out = meter::rms(&output[skip..]);
+    meter::to_db(rms_out) - meter::to_db(rms_in)
+}
+
+/// Assert that `node` changes the probe's RMS level by at most `max_db`
+pub fn assert_unity_gain<N: GraphNode>(node: N, sample_rate: f32, settle_secs: f32, max_db: f32) {
+    let change = rms_change_db(node, sample_rate, settle_secs);
+    assert!(
+        change.abs() <= max_db,
+        "level changed by {change:+.2} dB (allowed ±{max_db} dB)"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    /// In-place gain stage standing in for a buggy effect
+    struct Scale(f32);
+
+    impl GraphNode for Scale {
+        fn render_block(&mut self, out: &mut [f32], _ctx: &RenderCtx) {
+            out.iter_mut().for_each(|s| *s *= self.0);
+        }
+    }
+
+    #[test]
+    fn probe_stays_below_full_scale() {
+        let signal = probe_signal(SAMPLE_RATE, 48_000);
+        assert!(meter::peak(&signal) < 0.7);
+        assert!(meter::rms(&signal) > 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "null test failed")]
+    fn null_detects_gain() {
+        assert_null(Scale(0.999), SAMPLE_RATE);
+    }
+
+    #[test]
+    fn rms_change_measures_gain() {
+        let change = rms_change_db(Scale(0.5), SAMPLE_RATE, 0.0);
+        assert!((change + 6.02).abs() < 0.01, "{change}");
+    }
+}
@@ -0,0 +1,337 @@
+//! Golden-audio snapshots: compact fingerprints of rendered sound.
+
+/*
+Snapshot Testing for Audio
+==========================
+
+Refactoring DSP code is nerve-wracking: did that "harmless" cleanup change
+the sound? Comparing raw samples is too strict (a reordered sum changes
+the last bit of every sample) and listening doesn't scale.
+
+Instead we reduce a render to a FINGERPRINT that captures what the ear
+cares about, commit it as a small text file, and compare future renders
+against it with tolerances:
+
+  level      overall RMS and peak (dBFS)
+
+  bands      energy in 9 octave bands, 63 Hz … 16 kHz (dB)
+             Each band is a bandpass SVF (resonance 0.5 → unity peak gain).
+             A changed filter, oscillator, or waveshaper moves these.
+
+               63  125  250  500  1k  2k  4k  8k  16k
+               ▆   █    ▅    ▃    ▂   ▁   ▁   ▁   ▁      ← a kick
+
+  peaks      times (ms) of the loudest envelope peaks, found in 10 ms
+             RMS frames. A changed envelope or timing bug moves these.
+
+
+Workflow
+--------
+
+  1. Render something deterministic (a voice, a sequence)
+  2. `assert_snapshot("path/to/name.snap", &Fingerprint::of(&audio, sr))`
+  3. First run: the file doesn't exist, so it's written. Commit it.
+  4. Later runs: compared against the file; a panic lists every field
+     that drifted past tolerance.
+  5. Sound changed ON PURPOSE? Re-run with SAAVY_UPDATE_SNAPSHOTS=1 to
+     rewrite the files, listen, and commit the new fingerprints.
+*/
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::dsp::{filter::SVFilter, meter};
+use crate::graph::{GraphNode, RenderCtx};
+
+/// Octave band centres used for band energies (Hz)
+pub const BAND_CENTRES: [f32; 9] = [63.0, 125.0, 250.0, 500.0, 1_000.0, 2_000.0, 4_000.0, 8_000.0, 16_000.0];
+/// Envelope frame length for peak detection (seconds)
+const FRAME_SECS: f32 = 0.01;
+/// How many envelope peaks to record
+const MAX_PEAKS: usize = 4;
+/// Peaks quieter than the loudest frame by more than this are ignored (dB)
+const PEAK_RANGE_DB: f32 = 20.0;
+/// Floor for silent measurements (dB)
+const FLOOR_DB: f32 = -120.0;
+/// Set to rewrite snapshot files instead of comparing
+pub const UPDATE_ENV: &str = "SAAVY_UPDATE_SNAPSHOTS";
+
+/// Perceptual-ish summary of a rendered buffer
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fingerprint {
+    pub rms_db: f32,
+    pub peak_db: f32,
+    /// Energy in each `BAND_CENTRES` band (dB)
+    pub bands_db: Vec<f32>,
+    /// Times of the loudest envelope peaks, in order (ms)
+    pub peaks_ms: Vec<f32>,
+}
+
+/// How far a fingerprint may drift before a comparison fails
+#[derive(Clone, Copy, Debug)]
+pub struct Tolerance {
+    /// RMS and peak level (dB)
+    pub level_db: f32,
+    /// Per-band energy (dB)
+    pub band_db: f32,
+    /// Envelope peak position (ms)
+    pub time_ms: f32,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            level_db: 0.5,
+            band_db: 1.5,
+            time_ms: 10.0,
+        }
+    }
+}
+
+impl Fingerprint {
+    /// Fingerprint a mono buffer
+    pub fn of(buffer: &[f32], sample_rate: f32) -> Self {
+        let ctx = RenderCtx::from_freq(sample_rate, 0.0, 1.0);
+        let bands_db = BAND_CENTRES
+            .iter()
+            .map(|&centre| {
+                if centre >= sample_rate * 0.45 {
+                    return FLOOR_DB;
+                }
+                let mut band = buffer.to_vec();
+                let mut filter = SVFilter::bandpass(centre);
+                filter.set_resonance(0.5);
+                filter.render(&mut band, &ctx);
+                db(meter::rms(&band))
+            })
+            .collect();
+
+        Self {
+            rms_db: db(meter::rms(buffer)),
+            peak_db: db(meter::peak(buffer)),
+            bands_db,
+            peaks_ms: envelope_peaks(buffer, sample_rate),
+        }
+    }
+
+    /// Check `self` against an `expected` fingerprint
+    ///
+    /// Returns every field that drifted past `tolerance`, one per line.
+    pub fn compare(&self, expected: &Fingerprint, tolerance: &Tolerance) -> Result<(), String> {
+        let mut errors = String::new();
+        let mut check = |name: &str, actual: f32, expected: f32, limit: f32| {
+            if (actual - expected).abs() > limit {
+                let _ = writeln!(errors, "  {name}: {actual:.2}, expected {expected:.2} (±{limit})");
+            }
+        };
+
+        check("rms_db", self.rms_db, expected.rms_db, tolerance.level_db);
+        check("peak_db", self.peak_db, expected.peak_db, tolerance.level_db);
+        for (i, (&a, &e)) in self.bands_db.iter().zip(&expected.bands_db).enumerate() {
+            check(&format!("band {} Hz", BAND_CENTRES[i]), a, e, tolerance.band_db);
+        }
+        if self.peaks_ms.len() != expected.peaks_ms.len() {
+            let _ = writeln!(errors, "  peaks: {:?}, expected {:?}", self.peaks_ms, expected.peaks_ms);
+        } else {
+            for (i, (&a, &e)) in self.peaks_ms.iter().zip(&expected.peaks_ms).enumerate() {
+                check(&format!("peak {i} ms"), a, e, tolerance.time_ms);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Serialize to the snapshot text format
+    pub fn to_text(&self) -> String {
+        let list = |values: &[f32]| values.iter().map(|v| format!("{v:.2}")).collect::<Vec<_>>().join(" ");
+        format!(
+            "rms_db = {:.2}\npeak_db = {:.2}\nbands_db = {}\npeaks_ms = {}\n",
+            self.rms_db,
+            self.peak_db,
+            list(&self.bands_db),
+            list(&self.peaks_ms),
+        )
+    }
+
+    /// Parse the snapshot text format (`None` if malformed)
+    pub fn from_text(text: &str) -> Option<Self> {
+        let mut fields = std::collections::HashMap::new();
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            let (key, value) = line.split_once('=')?;
+            fields.insert(key.trim(), value.trim());
+        }
+        let number = |key: &str| fields.get(key)?.parse::<f32>().ok();
+        let list = |key: &str| {
+            fields
+                .get(key)?
+                .split_whitespace()
+                .map(|v| v.parse::<f32>().ok())
+                .collect::<Option<Vec<_>>>()
+        };
+
+        Some(Self {
+            rms_db: number("rms_db")?,
+            peak_db: number("peak_db")?,
+            bands_db: list("bands_db")?,
+            peaks_ms: list("peaks_ms")?,
+        })
+    }
+}
+
+/// Compare `actual` with the snapshot at `path`, writing it if missing
+///
+/// Panics with a field-by-field report on mismatch. Set
+/// `SAAVY_UPDATE_SNAPSHOTS=1` to overwrite existing snapshots.
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &Fingerprint) {
+    assert_snapshot_with(path, actual, &Tolerance::default());
+}
+
+/// `assert_snapshot` with custom tolerances
+pub fn assert_snapshot_with(path: impl AsRef<Path>, actual: &Fingerprint, tolerance: &Tolerance) {
+    let path = path.as_ref();
+    let update = std::env::var_os(UPDATE_ENV).is_some();
+
+    if update || !path.exists() {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).expect("create snapshot directory");
+        }
+        std::fs::write(path, actual.to_text()).expect("write snapshot");
+        return;
+    }
+
+    let text = std::fs::read_to_string(path).expect("read snapshot");
+    let expected = Fingerprint::from_text(&text)
+        .unwrap_or_else(|| panic!("malformed snapshot {}", path.display()));
+    if let Err(report) = actual.compare(&expected, tolerance) {
+        panic!(
+            "audio snapshot {} changed:\n{report}(set {UPDATE_ENV}=1 to accept)",
+            path.display()
+        );
+    }
+}
+
+/// Render one note through a fresh node: `hold_secs` held, then `tail_secs` of release
+pub fn render_note<N: GraphNode>(mut node: N, sample_rate: f32, note: u8, hold_secs: f32, tail_secs: f32) -> Vec<f32> {
+    const BLOCK: usize = 256;
+    let ctx = RenderCtx::from_note(sample_rate, note, 100.0);
+    let hold = (hold_secs * sample_rate) as usize;
+    let total = hold + (tail_secs * sample_rate) as usize;
+
+    node.prepare(sample_rate, BLOCK);
+    node.note_on(&ctx);
+
+    let mut out = vec![0.0; total];
+    let mut released = false;
+    let mut pos = 0;
+    while pos < total {
+        if !released && pos >= hold {
+            node.note_off(&ctx);
+            released = true;
+        }
+        // Split blocks at the release point so note_off lands on its exact sample
+        let end = if released { total } else { hold };
+        let len = BLOCK.min(end - pos);
+        node.render_block(&mut out[pos..pos + len], &ctx);
+        pos += len;
+    }
+    out
+}
+
+/// Times (ms) of the loudest local maxima of the 10 ms RMS envelope
+fn envelope_peaks(buffer: &[f32], sample_rate: f32) -> Vec<f32> {
+    let frame = ((sample_rate * FRAME_SECS) as usize).max(1);
+    let levels: Vec<f32> = buffer.chunks(frame).map(|f| db(meter::rms(f))).collect();
+    let loudest = levels.iter().copied().fold(FLOOR_DB, f32::max);
+    if loudest <= FLOOR_DB {
+        return Vec::new();
+    }
+
+    let mut peaks: Vec<(usize, f32)> = (0..levels.len())
+        .filter(|&i| {
+            let prev = if i > 0 { levels[i - 1] } else { FLOOR_DB };
+            let next = levels.get(i + 1).copied().unwrap_or(FLOOR_DB);
+            levels[i] > prev && levels[i] >= next && levels[i] > loudest - PEAK_RANGE_DB
+        })
+        .map(|i| (i, levels[i]))
+        .collect();
+
+    // Keep the loudest few, reported in time order
+    peaks.sort_by(|a, b| b.1.total_cmp(&a.1));
+    peaks.truncate(MAX_PEAKS);
+    peaks.sort_by_key(|&(i, _)| i);
+    peaks
+        .into_iter()
+        .map(|(i, _)| i as f32 * FRAME_SECS * 1000.0)
+        .collect()
+}
+
+#[inline]
+fn db(linear: f32) -> f32 {
+    if linear <= 0.0 {
+        return FLOOR_DB;
+    }
+    (20.0 * linear.log10()).max(FLOOR_DB)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::TAU;
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    fn tone(freq: f32, seconds: f32) -> Vec<f32> {
+        (0..(SAMPLE_RATE * seconds) as usize)
+            .map(|i| 0.5 * (TAU * freq * i as f32 / SAMPLE_RATE).sin())
+            .collect()
+    }
+
+    #[test]
+    fn band_energy_follows_frequency() {
+        let low = Fingerprint::of(&tone(125.0, 0.5), SAMPLE_RATE);
+        let high = Fingerprint::of(&tone(4_000.0, 0.5), SAMPLE_RATE);
+
+        let loudest_band = |f: &Fingerprint| {
+            (0..f.bands_db.len()).max_by(|&a, &b| f.bands_db[a].total_cmp(&f.bands_db[b])).unwrap()
+        };
+        assert_eq!(BAND_CENTRES[loudest_band(&low)], 125.0);
+        assert_eq!(BAND_CENTRES[loudest_band(&high)], 4_000.0);
+    }
+
+    #[test]
+    fn peaks_mark_bursts() {
+        let mut audio = vec![0.0; SAMPLE_RATE as usize];
+        for start in [0.1, 0.6] {
+            let at = (start * SAMPLE_RATE) as usize;
+            audio[at..at + 480].copy_from_slice(&tone(1_000.0, 0.01));
+        }
+
+        let peaks = Fingerprint::of(&audio, SAMPLE_RATE).peaks_ms;
+        assert_eq!(peaks.len(), 2);
+        assert!((peaks[0] - 100.0).abs() <= 10.0 && (peaks[1] - 600.0).abs() <= 10.0, "{peaks:?}");
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let fingerprint = Fingerprint::of(&tone(440.0, 0.2), SAMPLE_RATE);
+        let parsed = Fingerprint::from_text(&fingerprint.to_text()).unwrap();
+
+        assert!(parsed.compare(&fingerprint, &Tolerance::default()).is_ok());
+        assert!(Fingerprint::from_text("rms_db = nope").is_none());
+    }
+
+    #[test]
+    fn compare_reports_drift() {
+        let expected = Fingerprint::of(&tone(440.0, 0.2), SAMPLE_RATE);
+        let mut louder = expected.clone();
+        louder.rms_db += 3.0;
+
+        let report = louder.compare(&expected, &Tolerance::default()).unwrap_err();
+        assert!(report.contains("rms_db"));
+        assert!(!report.contains("band"));
+    }
+}
@@ -0,0 +1,227 @@
+//! FFT magnitude spectrum with log-spaced display bins.
+
+/*
+Spectrum Analysis
+=================
+
+The FFT turns a block of N samples into N/2 frequency bins, each
+`sample_rate / N` Hz wide. At 48kHz with N = 1024 that's ~47 Hz per bin.
+
+Three problems stand between raw FFT output and a useful display:
+
+1. LEAKAGE
+   The FFT assumes the block repeats forever. A sine that doesn't fit a
+   whole number of cycles in the block has a jump at the seam, which
+   smears energy across every bin. A WINDOW tapers both ends to zero
+   first. We use Hann:
+
+       w[n] = 0.5 × (1 - cos(2π n / (N-1)))
+
+        1 ┤    ╭──╮
+          │  ╭╯    ╰╮
+        0 ┼─╯        ╰─
+
+2. LINEAR BINS vs LOGARITHMIC HEARING
+   Bins are evenly spaced in Hz, but we hear octaves: 20→40 Hz is as big
+   a step as 10k→20k Hz. Plotting raw bins crams all the musical detail
+   into the left edge. We map to log-spaced DISPLAY bins from 20 Hz to
+   Nyquist; each display bin takes the loudest FFT bin within its range
+   (so narrow peaks at high frequencies don't fall between the cracks).
+
+       FFT:     |·|·|·|·|·|·|·|·|·|·|·|·|·|·|·|·|   even in Hz
+       display: |·|·|··|···|·····|·········|         even in octaves
+
+3. UNITS
+   Magnitudes are converted to dBFS, normalized for the FFT size and the
+   window's gain, so a full-scale sine reads 0 dB no matter the FFT size.
+
+       amplitude = |X[k]| × 2 / Σ w[n]
+       dB        = 20 × log10(amplitude)
+
+All buffers (window, FFT scratch, output) are allocated once in `new`;
+`analyze` reuses them.
+*/
+
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+use std::sync::Arc;
+
+/// Lowest frequency shown (Hz)
+const MIN_FREQ: f64 = 20.0;
+/// Highest frequency shown, if below Nyquist (Hz)
+const MAX_FREQ: f64 = 20_000.0;
+/// Floor for silent bins (dBFS)
+pub const FLOOR_DB: f64 = -120.0;
+
+/// Windowed FFT analyzer producing `(frequency_hz, magnitude_db)` pairs
+pub struct Spectrum {
+    /// Hann window coefficients
+    window: Vec<f32>,
+    /// Converts |X[k]| to linear amplitude (2 / Σ window)
+    amplitude_scale: f32,
+    /// Range of FFT bins (inclusive) covered by each display bin
+    ranges: Vec<(usize, usize)>,
+    fft: Arc<dyn Fft<f32>>,
+    /// Scratch buffer for FFT computation
+    scratch: Vec<Complex<f32>>,
+    /// Current spectrum: (frequency_hz, magnitude_db)
+    data: Vec<(f64, f64)>,
+}
+
+impl Spectrum {
+    /// Create an analyzer for blocks of `fft_size` samples with `bins` log-spaced outputs
+    pub fn new(fft_size: usize, sample_rate: f32, bins: usize) -> Self {
+        let fft_size = fft_size.max(2);
+        let bins = bins.max(1);
+        let fft = FftPlanner::new().plan_fft_forward(fft_size);
+
+        let window = hann_window(fft_size);
+        let amplitude_scale = 2.0 / window.iter().sum::<f32>();
+
+        // Log-spaced centres from 20 Hz to min(Nyquist, 20 kHz)
+        let hz_per_bin = sample_rate as f64 / fft_size as f64;
+        let last_bin = fft_size / 2 - 1;
+        let max_freq = (sample_rate as f64 / 2.0).clamp(MIN_FREQ, MAX_FREQ);
+        let ratio = max_freq / MIN_FREQ;
+        let centres: Vec<f64> = (0..bins)
+            .map(|i| {
+                let t = if bins > 1 { i as f64 / (bins - 1) as f64 } else { 0.0 };
+                MIN_FREQ * ratio.powf(t)
+            })
+            .collect();
+
+        // Each display bin spans halfway (geometrically) to its neighbours
+        let to_index = |hz: f64| ((hz / hz_per_bin).round() as usize).min(last_bin);
+        let ranges = (0..bins)
+            .map(|i| {
+                let centre = centres[i];
+                let lo = if i > 0 { (centres[i - 1] * centre).sqrt() } else { centre };
+                let hi = if i + 1 < bins { (centre * centres[i + 1]).sqrt() } else { centre };
+                let (lo, hi) = (to_index(lo), to_index(hi));
+                // Low bins are narrower than one FFT bin - fall back to the nearest
+                if hi < lo { (to_index(centre), to_index(centre)) } else { (lo, hi) }
+            })
+            .collect();
+
+        Self {
+            window,
+            amplitude_scale,
+            ranges,
+            fft,
+            scratch: vec![Complex::new(0.0, 0.0); fft_size],
+            data: centres.into_iter().map(|f| (f, FLOOR_DB)).collect(),
+        }
+    }
+
+    /// FFT size this analyzer expects
+    pub fn fft_size(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Analyze one block (must be exactly `fft_size` samples, otherwise ignored)
+    pub fn analyze(&mut self, buffer: &[f32]) -> &[(f64, f64)] {
+        if buffer.len() != self.window.len() {
+            return &self.data;
+        }
+
+        // Apply window and prepare for FFT
+        for ((slot, &sample), &w) in self.scratch.iter_mut().zip(buffer).zip(&self.window) {
+            *slot = Complex::new(sample * w, 0.0);
+        }
+        self.fft.process(&mut self.scratch);
+
+        // Loudest FFT bin within each display bin's range
+        for ((_, magnitude_db), &(lo, hi)) in self.data.iter_mut().zip(&self.ranges) {
+            let power = self.scratch[lo..=hi]
+                .iter()
+                .map(|bin| bin.norm_sqr())
+                .fold(0.0f32, f32::max);
+            let amplitude = power.sqrt() * self.amplitude_scale;
+            *magnitude_db = amplitude_to_db(amplitude as f64);
+        }
+
+        &self.data
+    }
+
+    /// Most recent result: (frequency_hz, magnitude_db)
+    pub fn data(&self) -> &[(f64, f64)] {
+        &self.data
+    }
+}
+
+/// Hann window of `len` coefficients
+pub fn hann_window(len: usize) -> Vec<f32> {
+    if len <= 1 {
+        return vec![1.0; len];
+    }
+    let denom = (len - 1) as f32;
+    (0..len)
+        .map(|i| 0.5 * (1.0 - (std::f32::consts::TAU * i as f32 / denom).cos()))
+        .collect()
+}
+
+/// Linear amplitude to dB, floored at `FLOOR_DB`
+#[inline]
+pub fn amplitude_to_db(amplitude: f64) -> f64 {
+    if amplitude <= 0.0 {
+        return FLOOR_DB;
+    }
+    (20.0 * amplitude.log10()).max(FLOOR_DB)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::TAU;
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    fn sine(freq: f32, amplitude: f32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| amplitude * (TAU * freq * i as f32 / SAMPLE_RATE).sin())
+            .collect()
+    }
+
+    fn loudest(data: &[(f64, f64)]) -> (f64, f64) {
+        data.iter().copied().fold((0.0, f64::MIN), |a, b| if b.1 > a.1 { b } else { a })
+    }
+
+    #[test]
+    fn full_scale_sine_reads_near_zero_db() {
+        let mut spectrum = Spectrum::new(4096, SAMPLE_RATE, 64);
+        // Exactly on an FFT bin: 100 × 48000/4096
+        let freq = 100.0 * SAMPLE_RATE / 4096.0;
+        let (_, db) = loudest(spectrum.analyze(&sine(freq, 1.0, 4096)));
+
+        assert!(db.abs() < 0.5, "peak {db} dB");
+    }
+
+    #[test]
+    fn peak_lands_in_matching_display_bin() {
+        let mut spectrum = Spectrum::new(2048, SAMPLE_RATE, 48);
+        let (freq, _) = loudest(spectrum.analyze(&sine(5_000.0, 0.5, 2048)));
+
+        // Display bins are ~1/7 octave apart
+        assert!((freq / 5_000.0).log2().abs() < 0.15, "peak at {freq} Hz");
+    }
+
+    #[test]
+    fn silence_sits_on_floor_and_wrong_length_is_ignored() {
+        let mut spectrum = Spectrum::new(1024, SAMPLE_RATE, 32);
+        assert!(spectrum.analyze(&[0.0; 1024]).iter().all(|&(_, db)| db == FLOOR_DB));
+
+        let before = spectrum.data().to_vec();
+        spectrum.analyze(&[1.0; 100]);
+        assert_eq!(spectrum.data(), &before[..]);
+    }
+
+    #[test]
+    fn display_bins_are_log_spaced() {
+        let spectrum = Spectrum::new(1024, SAMPLE_RATE, 32);
+        let freqs: Vec<f64> = spectrum.data().iter().map(|&(f, _)| f).collect();
+
+        assert!((freqs[0] - MIN_FREQ).abs() < 1e-9);
+        assert!((freqs[31] - MAX_FREQ).abs() < 1e-6);
+        let ratio = freqs[1] / freqs[0];
+        assert!(freqs.windows(2).all(|w| (w[1] / w[0] - ratio).abs() < 1e-9));
+    }
+}
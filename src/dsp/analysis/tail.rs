@@ -0,0 +1,110 @@
+//! Render until an effect tail has died away.
+
+/*
+Effect Tails
+============
+
+A reverb or delay keeps sounding after the last note ends. Rendering a
+fixed length either cuts that tail off mid-decay or pads the file with
+seconds of silence. Instead, keep rendering until the output is quiet:
+
+  level
+    │▆▇▆▅▇▆▅▇▆▅│▄▃▃▂▂▁▁▁ ▁                 ┆
+    │          │          ‾‾─── threshold  ┆
+    └──────────┴──────────────┴───────────┴→ time
+     sequence   tail           │←─ hold ─→│ stop
+     ends
+
+Output counts as silent once every sample has stayed below `threshold`
+(linear peak; 0.001 is -60 dB) for `SILENCE_HOLD_SECS`, so a delay's
+quiet gap between echoes doesn't end the render early. `max_seconds` caps
+the tail in case it never decays (a feedback loop at 100%, a drone).
+*/
+
+use crate::dsp::meter;
+use crate::graph::{GraphNode, RenderCtx};
+
+/// How long the output must stay below the threshold to count as silent
+pub const SILENCE_HOLD_SECS: f32 = 0.1;
+
+/// Block size used to render tails
+const TAIL_BLOCK: usize = 256;
+
+/// Call `render` block by block until its output falls silent (see module docs)
+///
+/// `render` fills each block it is given (at most `block_size` frames).
+/// Returns everything rendered, including the quiet stretch that ended it.
+pub fn render_until_silent(
+    mut render: impl FnMut(&mut [f32]),
+    sample_rate: f32,
+    block_size: usize,
+    threshold: f32,
+    max_seconds: f32,
+) -> Vec<f32> {
+    let block_size = block_size.max(1);
+    let max_len = (max_seconds.max(0.0) * sample_rate) as usize;
+    let hold = (SILENCE_HOLD_SECS * sample_rate) as usize;
+
+    let mut out = Vec::new();
+    let mut quiet = 0;
+    while out.len() < max_len && quiet < hold {
+        let start = out.len();
+        out.resize(start + block_size.min(max_len - start), 0.0);
+        render(&mut out[start..]);
+
+        if meter::peak(&out[start..]) < threshold {
+            quiet += out.len() - start;
+        } else {
+            quiet = 0;
+        }
+    }
+    out
+}
+
+/// Release a sounding node and render it until its tail dies away
+///
+/// Sends `note_off` with `ctx`, then renders as `render_until_silent`.
+pub fn release_until_silent<N: GraphNode + ?Sized>(
+    node: &mut N,
+    ctx: &RenderCtx,
+    threshold: f32,
+    max_seconds: f32,
+) -> Vec<f32> {
+    node.note_off(ctx);
+    render_until_silent(
+        |block| node.render_block(block, ctx),
+        ctx.sample_rate,
+        TAIL_BLOCK,
+        threshold,
+        max_seconds,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{envelope::EnvNode, extensions::NodeExt, oscillator::OscNode};
+
+    #[test]
+    fn renders_the_release_and_stops_once_quiet() {
+        let sample_rate = 48_000.0;
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 100.0);
+        let mut node = OscNode::sine().amplify(EnvNode::adsr(0.001, 0.01, 1.0, 0.3));
+        node.prepare(sample_rate, 4_800);
+        node.note_on(&ctx);
+        let mut held = [0.0; 4_800];
+        node.render_block(&mut held, &ctx);
+
+        let tail = release_until_silent(&mut node, &ctx, 0.001, 5.0);
+        let secs = tail.len() as f32 / sample_rate;
+        // The 300 ms release, plus the quiet hold, well short of the cap
+        assert!(secs > 0.3 && secs < 0.3 + SILENCE_HOLD_SECS + 0.05, "{secs} s");
+        assert!(meter::peak(&tail[tail.len() - 4_800..]) < 0.001);
+    }
+
+    #[test]
+    fn stops_at_the_cap_when_the_tail_never_decays() {
+        let tail = render_until_silent(|block| block.fill(0.5), 1_000.0, 64, 0.001, 1.0);
+        assert_eq!(tail.len(), 1_000);
+    }
+}
@@ -0,0 +1,151 @@
+//! Multi-hit burst envelope (hand claps, flams).
+
+/*
+Burst Envelopes
+===============
+
+A real hand clap is several hands hitting within a few tens of
+milliseconds, and then the room. Drum machines fake it with an envelope
+that fires a handful of sharp hits, then one longer tail:
+
+  level
+   1 ┤|╲  |╲  |╲  |╲
+     ┤| ╲ | ╲ | ╲ | ╲
+     ┤|  ╲|  ╲|  ╲|  ╲___
+   0 ┤|   ╵   ╵   ╵      ‾‾‾‾‾────___
+     └─┴───┴───┴───┴────────────────→ time
+       │←─→│ spacing   │←── tail ──→│
+       hit 1   2   3   4 (last)
+
+Every hit jumps back to full level and decays over `hit_decay_secs`;
+the last hit decays over `tail_secs` instead. Multiplied into noise
+through a band-pass, this is the 808/909 clap.
+
+
+Decay Times
+-----------
+
+Decays are exponential, like a struck object, and each time is how long
+the hit takes to fall by 60 dB:
+
+  level(t) = 10^(-3 · t / decay)      = 1.0 at t = 0, 0.001 at t = decay
+
+After the tail's decay the envelope is done and outputs exactly 0.0. A
+hit that hasn't finished when the next one starts is simply cut - the new
+hit is louder anyway, so the jump is masked.
+
+
+Spacing
+-------
+
+  ~8-12 ms   tight, modern clap
+  ~15-25 ms  loose, "crowd" clap
+  one hit    a plain noise burst (snare-ish)
+*/
+
+/// Envelope of `hits` sharp hits `spacing_secs` apart, the last one ringing out
+#[derive(Clone, Copy, Debug)]
+pub struct BurstEnvelope {
+    /// Number of hits (0 plays as one)
+    pub hits: u8,
+    /// Seconds between the start of each hit
+    pub spacing_secs: f32,
+    /// 60 dB decay of every hit but the last
+    pub hit_decay_secs: f32,
+    /// 60 dB decay of the last hit
+    pub tail_secs: f32,
+    /// Samples since the trigger (`None` when idle)
+    elapsed: Option<u32>,
+    level: f32,
+}
+
+impl BurstEnvelope {
+    pub fn new(hits: u8, spacing_secs: f32, hit_decay_secs: f32, tail_secs: f32) -> Self {
+        Self {
+            hits: hits.max(1),
+            spacing_secs: spacing_secs.max(0.0),
+            hit_decay_secs: hit_decay_secs.max(1e-4),
+            tail_secs: tail_secs.max(1e-4),
+            elapsed: None,
+            level: 0.0,
+        }
+    }
+
+    /// Start the first hit
+    pub fn trigger(&mut self) {
+        self.elapsed = Some(0);
+    }
+
+    /// Level for the next sample (0.0 when idle)
+    #[inline]
+    pub fn next_level(&mut self, sample_rate: f32) -> f32 {
+        let Some(elapsed) = self.elapsed else {
+            self.level = 0.0;
+            return 0.0;
+        };
+
+        // `hits` is public, so it can be 0 here even though `new` clamps it
+        let hits = self.hits.max(1) as u32;
+        let t = elapsed as f32 / sample_rate;
+        let spacing = self.spacing_secs.max(f32::EPSILON);
+        let hit = ((t / spacing) as u32).min(hits - 1);
+        let since_hit = t - hit as f32 * self.spacing_secs;
+        let last = hit + 1 == hits;
+        let decay = if last { self.tail_secs } else { self.hit_decay_secs };
+
+        if last && since_hit >= decay {
+            self.elapsed = None;
+            self.level = 0.0;
+            return 0.0;
+        }
+
+        self.elapsed = Some(elapsed + 1);
+        self.level = 10.0_f32.powf(-3.0 * since_hit / decay);
+        self.level
+    }
+
+    /// Most recent level
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    /// Whether hits or the tail are still sounding
+    pub fn is_active(&self) -> bool {
+        self.elapsed.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fires_each_hit_then_rings_out() {
+        let sample_rate = 1_000.0;
+        let mut env = BurstEnvelope::new(3, 0.010, 0.005, 0.050);
+        assert_eq!(env.next_level(sample_rate), 0.0);
+
+        env.trigger();
+        let levels: Vec<f32> = (0..80).map(|_| env.next_level(sample_rate)).collect();
+
+        // Full level at 0, 10 and 20 ms
+        let peaks: Vec<usize> = (0..levels.len()).filter(|&i| levels[i] == 1.0).collect();
+        assert_eq!(peaks, vec![0, 10, 20]);
+        // Hits fall 60 dB in 5 ms; the tail takes 50
+        assert!((levels[5] - 0.001).abs() < 1e-6);
+        assert!((levels[45] - 10.0_f32.powf(-1.5)).abs() < 1e-5);
+        assert_eq!(levels[70], 0.0);
+        assert!(!env.is_active());
+    }
+
+    #[test]
+    fn zero_hits_plays_as_one() {
+        let mut env = BurstEnvelope::new(1, 0.010, 0.005, 0.050);
+        env.hits = 0;
+        env.trigger();
+        let levels: Vec<f32> = (0..80).map(|_| env.next_level(1_000.0)).collect();
+        assert_eq!(levels[0], 1.0);
+        assert!(levels[1..50].windows(2).all(|w| w[1] < w[0]), "one decay, no re-hits");
+        assert!(!env.is_active());
+    }
+}
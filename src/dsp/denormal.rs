@@ -0,0 +1,205 @@
+//! Denormal protection for recursive DSP.
+
+/*
+Denormals
+=========
+
+A normal f32 stores a 24-bit mantissa and an 8-bit exponent. Once a value
+drops below ~1.18e-38 the exponent runs out, and the CPU switches to a
+"denormal" (subnormal) encoding that trades mantissa bits for range.
+
+On most x86 CPUs, arithmetic on denormals takes a slow microcode path that
+can be 10-100x slower than normal floats.
+
+
+Why Audio Hits Them
+-------------------
+
+Anything with FEEDBACK decays exponentially toward zero but never reaches it:
+
+    y[n] = x[n] + 0.9 × y[n-1]        (comb filter, SVF integrator, echo)
+
+After the input stops, y shrinks by 10% every sample. A few seconds of
+silence later every sample in the reverb tail is a denormal - and the CPU
+load SPIKES exactly when the music goes quiet.
+
+  level
+    1.0 ─╮
+         │╲
+         │ ╲___
+         │     ╲______
+  1e-38 ─┼────────────╲~~~~~~~~~~~~  ← denormal zone: same silence, 50x the CPU
+         └──────────────────────────→ time
+
+
+Two Defenses
+------------
+
+1. FLUSH-TO-ZERO (hardware)
+
+   The CPU can be told to treat denormals as zero:
+     FTZ  (flush to zero)        denormal RESULTS become 0
+     DAZ  (denormals are zero)   denormal INPUTS are read as 0
+
+   These are bits in a per-thread control register (MXCSR on x86, FPCR on
+   ARM). `DenormalGuard` sets them for the life of the guard and restores
+   the previous value on drop - so the audio callback can opt in without
+   changing float behaviour for the rest of the program.
+
+2. ANTI-DENORMAL OFFSET (software)
+
+   Add a tiny constant in every feedback path:
+
+     y[n] = x[n] + 0.9 × y[n-1] + 1e-18
+
+   The offset itself is -360 dBFS, and the loop settles at 1e-17 (-340 dBFS,
+   the offset times the loop gain 1 / (1 - 0.9)) instead of decaying forever.
+   That is far above the denormal range and far below audibility, and
+   portable to targets where we can't touch the FPU flags.
+
+We use both: the guard covers everything on the audio thread, and the offset
+keeps offline renders and tests (which don't install a guard) fast too.
+*/
+
+/// Tiny DC offset added in feedback paths to keep state out of the denormal range.
+///
+/// ~-360 dBFS: far below audibility, far above f32's smallest normal (~1.2e-38).
+pub const ANTI_DENORMAL: f32 = 1e-18;
+
+/// Enables flush-to-zero / denormals-are-zero for the current thread.
+///
+/// The previous FPU mode is restored when the guard is dropped. On targets
+/// without a known control register this is a no-op.
+///
+/// ```ignore
+/// move |data: &mut [f32], _| {
+///     let _guard = DenormalGuard::new();
+///     // ... render ...
+/// }
+/// ```
+pub struct DenormalGuard {
+    #[cfg_attr(not(any(target_arch = "x86_64", target_arch = "aarch64")), allow(dead_code))]
+    previous: u64,
+}
+
+impl DenormalGuard {
+    pub fn new() -> Self {
+        let previous = read_fpu_mode();
+        write_fpu_mode(previous | FLUSH_BITS);
+        Self { previous }
+    }
+}
+
+impl Default for DenormalGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for DenormalGuard {
+    fn drop(&mut self) {
+        write_fpu_mode(self.previous);
+    }
+}
+
+// x86_64: MXCSR bit 15 = FTZ, bit 6 = DAZ
+#[cfg(target_arch = "x86_64")]
+const FLUSH_BITS: u64 = (1 << 15) | (1 << 6);
+
+#[cfg(target_arch = "x86_64")]
+fn read_fpu_mode() -> u64 {
+    let mut csr: u32 = 0;
+    // SAFETY: stmxcsr only stores the SSE control register into `csr`
+    unsafe {
+        std::arch::asm!("stmxcsr [{}]", in(reg) &mut csr, options(nostack, preserves_flags));
+    }
+    csr as u64
+}
+
+#[cfg(target_arch = "x86_64")]
+fn write_fpu_mode(mode: u64) {
+    let csr = mode as u32;
+    // SAFETY: ldmxcsr only changes rounding/flush/exception-mask bits for this thread
+    unsafe {
+        std::arch::asm!("ldmxcsr [{}]", in(reg) &csr, options(nostack, preserves_flags, readonly));
+    }
+}
+
+// aarch64: FPCR bit 24 = FZ (covers both inputs and outputs)
+#[cfg(target_arch = "aarch64")]
+const FLUSH_BITS: u64 = 1 << 24;
+
+#[cfg(target_arch = "aarch64")]
+fn read_fpu_mode() -> u64 {
+    let fpcr: u64;
+    // SAFETY: reading FPCR has no side effects
+    unsafe {
+        std::arch::asm!("mrs {}, fpcr", out(reg) fpcr, options(nomem, nostack, preserves_flags));
+    }
+    fpcr
+}
+
+#[cfg(target_arch = "aarch64")]
+fn write_fpu_mode(mode: u64) {
+    // SAFETY: only changes floating-point mode bits for this thread
+    unsafe {
+        std::arch::asm!("msr fpcr, {}", in(reg) mode, options(nomem, nostack, preserves_flags));
+    }
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+const FLUSH_BITS: u64 = 0;
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn read_fpu_mode() -> u64 {
+    0
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn write_fpu_mode(_mode: u64) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+
+    const SUBNORMAL: f32 = 1e-39;
+
+    #[test]
+    fn reverb_tail_stays_normal() {
+        let mut reverb = crate::dsp::reverb::SchroederReverb::new(48_000.0);
+        reverb.set_room_size(0.9);
+        reverb.process(1.0);
+
+        let mut last = 0.0;
+        for _ in 0..48_000 * 60 {
+            last = reverb.process(0.0);
+        }
+        assert!(last.is_normal(), "tail went subnormal: {:e}", last);
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    fn guard_flushes_subnormal_results() {
+        assert!(!SUBNORMAL.is_normal());
+
+        let flushed = {
+            let _guard = DenormalGuard::new();
+            black_box(SUBNORMAL) * black_box(0.5)
+        };
+        assert_eq!(flushed, 0.0);
+
+        // Mode restored after the guard drops
+        let restored = black_box(SUBNORMAL) * black_box(0.5);
+        assert!(restored > 0.0);
+    }
+
+    #[test]
+    fn feedback_with_offset_stays_normal() {
+        let mut y = 1.0f32;
+        for _ in 0..100_000 {
+            y = y * 0.9 + ANTI_DENORMAL;
+        }
+        assert!(y.is_normal());
+    }
+}
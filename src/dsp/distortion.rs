@@ -142,6 +142,29 @@ FOLDBACK:
       Now |x| < threshold, done. y = 0.6
 
 
+WAVEFOLDER (west coast): f(x) = sin(π/2 × fold × (x + bias))
+
+    The smooth cousin of foldback, from Buchla-style synthesizers. Where
+    foldback reflects with sharp corners, the sine folds round every peak,
+    so harmonics grow steadily as `fold` rises instead of buzzing. A sine
+    fed through it sweeps from pure (fold 1) to bright and vocal (fold 3+):
+
+    Output                        fold 1: gentle saturation
+    +1 │  ╱‾╲    ╱‾╲              fold 3: each peak folds over twice
+       │ ╱   ╲  ╱   ╲
+     0 │╱─────╲╱─────╲──
+    -1 │        ╲╱
+       └─────────────────→ Input
+
+    `bias` shifts the input off center before folding, so the top and
+    bottom of the wave fold differently. Symmetric folding only makes odd
+    harmonics; bias blends in the even ones (warmer, more "tube-like").
+    The output at zero input is subtracted so silence stays silent.
+
+    Character: Evolving, vocal, organic. Sweep `fold` with an envelope or
+    LFO - that movement is the west-coast sound.
+
+
 Drive Values Reference
 ----------------------
 
@@ -209,6 +232,39 @@ pub fn foldback(sample: f32, drive: f32, threshold: f32) -> f32 {
     x.clamp(-threshold, threshold)
 }
 
+/// West-coast wavefolder: sin(π/2 × fold × (x + bias)), re-centered on silence.
+///
+/// `fold` of 1.0 is gentle saturation; every +2 folds each peak over once
+/// more. `bias` (-1.0 - 1.0) makes the folding asymmetric, adding even
+/// harmonics; re-centering means a biased fold can peak past ±1.
+#[inline]
+pub fn wavefold(sample: f32, fold: f32, bias: f32) -> f32 {
+    let k = std::f32::consts::FRAC_PI_2 * fold;
+    (k * (sample + bias)).sin() - (k * bias).sin()
+}
+
+/// Safety limiter curve: transparent below `threshold`, soft above, never past ±1.
+///
+/// Below the knee the signal passes untouched (unlike `soft_clip`, which
+/// colors everything). Above it the overshoot is squeezed with the same
+/// x / (1 + x) curve, scaled into the headroom left between the knee and
+/// full scale, so the slope stays continuous at the knee.
+#[inline]
+pub fn soft_limit(sample: f32, threshold: f32) -> f32 {
+    let threshold = threshold.clamp(0.0, 1.0);
+    let magnitude = sample.abs();
+    if magnitude <= threshold {
+        return sample;
+    }
+    let headroom = 1.0 - threshold;
+    // No headroom left (threshold 1.0) or an infinite input: pin to full scale
+    if headroom <= 0.0 || magnitude.is_infinite() {
+        return 1.0f32.copysign(sample);
+    }
+    let over = (magnitude - threshold) / headroom;
+    (threshold + headroom * over / (1.0 + over)).copysign(sample)
+}
+
 /// Apply soft clipping to an entire buffer in place.
 pub fn soft_clip_buffer(buffer: &mut [f32], drive: f32) {
     for sample in buffer.iter_mut() {
@@ -264,6 +320,17 @@ mod tests {
         assert!((output - 1.0).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_wavefold_folds_and_recenters() {
+        // fold 1: the peak of a full-scale input lands exactly on 1
+        assert!((wavefold(1.0, 1.0, 0.0) - 1.0).abs() < 1e-6);
+        // fold 3: full scale folds back over: sin(3π/2) = -1
+        assert!((wavefold(1.0, 3.0, 0.0) + 1.0).abs() < 1e-6);
+        // Biased: silence stays silent, but the two halves differ
+        assert_eq!(wavefold(0.0, 2.0, 0.3), 0.0);
+        assert!((wavefold(0.5, 2.0, 0.3) + wavefold(-0.5, 2.0, 0.3)).abs() > 0.1);
+    }
+
     #[test]
     fn test_foldback_below_threshold() {
         let output = foldback(0.3, 1.0, 1.0);
@@ -299,4 +366,21 @@ mod tests {
         let output = foldback(1.0, 2.0, -1.0);
         assert!(output.is_finite());
     }
+
+    #[test]
+    fn test_soft_limit_transparent_below_threshold() {
+        for x in [-0.8, -0.3, 0.0, 0.5, 0.8] {
+            assert_eq!(soft_limit(x, 0.8), x);
+        }
+        for x in [1.0, 4.0, 100.0] {
+            let y = soft_limit(x, 0.8);
+            assert!(y > 0.8 && y < 1.0, "{x} -> {y}");
+            assert_eq!(soft_limit(-x, 0.8), -y);
+        }
+        // Degenerate knees and infinite input stay finite
+        assert_eq!(soft_limit(f32::INFINITY, 0.8), 1.0);
+        assert_eq!(soft_limit(f32::NEG_INFINITY, 0.8), -1.0);
+        assert_eq!(soft_limit(2.0, 1.0), 1.0);
+        assert_eq!(soft_limit(-2.0, 1.5), -1.0);
+    }
 }
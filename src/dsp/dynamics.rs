@@ -0,0 +1,156 @@
+//! Level detection and gain computing for dynamics processors.
+
+/*
+Dynamics
+========
+
+Compressors, limiters, gates and dynamic EQs all do the same three
+things, per sample:
+
+  signal → [detector] → level → [gain computer] → gain → × signal
+
+The differences are only in what the detector listens to (the whole
+signal, one band, another track) and how the gain computer responds.
+
+
+Envelope Follower (the detector)
+--------------------------------
+
+Audio swings through zero hundreds of times a second; a level has to move
+slowly enough to act on. The follower tracks the signal's peaks with two
+one-pole smoothers - a fast one while the level rises (ATTACK) and a slow
+one while it falls (RELEASE):
+
+    |x|   ╱╲╱╲╱╲╱╲
+         ╱        ╲╱╲
+    level  ╱‾‾‾‾‾‾‾‾‾‾╲___     rises in ~attack, falls in ~release
+
+    coefficient = e^(-1 / (time × sample_rate))
+    level = |x| + coef × (level - |x|)
+
+`time` is how long the level takes to cover ~63% of a jump.
+
+  attack 0.1 - 1 ms     catches transients (limiters)
+  attack 5 - 30 ms      lets transients through (punchy compression)
+  release 50 - 300 ms   smooth recovery without pumping
+
+
+Gain Computer
+-------------
+
+Above the THRESHOLD, every `ratio` dB of input becomes 1 dB of output:
+
+    out dB                         ratio 1:1   no change
+      │         ╱ 1:1              ratio 4:1   firm compression
+      │       ╱   ___ 4:1          ratio ∞:1   limiting
+      │     ╱‾‾‾‾
+      │   ╱ ← threshold
+      └──────────── in dB
+
+    reduction dB = (level - threshold) × (1 - 1/ratio)      above threshold
+                 = 0                                         below it
+*/
+
+/// Convert decibels to a linear amplitude
+#[inline]
+pub fn db_to_gain(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// Gain reduction in dB (≥ 0) for a level above `threshold_db` at `ratio`:1
+#[inline]
+pub fn gain_reduction_db(level_db: f32, threshold_db: f32, ratio: f32) -> f32 {
+    let over = level_db - threshold_db;
+    if over <= 0.0 {
+        0.0
+    } else {
+        over * (1.0 - 1.0 / ratio.max(1.0))
+    }
+}
+
+/// Peak envelope follower with separate attack and release times
+#[derive(Clone, Debug)]
+pub struct EnvelopeFollower {
+    attack_coef: f32,
+    release_coef: f32,
+    level: f32,
+}
+
+impl EnvelopeFollower {
+    pub fn new(attack_secs: f32, release_secs: f32, sample_rate: f32) -> Self {
+        let mut follower = Self {
+            attack_coef: 0.0,
+            release_coef: 0.0,
+            level: 0.0,
+        };
+        follower.set_times(attack_secs, release_secs, sample_rate);
+        follower
+    }
+
+    /// Change attack/release (RT-safe; keeps the current level)
+    pub fn set_times(&mut self, attack_secs: f32, release_secs: f32, sample_rate: f32) {
+        self.attack_coef = Self::coefficient(attack_secs, sample_rate);
+        self.release_coef = Self::coefficient(release_secs, sample_rate);
+    }
+
+    fn coefficient(secs: f32, sample_rate: f32) -> f32 {
+        if secs <= 0.0 {
+            0.0
+        } else {
+            (-1.0 / (secs * sample_rate)).exp()
+        }
+    }
+
+    /// Feed one sample, returning the updated level (linear)
+    #[inline]
+    pub fn process(&mut self, sample: f32) -> f32 {
+        let input = sample.abs();
+        let coef = if input > self.level {
+            self.attack_coef
+        } else {
+            self.release_coef
+        };
+        self.level = input + coef * (self.level - input);
+        self.level
+    }
+
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    pub fn reset(&mut self) {
+        self.level = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follower_attacks_fast_and_releases_slow() {
+        let sample_rate = 1_000.0;
+        let mut follower = EnvelopeFollower::new(0.001, 0.1, sample_rate);
+        // 1 ms attack at 1 kHz: one time constant per sample
+        let level = follower.process(1.0);
+        assert!((level - (1.0 - (-1.0f32).exp())).abs() < 1e-6);
+        for _ in 0..20 {
+            follower.process(1.0);
+        }
+        assert!(follower.level() > 0.999);
+
+        // 100 ms release: ~63% of the way down after 100 samples
+        for _ in 0..100 {
+            follower.process(0.0);
+        }
+        assert!((follower.level() - (-1.0f32).exp()).abs() < 0.01);
+    }
+
+    #[test]
+    fn gain_computer_follows_the_ratio() {
+        assert_eq!(gain_reduction_db(-30.0, -20.0, 4.0), 0.0);
+        assert_eq!(gain_reduction_db(-12.0, -20.0, 4.0), 6.0);
+        assert_eq!(gain_reduction_db(-12.0, -20.0, 1.0), 0.0);
+        assert!((db_to_gain(-6.0) - 0.501).abs() < 1e-3);
+    }
+}
@@ -102,7 +102,7 @@ note_off time, then interpolate linearly. This ensures we hit exactly 0.0.
 
 /// The current stage of the envelope state machine.
 /// Renamed from "State" to "Stage" to avoid confusion with Rust's state terminology.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EnvelopeState {
     Idle,    // Gate low, envelope inactive, level = 0
     Attack,  // Gate just went high, ramping up to 1.0
@@ -285,6 +285,12 @@ impl Envelope {
     }
 }
 
+impl Default for Envelope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,6 @@
 use std::f32::consts::TAU;
 
+use super::{denormal::ANTI_DENORMAL, smooth::SmoothedParam, Real};
 use crate::graph::node::RenderCtx;
 
 /*
@@ -157,8 +158,8 @@ pub struct FilterOutputs {
 }
 
 pub struct SVFilter {
-    ic1eq: f32, // First integrator's memory
-    ic2eq: f32, // Second integrator's memory
+    ic1eq: Real, // First integrator's memory
+    ic2eq: Real, // Second integrator's memory
 
     pub cutoff_hz: f32,
     pub resonance: f32,
@@ -217,15 +218,20 @@ impl SVFilter {
     }
 
     #[inline]
-    fn compute_g(&self, ctx: &RenderCtx) -> f32 {
-        let wd = TAU * self.cutoff_hz;
-        let wa = (2.0 * ctx.sample_rate) * (wd / (2.0 * ctx.sample_rate)).tan();
-        wa / (2.0 * ctx.sample_rate)
+    pub(crate) fn compute_g(cutoff_hz: f32, sample_rate: f32) -> f32 {
+        let wd = TAU * cutoff_hz;
+        let wa = (2.0 * sample_rate) * (wd / (2.0 * sample_rate)).tan();
+        wa / (2.0 * sample_rate)
     }
 
+    #[allow(clippy::unnecessary_cast)] // Real is f32 unless the `f64` feature is on
     pub fn next_sample(&mut self, sample: f32, k: f32, g: f32) -> FilterOutputs {
+        // Integrator math runs at `Real` precision (f64 with the `f64` feature)
+        let (x, k, g) = (sample as Real, k as Real, g as Real);
+
         let h = 1.0 / (1.0 + g * (g + k));
-        let v3 = sample - self.ic2eq;
+        // Offset keeps the integrators out of the denormal range on silent input
+        let v3 = x + ANTI_DENORMAL as Real - self.ic2eq;
         let v1 = h * (self.ic1eq + g * v3);
         let v2 = self.ic2eq + g * v1;
 
@@ -233,26 +239,68 @@ impl SVFilter {
         self.ic2eq = 2.0 * v2 - self.ic2eq;
 
         FilterOutputs {
-            lowpass: v2,
-            bandpass: v1,
-            highpass: sample - k * v1 - v2,
-            notch: sample - k * v1,
+            lowpass: v2 as f32,
+            bandpass: v1 as f32,
+            highpass: (x - k * v1 - v2) as f32,
+            notch: (x - k * v1) as f32,
         }
     }
 
     pub fn render(&mut self, buffer: &mut [f32], ctx: &RenderCtx) {
-        let g = self.compute_g(ctx);
+        let g = Self::compute_g(self.cutoff_hz, ctx.sample_rate);
         let k = 2.0 - (2.0 * self.resonance);
 
         for sample in buffer.iter_mut() {
             let outputs = self.next_sample(*sample, k, g);
+            *sample = self.select(outputs);
+        }
+    }
+
+    /// Render while the cutoff ramps, recomputing `g` every sample
+    ///
+    /// `log2_cutoff` is the cutoff in octaves (log2 Hz): ramping in pitch
+    /// rather than Hz keeps a 200 Hz → 5 kHz sweep from lurching at the low
+    /// end. Once the ramp has settled this is the same as `render` (one
+    /// `tan` per block); only the ramp itself pays per sample.
+    pub fn render_smoothed(&mut self, buffer: &mut [f32], ctx: &RenderCtx, log2_cutoff: &mut SmoothedParam) {
+        if !log2_cutoff.is_smoothing() {
+            self.render(buffer, ctx);
+            return;
+        }
+
+        let k = 2.0 - (2.0 * self.resonance);
+        for sample in buffer.iter_mut() {
+            self.cutoff_hz = log2_cutoff.next_value().exp2();
+            let g = Self::compute_g(self.cutoff_hz, ctx.sample_rate);
+            let outputs = self.next_sample(*sample, k, g);
+            *sample = self.select(outputs);
+        }
+    }
+
+    /// Render with a separate cutoff for every sample (audio-rate filter FM)
+    ///
+    /// `cutoff_hz[i]` applies to `buffer[i]`; both must be the same length.
+    /// Costs a `tan` per sample, like a ramp in `render_smoothed`.
+    pub fn render_cutoff_buffer(&mut self, buffer: &mut [f32], ctx: &RenderCtx, cutoff_hz: &[f32]) {
+        debug_assert_eq!(buffer.len(), cutoff_hz.len());
+        let k = 2.0 - (2.0 * self.resonance);
+        for (sample, &cutoff) in buffer.iter_mut().zip(cutoff_hz) {
+            let g = Self::compute_g(cutoff, ctx.sample_rate);
+            let outputs = self.next_sample(*sample, k, g);
+            *sample = self.select(outputs);
+        }
+        if let Some(&last) = cutoff_hz.last() {
+            self.cutoff_hz = last;
+        }
+    }
 
-            *sample = match self.filter_type {
-                FilterType::LowPass => outputs.lowpass,
-                FilterType::HighPass => outputs.highpass,
-                FilterType::BandPass => outputs.bandpass,
-                FilterType::Notch => outputs.notch,
-            }
+    #[inline]
+    fn select(&self, outputs: FilterOutputs) -> f32 {
+        match self.filter_type {
+            FilterType::LowPass => outputs.lowpass,
+            FilterType::HighPass => outputs.highpass,
+            FilterType::BandPass => outputs.bandpass,
+            FilterType::Notch => outputs.notch,
         }
     }
 
@@ -268,6 +316,32 @@ impl SVFilter {
     pub fn set_resonance(&mut self, resonance: f32) {
         self.resonance = resonance;
     }
+
+    /// Magnitude response (dB) at `freq_hz`, computed from the coefficients
+    ///
+    /// The TPT filter is exactly the analog prototype warped by the bilinear
+    /// transform, so evaluate H(s) at s = jΩ with the prewarped frequency
+    /// Ω = tan(π·f/fs) / tan(π·fc/fs):
+    ///
+    ///   lowpass  1 / (s² + ks + 1)      bandpass  s / (s² + ks + 1)
+    ///   highpass s² / (s² + ks + 1)     notch     (s² + 1) / (s² + ks + 1)
+    pub fn magnitude_db(&self, freq_hz: f32, sample_rate: f32) -> f32 {
+        let pi_over_fs = std::f64::consts::PI / sample_rate as f64;
+        let omega = (pi_over_fs * freq_hz as f64).tan() / (pi_over_fs * self.cutoff_hz as f64).tan();
+        let k = 2.0 - 2.0 * self.resonance as f64;
+
+        // |s² + ks + 1| at s = jΩ
+        let (re, im) = (1.0 - omega * omega, k * omega);
+        let denominator = (re * re + im * im).sqrt();
+        let numerator = match self.filter_type {
+            FilterType::LowPass => 1.0,
+            FilterType::HighPass => omega * omega,
+            FilterType::BandPass => omega,
+            FilterType::Notch => re.abs(),
+        };
+
+        (20.0 * (numerator / denominator).max(1e-12).log10()) as f32
+    }
 }
 
 #[cfg(test)]
@@ -285,6 +359,19 @@ mod tests {
             .fold(0.0f32, |acc, &x| acc.max(x.abs()))
     }
 
+    #[test]
+    fn test_low_cutoff_lowpass_settles_to_dc() {
+        // 20 Hz at 96 kHz: tiny g, where integrator precision matters most
+        let mut filter = SVFilter::lowpass(20.0);
+        let ctx = RenderCtx::from_freq(96_000.0, 440.0, 100.0);
+        let mut buffer = vec![1.0; 96_000];
+
+        filter.render(&mut buffer, &ctx);
+
+        let tolerance = if cfg!(feature = "f64") { 1e-6 } else { 1e-3 };
+        assert!((buffer[95_999] - 1.0).abs() < tolerance, "settled at {}", buffer[95_999]);
+    }
+
     #[test]
     fn test_lowpass_basic() {
         let mut filter = SVFilter::lowpass(500.0);
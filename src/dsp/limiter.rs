@@ -0,0 +1,290 @@
+//! Lookahead brick-wall limiting with a true-peak ceiling.
+
+/*
+Lookahead Limiter
+=================
+
+A limiter is a compressor with an infinite ratio: nothing gets past the
+CEILING. The hard part is doing that without distortion. Clamping a peak
+(`OutputStage::HardClamp`) squares it off; turning the gain down only once
+the peak has arrived is already too late.
+
+A lookahead limiter cheats by delaying the audio. It sees every peak a few
+milliseconds before it plays, and has that long to fade the gain down:
+
+  detector  ──────╱╲───────────            sees the peak at t
+  gain      ‾‾‾‾‾╲___╱‾‾‾‾‾‾‾‾‾‾‾‾         already down when...
+  output    ────────────╱╲────────         ...the delayed peak plays at t + L
+
+The price is LATENCY: the output is `lookahead` late. The renderer reports
+it (`Renderer::latency_samples`) so clocks and, later, other tracks can be
+lined up.
+
+
+How the Gain Is Computed
+------------------------
+
+For every incoming sample the gain it would need is
+
+    required = min(1, ceiling / |peak|)
+
+1. Hold the minimum over the last L + 1 samples: while a peak is anywhere
+   in the lookahead window, the gain aims at least that low. A monotonic
+   queue keeps the minimum current in constant time per sample instead of
+   rescanning the window.
+2. Release: when the window empties, the gain rises back toward 1 slowly
+   (`release_secs`) instead of jumping.
+3. Average the result over L samples (a box filter). This turns each
+   step down into a straight ramp lasting exactly the lookahead - the
+   fade - and since every value averaged is already at or below what the
+   peak needs, the gain is all the way down when the peak plays.
+
+Steps 1 and 3 together guarantee the ceiling: no overshoot, ever.
+
+
+True Peak
+---------
+
+The DAC draws a smooth curve through the samples, and that curve can
+peak between two samples above either of them (see `dsp/meter.rs`). The
+detector also estimates these inter-sample peaks with the same 4x cubic
+interpolation as `meter::true_peak`, so a -1 dBTP ceiling holds for the
+analog output, not just the sample values.
+*/
+
+use super::meter::peak;
+
+/// Longest supported lookahead
+pub const MAX_LOOKAHEAD_SECS: f32 = 0.005;
+/// Lookahead used by `LookaheadLimiter::new` callers that don't care
+pub const DEFAULT_LOOKAHEAD_SECS: f32 = 0.003;
+/// Release used by `LookaheadLimiter::new` callers that don't care
+pub const DEFAULT_RELEASE_SECS: f32 = 0.1;
+/// Ring size: 5 ms at 192kHz, plus the detector's extra samples
+const MAX_LOOKAHEAD_SAMPLES: usize = 960 + 4;
+
+/// Brick-wall limiter with lookahead (pre-allocated, RT-safe)
+#[derive(Clone)]
+pub struct LookaheadLimiter {
+    ceiling: f32,
+    release_coef: f32,
+    /// Lookahead in samples (the latency)
+    lookahead: usize,
+
+    /// Delayed audio
+    audio: [f32; MAX_LOOKAHEAD_SAMPLES],
+    /// Minimum of the gains recent samples needed
+    minimum: RunningMin,
+    /// Held-and-released gain, averaged into the output gain
+    held: [f32; MAX_LOOKAHEAD_SAMPLES],
+    held_sum: f64,
+    /// Last held value, for the release
+    release: f32,
+    /// Last four inputs, for the inter-sample peak estimate
+    history: [f32; 4],
+    pos: usize,
+}
+
+impl LookaheadLimiter {
+    /// Limit to `ceiling_db` (true peak) with `lookahead_secs` of latency
+    pub fn new(ceiling_db: f32, lookahead_secs: f32, release_secs: f32, sample_rate: f32) -> Self {
+        let mut limiter = Self {
+            ceiling: 1.0,
+            release_coef: 0.0,
+            lookahead: 1,
+            audio: [0.0; MAX_LOOKAHEAD_SAMPLES],
+            minimum: RunningMin::new(),
+            held: [1.0; MAX_LOOKAHEAD_SAMPLES],
+            held_sum: 0.0,
+            release: 1.0,
+            history: [0.0; 4],
+            pos: 0,
+        };
+        limiter.set_ceiling_db(ceiling_db);
+        limiter.configure(lookahead_secs, release_secs, sample_rate);
+        limiter
+    }
+
+    /// Change lookahead and release (RT-safe; clears the delay)
+    pub fn configure(&mut self, lookahead_secs: f32, release_secs: f32, sample_rate: f32) {
+        let lookahead = (lookahead_secs.clamp(0.0, MAX_LOOKAHEAD_SECS) * sample_rate).round() as usize;
+        self.lookahead = lookahead.clamp(1, MAX_LOOKAHEAD_SAMPLES - 4);
+        self.release_coef = if release_secs > 0.0 {
+            (-1.0 / (release_secs * sample_rate)).exp()
+        } else {
+            0.0
+        };
+        self.reset();
+    }
+
+    pub fn set_ceiling_db(&mut self, ceiling_db: f32) {
+        self.ceiling = 10f32.powf(ceiling_db.min(0.0) / 20.0);
+    }
+
+    /// Delay the limiter adds, in samples
+    pub fn latency_samples(&self) -> usize {
+        self.lookahead
+    }
+
+    pub fn reset(&mut self) {
+        self.audio.fill(0.0);
+        self.minimum.reset();
+        self.held.fill(1.0);
+        self.held_sum = self.lookahead as f64;
+        self.release = 1.0;
+        self.history = [0.0; 4];
+        self.pos = 0;
+    }
+
+    /// Process one sample; the output is `latency_samples` late
+    pub fn process(&mut self, input: f32) -> f32 {
+        let len = self.audio.len();
+        let at = |back: usize| (self.pos + len - back) % len;
+
+        // 1. Gain this sample (and the curve just before it) needs
+        self.history = [self.history[1], self.history[2], self.history[3], input];
+        let level = inter_sample_peak(&self.history).max(input.abs());
+        let required = if level > self.ceiling { self.ceiling / level } else { 1.0 };
+
+        // 2. Hold the minimum over the window (the estimate above lags a
+        //    sample behind, so the window reaches two samples further),
+        //    then release slowly
+        let target = self.minimum.push(required, self.lookahead + 3);
+        self.release = if target < self.release {
+            target
+        } else {
+            target + self.release_coef * (self.release - target)
+        };
+
+        // 3. Average over the lookahead
+        self.held_sum += (self.release - self.held[at(self.lookahead)]) as f64;
+        self.held[self.pos] = self.release;
+        let gain = (self.held_sum / self.lookahead as f64) as f32;
+
+        self.audio[self.pos] = input;
+        let output = self.audio[at(self.lookahead)] * gain.min(1.0);
+        self.pos = (self.pos + 1) % len;
+        output
+    }
+
+    /// Process a buffer in place
+    pub fn render(&mut self, buffer: &mut [f32]) {
+        for sample in buffer.iter_mut() {
+            *sample = self.process(*sample);
+        }
+    }
+}
+
+/// Minimum over a sliding window, O(1) amortized per sample (RT-safe)
+///
+/// A monotonic queue: values stay in arrival order and increase from the
+/// front, so the front is the window's minimum. A new value evicts every
+/// queued value it is not above, since those can never be the minimum
+/// again; the front leaves once it falls out of the window.
+#[derive(Clone)]
+struct RunningMin {
+    /// (sample number, value) ring; holds at most one window
+    queue: [(usize, f32); MAX_LOOKAHEAD_SAMPLES],
+    head: usize,
+    len: usize,
+    /// Samples pushed so far
+    count: usize,
+}
+
+impl RunningMin {
+    fn new() -> Self {
+        Self {
+            queue: [(0, 1.0); MAX_LOOKAHEAD_SAMPLES],
+            head: 0,
+            len: 0,
+            count: 0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.head = 0;
+        self.len = 0;
+        self.count = 0;
+    }
+
+    /// Add a value; returns the minimum of the last `window` values
+    /// (`window` at most `MAX_LOOKAHEAD_SAMPLES`)
+    fn push(&mut self, value: f32, window: usize) -> f32 {
+        let capacity = self.queue.len();
+        while self.len > 0 && self.queue[(self.head + self.len - 1) % capacity].1 >= value {
+            self.len -= 1;
+        }
+        while self.len > 0 && self.count.wrapping_sub(self.queue[self.head].0) >= window {
+            self.head = (self.head + 1) % capacity;
+            self.len -= 1;
+        }
+        self.queue[(self.head + self.len) % capacity] = (self.count, value);
+        self.len += 1;
+        self.count = self.count.wrapping_add(1);
+        self.queue[self.head].1
+    }
+}
+
+/// Largest 4x cubic-interpolated value between the middle two of four samples
+#[inline]
+fn inter_sample_peak(w: &[f32; 4]) -> f32 {
+    let (y0, y1, y2, y3) = (w[0], w[1], w[2], w[3]);
+    let mut max = peak(&w[1..3]);
+    for t in [0.25f32, 0.5, 0.75] {
+        let t2 = t * t;
+        let t3 = t2 * t;
+        let value = 0.5
+            * ((2.0 * y1)
+                + (-y0 + y2) * t
+                + (2.0 * y0 - 5.0 * y1 + 4.0 * y2 - y3) * t2
+                + (-y0 + 3.0 * y1 - 3.0 * y2 + y3) * t3);
+        max = max.max(value.abs());
+    }
+    max
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::meter::{to_db, true_peak};
+
+    #[test]
+    fn holds_the_true_peak_ceiling_without_touching_quiet_audio() {
+        let sample_rate = 48_000.0;
+        let mut limiter = LookaheadLimiter::new(-1.0, 0.003, 0.05, sample_rate);
+        let latency = limiter.latency_samples();
+        assert_eq!(latency, 144);
+
+        // Quiet passage, a loud near-Nyquist burst, quiet again
+        let input: Vec<f32> = (0..24_000)
+            .map(|i| {
+                let amplitude = if (8_000..12_000).contains(&i) { 2.0 } else { 0.3 };
+                amplitude * (std::f32::consts::TAU * 11_025.0 * i as f32 / sample_rate + 0.3).sin()
+            })
+            .collect();
+        let mut output = input.clone();
+        limiter.render(&mut output);
+
+        assert!(to_db(true_peak(&output)) <= -1.0 + 0.05, "{} dBTP", to_db(true_peak(&output)));
+        // Before the burst the audio is just delayed
+        for i in latency..7_000 {
+            assert_eq!(output[i], input[i - latency]);
+        }
+        // ...and it recovers after the release
+        assert!((peak(&output[20_000..]) - peak(&input[20_000..])).abs() < 0.01);
+    }
+
+    #[test]
+    fn running_min_matches_a_window_scan() {
+        let mut minimum = RunningMin::new();
+        let mut rng = crate::dsp::rng::Rng::new(7);
+        let values: Vec<f32> = (0..5_000).map(|_| rng.next_f32()).collect();
+        for window in [1, 3, 147] {
+            minimum.reset();
+            for (i, &value) in values.iter().enumerate() {
+                let scan = values[i.saturating_sub(window - 1)..=i].iter().copied().fold(f32::MAX, f32::min);
+                assert_eq!(minimum.push(value, window), scan, "window {window} at {i}");
+            }
+        }
+    }
+}
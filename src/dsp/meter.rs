@@ -0,0 +1,428 @@
+//! Level and loudness metering (peak, RMS, true peak, LUFS).
+
+/*
+Metering
+========
+
+Meters answer "how loud is this?" - but there are several different answers.
+
+  peak         Largest |sample|. Tells you about clipping, not loudness.
+
+  true peak    Largest value of the CONTINUOUS waveform the DAC will
+               reconstruct. Between two samples the analog signal can
+               overshoot both of them:
+
+                    ·  ← reconstructed peak (> 1.0!)
+                  ╱   ╲
+               ●         ●        ● = samples, both ≤ 1.0
+                                  A sample-peak meter says "safe", the DAC clips.
+
+               We estimate it by interpolating 4 points between each pair of
+               samples (4x oversampling) and taking the max.
+
+  RMS          Root-mean-square: sqrt(average of x²) over a window. Tracks
+               signal ENERGY, which is closer to perceived loudness than
+               peak. A full-scale sine has peak 1.0 but RMS 0.707 (-3 dB).
+
+  LUFS         Loudness Units relative to Full Scale (ITU-R BS.1770). RMS,
+               but with a filter in front that mimics the ear's sensitivity
+               ("K-weighting"), measured in 400ms blocks:
+
+                 x ──→ [high shelf +4dB above ~1.7kHz] ──→ [highpass 38Hz] ──→ mean(x²)
+
+                 loudness = -0.691 + 10 × log10(mean square)
+
+               Streaming services normalize to roughly -14 LUFS; a 0 dBFS
+               1kHz sine measures -3.01 LUFS.
+
+
+Momentary, Short-Term, Integrated
+---------------------------------
+
+  momentary    last 400ms           (fast, jumpy - for meters)
+  short-term   last 3s              (smoothed - for mixing)
+  integrated   the whole program    (one number for the track)
+
+Integrated loudness is GATED so silence and quiet passages don't drag it
+down: 400ms blocks below -70 LUFS are ignored, then anything more than
+10 LU below the average of the rest is ignored too.
+
+We measure in 100ms steps (75% overlap of 400ms blocks) and keep a
+fixed histogram of block loudness for gating instead of storing every
+block - so the meter never allocates after construction.
+
+
+Simplifications
+---------------
+
+- Mono only (one channel, weight 1.0).
+- True peak uses cubic interpolation, not the standard's polyphase FIR.
+  Can under-read by ~1 dB for content near fs/4 and above, much less
+  for typical musical material.
+*/
+
+use std::f64::consts::PI;
+
+/// Convert a linear amplitude to decibels (floored at -120 dB)
+#[inline]
+pub fn to_db(linear: f32) -> f32 {
+    20.0 * linear.max(1e-6).log10()
+}
+
+/// Largest absolute sample value
+pub fn peak(buffer: &[f32]) -> f32 {
+    buffer.iter().fold(0.0f32, |acc, &x| acc.max(x.abs()))
+}
+
+/// Root-mean-square level of a buffer
+pub fn rms(buffer: &[f32]) -> f32 {
+    if buffer.is_empty() {
+        return 0.0;
+    }
+    let sum_sq: f64 = buffer.iter().map(|&x| (x as f64) * (x as f64)).sum();
+    (sum_sq / buffer.len() as f64).sqrt() as f32
+}
+
+/// Estimate the inter-sample (true) peak with 4x cubic interpolation
+pub fn true_peak(buffer: &[f32]) -> f32 {
+    let mut max = peak(buffer);
+    if buffer.len() < 4 {
+        return max;
+    }
+
+    for w in buffer.windows(4) {
+        let (y0, y1, y2, y3) = (w[0], w[1], w[2], w[3]);
+        // Catmull-Rom between y1 and y2 at t = 0.25, 0.5, 0.75
+        for t in [0.25f32, 0.5, 0.75] {
+            let t2 = t * t;
+            let t3 = t2 * t;
+            let value = 0.5
+                * ((2.0 * y1)
+                    + (-y0 + y2) * t
+                    + (2.0 * y0 - 5.0 * y1 + 4.0 * y2 - y3) * t2
+                    + (-y0 + 3.0 * y1 - 3.0 * y2 + y3) * t3);
+            max = max.max(value.abs());
+        }
+    }
+    max
+}
+
+/// RMS over a sliding window of the most recent samples
+pub struct RmsMeter {
+    squares: Vec<f32>,
+    pos: usize,
+    sum: f64,
+}
+
+impl RmsMeter {
+    /// Create a meter averaging over `window_secs` (300ms is a typical VU-style window)
+    pub fn new(sample_rate: f32, window_secs: f32) -> Self {
+        let len = ((sample_rate * window_secs) as usize).max(1);
+        Self {
+            squares: vec![0.0; len],
+            pos: 0,
+            sum: 0.0,
+        }
+    }
+
+    /// Feed samples into the window
+    pub fn process(&mut self, buffer: &[f32]) {
+        for &x in buffer {
+            let sq = x * x;
+            self.sum += sq as f64 - self.squares[self.pos] as f64;
+            self.squares[self.pos] = sq;
+            self.pos += 1;
+            if self.pos == self.squares.len() {
+                self.pos = 0;
+                // Recompute once per window so rounding error can't accumulate
+                self.sum = self.squares.iter().map(|&s| s as f64).sum();
+            }
+        }
+    }
+
+    /// Current RMS level (linear)
+    pub fn level(&self) -> f32 {
+        (self.sum.max(0.0) / self.squares.len() as f64).sqrt() as f32
+    }
+}
+
+/// Second-order IIR section used for K-weighting (direct form I)
+#[derive(Clone, Copy, Default)]
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    x1: f64,
+    x2: f64,
+    y1: f64,
+    y2: f64,
+}
+
+impl Biquad {
+    #[inline]
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2 - self.a1 * self.y1 - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+/// Build the two BS.1770 K-weighting stages for a sample rate
+///
+/// Coefficients are derived from the analog prototypes so any sample rate
+/// works (the standard only tabulates 48kHz).
+fn k_weighting(sample_rate: f64) -> [Biquad; 2] {
+    // Stage 1: high shelf (+4dB, models the head's acoustic effect)
+    let f0 = 1681.974450955533;
+    let gain_db = 3.999843853973347;
+    let q = 0.7071752369554196;
+    let k = (PI * f0 / sample_rate).tan();
+    let vh = 10f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad {
+        b0: (vh + vb * k / q + k * k) / a0,
+        b1: 2.0 * (k * k - vh) / a0,
+        b2: (vh - vb * k / q + k * k) / a0,
+        a1: 2.0 * (k * k - 1.0) / a0,
+        a2: (1.0 - k / q + k * k) / a0,
+        ..Default::default()
+    };
+
+    // Stage 2: highpass ("RLB" curve, removes sub-bass rumble)
+    let f0 = 38.13547087602444;
+    let q = 0.5003270373238773;
+    let k = (PI * f0 / sample_rate).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let highpass = Biquad {
+        b0: 1.0,
+        b1: -2.0,
+        b2: 1.0,
+        a1: 2.0 * (k * k - 1.0) / a0,
+        a2: (1.0 - k / q + k * k) / a0,
+        ..Default::default()
+    };
+
+    [shelf, highpass]
+}
+
+/// 100ms steps per 400ms momentary block
+const MOMENTARY_STEPS: usize = 4;
+/// 100ms steps per 3s short-term window
+const SHORT_TERM_STEPS: usize = 30;
+/// Gating histogram range and resolution (0.1 LU bins from -70 to +5 LUFS)
+const HISTOGRAM_MIN_LUFS: f64 = -70.0;
+const HISTOGRAM_BINS: usize = 750;
+/// Reported for silence / not enough data
+pub const SILENCE_LUFS: f32 = -f32::INFINITY;
+
+/// Simplified ITU-R BS.1770 loudness meter (mono)
+pub struct LufsMeter {
+    filters: [Biquad; 2],
+    /// Samples per 100ms step
+    step_len: usize,
+    /// Sum of squares in the step being accumulated
+    step_sum: f64,
+    step_count: usize,
+    /// Mean square of the most recent 100ms steps (ring)
+    steps: [f64; SHORT_TERM_STEPS],
+    steps_pos: usize,
+    steps_filled: usize,
+    /// Gating histogram of 400ms block loudness
+    histogram: [u32; HISTOGRAM_BINS],
+}
+
+impl LufsMeter {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            filters: k_weighting(sample_rate as f64),
+            step_len: ((sample_rate * 0.1) as usize).max(1),
+            step_sum: 0.0,
+            step_count: 0,
+            steps: [0.0; SHORT_TERM_STEPS],
+            steps_pos: 0,
+            steps_filled: 0,
+            histogram: [0; HISTOGRAM_BINS],
+        }
+    }
+
+    /// Feed samples (REAL-TIME SAFE: no allocation)
+    pub fn process(&mut self, buffer: &[f32]) {
+        for &x in buffer {
+            let shelved = self.filters[0].process(x as f64);
+            let weighted = self.filters[1].process(shelved);
+            self.step_sum += weighted * weighted;
+            self.step_count += 1;
+
+            if self.step_count == self.step_len {
+                self.push_step(self.step_sum / self.step_len as f64);
+                self.step_sum = 0.0;
+                self.step_count = 0;
+            }
+        }
+    }
+
+    fn push_step(&mut self, mean_square: f64) {
+        self.steps[self.steps_pos] = mean_square;
+        self.steps_pos = (self.steps_pos + 1) % SHORT_TERM_STEPS;
+        self.steps_filled = (self.steps_filled + 1).min(SHORT_TERM_STEPS);
+
+        // Every step completes a new (75% overlapped) 400ms block
+        if self.steps_filled >= MOMENTARY_STEPS {
+            let block = loudness(self.mean_of_last(MOMENTARY_STEPS));
+            if block > HISTOGRAM_MIN_LUFS {
+                let bin = ((block - HISTOGRAM_MIN_LUFS) * 10.0) as usize;
+                self.histogram[bin.min(HISTOGRAM_BINS - 1)] += 1;
+            }
+        }
+    }
+
+    fn mean_of_last(&self, count: usize) -> f64 {
+        let sum: f64 = (1..=count)
+            .map(|back| self.steps[(self.steps_pos + SHORT_TERM_STEPS - back) % SHORT_TERM_STEPS])
+            .sum();
+        sum / count as f64
+    }
+
+    /// Loudness of the last 400ms
+    pub fn momentary(&self) -> f32 {
+        if self.steps_filled < MOMENTARY_STEPS {
+            return SILENCE_LUFS;
+        }
+        loudness(self.mean_of_last(MOMENTARY_STEPS)) as f32
+    }
+
+    /// Loudness of the last 3s
+    pub fn short_term(&self) -> f32 {
+        if self.steps_filled < SHORT_TERM_STEPS {
+            return SILENCE_LUFS;
+        }
+        loudness(self.mean_of_last(SHORT_TERM_STEPS)) as f32
+    }
+
+    /// Gated loudness of everything measured so far
+    pub fn integrated(&self) -> f32 {
+        let bin_energy = |bin: usize| energy(HISTOGRAM_MIN_LUFS + (bin as f64 + 0.5) / 10.0);
+
+        // Absolute gate already applied (only blocks above -70 LUFS are binned)
+        let (mut sum, mut count) = (0.0, 0u64);
+        for (bin, &n) in self.histogram.iter().enumerate() {
+            sum += bin_energy(bin) * n as f64;
+            count += n as u64;
+        }
+        if count == 0 {
+            return SILENCE_LUFS;
+        }
+
+        // Relative gate: drop blocks more than 10 LU below the ungated average
+        let relative_gate = loudness(sum / count as f64) - 10.0;
+        let first_bin = (((relative_gate - HISTOGRAM_MIN_LUFS) * 10.0).max(0.0) as usize).min(HISTOGRAM_BINS);
+        let (mut sum, mut count) = (0.0, 0u64);
+        for (bin, &n) in self.histogram.iter().enumerate().skip(first_bin) {
+            sum += bin_energy(bin) * n as f64;
+            count += n as u64;
+        }
+        if count == 0 {
+            return SILENCE_LUFS;
+        }
+        loudness(sum / count as f64) as f32
+    }
+
+    /// Clear all history (e.g. when playback restarts)
+    pub fn reset(&mut self) {
+        for filter in &mut self.filters {
+            filter.x1 = 0.0;
+            filter.x2 = 0.0;
+            filter.y1 = 0.0;
+            filter.y2 = 0.0;
+        }
+        self.step_sum = 0.0;
+        self.step_count = 0;
+        self.steps = [0.0; SHORT_TERM_STEPS];
+        self.steps_pos = 0;
+        self.steps_filled = 0;
+        self.histogram = [0; HISTOGRAM_BINS];
+    }
+}
+
+/// BS.1770 loudness of a K-weighted mean square
+#[inline]
+fn loudness(mean_square: f64) -> f64 {
+    -0.691 + 10.0 * mean_square.max(1e-20).log10()
+}
+
+/// Inverse of `loudness`
+#[inline]
+fn energy(lufs: f64) -> f64 {
+    10f64.powf((lufs + 0.691) / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::TAU;
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    fn sine(freq: f32, amplitude: f32, seconds: f32) -> Vec<f32> {
+        let len = (SAMPLE_RATE * seconds) as usize;
+        (0..len)
+            .map(|i| amplitude * (TAU * freq * i as f32 / SAMPLE_RATE).sin())
+            .collect()
+    }
+
+    #[test]
+    fn sine_rms_is_minus_three_db() {
+        let signal = sine(1000.0, 1.0, 1.0);
+        assert!((rms(&signal) - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-3);
+        assert!((to_db(rms(&signal)) + 3.01).abs() < 0.02);
+    }
+
+    #[test]
+    fn true_peak_catches_intersample_overs() {
+        // fs/4 sine at 45° phase: samples sit at ±0.707, the waveform peaks at 1.0
+        let signal: Vec<f32> = (0..64)
+            .map(|i| (TAU * 0.25 * i as f32 + TAU / 8.0).sin())
+            .collect();
+
+        assert!(peak(&signal) < 0.75);
+        // Cubic interpolation recovers most (not all) of the overshoot
+        assert!(true_peak(&signal) > 0.85);
+    }
+
+    #[test]
+    fn rms_meter_tracks_window() {
+        let mut meter = RmsMeter::new(SAMPLE_RATE, 0.3);
+        meter.process(&sine(1000.0, 0.5, 1.0));
+        assert!((meter.level() - 0.5 * std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-3);
+
+        meter.process(&vec![0.0; SAMPLE_RATE as usize]);
+        assert!(meter.level() < 1e-4);
+    }
+
+    #[test]
+    fn full_scale_1khz_sine_is_minus_3_lufs() {
+        let mut meter = LufsMeter::new(SAMPLE_RATE);
+        meter.process(&sine(1000.0, 1.0, 5.0));
+
+        assert!((meter.momentary() + 3.01).abs() < 0.1, "momentary {}", meter.momentary());
+        assert!((meter.short_term() + 3.01).abs() < 0.1, "short-term {}", meter.short_term());
+        assert!((meter.integrated() + 3.01).abs() < 0.1, "integrated {}", meter.integrated());
+    }
+
+    #[test]
+    fn integrated_gates_silence() {
+        let mut meter = LufsMeter::new(SAMPLE_RATE);
+        meter.process(&sine(1000.0, 0.1, 5.0));
+        let before = meter.integrated();
+
+        meter.process(&vec![0.0; SAMPLE_RATE as usize * 10]);
+        assert!((meter.integrated() - before).abs() < 0.2);
+        assert!(meter.momentary() < -70.0);
+    }
+}
@@ -324,7 +324,7 @@ mod tests {
     fn test_apply_dry_wet_all_wet() {
         let dry = [0.0, 0.0, 0.0, 0.0];
         let mut wet = [1.0, 0.5, -0.5, -1.0];
-        let original = wet.clone();
+        let original = wet;
 
         apply_dry_wet(&dry, &mut wet, 1.0);
 
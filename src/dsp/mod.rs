@@ -10,26 +10,69 @@
 
 /// Signal multiplication for amplitude control and ring modulation.
 pub mod amplify;
+/// Analysis tools for UIs and tests (spectrum; FFT parts need `rustfft`).
+pub mod analysis;
+/// Multi-hit burst envelope for claps.
+pub mod burst;
 /// Time-domain delay line with optional interpolation.
 pub mod delay;
+/// Flush-to-zero guard and anti-denormal offset for feedback paths.
+pub mod denormal;
 /// Waveshaping distortion (soft clip, hard clip, foldback).
 pub mod distortion;
+/// Envelope follower and gain computer for dynamics processors.
+pub mod dynamics;
 /// Attack/decay/sustain/release envelope generator.
 pub mod envelope;
 /// State-variable filter implementation with multiple responses.
 pub mod filter;
 /// Low frequency oscillator concepts (control-rate vs audio-rate).
 pub mod lfo;
+/// Lookahead brick-wall limiter with a true-peak ceiling.
+pub mod limiter;
+/// Level and loudness metering (peak, RMS, true peak, LUFS).
+pub mod meter;
 /// Signal mixing and crossfading.
 pub mod mix;
 /// Parameter modulation (LFO → filter cutoff, etc).
 pub mod modulate;
 /// Oscillator waveforms and noise sources.
 pub mod oscillator;
+/// One-shot exponential pitch drop for drum synthesis.
+pub mod pitch_envelope;
+/// Realtime pitch shifting with crossfaded sliding delay taps.
+pub mod pitch_shift;
+/// Seedable xorshift PRNG and per-component stream derivation.
+pub mod rng;
 /// Reverb via comb and allpass filter networks.
 pub mod reverb;
+/// SIMD block kernels (mix, gain, soft clip, phase) with scalar fallbacks.
+pub mod simd;
+/// Slew limiting with independent rise and fall rates.
+pub mod slew;
+/// Parameter smoothing (linear and exponential ramps) against zipper noise.
+pub mod smooth;
+/// Spring reverb via dispersive allpass chains.
+pub mod spring;
+/// Granular time-stretch (speed change without pitch change).
+pub mod stretch;
+/// Step-sequenced modulation shapes with per-step glide.
+pub mod step_lfo;
 /// Serial signal chain concepts.
 pub mod through;
+/// Pitch math: MIDI notes, hertz, semitones, cents, and intervals.
+pub mod tuning;
 
 pub use envelope::EnvelopeState;
-pub use oscillator::Waveform;
+pub use oscillator::{PhaseMode, Waveform};
+
+/// Precision of recursive state (filter integrators, reverb feedback).
+///
+/// Buffers passed in and out stay `f32`; only the state that accumulates
+/// error sample after sample is widened. Enable the `f64` feature when a
+/// low-cutoff filter or a long reverb tail drifts or sounds grainy.
+#[cfg(feature = "f64")]
+pub type Real = f64;
+/// Precision of recursive state (filter integrators, reverb feedback).
+#[cfg(not(feature = "f64"))]
+pub type Real = f32;
@@ -92,6 +92,11 @@ For an LFO at 5 Hz with 512-sample blocks at 48kHz:
 
 That's plenty smooth for most musical applications.
 
+The exception is audio-rate modulation (an oscillator sweeping a filter's
+cutoff hundreds of times a second, "filter FM"): averaging over a block
+throws the whole effect away. `FilterNode::with_cutoff_fm` opts into
+sample-rate cutoff updates for exactly that case.
+
 
 Averaging vs Sampling
 ---------------------
@@ -126,8 +131,61 @@ modulation where the parameter hits its limit.
 Design your base value and depth so the modulated range stays valid:
     cutoff range: [500, 4500] with base=2500, depth=2000 ✓
     cutoff range: [-500, 1500] with base=500, depth=1000 ✗ (hits negative)
+
+
+Polarity and Response Curves
+----------------------------
+
+Each route can flip and bend its modulator before the depth is applied:
+
+    inverted      modulator × -1. An envelope (0 → 1 → 0) now CLOSES a
+                  filter on every note instead of opening it.
+
+    Linear        the modulator as-is.
+
+    Exponential   slow near zero, fast near ±1:
+
+                      shape(x) = sign(x) × (e^(k|x|) - 1) / (e^k - 1),  k = 4
+
+                  Ends stay at 0 and ±1, so depth keeps its meaning. An
+                  envelope's decay then spends longer near the top, which
+                  sounds like an analog filter sweep rather than a ramp.
+
+    x (modulator) 0.0   0.25   0.5    0.75   1.0
+    Linear        0.0   0.25   0.5    0.75   1.0
+    Exponential   0.0   0.03   0.12   0.36   1.0
 */
 
+/// Steepness of `ModCurve::Exponential`
+const EXP_CURVE_K: f32 = 4.0;
+
+/// How a modulation route maps its modulator (see module docs)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ModCurve {
+    /// Modulator used as-is
+    #[default]
+    Linear,
+    /// Slow near zero, fast near ±1 (ends unchanged)
+    Exponential,
+}
+
+/// Apply a route's polarity and response curve to a modulator value
+#[inline]
+pub fn shape_modulator(modulator: f32, curve: ModCurve, inverted: bool) -> f32 {
+    let shaped = match curve {
+        ModCurve::Linear => modulator,
+        ModCurve::Exponential => {
+            let magnitude = ((EXP_CURVE_K * modulator.abs()).exp() - 1.0) / (EXP_CURVE_K.exp() - 1.0);
+            magnitude.copysign(modulator)
+        }
+    };
+    if inverted {
+        -shaped
+    } else {
+        shaped
+    }
+}
+
 /// Calculate the modulated parameter value.
 ///
 /// # Arguments
@@ -182,6 +240,17 @@ mod tests {
         assert_eq!(apply_modulation(1000.0, 0.5, 500.0), 1250.0);
     }
 
+    #[test]
+    fn test_shape_modulator() {
+        assert_eq!(shape_modulator(0.5, ModCurve::Linear, true), -0.5);
+        // Exponential keeps the ends and sags in between, symmetrically
+        assert_eq!(shape_modulator(1.0, ModCurve::Exponential, false), 1.0);
+        assert_eq!(shape_modulator(0.0, ModCurve::Exponential, false), 0.0);
+        let half = shape_modulator(0.5, ModCurve::Exponential, false);
+        assert!((half - 0.12).abs() < 0.005, "{half}");
+        assert_eq!(shape_modulator(-0.5, ModCurve::Exponential, true), half);
+    }
+
     #[test]
     fn test_block_average() {
         let samples = [1.0, 2.0, 3.0, 4.0];
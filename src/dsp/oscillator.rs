@@ -2,6 +2,8 @@
 use serde::{Deserialize, Serialize};
 use std::f32::consts::TAU;
 
+use super::rng::{Rng, DEFAULT_SEED};
+use super::simd;
 use crate::graph::node::RenderCtx;
 
 /*
@@ -165,6 +167,27 @@ avoids a conversion every sample for sine waves.
 
 Alternative: Store normalized φ and convert for sin(). Some implementations
 do this. Both approaches work; we chose radians for sine efficiency.
+
+
+Phase on Note-On
+----------------
+
+What should the phase be when a new note starts? Three common answers:
+
+  FreeRunning  Keep spinning from wherever the last note left off. Like an
+               analog VCO that never stops. Every note starts somewhere
+               different - natural for pads, but the attack varies.
+
+  Reset        Jump to phase 0. Every note starts identically - tight,
+               punchy, repeatable (kicks, basses). A sine starts at 0 so
+               it's click-free; a saw starts at -1, which is a deliberate
+               edge.
+
+  Random       Jump to a random phase. Each note differs (like FreeRunning)
+               but independent of timing - layered detuned oscillators
+               don't line up and "phase" on every attack.
+
+Random draws from the oscillator's seeded Rng, so renders stay repeatable.
 */
 
 /// The shape of the waveform. Each has a distinct timbre (tonal color).
@@ -178,15 +201,24 @@ pub enum Waveform {
     Noise,    // Random - no pitch, for percussion/texture
 }
 
+/// Where the phase goes when a note starts (see "Phase on Note-On")
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PhaseMode {
+    #[default]
+    FreeRunning, // Continue from the previous note
+    Reset,       // Restart at phase 0
+    Random,      // Restart at a random phase
+}
+
 pub struct OscillatorBlock {
     phase: f32,        // Current position in cycle (0 to τ radians)
     waveform: Waveform,
     duty_cycle: f32,   // For square wave: fraction spent "high" (0.0-1.0)
-    noise_state: u32,  // PRNG state for noise waveform
+    rng: Rng,          // PRNG for noise waveform and random phase
+    phase_mode: PhaseMode,
 }
 
-/// Initial seed for noise PRNG - a common choice from numerical recipes
-const NOISE_SEED: u32 = 0x9E3779B9;
 
 impl OscillatorBlock {
     pub fn new(waveform: Waveform) -> Self {
@@ -194,7 +226,8 @@ impl OscillatorBlock {
             phase: 0.0,
             waveform,
             duty_cycle: 0.5,
-            noise_state: NOISE_SEED,
+            rng: Rng::new(DEFAULT_SEED),
+            phase_mode: PhaseMode::FreeRunning,
         }
     }
 
@@ -275,6 +308,12 @@ impl OscillatorBlock {
         // This is how much phase advances per sample
         let phase_inc = TAU * ctx.frequency / ctx.sample_rate;
 
+        // The ramp has a closed form per sample, so it runs as a block kernel
+        if let Waveform::Sawtooth = self.waveform {
+            self.phase = simd::sawtooth(buffer, self.phase, phase_inc);
+            return;
+        }
+
         for sample in buffer.iter_mut() {
             *sample = self.next_sample();
             // Advance and wrap phase using rem_euclid (handles negatives correctly)
@@ -282,32 +321,45 @@ impl OscillatorBlock {
         }
     }
 
-    /// Generate the next pseudo-random noise sample using xorshift32.
-    ///
-    /// Xorshift32 is a fast PRNG with good statistical properties:
-    ///   x ^= x << 13
-    ///   x ^= x >> 17
-    ///   x ^= x << 5
+    /// Render a block with a new frequency every sample
     ///
-    /// We convert the u32 to a float in [-1, +1]:
-    ///   1. Right-shift by 9 to get 23 bits (matches f32 mantissa precision)
-    ///   2. Divide by 2^23 to get [0, 1)
-    ///   3. Scale to [0, 2) and shift to [-1, +1)
-    #[inline]
-    fn next_noise_sample(&mut self) -> f32 {
-        let mut x = self.noise_state;
+    /// `frequency` is called once per sample, for sweeps too fast for
+    /// block-rate modulation (drum pitch drops).
+    pub fn render_swept(&mut self, buffer: &mut [f32], sample_rate: f32, mut frequency: impl FnMut() -> f32) {
+        if sample_rate <= 0.0 {
+            buffer.fill(0.0);
+            return;
+        }
 
-        // Xorshift32 iteration
-        x ^= x << 13;
-        x ^= x >> 17;
-        x ^= x << 5;
+        for sample in buffer.iter_mut() {
+            *sample = self.next_sample();
+            self.phase = (self.phase + TAU * frequency() / sample_rate).rem_euclid(TAU);
+        }
+    }
 
-        self.noise_state = x;
+    /// Choose what happens to the phase on `note_on`
+    pub fn set_phase_mode(&mut self, mode: PhaseMode) {
+        self.phase_mode = mode;
+    }
+
+    /// Apply the phase mode at the start of a note
+    pub fn note_on(&mut self) {
+        match self.phase_mode {
+            PhaseMode::FreeRunning => {}
+            PhaseMode::Reset => self.phase = 0.0,
+            PhaseMode::Random => self.phase = self.rng.next_f32() * TAU,
+        }
+    }
 
-        // Convert to float in [-1, +1]
-        // 8388608 = 2^23 (23-bit mantissa of f32)
-        let normalized = (x >> 9) as f32 / 8388608.0;
-        normalized * 2.0 - 1.0
+    /// Restart the noise generator from `seed` (see `dsp/rng.rs`)
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+
+    /// Next white noise sample in [-1, +1) from the xorshift32 generator.
+    #[inline]
+    fn next_noise_sample(&mut self) -> f32 {
+        self.rng.next_bipolar()
     }
 }
 
@@ -323,4 +375,48 @@ mod tests {
         osc.render(&mut buffer, &ctx);
         assert!(osc.phase < TAU);
     }
+
+    fn first_block(osc: &mut OscillatorBlock) -> [f32; 64] {
+        let ctx = RenderCtx::from_note(48_000.0, 57, 100.0);
+        let mut buffer = [0.0; 64];
+        osc.note_on();
+        osc.render(&mut buffer, &ctx);
+        buffer
+    }
+
+    #[test]
+    fn reset_restarts_every_note_identically() {
+        let mut osc = OscillatorBlock::sawtooth();
+        osc.set_phase_mode(PhaseMode::Reset);
+
+        let first = first_block(&mut osc);
+        let second = first_block(&mut osc);
+        assert_eq!(first, second);
+        assert_eq!(first[0], -1.0);
+    }
+
+    #[test]
+    fn free_running_continues_from_previous_note() {
+        let mut osc = OscillatorBlock::sine();
+
+        let first = first_block(&mut osc);
+        let second = first_block(&mut osc);
+        assert_ne!(first, second);
+        assert_eq!(first[0], 0.0);
+    }
+
+    #[test]
+    fn random_phase_varies_but_replays_with_seed() {
+        let notes = |seed| {
+            let mut osc = OscillatorBlock::sine();
+            osc.set_phase_mode(PhaseMode::Random);
+            osc.set_seed(seed);
+            (0..4).map(|_| first_block(&mut osc)[0]).collect::<Vec<_>>()
+        };
+
+        let starts = notes(3);
+        assert!(starts.windows(2).all(|w| w[0] != w[1]), "{starts:?}");
+        assert_eq!(starts, notes(3));
+        assert_ne!(starts, notes(4));
+    }
 }
@@ -0,0 +1,157 @@
+//! One-shot pitch drop for drum synthesis (kick, tom, zap).
+
+/*
+Pitch Envelopes
+===============
+
+A synthesized kick is mostly a sine wave whose pitch falls fast: it starts
+a couple of octaves up (the "punch" or "click" of the beater) and settles
+on the fundamental (the "body"). The ear hears the drop, not the pitches.
+
+    freq
+     │╲
+     │ ╲
+     │  ╲_
+     │    ‾‾──___
+     │           ‾‾‾‾‾‾‾‾‾‾‾‾‾‾───  end
+     └────────────────────────────→ time
+      │←── decay ──→│
+
+
+Ratios, Not Hertz
+-----------------
+
+The envelope outputs a frequency RATIO, multiplied into the oscillator's
+frequency every sample:
+
+    start_ratio = 3.0, end_ratio = 1.0   →  150 Hz falling to 50 Hz
+                                            on a 50 Hz oscillator
+
+so the same envelope shape works at any tuning.
+
+
+The Curve
+---------
+
+Pitch is perceived logarithmically, so the drop is computed in octaves
+and then converted back to a ratio:
+
+    octaves(t) = log2(end) + (log2(start) - log2(end)) × shape(t / decay)
+    ratio(t)   = 2^octaves(t)
+
+`shape` falls from 1 to 0 over the decay. `curve` bends it:
+
+    curve = 0      shape(x) = 1 - x                   even drop in pitch
+    curve > 0      shape(x) = (e^(-curve·x) - e^(-curve))
+                              / (1 - e^(-curve))      fast drop, long settle
+
+Around 4-8 sounds like an analog kick: most of the drop happens in the
+first quarter of the decay. Both shapes land exactly on `end_ratio` when
+the decay is over, so there is no pitch step at the end.
+
+
+Why Not an ADSR on Frequency?
+-----------------------------
+
+An ADSR modulating frequency works (the presets used to do exactly that),
+but its ramps are linear in Hz and it updates once per block through the
+modulation system. A 256-sample block is 5 ms; a kick's drop is barely
+twenty of those, so the sweep comes out as audible steps. This envelope
+runs every sample and drops in octaves.
+*/
+
+/// Exponential-style pitch drop from `start_ratio` to `end_ratio`
+#[derive(Clone, Copy, Debug)]
+pub struct PitchEnvelope {
+    /// Frequency ratio at the note-on
+    pub start_ratio: f32,
+    /// Frequency ratio once the decay is over
+    pub end_ratio: f32,
+    /// Seconds to get from start to end
+    pub decay_secs: f32,
+    /// Bend of the drop: 0 = even in pitch, higher = faster start
+    pub curve: f32,
+    /// Samples since the note-on (`None` before the first one)
+    elapsed: Option<u32>,
+}
+
+impl PitchEnvelope {
+    pub fn new(start_ratio: f32, end_ratio: f32, decay_secs: f32) -> Self {
+        Self {
+            start_ratio: start_ratio.max(1e-3),
+            end_ratio: end_ratio.max(1e-3),
+            decay_secs: decay_secs.max(0.0),
+            curve: 0.0,
+            elapsed: None,
+        }
+    }
+
+    /// Set the bend of the drop (see module docs)
+    pub fn with_curve(mut self, curve: f32) -> Self {
+        self.curve = curve.max(0.0);
+        self
+    }
+
+    /// Restart the drop from `start_ratio`
+    pub fn trigger(&mut self) {
+        self.elapsed = Some(0);
+    }
+
+    /// Ratio for the next sample
+    ///
+    /// Before the first trigger this is `end_ratio`, so an untriggered
+    /// oscillator sits on its resting pitch.
+    #[inline]
+    pub fn next_ratio(&mut self, sample_rate: f32) -> f32 {
+        let Some(elapsed) = self.elapsed else {
+            return self.end_ratio;
+        };
+        let decay_samples = self.decay_secs * sample_rate;
+        if elapsed as f32 >= decay_samples {
+            return self.end_ratio;
+        }
+        self.elapsed = Some(elapsed + 1);
+
+        let x = elapsed as f32 / decay_samples;
+        let shape = if self.curve < 1e-3 {
+            1.0 - x
+        } else {
+            let floor = (-self.curve).exp();
+            ((-self.curve * x).exp() - floor) / (1.0 - floor)
+        };
+        let (start, end) = (self.start_ratio.log2(), self.end_ratio.log2());
+        (end + (start - end) * shape).exp2()
+    }
+
+    /// Whether the drop is still moving
+    pub fn is_dropping(&self, sample_rate: f32) -> bool {
+        self.elapsed
+            .is_some_and(|elapsed| (elapsed as f32) < self.decay_secs * sample_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_from_start_to_end_over_the_decay() {
+        let sample_rate = 1_000.0;
+        let mut env = PitchEnvelope::new(4.0, 1.0, 0.1).with_curve(5.0);
+        assert_eq!(env.next_ratio(sample_rate), 1.0, "resting before a trigger");
+
+        env.trigger();
+        let ratios: Vec<f32> = (0..120).map(|_| env.next_ratio(sample_rate)).collect();
+        assert!((ratios[0] - 4.0).abs() < 1e-5);
+        assert!(ratios.windows(2).all(|w| w[1] <= w[0]));
+        assert_eq!(ratios[100..], [1.0; 20]);
+        // Curved: more than half the two octaves are gone a quarter of the way in
+        assert!(ratios[25] < 2.0, "{}", ratios[25]);
+
+        // Linear in pitch: halfway through is one octave down
+        let mut even = PitchEnvelope::new(4.0, 1.0, 0.1);
+        even.trigger();
+        let halfway = (0..=50).map(|_| even.next_ratio(sample_rate)).last().unwrap();
+        assert!((halfway - 2.0).abs() < 1e-3, "{halfway}");
+    }
+}
@@ -34,32 +34,73 @@
 //! y[n] = -g * x[n] + x[n - delay] + g * y[n - delay]
 //! ```
 //!
+//! ## Early Reflections
+//!
+//! Before the tail builds up, a real room sends back a handful of distinct
+//! first bounces (floor, nearest walls, ceiling). Their spacing is what the
+//! ear reads as the size of the room. `EarlyReflections` is a tapped delay
+//! line with a fixed, irregular tap pattern, spread out for bigger rooms:
+//!
+//! ```text
+//! Input ──→ [Delay] ──┬── 4 ms × 0.84
+//!                     ├── 8 ms × 0.71    sum ──→ early reflections
+//!                     ├── ...
+//!                     └── 34 ms × -0.30
+//! ```
+//!
+//! Signs alternate so the taps don't pile up into a comb-filtered buzz.
+//!
+//! ## Freeze
+//!
+//! With the comb feedback at exactly 1.0 and no damping, nothing leaves the
+//! loops: whatever is in the reverb when it freezes rings forever, unchanged.
+//! `SchroederReverb::set_frozen` does that (callers stop feeding new input
+//! so the held sound doesn't pile up); unfreezing restores the room.
+//!
 //! # Parameters
 //!
 //! - **Room Size**: Scales all delay times (larger = longer reverb)
 //! - **Damping**: High-frequency absorption (higher = darker sound)
 //! - **Feedback**: Controls reverb decay time
 
+use super::{delay::DelayLine, denormal::ANTI_DENORMAL, Real};
+
 /// Max comb filter delay: 50ms at 192kHz = 9600 samples
 const MAX_COMB_DELAY: usize = 9600;
 /// Max allpass filter delay: 10ms at 192kHz = 1920 samples
 const MAX_ALLPASS_DELAY: usize = 1920;
 
+/// Early reflection taps at room size 0.5: (delay in ms, gain)
+const EARLY_TAPS: [(f32, f32); 8] = [
+    (4.3, 0.84),
+    (7.9, 0.71),
+    (11.2, -0.62),
+    (14.7, 0.55),
+    (19.1, -0.47),
+    (23.3, 0.41),
+    (28.6, 0.36),
+    (34.1, -0.30),
+];
+/// Longest early reflection (last tap at room size 1.0), with room to spare
+const MAX_EARLY_SECS: f32 = 0.06;
+/// Highest sample rate the early reflection delay is sized for
+const MAX_EARLY_SAMPLE_RATE: f32 = 192_000.0;
+
 /// A simple comb filter for reverb (pre-allocated, RT-safe)
 pub struct CombFilter {
-    buffer: [f32; MAX_COMB_DELAY],
+    buffer: Box<[Real]>, // Heap-allocated once (too large for the stack at f64)
     delay_samples: usize,
     write_pos: usize,
-    feedback: f32,
-    damp: f32,
-    filter_state: f32,
+    feedback: Real,
+    damp: Real,
+    filter_state: Real,
 }
 
 impl CombFilter {
     pub fn new(delay_samples: usize) -> Self {
         Self {
-            buffer: [0.0; MAX_COMB_DELAY],
-            delay_samples: delay_samples.min(MAX_COMB_DELAY).max(1),
+            buffer: vec![0.0; MAX_COMB_DELAY].into_boxed_slice(),
+            delay_samples: delay_samples.clamp(1, MAX_COMB_DELAY),
             write_pos: 0,
             feedback: 0.5,
             damp: 0.5,
@@ -68,32 +109,40 @@ impl CombFilter {
     }
 
     pub fn set_feedback(&mut self, feedback: f32) {
-        self.feedback = feedback.clamp(0.0, 0.99);
+        self.feedback = feedback.clamp(0.0, 0.99) as Real;
     }
 
     pub fn set_damp(&mut self, damp: f32) {
-        self.damp = damp.clamp(0.0, 1.0);
+        self.damp = damp.clamp(0.0, 1.0) as Real;
+    }
+
+    /// Lossless loop: feedback 1.0, no damping (see "Freeze" above)
+    pub fn freeze(&mut self) {
+        self.feedback = 1.0;
+        self.damp = 0.0;
     }
 
     /// Set delay length (RT-safe, no allocation)
     pub fn set_delay(&mut self, delay_samples: usize) {
-        self.delay_samples = delay_samples.min(MAX_COMB_DELAY).max(1);
-        self.write_pos = self.write_pos % self.delay_samples;
+        self.delay_samples = delay_samples.clamp(1, MAX_COMB_DELAY);
+        self.write_pos %= self.delay_samples;
     }
 
+    #[allow(clippy::unnecessary_cast)] // Real is f32 unless the `f64` feature is on
     pub fn process(&mut self, input: f32) -> f32 {
         let output = self.buffer[self.write_pos];
 
         // One-pole lowpass filter for damping (absorbs high frequencies)
-        self.filter_state = output * (1.0 - self.damp) + self.filter_state * self.damp;
+        // (offset keeps the decaying tail out of the denormal range)
+        self.filter_state = output * (1.0 - self.damp) + self.filter_state * self.damp + ANTI_DENORMAL as Real;
 
         // Write new sample: input + filtered feedback
-        self.buffer[self.write_pos] = input + self.filter_state * self.feedback;
+        self.buffer[self.write_pos] = input as Real + self.filter_state * self.feedback;
 
         // Advance write position (wrap at actual delay length)
         self.write_pos = (self.write_pos + 1) % self.delay_samples;
 
-        output
+        output as f32
     }
 
     pub fn reset(&mut self) {
@@ -105,45 +154,47 @@ impl CombFilter {
 
 /// An allpass filter for reverb diffusion (pre-allocated, RT-safe)
 pub struct AllpassFilter {
-    buffer: [f32; MAX_ALLPASS_DELAY],
+    buffer: Box<[Real]>, // Heap-allocated once (too large for the stack at f64)
     delay_samples: usize,
     write_pos: usize,
-    feedback: f32,
+    feedback: Real,
 }
 
 impl AllpassFilter {
     pub fn new(delay_samples: usize) -> Self {
         Self {
-            buffer: [0.0; MAX_ALLPASS_DELAY],
-            delay_samples: delay_samples.min(MAX_ALLPASS_DELAY).max(1),
+            buffer: vec![0.0; MAX_ALLPASS_DELAY].into_boxed_slice(),
+            delay_samples: delay_samples.clamp(1, MAX_ALLPASS_DELAY),
             write_pos: 0,
             feedback: 0.5,
         }
     }
 
     pub fn set_feedback(&mut self, feedback: f32) {
-        self.feedback = feedback.clamp(0.0, 0.9);
+        self.feedback = feedback.clamp(0.0, 0.9) as Real;
     }
 
     /// Set delay length (RT-safe, no allocation)
     pub fn set_delay(&mut self, delay_samples: usize) {
-        self.delay_samples = delay_samples.min(MAX_ALLPASS_DELAY).max(1);
-        self.write_pos = self.write_pos % self.delay_samples;
+        self.delay_samples = delay_samples.clamp(1, MAX_ALLPASS_DELAY);
+        self.write_pos %= self.delay_samples;
     }
 
+    #[allow(clippy::unnecessary_cast)] // Real is f32 unless the `f64` feature is on
     pub fn process(&mut self, input: f32) -> f32 {
+        let input = input as Real;
         let delayed = self.buffer[self.write_pos];
 
         // Allpass: output = -g*input + delayed + g*delayed_output
         let output = -self.feedback * input + delayed;
 
         // Write: input + feedback * output
-        self.buffer[self.write_pos] = input + self.feedback * output;
+        self.buffer[self.write_pos] = input + self.feedback * output + ANTI_DENORMAL as Real;
 
         // Advance write position (wrap at actual delay length)
         self.write_pos = (self.write_pos + 1) % self.delay_samples;
 
-        output
+        output as f32
     }
 
     pub fn reset(&mut self) {
@@ -152,10 +203,64 @@ impl AllpassFilter {
     }
 }
 
+/// First bounces of a room: a tapped delay line (pre-allocated, RT-safe)
+pub struct EarlyReflections {
+    delay: DelayLine,
+    /// Tap delays in samples for the current rate and room size
+    taps: [usize; EARLY_TAPS.len()],
+    room_size: f32,
+    sample_rate: f32,
+}
+
+impl EarlyReflections {
+    pub fn new(room_size: f32, sample_rate: f32) -> Self {
+        let mut early = Self {
+            delay: DelayLine::with_max_seconds(MAX_EARLY_SECS, MAX_EARLY_SAMPLE_RATE),
+            taps: [1; EARLY_TAPS.len()],
+            room_size: room_size.clamp(0.0, 1.0),
+            sample_rate,
+        };
+        early.configure(sample_rate);
+        early
+    }
+
+    /// Set tap delays for a sample rate (RT-safe, no allocation)
+    pub fn configure(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        // Half the spacing in a small room, 1.5× in a large one
+        let scale = 0.5 + self.room_size;
+        let max_tap = self.delay.capacity() - 1;
+        for (tap, &(ms, _)) in self.taps.iter_mut().zip(EARLY_TAPS.iter()) {
+            *tap = ((ms * scale * sample_rate / 1000.0) as usize).clamp(1, max_tap);
+        }
+    }
+
+    /// Spread the reflections for a room size (0.0 - 1.0)
+    pub fn set_room_size(&mut self, size: f32) {
+        self.room_size = size.clamp(0.0, 1.0);
+        self.configure(self.sample_rate);
+    }
+
+    pub fn process(&mut self, input: f32) -> f32 {
+        let early = self.taps.iter().zip(EARLY_TAPS.iter()).map(|(&tap, &(_, gain))| self.delay.read(tap) * gain).sum::<f32>();
+        self.delay.write(input);
+        // Keeps the reflections about as loud as the input
+        early * 0.5
+    }
+
+    pub fn reset(&mut self) {
+        self.delay.reset();
+    }
+}
+
 /// Schroeder reverb with 4 comb filters and 2 allpass filters
 pub struct SchroederReverb {
     combs: [CombFilter; 4],
     allpasses: [AllpassFilter; 2],
+    /// Comb feedback and damping to restore when unfrozen
+    feedback: f32,
+    damp: f32,
+    frozen: bool,
 }
 
 impl SchroederReverb {
@@ -182,7 +287,13 @@ impl SchroederReverb {
             AllpassFilter::new((allpass_delays_ms[1] * sample_rate / 1000.0) as usize),
         ];
 
-        Self { combs, allpasses }
+        Self {
+            combs,
+            allpasses,
+            feedback: 0.5,
+            damp: 0.5,
+            frozen: false,
+        }
     }
 
     /// Configure delay times for a specific sample rate (RT-safe, no allocation).
@@ -202,16 +313,39 @@ impl SchroederReverb {
 
     /// Set the room size (scales feedback for longer/shorter decay)
     pub fn set_room_size(&mut self, size: f32) {
-        let feedback = 0.7 + size.clamp(0.0, 1.0) * 0.28; // 0.7 to 0.98
-        for comb in &mut self.combs {
-            comb.set_feedback(feedback);
-        }
+        self.feedback = Self::comb_feedback(size);
+        self.apply_combs();
+    }
+
+    /// Comb feedback for a room size: 0.7 (small) to 0.98 (huge)
+    pub fn comb_feedback(room_size: f32) -> f32 {
+        0.7 + room_size.clamp(0.0, 1.0) * 0.28
     }
 
     /// Set damping (high frequency absorption)
     pub fn set_damping(&mut self, damp: f32) {
+        self.damp = damp.clamp(0.0, 1.0);
+        self.apply_combs();
+    }
+
+    /// Hold the current tail forever (true) or let it decay again (false)
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+        self.apply_combs();
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    fn apply_combs(&mut self) {
         for comb in &mut self.combs {
-            comb.set_damp(damp.clamp(0.0, 1.0));
+            if self.frozen {
+                comb.freeze();
+            } else {
+                comb.set_feedback(self.feedback);
+                comb.set_damp(self.damp);
+            }
         }
     }
 
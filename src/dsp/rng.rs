@@ -0,0 +1,162 @@
+//! Seedable pseudo-random numbers for stochastic DSP.
+
+/*
+Deterministic Randomness
+========================
+
+Noise, humanization, probability triggers, analog drift - a lot of music
+DSP wants randomness. But tests, offline renders and bug reports want the
+SAME output every time. The answer is pseudo-random numbers from a known
+SEED: the sequence looks random but replays exactly.
+
+
+The Generator: xorshift32
+-------------------------
+
+    x ^= x << 13
+    x ^= x >> 17
+    x ^= x << 5
+
+Three shifts and three XORs per number, no multiplications, no tables.
+Period 2³² - 1 (every nonzero state is visited once). Not cryptographic,
+but statistically plenty for audio. The state must never be 0 (it would
+stay 0 forever), so seeds are forced nonzero.
+
+
+Streams from One Master Seed
+----------------------------
+
+If every noise source started from the same seed, a hihat and a snare
+would play the IDENTICAL noise - correlated, phasey, wrong. Instead each
+component gets its own STREAM, derived from a master seed plus a path:
+
+    master ──┬── derive(master, 0) → track 0 ──┬── derive(.., 0) → osc A
+             │                                 └── derive(.., 1) → osc B
+             └── derive(master, 1) → track 1 ──── ...
+
+`derive` mixes the two numbers with SplitMix64 (a strong 64-bit hash), so
+neighbouring ids give unrelated streams. Change the master seed and every
+stream changes; keep it and every render is bit-identical.
+
+Graph containers do this automatically in `GraphNode::seed`: each child
+gets `derive(seed, child_index)`.
+*/
+
+/// Master seed used when none is given
+pub const DEFAULT_SEED: u64 = 0x9E37_79B9;
+
+/// Small, fast, seedable PRNG (xorshift32)
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u32,
+}
+
+impl Rng {
+    /// Create a generator from a seed (any value, including 0)
+    pub fn new(seed: u64) -> Self {
+        // Fold 64 → 32 bits; zero is a fixed point of xorshift, so avoid it
+        let folded = (seed ^ (seed >> 32)) as u32;
+        Self {
+            state: if folded == 0 { DEFAULT_SEED as u32 } else { folded },
+        }
+    }
+
+    /// Derive an independent child seed from a parent seed and an id
+    pub fn derive(seed: u64, id: u64) -> u64 {
+        splitmix64(seed ^ splitmix64(id.wrapping_add(0x632B_E59B_D9B4_E019)))
+    }
+
+    /// Next raw 32-bit value
+    #[inline]
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// Uniform in [0, 1)
+    #[inline]
+    pub fn next_f32(&mut self) -> f32 {
+        // Top 23 bits → exactly representable in an f32 mantissa
+        // 8388608 = 2^23
+        (self.next_u32() >> 9) as f32 / 8_388_608.0
+    }
+
+    /// Uniform in [-1, 1) - white noise
+    #[inline]
+    pub fn next_bipolar(&mut self) -> f32 {
+        self.next_f32() * 2.0 - 1.0
+    }
+
+    /// Uniform in [low, high)
+    #[inline]
+    pub fn range(&mut self, low: f32, high: f32) -> f32 {
+        low + (high - low) * self.next_f32()
+    }
+
+    /// True with probability `p` (0.0 = never, 1.0 = always)
+    #[inline]
+    pub fn chance(&mut self, p: f32) -> bool {
+        self.next_f32() < p
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED)
+    }
+}
+
+#[inline]
+fn splitmix64(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        assert!((0..1000).all(|_| a.next_u32() == b.next_u32()));
+    }
+
+    #[test]
+    fn zero_seed_still_produces_values() {
+        let mut rng = Rng::new(0);
+        assert_ne!(rng.next_u32(), 0);
+    }
+
+    #[test]
+    fn derived_streams_differ() {
+        let mut a = Rng::new(Rng::derive(DEFAULT_SEED, 0));
+        let mut b = Rng::new(Rng::derive(DEFAULT_SEED, 1));
+        let matches = (0..1000).filter(|_| a.next_u32() == b.next_u32()).count();
+        assert_eq!(matches, 0);
+    }
+
+    #[test]
+    fn bipolar_range_and_mean() {
+        let mut rng = Rng::default();
+        let values: Vec<f32> = (0..100_000).map(|_| rng.next_bipolar()).collect();
+
+        assert!(values.iter().all(|v| (-1.0..1.0).contains(v)));
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        assert!(mean.abs() < 0.01, "mean {mean}");
+    }
+
+    #[test]
+    fn chance_matches_probability() {
+        let mut rng = Rng::new(7);
+        let hits = (0..100_000).filter(|_| rng.chance(0.25)).count();
+        assert!((hits as f32 / 100_000.0 - 0.25).abs() < 0.01);
+    }
+}
@@ -0,0 +1,346 @@
+//! SIMD block kernels with scalar fallbacks.
+
+/*
+SIMD (Single Instruction, Multiple Data)
+========================================
+
+Most DSP loops do the same arithmetic to every sample in a block. A CPU with
+SIMD registers can do that arithmetic on several samples at once: one SSE
+instruction multiplies FOUR f32 values in the time a scalar multiply handles
+one.
+
+  scalar:   a[0]*g   a[1]*g   a[2]*g   a[3]*g      (4 instructions)
+  SSE:      [a0 a1 a2 a3] * [g g g g]             (1 instruction)
+
+Each slot in a SIMD register is called a LANE. Kernels here process the
+block in chunks of 4 lanes, then finish the leftover 0-3 samples (the TAIL)
+with ordinary scalar code.
+
+
+Which Loops Vectorize?
+----------------------
+
+Only loops where each output sample is INDEPENDENT of the previous one:
+
+  gain, ring mod, mixing      out[n] = f(a[n], b[n])          ✓
+  soft clip                   out[n] = f(in[n])               ✓
+  phase → waveform            out[n] = f(phase[n])            ✓
+
+  SVF / one-pole filters      y[n] = f(x[n], y[n-1])          ✗
+  feedback delay, reverb      y[n] = f(x[n], y[n-D])          ✗ (short D)
+
+Recursive filters feed each output back into the next sample, so lane 1
+would need lane 0's result before it can start. They stay scalar. (Filters
+CAN be vectorized ACROSS voices - 4 voices in 4 lanes - but that needs a
+voice layout this crate doesn't have yet.)
+
+Phase accumulation looks recursive (phase += inc) but has a closed form:
+the phase n samples ahead is just phase + n × inc. In float arithmetic,
+though, phase + 4 × inc rounds differently from four separate += inc
+steps, so a closed-form oscillator drifts from the scalar one by a few ULPs
+and the drift depends on where blocks start. The sawtooth kernel therefore
+keeps the scalar phase recurrence (it's one add and a wrap per sample) and
+vectorizes only the phase-to-ramp math, so the simd feature never changes
+what a patch renders.
+
+
+Feature Flag
+------------
+
+The SSE paths are compiled only with the `simd` feature on x86_64. SSE2 is
+part of the x86_64 baseline, so no runtime CPU detection is needed. Every
+other target (and the default build) uses the scalar fallback, which is the
+same code as the plain `dsp::*` functions.
+
+The graph calls these kernels on its hot paths - `Amplify` (multiply),
+`Gain` and every steady `SmoothedParam` (gain), `Mix` (crossfade),
+soft-mode `DistortionNode` (soft clip) and sawtooth `OscillatorBlock`s -
+so turning the feature on speeds up real patches, not just the benches.
+
+The compiler often auto-vectorizes the simple scalar loops anyway - compare
+`dsp/simd` against `dsp/amplify` and `dsp/mix` in the benches to see how
+much explicit intrinsics buy on your machine.
+*/
+
+use std::f32::consts::TAU;
+
+/// Multiply every sample by a constant gain.
+#[inline]
+pub fn apply_gain(signal: &mut [f32], gain: f32) {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        sse::apply_gain(signal, gain)
+    }
+    #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+    {
+        super::amplify::apply_gain(signal, gain)
+    }
+}
+
+/// Multiply two signals sample-by-sample, writing into the first.
+#[inline]
+pub fn multiply_in_place(signal: &mut [f32], modulator: &[f32]) {
+    debug_assert_eq!(signal.len(), modulator.len());
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        sse::multiply_in_place(signal, modulator)
+    }
+    #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+    {
+        super::amplify::multiply_in_place(signal, modulator)
+    }
+}
+
+/// Add `b` into `a` (unweighted sum).
+#[inline]
+pub fn sum_in_place(a: &mut [f32], b: &[f32]) {
+    debug_assert_eq!(a.len(), b.len());
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        sse::sum_in_place(a, b)
+    }
+    #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+    {
+        super::mix::sum_in_place(a, b)
+    }
+}
+
+/// Linear crossfade of `b` into `a` (balance 0.0 = all A, 1.0 = all B).
+#[inline]
+pub fn mix_in_place(a: &mut [f32], b: &[f32], balance: f32) {
+    debug_assert_eq!(a.len(), b.len());
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        sse::mix_in_place(a, b, balance)
+    }
+    #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+    {
+        super::mix::mix_in_place(a, b, balance)
+    }
+}
+
+/// Soft clip a buffer in place: x / (1 + |x|) after drive.
+#[inline]
+pub fn soft_clip_buffer(buffer: &mut [f32], drive: f32) {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        sse::soft_clip_buffer(buffer, drive)
+    }
+    #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+    {
+        super::distortion::soft_clip_buffer(buffer, drive)
+    }
+}
+
+/// Fill `buffer` with a naive sawtooth starting at `phase` (radians).
+///
+/// Returns the phase after the block, wrapped to [0, τ). Bit-identical to
+/// `OscillatorBlock` with `Waveform::Sawtooth` for any block length.
+#[inline]
+pub fn sawtooth(buffer: &mut [f32], phase: f32, phase_inc: f32) -> f32 {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        sse::sawtooth(buffer, phase, phase_inc)
+    }
+    #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+    {
+        sawtooth_scalar(buffer, phase, phase_inc)
+    }
+}
+
+/// Scalar sawtooth kernel (also used for the SIMD tail)
+#[inline]
+fn sawtooth_scalar(buffer: &mut [f32], mut phase: f32, phase_inc: f32) -> f32 {
+    for sample in buffer.iter_mut() {
+        *sample = 2.0 * (phase / TAU) - 1.0;
+        phase = (phase + phase_inc).rem_euclid(TAU);
+    }
+    phase
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod sse {
+    use std::arch::x86_64::*;
+    use std::f32::consts::TAU;
+
+    const LANES: usize = 4;
+
+    // SAFETY (all kernels): SSE/SSE2 are always available on x86_64, and
+    // loads/stores use the unaligned variants on in-bounds 4-sample chunks.
+
+    pub fn apply_gain(signal: &mut [f32], gain: f32) {
+        let mut chunks = signal.chunks_exact_mut(LANES);
+        unsafe {
+            let g = _mm_set1_ps(gain);
+            for chunk in &mut chunks {
+                let x = _mm_loadu_ps(chunk.as_ptr());
+                _mm_storeu_ps(chunk.as_mut_ptr(), _mm_mul_ps(x, g));
+            }
+        }
+        for sample in chunks.into_remainder() {
+            *sample *= gain;
+        }
+    }
+
+    pub fn multiply_in_place(signal: &mut [f32], modulator: &[f32]) {
+        let mut chunks = signal.chunks_exact_mut(LANES);
+        let mut mods = modulator.chunks_exact(LANES);
+        unsafe {
+            for (chunk, m) in (&mut chunks).zip(&mut mods) {
+                let x = _mm_loadu_ps(chunk.as_ptr());
+                let y = _mm_loadu_ps(m.as_ptr());
+                _mm_storeu_ps(chunk.as_mut_ptr(), _mm_mul_ps(x, y));
+            }
+        }
+        for (s, &m) in chunks.into_remainder().iter_mut().zip(mods.remainder()) {
+            *s *= m;
+        }
+    }
+
+    pub fn sum_in_place(a: &mut [f32], b: &[f32]) {
+        let mut chunks = a.chunks_exact_mut(LANES);
+        let mut others = b.chunks_exact(LANES);
+        unsafe {
+            for (chunk, other) in (&mut chunks).zip(&mut others) {
+                let x = _mm_loadu_ps(chunk.as_ptr());
+                let y = _mm_loadu_ps(other.as_ptr());
+                _mm_storeu_ps(chunk.as_mut_ptr(), _mm_add_ps(x, y));
+            }
+        }
+        for (sa, &sb) in chunks.into_remainder().iter_mut().zip(others.remainder()) {
+            *sa += sb;
+        }
+    }
+
+    pub fn mix_in_place(a: &mut [f32], b: &[f32], balance: f32) {
+        let balance = balance.clamp(0.0, 1.0);
+        let (weight_a, weight_b) = (1.0 - balance, balance);
+
+        let mut chunks = a.chunks_exact_mut(LANES);
+        let mut others = b.chunks_exact(LANES);
+        unsafe {
+            let wa = _mm_set1_ps(weight_a);
+            let wb = _mm_set1_ps(weight_b);
+            for (chunk, other) in (&mut chunks).zip(&mut others) {
+                let x = _mm_mul_ps(_mm_loadu_ps(chunk.as_ptr()), wa);
+                let y = _mm_mul_ps(_mm_loadu_ps(other.as_ptr()), wb);
+                _mm_storeu_ps(chunk.as_mut_ptr(), _mm_add_ps(x, y));
+            }
+        }
+        for (sa, &sb) in chunks.into_remainder().iter_mut().zip(others.remainder()) {
+            *sa = (*sa * weight_a) + (sb * weight_b);
+        }
+    }
+
+    pub fn soft_clip_buffer(buffer: &mut [f32], drive: f32) {
+        let mut chunks = buffer.chunks_exact_mut(LANES);
+        unsafe {
+            let d = _mm_set1_ps(drive);
+            let one = _mm_set1_ps(1.0);
+            // Clearing the sign bit gives |x|
+            let abs_mask = _mm_castsi128_ps(_mm_set1_epi32(0x7FFF_FFFF));
+            for chunk in &mut chunks {
+                let x = _mm_mul_ps(_mm_loadu_ps(chunk.as_ptr()), d);
+                let denom = _mm_add_ps(one, _mm_and_ps(x, abs_mask));
+                _mm_storeu_ps(chunk.as_mut_ptr(), _mm_div_ps(x, denom));
+            }
+        }
+        for sample in chunks.into_remainder() {
+            *sample = crate::dsp::distortion::soft_clip(*sample, drive);
+        }
+    }
+
+    pub fn sawtooth(buffer: &mut [f32], mut phase: f32, phase_inc: f32) -> f32 {
+        let mut chunks = buffer.chunks_exact_mut(LANES);
+        unsafe {
+            let tau = _mm_set1_ps(TAU);
+            let two = _mm_set1_ps(2.0);
+            let one = _mm_set1_ps(1.0);
+            let mut lanes = [0.0; LANES];
+            for chunk in &mut chunks {
+                // Accumulate lane phases exactly like the scalar loop, so
+                // the output is bit-identical whatever the block length
+                for lane in &mut lanes {
+                    *lane = phase;
+                    phase = (phase + phase_inc).rem_euclid(TAU);
+                }
+                let phi = _mm_div_ps(_mm_loadu_ps(lanes.as_ptr()), tau);
+                _mm_storeu_ps(chunk.as_mut_ptr(), _mm_sub_ps(_mm_mul_ps(two, phi), one));
+            }
+        }
+        super::sawtooth_scalar(chunks.into_remainder(), phase, phase_inc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::{amplify, distortion, mix};
+
+    // Odd length so every kernel exercises its scalar tail
+    const LEN: usize = 67;
+
+    fn signal(scale: f32) -> Vec<f32> {
+        (0..LEN).map(|i| (i as f32 * scale).sin() * 1.5).collect()
+    }
+
+    fn assert_close(a: &[f32], b: &[f32]) {
+        for (i, (x, y)) in a.iter().zip(b).enumerate() {
+            assert!((x - y).abs() < 1e-5, "sample {}: {} vs {}", i, x, y);
+        }
+    }
+
+    #[test]
+    fn gain_matches_scalar() {
+        let (mut fast, mut slow) = (signal(0.1), signal(0.1));
+        apply_gain(&mut fast, 0.7);
+        amplify::apply_gain(&mut slow, 0.7);
+        assert_close(&fast, &slow);
+    }
+
+    #[test]
+    fn multiply_matches_scalar() {
+        let modulator = signal(0.03);
+        let (mut fast, mut slow) = (signal(0.1), signal(0.1));
+        multiply_in_place(&mut fast, &modulator);
+        amplify::multiply_in_place(&mut slow, &modulator);
+        assert_close(&fast, &slow);
+    }
+
+    #[test]
+    fn sum_and_mix_match_scalar() {
+        let other = signal(0.2);
+        let (mut fast, mut slow) = (signal(0.1), signal(0.1));
+        sum_in_place(&mut fast, &other);
+        mix::sum_in_place(&mut slow, &other);
+        assert_close(&fast, &slow);
+
+        let (mut fast, mut slow) = (signal(0.1), signal(0.1));
+        mix_in_place(&mut fast, &other, 0.3);
+        mix::mix_in_place(&mut slow, &other, 0.3);
+        assert_close(&fast, &slow);
+    }
+
+    #[test]
+    fn soft_clip_matches_scalar() {
+        let (mut fast, mut slow) = (signal(0.1), signal(0.1));
+        soft_clip_buffer(&mut fast, 3.0);
+        distortion::soft_clip_buffer(&mut slow, 3.0);
+        assert_close(&fast, &slow);
+    }
+
+    #[test]
+    fn sawtooth_matches_scalar_bit_for_bit_across_odd_blocks() {
+        let phase_inc = TAU * 440.0 / 48_000.0;
+        let (mut fast_phase, mut slow_phase) = (1.0, 1.0);
+        for len in [1, 3, 5, 67, 4, 97, 2, 441] {
+            let (mut fast, mut slow) = (vec![0.0; len], vec![0.0; len]);
+            fast_phase = sawtooth(&mut fast, fast_phase, phase_inc);
+            slow_phase = sawtooth_scalar(&mut slow, slow_phase, phase_inc);
+
+            assert_eq!(fast, slow, "block of {}", len);
+            assert_eq!(fast_phase, slow_phase);
+            assert!((0.0..TAU).contains(&fast_phase));
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Slew limiting: cap how fast a signal may rise and fall.
+
+/*
+Slew Limiter
+============
+
+A slew limiter lets a signal move, but no faster than a set rate. Small,
+slow changes pass untouched; jumps turn into straight ramps:
+
+  input      ┌──────┐            output      ╱‾‾‾‾╲
+             │      │                       ╱      ╲
+        ─────┘      └─────              ───╱        ╲───
+                                           rise     fall
+
+    y[n] = y[n-1] + clamp(x[n] - y[n-1], -fall_step, rise_step)
+
+Unlike a lowpass (`SmoothedParam`'s exponential ramp, a one-pole filter)
+it doesn't care how far the signal has to go, only how fast - which is
+why it's the classic modular tool for portamento on a CV and for taming
+a stepped random or square LFO into a triangle-ish sweep.
+
+Rise and fall are independent: a fast rise and a slow fall turns a gate
+into a quick attack and a long decay - a crude envelope.
+
+
+On Audio
+--------
+
+On audio the limit bites on steep, loud edges: high frequencies at high
+levels get turned into triangles, quiet ones pass. The result is a
+level-dependent lowpass with a gritty, lo-fi edge - the "slew rate
+distortion" of a struggling op-amp.
+
+
+Rates
+-----
+
+Rates are given as the time to cover a full-scale change of 1.0, so
+`rise_secs = 0.1` lets the signal climb from 0 to 1 in 100 ms (and from
+-1 to 1 in 200 ms). A time of 0 means unlimited.
+*/
+
+/// Rise/fall rate limiter (one sample of state, RT-safe)
+#[derive(Clone, Debug)]
+pub struct SlewLimiter {
+    /// Largest upward step per sample
+    rise_step: f32,
+    /// Largest downward step per sample
+    fall_step: f32,
+    value: f32,
+}
+
+impl SlewLimiter {
+    /// Limit to `rise_secs` / `fall_secs` per full-scale (1.0) change
+    pub fn new(rise_secs: f32, fall_secs: f32, sample_rate: f32) -> Self {
+        let mut slew = Self {
+            rise_step: f32::INFINITY,
+            fall_step: f32::INFINITY,
+            value: 0.0,
+        };
+        slew.set_times(rise_secs, fall_secs, sample_rate);
+        slew
+    }
+
+    /// Change the rates (RT-safe; keeps the current value)
+    pub fn set_times(&mut self, rise_secs: f32, fall_secs: f32, sample_rate: f32) {
+        self.rise_step = Self::step(rise_secs, sample_rate);
+        self.fall_step = Self::step(fall_secs, sample_rate);
+    }
+
+    fn step(secs: f32, sample_rate: f32) -> f32 {
+        if secs <= 0.0 {
+            f32::INFINITY
+        } else {
+            1.0 / (secs * sample_rate)
+        }
+    }
+
+    #[inline]
+    pub fn process(&mut self, input: f32) -> f32 {
+        self.value += (input - self.value).clamp(-self.fall_step, self.rise_step);
+        self.value
+    }
+
+    /// Process a buffer in place
+    pub fn render(&mut self, buffer: &mut [f32]) {
+        for sample in buffer.iter_mut() {
+            *sample = self.process(*sample);
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.value = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rises_and_falls_at_their_own_rates() {
+        // 10 ms up, 40 ms down at 1 kHz: 10 and 40 samples per unit
+        let mut slew = SlewLimiter::new(0.01, 0.04, 1_000.0);
+        let up: Vec<f32> = (0..12).map(|_| slew.process(1.0)).collect();
+        assert!((up[4] - 0.5).abs() < 1e-6);
+        assert_eq!(up[11], 1.0, "lands exactly on the target");
+
+        for _ in 0..20 {
+            slew.process(0.0);
+        }
+        assert!((slew.value() - 0.5).abs() < 1e-5);
+
+        // Unlimited: follows the input exactly
+        let mut free = SlewLimiter::new(0.0, 0.0, 1_000.0);
+        assert_eq!(free.process(-0.7), -0.7);
+    }
+}
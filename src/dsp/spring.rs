@@ -0,0 +1,180 @@
+//! Spring reverb: dispersive allpass chains in a feedback loop.
+
+/*
+Spring Reverb
+=============
+
+Guitar amps and dub mixing desks get their reverb from a couple of steel
+springs: a transducer shakes one end, a pickup listens at the other. A
+spring is not a room - it doesn't build a dense wash of reflections. It
+carries the sound as a wave that bounces back and forth along the coil,
+and the coil is DISPERSIVE: different frequencies travel at different
+speeds. Every echo arrives smeared into a chirp, and hit hard (a snare, a
+kicked amp) that chirp is the famous "boing" / "drip".
+
+
+Modelling Dispersion
+--------------------
+
+A first-order allpass passes every frequency at full level, but with a
+negative coefficient it delays low frequencies more than high ones:
+
+    y[n] = a·x[n] + x[n-1] - a·y[n-1]
+
+    delay at DC       (1 - a) / (1 + a) samples     a = -0.7 → 5.7 samples
+    delay at Nyquist  (1 + a) / (1 - a) samples              → 0.18
+
+One stage barely matters; a chain of dozens spreads a click out into a
+falling sweep, highs first and lows trailing ~6 ms behind - a chirp.
+
+
+The Loop
+--------
+
+  Input ──(+)──→ [Allpass × N] ──→ [Delay: transit] ──→ [Lowpass] ──┬──→ Output
+           ↑                                                        │
+           └────────────────────── × decay ←────────────────────────┘
+
+Each trip around the loop is one trip along the spring and back: the
+chirp repeats every transit time, getting darker (the lowpass) and more
+smeared (another pass through the chain) each time. Two springs of
+slightly different lengths are summed, like the two- and three-spring
+tanks in real amps, so the repeats don't line up into a flutter.
+*/
+
+use super::delay::DelayLine;
+
+/// Allpass stages per spring (more = longer, more pronounced chirp)
+const DISPERSION_STAGES: usize = 48;
+/// Allpass coefficient: how strongly each stage delays the lows
+const DISPERSION_COEF: f32 = -0.7;
+/// Round-trip time of each spring
+const SPRING_TRANSIT_SECS: [f32; 2] = [0.037, 0.043];
+/// Longest transit, with room to spare
+const MAX_TRANSIT_SECS: f32 = 0.05;
+/// Highest sample rate the delays are sized for
+const MAX_SAMPLE_RATE: f32 = 192_000.0;
+
+/// One spring: dispersion chain, transit delay and damping in a loop
+struct Spring {
+    /// Allpass states (transposed direct form: one per stage)
+    allpass: [f32; DISPERSION_STAGES],
+    delay: DelayLine,
+    transit_secs: f32,
+    delay_samples: usize,
+    lowpass: f32,
+    last: f32,
+}
+
+impl Spring {
+    fn new(transit_secs: f32, sample_rate: f32) -> Self {
+        let mut spring = Self {
+            allpass: [0.0; DISPERSION_STAGES],
+            delay: DelayLine::with_max_seconds(MAX_TRANSIT_SECS, MAX_SAMPLE_RATE),
+            transit_secs,
+            delay_samples: 1,
+            lowpass: 0.0,
+            last: 0.0,
+        };
+        spring.configure(sample_rate);
+        spring
+    }
+
+    fn configure(&mut self, sample_rate: f32) {
+        self.delay_samples = ((self.transit_secs * sample_rate) as usize).clamp(1, self.delay.capacity() - 1);
+    }
+
+    #[inline]
+    fn process(&mut self, input: f32, decay: f32, damp: f32) -> f32 {
+        let mut x = input + self.last * decay;
+        for state in self.allpass.iter_mut() {
+            let y = DISPERSION_COEF * x + *state;
+            *state = x - DISPERSION_COEF * y;
+            x = y;
+        }
+        let delayed = self.delay.next_sample(x, self.delay_samples);
+        self.lowpass = delayed + damp * (self.lowpass - delayed);
+        self.last = self.lowpass;
+        self.lowpass
+    }
+
+    fn reset(&mut self) {
+        self.allpass.fill(0.0);
+        self.delay.reset();
+        self.lowpass = 0.0;
+        self.last = 0.0;
+    }
+}
+
+/// Two-spring reverb tank (pre-allocated, RT-safe)
+pub struct SpringReverb {
+    springs: [Spring; 2],
+    decay: f32,
+    damp: f32,
+    frozen: bool,
+}
+
+impl SpringReverb {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            springs: SPRING_TRANSIT_SECS.map(|secs| Spring::new(secs, sample_rate)),
+            decay: 0.7,
+            damp: 0.3,
+            frozen: false,
+        }
+    }
+
+    /// Set transit delays for a sample rate (RT-safe, no allocation)
+    pub fn configure(&mut self, sample_rate: f32) {
+        for spring in self.springs.iter_mut() {
+            spring.configure(sample_rate);
+        }
+    }
+
+    /// How long the repeats ring (0.0 = a single bounce, 1.0 = long drip)
+    pub fn set_decay(&mut self, decay: f32) {
+        self.decay = 0.3 + decay.clamp(0.0, 1.0) * 0.6;
+    }
+
+    /// High-frequency loss per bounce (0.0 = bright, 1.0 = dark)
+    pub fn set_damping(&mut self, damp: f32) {
+        self.damp = damp.clamp(0.0, 1.0) * 0.8;
+    }
+
+    /// Keep the repeats bouncing forever (no decay, no damping)
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
+
+    pub fn process(&mut self, input: f32) -> f32 {
+        let (decay, damp) = if self.frozen { (1.0, 0.0) } else { (self.decay, self.damp) };
+        self.springs.iter_mut().map(|spring| spring.process(input, decay, damp)).sum::<f32>() * 0.5
+    }
+
+    pub fn reset(&mut self) {
+        self.springs.iter_mut().for_each(Spring::reset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_echo_is_a_chirp() {
+        let sample_rate = 48_000.0;
+        let mut spring = SpringReverb::new(sample_rate);
+        let response: Vec<f32> = (0..sample_rate as usize).map(|i| spring.process(if i == 0 { 1.0 } else { 0.0 })).collect();
+
+        // First echo of the short spring: highs arrive first, lows trail
+        let start = (SPRING_TRANSIT_SECS[0] * sample_rate) as usize;
+        let echo = &response[start..start + 256];
+        let crossings = |s: &[f32]| s.windows(2).filter(|w| (w[0] > 0.0) != (w[1] > 0.0)).count();
+        assert!(crossings(&echo[..128]) > 2 * crossings(&echo[128..]), "{} vs {}", crossings(&echo[..128]), crossings(&echo[128..]));
+
+        // The tank rings, then dies away
+        let peak = |s: &[f32]| s.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+        assert!(peak(&response[9_600..14_400]) > 1e-3);
+        assert!(peak(&response[43_200..]) < 1e-3 * peak(&response[..4_800]));
+    }
+}
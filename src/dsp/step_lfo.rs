@@ -0,0 +1,126 @@
+//! Step-sequenced modulation shapes (rhythmic filters, trance gates).
+
+/*
+Step LFOs
+=========
+
+A step LFO plays a short loop of fixed values, one per step, instead of a
+smooth waveform. Locked to tempo, it turns any modulated parameter into a
+rhythm:
+
+  value
+   1.0 ┤██    ██          ██          trance gate:
+       ┤██    ██    ▐▌    ██          [1, 0, 1, 0, 0.5, 0, 1, 0]
+   0.0 ┤  ▁▁▁▁  ▁▁▁▁  ▁▁▁▁  ▁▁▁▁      one value per 16th note
+       └─┴─┴─┴─┴─┴─┴─┴─┴───→ steps
+
+Position is measured in steps (fractional), so step `i` of an `n`-step
+loop covers positions `i .. i + 1`, then the loop repeats.
+
+
+Glide
+-----
+
+Hard steps click when they drive volume and sound mechanical on a filter.
+Each step has a glide amount, 0.0 - 1.0: the share of the step spent
+sliding from the previous step's value to its own.
+
+     glide 0            glide 0.5          glide 1
+    ┌───               ╱‾‾               ╱
+    │                 ╱                 ╱
+  ──┘              ──╱               ──╱
+    │← step →│       │← step →│        │← step →│
+
+A glide of 1.0 gives a continuous piecewise-linear shape (a custom
+triangle-ish LFO); small glides (0.05 - 0.1) just de-click a gate.
+
+
+Values
+------
+
+Values are used as-is. Keep them in -1.0 to 1.0 for `.modulate()` (like
+any LFO) or 0.0 to 1.0 when the shape drives `.amplify()` as a gate.
+*/
+
+/// One step: a target value and how much of the step is spent gliding to it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Step {
+    pub value: f32,
+    /// Share of the step spent sliding from the previous value, 0.0 - 1.0
+    pub glide: f32,
+}
+
+/// Loop of steps evaluated at a fractional step position
+#[derive(Clone, Debug)]
+pub struct StepShape {
+    steps: Vec<Step>,
+}
+
+impl StepShape {
+    /// Hard steps (no glide) through `values`
+    ///
+    /// An empty slice gives a single step at 0.0.
+    pub fn new(values: &[f32]) -> Self {
+        let mut steps: Vec<Step> = values.iter().map(|&value| Step { value, glide: 0.0 }).collect();
+        if steps.is_empty() {
+            steps.push(Step { value: 0.0, glide: 0.0 });
+        }
+        Self { steps }
+    }
+
+    /// Set every step's glide
+    pub fn set_glide(&mut self, glide: f32) {
+        for step in self.steps.iter_mut() {
+            step.glide = glide.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Set one step's glide (ignored if `index` is out of range)
+    pub fn set_step_glide(&mut self, index: usize, glide: f32) {
+        if let Some(step) = self.steps.get_mut(index) {
+            step.glide = glide.clamp(0.0, 1.0);
+        }
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// Value at `position` steps from the start of the loop
+    #[inline]
+    pub fn value_at(&self, position: f64) -> f32 {
+        let len = self.steps.len();
+        let position = position.rem_euclid(len as f64);
+        let index = (position as usize).min(len - 1);
+        let step = self.steps[index];
+        let previous = self.steps[(index + len - 1) % len].value;
+
+        let through = (position - index as f64) as f32;
+        if through >= step.glide {
+            step.value
+        } else {
+            previous + (step.value - previous) * through / step.glide
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holds_each_step_and_glides_into_it() {
+        let mut shape = StepShape::new(&[1.0, 0.0, 0.5]);
+        assert_eq!(shape.value_at(0.5), 1.0);
+        assert_eq!(shape.value_at(1.9), 0.0);
+        assert_eq!(shape.value_at(3.2), 1.0, "loops");
+
+        // Step 2 glides from 0.0 to 0.5 over its first half
+        shape.set_step_glide(2, 0.5);
+        assert_eq!(shape.value_at(2.0), 0.0);
+        assert!((shape.value_at(2.25) - 0.25).abs() < 1e-6);
+        assert_eq!(shape.value_at(2.75), 0.5);
+        // Step 0 still jumps back from 0.5 to 1.0
+        assert_eq!(shape.value_at(3.0), 1.0);
+    }
+}
@@ -0,0 +1,152 @@
+//! Pitch math: MIDI notes, hertz, semitones, and cents.
+
+/*
+Tuning
+======
+
+Everything here is twelve-tone equal temperament (12-TET) tuned to
+A4 = 440 Hz, the standard for synthesizers and MIDI.
+
+Vocabulary
+----------
+
+  MIDI note     A pitch number: 60 = middle C (C4), 69 = A4. One step is
+                one semitone. Fractional notes (60.5) are allowed here and
+                mean "between the keys" - glides and pitch bends use them.
+
+  semitone      1/12 of an octave: a frequency ratio of 2^(1/12) ≈ 1.0595.
+
+  cent          1/100 of a semitone, 1/1200 of an octave. The ear notices
+                around 5-10 cents between two notes played one after the
+                other; detuned unisons live at 5-20.
+
+  ratio         Frequency multiplier. Octave = 2.0, fifth ≈ 1.498.
+
+
+The Formulas
+------------
+
+Pitch is logarithmic in frequency, so every conversion is an exp2 or log2:
+
+    hz(note)        = 440 × 2^((note - 69) / 12)
+    note(hz)        = 69 + 12 × log2(hz / 440)
+
+    ratio(semis)    = 2^(semis / 12)
+    ratio(cents)    = 2^(cents / 1200)
+    cents(a → b)    = 1200 × log2(b / a)
+
+A few checkpoints:
+
+    note    hz                  interval       ratio
+    57      220.00              ten cents      1.006
+    60      261.63              major third    1.260
+    69      440.00              fifth          1.498
+    81      880.00              octave         2.000
+
+
+Intervals
+---------
+
+`interval` names the common distances in semitones, so chord shapes and
+transpositions read like music rather than numbers:
+
+    midi_to_hz(root + interval::FIFTH as f32)
+*/
+
+/// Reference pitch: A4 in hertz
+pub const A4_HZ: f32 = 440.0;
+/// MIDI note number of A4
+pub const A4_NOTE: f32 = 69.0;
+
+/// Frequency (Hz) of a MIDI note (fractional notes allowed)
+#[inline]
+pub fn midi_to_hz(note: f32) -> f32 {
+    A4_HZ * ((note - A4_NOTE) / 12.0).exp2()
+}
+
+/// MIDI note (fractional) of a frequency in Hz
+#[inline]
+pub fn hz_to_midi(hz: f32) -> f32 {
+    A4_NOTE + 12.0 * (hz.max(f32::MIN_POSITIVE) / A4_HZ).log2()
+}
+
+/// Nearest MIDI note to a frequency, and how far off it is in cents (±50)
+pub fn nearest_note(hz: f32) -> (u8, f32) {
+    let note = hz_to_midi(hz);
+    let nearest = note.round().clamp(0.0, 127.0);
+    (nearest as u8, (note - nearest) * 100.0)
+}
+
+/// Frequency ratio of an interval in semitones (12 = 2.0)
+#[inline]
+pub fn semitones_to_ratio(semitones: f32) -> f32 {
+    (semitones / 12.0).exp2()
+}
+
+/// Frequency ratio of an offset in cents (1200 = 2.0)
+#[inline]
+pub fn cents_to_ratio(cents: f32) -> f32 {
+    (cents / 1200.0).exp2()
+}
+
+/// Offset in cents of a frequency ratio (2.0 = 1200)
+#[inline]
+pub fn ratio_to_cents(ratio: f32) -> f32 {
+    1200.0 * ratio.max(f32::MIN_POSITIVE).log2()
+}
+
+/// Distance in cents from `from_hz` up to `to_hz` (negative if lower)
+#[inline]
+pub fn cents_between(from_hz: f32, to_hz: f32) -> f32 {
+    ratio_to_cents(to_hz / from_hz.max(f32::MIN_POSITIVE))
+}
+
+/// `hz` moved by `semitones` (and fractional semitones)
+#[inline]
+pub fn transpose_hz(hz: f32, semitones: f32) -> f32 {
+    hz * semitones_to_ratio(semitones)
+}
+
+/// Common intervals in semitones
+pub mod interval {
+    pub const UNISON: i32 = 0;
+    pub const MINOR_SECOND: i32 = 1;
+    pub const MAJOR_SECOND: i32 = 2;
+    pub const MINOR_THIRD: i32 = 3;
+    pub const MAJOR_THIRD: i32 = 4;
+    pub const FOURTH: i32 = 5;
+    pub const TRITONE: i32 = 6;
+    pub const FIFTH: i32 = 7;
+    pub const MINOR_SIXTH: i32 = 8;
+    pub const MAJOR_SIXTH: i32 = 9;
+    pub const MINOR_SEVENTH: i32 = 10;
+    pub const MAJOR_SEVENTH: i32 = 11;
+    pub const OCTAVE: i32 = 12;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_and_hertz_round_trip() {
+        assert_eq!(midi_to_hz(69.0), 440.0);
+        assert!((midi_to_hz(60.0) - 261.626).abs() < 1e-3);
+        assert!((hz_to_midi(880.0) - 81.0).abs() < 1e-5);
+        assert!((hz_to_midi(midi_to_hz(42.37)) - 42.37).abs() < 1e-4);
+
+        // 10 cents sharp of A4
+        let (note, cents) = nearest_note(440.0 * cents_to_ratio(10.0));
+        assert_eq!(note, 69);
+        assert!((cents - 10.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn ratios_cents_and_intervals() {
+        assert_eq!(semitones_to_ratio(interval::OCTAVE as f32), 2.0);
+        assert!((semitones_to_ratio(interval::FIFTH as f32) - 1.4983).abs() < 1e-4);
+        assert!((ratio_to_cents(2.0) - 1200.0).abs() < 1e-3);
+        assert!((cents_between(440.0, 220.0) + 1200.0).abs() < 1e-3);
+        assert!((transpose_hz(440.0, -12.0) - 220.0).abs() < 1e-3);
+    }
+}
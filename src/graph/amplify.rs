@@ -1,6 +1,11 @@
 use crate::{
-    dsp::amplify::{apply_gain, multiply_in_place},
-    graph::node::{GraphNode, RenderCtx},
+    dsp::{
+        envelope::EnvelopeState,
+        rng::Rng,
+        simd::multiply_in_place,
+        smooth::{SmoothedParam, DEFAULT_SMOOTHING_SECS},
+    },
+    graph::node::{GraphNode, NodeCommand, RenderCtx},
     MAX_BLOCK_SIZE,
 };
 
@@ -91,6 +96,17 @@ impl<S: GraphNode, M: GraphNode> GraphNode for Amplify<S, M> {
         multiply_in_place(out, mod_out);
     }
 
+    fn prepare(&mut self, sample_rate: f32, max_block: usize) {
+        self.mod_buffer.resize(max_block, 0.0);
+        self.signal.prepare(sample_rate, max_block);
+        self.modulator.prepare(sample_rate, max_block);
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.signal.seed(Rng::derive(seed, 0));
+        self.modulator.seed(Rng::derive(seed, 1));
+    }
+
     fn note_on(&mut self, ctx: &RenderCtx) {
         self.signal.note_on(ctx);
         self.modulator.note_on(ctx);
@@ -101,6 +117,11 @@ impl<S: GraphNode, M: GraphNode> GraphNode for Amplify<S, M> {
         self.modulator.note_off(ctx);
     }
 
+    fn command(&mut self, command: NodeCommand) {
+        self.signal.command(command);
+        self.modulator.command(command);
+    }
+
     fn is_active(&self) -> bool {
         // Check modulator, not signal - envelope determines when voice is done
         self.modulator.is_active()
@@ -112,6 +133,17 @@ impl<S: GraphNode, M: GraphNode> GraphNode for Amplify<S, M> {
             .get_envelope_level()
             .or_else(|| self.signal.get_envelope_level())
     }
+
+    fn get_envelope_state(&self) -> Option<EnvelopeState> {
+        // Same source as `get_envelope_level`
+        self.modulator
+            .get_envelope_state()
+            .or_else(|| self.signal.get_envelope_state())
+    }
+
+    fn latency_samples(&self) -> usize {
+        self.signal.latency_samples()
+    }
 }
 
 /*
@@ -142,23 +174,51 @@ Common gain values:
 */
 
 /// Applies a constant gain multiplier to a signal.
+///
+/// The multiplier used to be the public `gain` field; it is now smoothed,
+/// so read and change it through [`Gain::gain`] and [`Gain::set_gain`].
 pub struct Gain<S> {
     /// The signal source
     pub signal: S,
-    /// The gain multiplier
-    pub gain: f32,
+    /// The gain multiplier, ramped when changed with `set_gain`
+    gain: SmoothedParam,
+    sample_rate: f32,
 }
 
 impl<S> Gain<S> {
     pub fn new(signal: S, gain: f32) -> Self {
-        Self { signal, gain }
+        Self {
+            signal,
+            gain: SmoothedParam::new(gain),
+            sample_rate: 48_000.0,
+        }
+    }
+
+    /// Current gain target
+    pub fn gain(&self) -> f32 {
+        self.gain.target()
+    }
+
+    /// Change the gain, ramping over a few milliseconds so it doesn't click
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain.set_target(gain, DEFAULT_SMOOTHING_SECS, self.sample_rate);
     }
 }
 
 impl<S: GraphNode> GraphNode for Gain<S> {
     fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        self.sample_rate = ctx.sample_rate;
         self.signal.render_block(out, ctx);
-        apply_gain(out, self.gain);
+        self.gain.apply(out);
+    }
+
+    fn prepare(&mut self, sample_rate: f32, max_block: usize) {
+        self.sample_rate = sample_rate;
+        self.signal.prepare(sample_rate, max_block);
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.signal.seed(seed);
     }
 
     fn note_on(&mut self, ctx: &RenderCtx) {
@@ -169,11 +229,57 @@ impl<S: GraphNode> GraphNode for Gain<S> {
         self.signal.note_off(ctx);
     }
 
+    fn command(&mut self, command: NodeCommand) {
+        self.signal.command(command);
+    }
+
     fn is_active(&self) -> bool {
         self.signal.is_active()
     }
 
+    fn latency_samples(&self) -> usize {
+        self.signal.latency_samples()
+    }
+
     fn get_envelope_level(&self) -> Option<f32> {
         self.signal.get_envelope_level()
     }
+
+    fn get_envelope_state(&self) -> Option<EnvelopeState> {
+        self.signal.get_envelope_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Constant 1.0 source, so any step in the output is the gain's
+    struct Dc;
+
+    impl GraphNode for Dc {
+        fn render_block(&mut self, out: &mut [f32], _ctx: &RenderCtx) {
+            out.fill(1.0);
+        }
+    }
+
+    #[test]
+    fn gain_change_ramps_without_zipper() {
+        let ctx = RenderCtx::from_freq(48_000.0, 440.0, 1.0);
+        let mut node = Gain::new(Dc, 1.0);
+        node.prepare(48_000.0, 64);
+
+        let mut out = vec![0.0; 64 * 8];
+        for (i, block) in out.chunks_mut(64).enumerate() {
+            if i == 2 {
+                node.set_gain(0.0);
+            }
+            node.render_block(block, &ctx);
+        }
+
+        let max_step = out.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0, f32::max);
+        assert!(max_step < 0.01, "largest step {max_step}");
+        assert_eq!(out[out.len() - 1], 0.0);
+        assert_eq!(node.gain(), 0.0);
+    }
 }
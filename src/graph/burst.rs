@@ -0,0 +1,64 @@
+use crate::{
+    dsp::{burst::BurstEnvelope, envelope::EnvelopeState},
+    graph::node::{GraphNode, RenderCtx},
+};
+
+/*
+Burst Envelope Node
+===================
+
+A one-shot envelope of several quick hits and a tail - the multi-hand
+"smear" of a hand clap. Multiply it into noise like any envelope:
+
+  // 4 hits 10 ms apart, then a 150 ms room tail
+  let clap = OscNode::noise()
+      .amplify(BurstNode::new(4, 0.010, 0.008, 0.15))
+      .through(FilterNode::bandpass(1200.0));
+
+Note-off is ignored: like a real clap, the burst always plays out. See
+`dsp/burst.rs` for the shape and timing.
+*/
+
+/// Multi-hit one-shot envelope (see module docs)
+pub struct BurstNode {
+    env: BurstEnvelope,
+}
+
+impl BurstNode {
+    /// `hits` hits `spacing_secs` apart, each decaying over `hit_decay_secs`,
+    /// the last over `tail_secs` (decays are 60 dB times)
+    pub fn new(hits: u8, spacing_secs: f32, hit_decay_secs: f32, tail_secs: f32) -> Self {
+        Self {
+            env: BurstEnvelope::new(hits, spacing_secs, hit_decay_secs, tail_secs),
+        }
+    }
+}
+
+impl GraphNode for BurstNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        for sample in out.iter_mut() {
+            *sample = self.env.next_level(ctx.sample_rate);
+        }
+    }
+
+    fn note_on(&mut self, _ctx: &RenderCtx) {
+        self.env.trigger();
+    }
+
+    fn get_envelope_level(&self) -> Option<f32> {
+        Some(self.env.level())
+    }
+
+    fn get_envelope_state(&self) -> Option<EnvelopeState> {
+        // One-shot: decaying while active
+        Some(if self.env.is_active() {
+            EnvelopeState::Decay
+        } else {
+            EnvelopeState::Idle
+        })
+    }
+
+    fn is_active(&self) -> bool {
+        self.env.is_active()
+    }
+}
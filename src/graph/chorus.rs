@@ -1,4 +1,4 @@
-use crate::dsp::delay::DelayLine;
+use crate::dsp::delay::{DelayLine, Interpolation};
 use crate::dsp::mix::blend_dry_wet;
 use crate::graph::node::{GraphNode, Modulatable, RenderCtx};
 use std::f32::consts::TAU;
@@ -52,8 +52,45 @@ Example usage:
   let wide_lead = OscNode::square()
       .through(ChorusNode::new(1.5, 4.0, 0.5))
       .through(FilterNode::lowpass(3000.0));
+
+
+Ensemble Mode
+-------------
+
+One modulated tap wobbles audibly: you can hear the single copy drift
+sharp, then flat. String machines of the 70s used three bucket-brigade
+(BBD) delays instead, each swept by the same LFO a third of a cycle
+apart. At any moment one copy is rising, one falling and one turning
+around, so the wobble averages out into a dense, steady shimmer:
+
+  voice 1   ╱╲  ╱╲  ╱╲        phase 0°      rate × 1.00
+  voice 2    ╱╲  ╱╲  ╱╲       phase 120°    rate × 1.07
+  voice 3   ╲  ╱╲  ╱╲  ╱      phase 240°    rate × 0.94
+
+The small rate offsets keep the three from locking into a repeating
+pattern. The voices are mostly uncorrelated, so each is scaled by 1/√3
+rather than 1/3: ensemble mode is about as loud as the single-voice
+chorus.
+
+  // Solina-style string ensemble
+  let strings = voices::strings().through(ChorusNode::ensemble(0.6, 3.0, 0.6));
+
+
+Interpolation
+-------------
+
+The swept delay is read between samples with 4-point Hermite
+interpolation: linear reads dull the highs by a varying amount as the
+delay sweeps, which smears bright sounds and adds a faint flutter (see
+`dsp/delay.rs`). `with_interpolation(Interpolation::Linear)` trades that
+back for the cheaper read.
 */
 
+/// Delay taps in ensemble mode
+const ENSEMBLE_VOICES: usize = 3;
+/// Rate of each ensemble voice relative to the chorus rate
+const ENSEMBLE_RATE_RATIOS: [f32; ENSEMBLE_VOICES] = [1.0, 1.07, 0.94];
+
 /// Parameters that can be modulated
 #[derive(Clone, Copy, Debug)]
 pub enum ChorusParam {
@@ -68,11 +105,15 @@ pub enum ChorusParam {
 /// Chorus effect - thickens sound with modulated delay
 pub struct ChorusNode {
     delay_line: DelayLine,
-    lfo_phase: f32,
+    /// LFO phase of each voice (only the first in single-voice mode)
+    lfo_phases: [f32; ENSEMBLE_VOICES],
+    /// Modulated taps in use: 1, or `ENSEMBLE_VOICES` in ensemble mode
+    voices: usize,
     rate: f32,        // LFO Hz
     depth_ms: f32,    // Modulation depth in ms
     mix: f32,         // Dry/wet
     base_delay_ms: f32,
+    interpolation: Interpolation,
 }
 
 impl ChorusNode {
@@ -84,46 +125,72 @@ impl ChorusNode {
     pub fn new(rate: f32, depth_ms: f32, mix: f32) -> Self {
         Self {
             delay_line: DelayLine::new(),
-            lfo_phase: 0.0,
+            lfo_phases: [0.0; ENSEMBLE_VOICES],
+            voices: 1,
             rate: rate.clamp(0.1, 10.0),
             depth_ms: depth_ms.clamp(0.5, 10.0),
             mix: mix.clamp(0.0, 1.0),
             base_delay_ms: 20.0, // Classic chorus base delay
+            interpolation: Interpolation::Hermite,
         }
     }
 
+    /// Create a three-voice ensemble chorus (see "Ensemble Mode" above).
+    ///
+    /// Same parameters as `new`; the voices' LFOs start a third of a cycle
+    /// apart and run at slightly different rates.
+    pub fn ensemble(rate: f32, depth_ms: f32, mix: f32) -> Self {
+        let mut chorus = Self::new(rate, depth_ms, mix);
+        chorus.voices = ENSEMBLE_VOICES;
+        for (voice, phase) in chorus.lfo_phases.iter_mut().enumerate() {
+            *phase = TAU * voice as f32 / ENSEMBLE_VOICES as f32;
+        }
+        chorus
+    }
+
     /// Set the base delay time (default 20ms).
     pub fn with_base_delay(mut self, ms: f32) -> Self {
         self.base_delay_ms = ms.clamp(5.0, 50.0);
         self
     }
+
+    /// Choose how the swept delay is read (default Hermite).
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
 }
 
 impl GraphNode for ChorusNode {
     fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
         let sample_rate = ctx.sample_rate;
         let phase_inc = TAU * self.rate / sample_rate;
+        // Equal-power sum of the voices
+        let voice_gain = 1.0 / (self.voices as f32).sqrt();
 
         for sample in out.iter_mut() {
-            // Calculate modulated delay time
-            let lfo_value = self.lfo_phase.sin(); // -1 to +1
-            let delay_ms = self.base_delay_ms + lfo_value * self.depth_ms;
-            let delay_samples = (delay_ms * sample_rate / 1000.0).max(1.0);
-
-            // Get delayed sample (interpolated for smooth modulation)
-            let delayed = self.delay_line.read_interpolated(delay_samples);
+            let mut delayed = 0.0;
+            for (phase, ratio) in self.lfo_phases[..self.voices].iter_mut().zip(ENSEMBLE_RATE_RATIOS) {
+                // Calculate modulated delay time
+                let lfo_value = phase.sin(); // -1 to +1
+                let delay_ms = self.base_delay_ms + lfo_value * self.depth_ms;
+                let delay_samples = (delay_ms * sample_rate / 1000.0).max(1.0);
+
+                // Get delayed sample (interpolated for smooth modulation)
+                delayed += self.delay_line.read_with(delay_samples, self.interpolation) * voice_gain;
+
+                // Advance LFO phase
+                *phase += phase_inc * ratio;
+                if *phase >= TAU {
+                    *phase -= TAU;
+                }
+            }
 
             // Write current sample to delay line
             self.delay_line.write(*sample);
 
             // Mix dry and wet using shared helper
             *sample = blend_dry_wet(*sample, delayed, self.mix);
-
-            // Advance LFO phase
-            self.lfo_phase += phase_inc;
-            if self.lfo_phase >= TAU {
-                self.lfo_phase -= TAU;
-            }
         }
     }
 
@@ -163,6 +230,7 @@ impl Modulatable for ChorusNode {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dsp::analysis::null_test;
 
     fn test_ctx() -> RenderCtx {
         RenderCtx::from_note(48000.0, 60, 100.0)
@@ -212,4 +280,35 @@ mod tests {
             assert!(sample.abs() < 2.0);
         }
     }
+
+    #[test]
+    fn test_dry_chorus_nulls() {
+        null_test::assert_null(ChorusNode::new(1.0, 3.0, 0.0), 48_000.0);
+    }
+
+    #[test]
+    fn test_ensemble_voices_are_staggered() {
+        let sample_rate = 48_000.0;
+        let ctx = RenderCtx::from_note(sample_rate, 60, 100.0);
+
+        // An impulse comes back once per voice, at three different delays
+        let mut ensemble = ChorusNode::ensemble(0.5, 3.0, 1.0);
+        let mut buffer = vec![0.0; 2_048];
+        buffer[0] = 1.0;
+        ensemble.render_block(&mut buffer, &ctx);
+        let arrivals: Vec<usize> = (1..buffer.len() - 1)
+            .filter(|&i| buffer[i] > buffer[i - 1] && buffer[i] >= buffer[i + 1] && buffer[i] > 0.05)
+            .collect();
+        assert_eq!(arrivals.len(), 3, "{arrivals:?}");
+        let total: f32 = buffer.iter().sum();
+        assert!((total - 3.0f32.sqrt()).abs() < 1e-3, "voices scaled by 1/√3: {total}");
+
+        null_test::assert_unity_gain(ChorusNode::ensemble(0.8, 3.0, 1.0), sample_rate, 0.1, 1.5);
+    }
+
+    #[test]
+    fn test_wet_chorus_keeps_level() {
+        // A modulated delay is a time shift - level should barely move
+        null_test::assert_unity_gain(ChorusNode::new(1.0, 3.0, 1.0), 48_000.0, 0.1, 0.5);
+    }
 }
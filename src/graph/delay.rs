@@ -1,6 +1,9 @@
 use crate::{
     dsp::delay::DelayLine,
+    dsp::denormal::ANTI_DENORMAL,
+    dsp::dynamics::{db_to_gain, EnvelopeFollower},
     dsp::mix::blend_dry_wet,
+    dsp::smooth::{SmoothedParam, DEFAULT_SMOOTHING_SECS},
     graph::node::{GraphNode, Modulatable},
 };
 
@@ -22,22 +25,55 @@ Parameters:
   - 0.5 = equal mix
   - 1.0 = wet only (delayed signal only)
 
+- ducking: How far the echoes dip while input is playing, in dB (0 = off)
+
 How it works:
 1. Store incoming audio in a circular buffer
 2. Read from buffer at (delay_time) ago
 3. Mix delayed signal back into input (feedback)
 4. Blend dry and wet signals (mix)
 
+Ducking:
+An envelope follower listens to the dry input and turns the echoes down
+while it's playing. The repeats keep circulating at full level inside the
+feedback loop; only what's heard is ducked, so they bloom back in the
+gaps between notes (over ~250 ms) instead of smearing the notes
+themselves. Keeps a busy sequenced lead intelligible under big feedback.
+
+  input    ▇▇▇  ▇▇▇  ▇▇▇
+  echoes   ▁▁▁▅▃▁▁▁▅▃▁▁▁▇▆▅▄▃▂
+
+Input at or above -20 dBFS ducks by the full amount; quieter input
+ducks proportionally less. 12 dB is a clear dip, 24+ dB nearly mutes.
+
 Example:
   let delay = DelayNode::new(250.0, 0.4, 0.3);
   // 250ms delay, 40% feedback (few echoes), 30% wet mix
+
+  let wash = DelayNode::new(6_000.0, 0.7, 0.4).with_max_delay(8.0);
+  // 6 second ambient echoes need a bigger buffer than the ~4 s default
+
+  let lead_echo = DelayNode::new(375.0, 0.8, 0.4).with_ducking(18.0);
+  // Long, loud echoes that stay out of the way until the lead pauses
 */
 
+/// Input level (linear, -20 dBFS) that ducks the echoes by the full amount
+const DUCK_THRESHOLD: f32 = 0.1;
+/// Ducking follower attack: fast enough to clear the way for a note's onset
+const DUCK_ATTACK_SECS: f32 = 0.01;
+/// Ducking follower release: how long the echoes take to swell back
+const DUCK_RELEASE_SECS: f32 = 0.25;
+/// Highest sample rate `with_max_delay` sizes the buffer for
+const MAX_SAMPLE_RATE: f32 = 192_000.0;
+
 pub struct DelayNode {
     delay_line: DelayLine,
     delay_ms: f32,
-    feedback: f32, // 0.0 - 0.95 (amount of delayed signal fed back)
-    mix: f32,      // 0.0 - 1.0 (dry/wet balance)
+    feedback: SmoothedParam, // 0.0 - 0.95 (amount of delayed signal fed back)
+    mix: SmoothedParam,      // 0.0 - 1.0 (dry/wet balance)
+    ducking_db: f32,         // 0.0 - 48.0 (echo dip while input plays, 0 = off)
+    duck_follower: EnvelopeFollower,
+    sample_rate: f32,
     // For smooth, click-free modulation we ramp delay time across the block.
     prev_delay_samples: f32,
     first_block: bool,
@@ -54,16 +90,48 @@ impl DelayNode {
         Self {
             delay_line: DelayLine::new(),
             delay_ms,
-            feedback: feedback.clamp(0.0, 0.95), // Prevent runaway
-            mix: mix.clamp(0.0, 1.0),
+            feedback: SmoothedParam::new(feedback.clamp(0.0, 0.95)), // Prevent runaway
+            mix: SmoothedParam::new(mix.clamp(0.0, 1.0)),
+            ducking_db: 0.0,
+            duck_follower: EnvelopeFollower::new(DUCK_ATTACK_SECS, DUCK_RELEASE_SECS, 48_000.0),
+            sample_rate: 48_000.0,
             prev_delay_samples: 0.0,
             first_block: true,
         }
     }
+
+    /// Size the delay buffer for up to `max_secs` at any supported rate
+    ///
+    /// The default buffer holds MAX_DELAY_SAMPLES (~4 s at 48kHz). This one
+    /// is sized for 192kHz, so `max_secs` holds whatever rate the device
+    /// runs at. Allocates here, at construction; longer delay times are
+    /// clamped to the buffer.
+    pub fn with_max_delay(mut self, max_secs: f32) -> Self {
+        self.delay_line = DelayLine::with_max_seconds(max_secs, MAX_SAMPLE_RATE);
+        self
+    }
+
+    /// Duck the echoes by up to `amount_db` while the input is playing
+    ///
+    /// See "Ducking" in the module docs. 0 turns it off.
+    pub fn with_ducking(mut self, amount_db: f32) -> Self {
+        self.ducking_db = amount_db.clamp(0.0, 48.0);
+        self
+    }
+
+    /// Gain for the echoes given the current input level
+    #[inline]
+    fn duck_gain(&self, level: f32) -> f32 {
+        db_to_gain(-self.ducking_db * (level / DUCK_THRESHOLD).min(1.0))
+    }
 }
 
 impl GraphNode for DelayNode {
     fn render_block(&mut self, out: &mut [f32], ctx: &super::node::RenderCtx) {
+        if ctx.sample_rate != self.sample_rate {
+            self.prepare(ctx.sample_rate, out.len());
+        }
+
         // Convert delay time from milliseconds to samples (as float for interpolation)
         let target_delay_samples = (self.delay_ms / 1000.0) * ctx.sample_rate;
 
@@ -89,11 +157,20 @@ impl GraphNode for DelayNode {
             let wet = self.delay_line.read_interpolated(delay_s);
 
             // Feedback: write dry + (wet * feedback)
-            let input_with_feedback = dry + (wet * self.feedback);
+            // (offset keeps decaying echoes out of the denormal range)
+            let input_with_feedback = dry + (wet * self.feedback.next_value()) + ANTI_DENORMAL;
             self.delay_line.write(input_with_feedback);
 
+            // Ducking dips only what's heard; the loop above keeps full level
+            let heard = if self.ducking_db > 0.0 {
+                let level = self.duck_follower.process(dry);
+                wet * self.duck_gain(level)
+            } else {
+                wet
+            };
+
             // Mix dry and wet using shared helper
-            *sample = blend_dry_wet(dry, wet, self.mix);
+            *sample = blend_dry_wet(dry, heard, self.mix.next_value());
 
             // Advance delay towards target across the block
             delay_s += step;
@@ -103,9 +180,15 @@ impl GraphNode for DelayNode {
         self.prev_delay_samples = target_delay_samples;
     }
 
+    fn prepare(&mut self, sample_rate: f32, _max_block: usize) {
+        self.sample_rate = sample_rate;
+        self.duck_follower.set_times(DUCK_ATTACK_SECS, DUCK_RELEASE_SECS, sample_rate);
+    }
+
     fn note_on(&mut self, _ctx: &super::node::RenderCtx) {
         // Clear buffer to avoid clicks from previous notes
         self.delay_line.reset();
+        self.duck_follower.reset();
     }
 }
 
@@ -114,6 +197,7 @@ pub enum DelayParam {
     DelayTime,
     Feedback,
     Mix,
+    Ducking,
 }
 
 impl Modulatable for DelayNode {
@@ -122,8 +206,9 @@ impl Modulatable for DelayNode {
     fn get_param(&self, param: Self::Param) -> f32 {
         match param {
             DelayParam::DelayTime => self.delay_ms,
-            DelayParam::Feedback => self.feedback,
-            DelayParam::Mix => self.mix,
+            DelayParam::Feedback => self.feedback.target(),
+            DelayParam::Mix => self.mix.target(),
+            DelayParam::Ducking => self.ducking_db,
         }
     }
 
@@ -133,10 +218,15 @@ impl Modulatable for DelayNode {
                 self.delay_ms = (base + modulation).clamp(0.1, 2000.0);
             }
             DelayParam::Feedback => {
-                self.feedback = (base + modulation).clamp(0.0, 0.95);
+                let feedback = (base + modulation).clamp(0.0, 0.95);
+                self.feedback.set_target(feedback, DEFAULT_SMOOTHING_SECS, self.sample_rate);
             }
             DelayParam::Mix => {
-                self.mix = (base + modulation).clamp(0.0, 1.0);
+                let mix = (base + modulation).clamp(0.0, 1.0);
+                self.mix.set_target(mix, DEFAULT_SMOOTHING_SECS, self.sample_rate);
+            }
+            DelayParam::Ducking => {
+                self.ducking_db = (base + modulation).clamp(0.0, 48.0);
             }
         }
     }
@@ -145,6 +235,7 @@ impl Modulatable for DelayNode {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dsp::analysis::null_test;
     use crate::graph::node::RenderCtx;
 
     #[test]
@@ -239,6 +330,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_max_delay_holds_at_high_sample_rates() {
+        // 6 s of delay at 96kHz needs twice the samples it does at 48kHz
+        let sample_rate = 96_000.0;
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 1.0);
+        let mut delay = DelayNode::new(6_000.0, 0.0, 1.0).with_max_delay(8.0);
+        assert!(delay.delay_line.capacity() >= (8.0 * 192_000.0) as usize);
+
+        let mut buffer = vec![0.0; 600_000];
+        buffer[0] = 1.0;
+        for block in buffer.chunks_mut(512) {
+            delay.render_block(block, &ctx);
+        }
+
+        let peak_pos = buffer
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.abs().partial_cmp(&b.1.abs()).unwrap())
+            .map(|(i, _)| i)
+            .unwrap();
+        assert!((peak_pos as i64 - 576_000).abs() < 5, "echo at {peak_pos}");
+    }
+
     #[test]
     fn test_delay_node_modulatable() {
         let mut delay = DelayNode::new(100.0, 0.3, 0.5);
@@ -253,10 +367,10 @@ mod tests {
         assert!((delay.delay_ms - 150.0).abs() < 0.1);
 
         delay.apply_modulation(DelayParam::Feedback, 0.3, 0.2);
-        assert!((delay.feedback - 0.5).abs() < 0.01);
+        assert!((delay.feedback.target() - 0.5).abs() < 0.01);
 
         delay.apply_modulation(DelayParam::Mix, 0.5, 0.3);
-        assert!((delay.mix - 0.8).abs() < 0.01);
+        assert!((delay.mix.target() - 0.8).abs() < 0.01);
     }
 
     #[test]
@@ -265,14 +379,14 @@ mod tests {
 
         // Test feedback clamping (should not exceed 0.95)
         delay.apply_modulation(DelayParam::Feedback, 0.5, 1.0);
-        assert!(delay.feedback <= 0.95, "Feedback should clamp to 0.95");
+        assert!(delay.feedback.target() <= 0.95, "Feedback should clamp to 0.95");
 
         // Test mix clamping
         delay.apply_modulation(DelayParam::Mix, 0.5, 1.0);
-        assert!(delay.mix <= 1.0, "Mix should clamp to 1.0");
+        assert!(delay.mix.target() <= 1.0, "Mix should clamp to 1.0");
 
         delay.apply_modulation(DelayParam::Mix, 0.5, -1.0);
-        assert!(delay.mix >= 0.0, "Mix should clamp to 0.0");
+        assert!(delay.mix.target() >= 0.0, "Mix should clamp to 0.0");
 
         // Test delay time clamping
         delay.apply_modulation(DelayParam::DelayTime, 100.0, -200.0);
@@ -320,4 +434,59 @@ mod tests {
             assert!(sample.is_finite(), "Smoothing should produce finite values");
         }
     }
+
+    #[test]
+    fn test_dry_delay_nulls() {
+        null_test::assert_null(DelayNode::new(250.0, 0.6, 0.0), 48_000.0);
+    }
+
+    #[test]
+    fn test_wet_delay_keeps_level() {
+        // Wet only, no feedback: a pure time shift, so level is unchanged once filled
+        null_test::assert_unity_gain(DelayNode::new(10.0, 0.0, 1.0), 48_000.0, 0.05, 0.1);
+    }
+
+    #[test]
+    fn test_mix_change_ramps_without_zipper() {
+        // DC in, delay not yet filled: dry = 1, wet = 0, so output = 1 - mix
+        let ctx = RenderCtx::from_freq(48_000.0, 440.0, 1.0);
+        let mut delay = DelayNode::new(100.0, 0.0, 0.0);
+        delay.prepare(48_000.0, 64);
+
+        let mut out = vec![1.0; 64 * 8];
+        for (i, block) in out.chunks_mut(64).enumerate() {
+            if i == 2 {
+                delay.apply_modulation(DelayParam::Mix, 0.0, 1.0);
+            }
+            delay.render_block(block, &ctx);
+        }
+
+        let max_step = out.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0, f32::max);
+        assert!(max_step < 0.01, "largest step {max_step}");
+        assert!(out[out.len() - 1].abs() < 1e-6);
+    }
+
+    #[test]
+    fn ducked_echoes_bloom_in_the_gaps() {
+        let sample_rate = 48_000.0;
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 1.0);
+        // 100 ms echoes with big feedback, wet only; a 0.5 DC "note" for 300 ms, then silence
+        let render = |ducking: f32| {
+            let mut delay = DelayNode::new(100.0, 0.9, 1.0).with_ducking(ducking);
+            delay.prepare(sample_rate, 512);
+            let mut out: Vec<f32> = (0..96_000).map(|i| if i < 14_400 { 0.5 } else { 0.0 }).collect();
+            for block in out.chunks_mut(512) {
+                delay.render_block(block, &ctx);
+            }
+            out
+        };
+        let plain = render(0.0);
+        let ducked = render(24.0);
+
+        // While the note plays the echoes are ~24 dB down
+        assert!(ducked[12_000] < 0.1 * plain[12_000], "{} vs {}", ducked[12_000], plain[12_000]);
+        // 1.5 s into the gap they've swelled back to (nearly) full level
+        let (a, b) = (ducked[14_400 + 72_000], plain[14_400 + 72_000]);
+        assert!(b > 0.01 && (a - b).abs() < 0.1 * b, "{a} vs {b}");
+    }
 }
@@ -1,5 +1,6 @@
-use crate::dsp::distortion::{foldback_buffer, hard_clip_buffer, soft_clip_buffer};
+use crate::dsp::distortion::{foldback_buffer, hard_clip_buffer};
 use crate::dsp::mix::apply_dry_wet;
+use crate::dsp::simd::soft_clip_buffer;
 use crate::graph::node::{GraphNode, Modulatable, RenderCtx};
 use crate::MAX_BLOCK_SIZE;
 
@@ -74,7 +75,7 @@ pub struct DistortionNode {
     drive: f32,
     mix: f32,
     threshold: f32, // For hard clip and foldback
-    dry_buffer: [f32; MAX_BLOCK_SIZE], // Pre-allocated for allocation-free rendering
+    dry_buffer: Vec<f32>, // Pre-allocated for allocation-free rendering (resized in prepare)
 }
 
 impl DistortionNode {
@@ -85,7 +86,7 @@ impl DistortionNode {
             drive: drive.max(1.0),
             mix: mix.clamp(0.0, 1.0),
             threshold: 1.0,
-            dry_buffer: [0.0; MAX_BLOCK_SIZE],
+            dry_buffer: vec![0.0; MAX_BLOCK_SIZE],
         }
     }
 
@@ -96,7 +97,7 @@ impl DistortionNode {
             drive: drive.max(1.0),
             mix: mix.clamp(0.0, 1.0),
             threshold: 1.0,
-            dry_buffer: [0.0; MAX_BLOCK_SIZE],
+            dry_buffer: vec![0.0; MAX_BLOCK_SIZE],
         }
     }
 
@@ -107,7 +108,7 @@ impl DistortionNode {
             drive: drive.max(1.0),
             mix: mix.clamp(0.0, 1.0),
             threshold: 1.0,
-            dry_buffer: [0.0; MAX_BLOCK_SIZE],
+            dry_buffer: vec![0.0; MAX_BLOCK_SIZE],
         }
     }
 
@@ -127,7 +128,7 @@ impl DistortionNode {
 
 impl GraphNode for DistortionNode {
     fn render_block(&mut self, out: &mut [f32], _ctx: &RenderCtx) {
-        let len = out.len().min(MAX_BLOCK_SIZE);
+        let len = out.len().min(self.dry_buffer.len());
 
         // Store dry signal in pre-allocated buffer (no allocation)
         self.dry_buffer[..len].copy_from_slice(&out[..len]);
@@ -148,6 +149,10 @@ impl GraphNode for DistortionNode {
         // Mix dry/wet using shared helper
         apply_dry_wet(&self.dry_buffer[..len], &mut out[..len], self.mix);
     }
+
+    fn prepare(&mut self, _sample_rate: f32, max_block: usize) {
+        self.dry_buffer.resize(max_block, 0.0);
+    }
 }
 
 impl Modulatable for DistortionNode {
@@ -175,6 +180,7 @@ impl Modulatable for DistortionNode {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dsp::analysis::null_test;
 
     fn test_ctx() -> RenderCtx {
         RenderCtx::from_note(48000.0, 60, 100.0)
@@ -247,4 +253,17 @@ mod tests {
             assert!(sample.abs() <= 0.5 + 1e-6);
         }
     }
+
+    #[test]
+    fn test_dry_distortion_nulls() {
+        null_test::assert_null(DistortionNode::soft(8.0, 0.0), 48_000.0);
+        null_test::assert_null(DistortionNode::hard(8.0, 0.0), 48_000.0);
+        null_test::assert_null(DistortionNode::foldback(8.0, 0.0), 48_000.0);
+    }
+
+    #[test]
+    fn test_hard_clip_below_threshold_keeps_level() {
+        // Drive 1 and a signal under the threshold: the clipper never engages
+        null_test::assert_unity_gain(DistortionNode::hard(1.0, 1.0), 48_000.0, 0.0, 0.01);
+    }
 }
@@ -0,0 +1,215 @@
+use crate::dsp::dynamics::{db_to_gain, gain_reduction_db, EnvelopeFollower};
+use crate::dsp::filter::SVFilter;
+use crate::dsp::meter::to_db;
+use crate::graph::node::{GraphNode, Modulatable, RenderCtx};
+
+/*
+Dynamic EQ Node
+===============
+
+An EQ band that only cuts when it has to. A static cut at a resonant
+filter's peak dulls every note; a compressor reacts to the whole signal.
+A dynamic EQ band listens to one frequency range and turns just that
+range down, and only while it's louder than the threshold:
+
+  Input ──┬──────────────────────────(−)──→ Output
+          │                           ↑
+          └→ [Bandpass] ──┬──→ × (1 - gain)
+                          │          ↑
+                          └→ [Follower] → [Gain computer]
+
+  Quiet notes          band below threshold → passes untouched
+  Resonant squelch     band over threshold  → that band is pulled down
+
+Classic uses: an acid bassline whose resonance whistles on a few notes,
+a pad with one harsh note, a snare ring that only booms on accents.
+
+
+Parameters
+----------
+
+Frequency (Hz):
+  Center of the band to watch and cut.
+
+Threshold (dB):
+  Band level where the cut starts. -20 to -30 dB catches peaks on a
+  normally leveled part.
+
+Ratio (1.0 - 20.0):
+  How firmly the band is held down once it's over the threshold.
+  2:1 gentle, 4:1 firm, 10:1+ nearly a ceiling.
+
+Q (0.5 - 10.0, default 2.0):
+  Band width. Higher = narrower, for a single whistling resonance.
+
+Attack / release default to 5 ms / 100 ms; see `dsp/dynamics.rs`.
+
+Example usage:
+
+  // Tame the 1.2 kHz whistle of an acid line when the resonance spikes
+  let acid = voices::bass()
+      .through(DynamicEqNode::new(1200.0, -24.0, 4.0).with_q(4.0));
+
+
+How the Cut Works
+-----------------
+
+The band-passed copy (unity gain at the center) is subtracted in
+proportion to the reduction: out = x - (1 - g) × band. At the center the
+level becomes x × g; away from it the band copy fades out and the signal
+passes untouched - a bell-shaped cut whose depth follows the detector.
+*/
+
+/// Default detector attack
+const DEFAULT_ATTACK_SECS: f32 = 0.005;
+/// Default detector release
+const DEFAULT_RELEASE_SECS: f32 = 0.1;
+/// Default band width
+const DEFAULT_Q: f32 = 2.0;
+
+/// Parameters that can be modulated
+#[derive(Clone, Copy, Debug)]
+pub enum DynamicEqParam {
+    /// Band center (Hz)
+    Frequency,
+    /// Level where the cut starts (dB)
+    Threshold,
+    /// Compression ratio above the threshold
+    Ratio,
+}
+
+/// A downward dynamic EQ band (see module docs)
+pub struct DynamicEqNode {
+    band: SVFilter,
+    follower: EnvelopeFollower,
+    threshold_db: f32,
+    ratio: f32,
+    q: f32,
+    attack_secs: f32,
+    release_secs: f32,
+    /// Sample rate the follower's times were computed for
+    sample_rate: f32,
+    /// Most recent gain applied at the band center (1.0 = no cut)
+    gain: f32,
+}
+
+impl DynamicEqNode {
+    /// Cut the band around `frequency_hz` by `ratio`:1 above `threshold_db`
+    pub fn new(frequency_hz: f32, threshold_db: f32, ratio: f32) -> Self {
+        Self {
+            band: SVFilter::bandpass(frequency_hz.clamp(20.0, 20_000.0)),
+            follower: EnvelopeFollower::new(DEFAULT_ATTACK_SECS, DEFAULT_RELEASE_SECS, 48_000.0),
+            threshold_db,
+            ratio: ratio.max(1.0),
+            q: DEFAULT_Q,
+            attack_secs: DEFAULT_ATTACK_SECS,
+            release_secs: DEFAULT_RELEASE_SECS,
+            sample_rate: 48_000.0,
+            gain: 1.0,
+        }
+    }
+
+    /// Set the band width (higher = narrower)
+    pub fn with_q(mut self, q: f32) -> Self {
+        self.q = q.clamp(0.1, 20.0);
+        self
+    }
+
+    /// Set how fast the cut engages and lets go
+    pub fn with_times(mut self, attack_secs: f32, release_secs: f32) -> Self {
+        self.attack_secs = attack_secs.max(0.0);
+        self.release_secs = release_secs.max(0.0);
+        self.follower.set_times(self.attack_secs, self.release_secs, self.sample_rate);
+        self
+    }
+
+    /// Current gain at the band center in dB (0 = not cutting), for meters
+    pub fn gain_db(&self) -> f32 {
+        to_db(self.gain)
+    }
+}
+
+impl GraphNode for DynamicEqNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        if ctx.sample_rate != self.sample_rate {
+            self.prepare(ctx.sample_rate, out.len());
+        }
+        let k = 1.0 / self.q;
+        let g = SVFilter::compute_g(self.band.cutoff_hz, ctx.sample_rate);
+
+        for sample in out.iter_mut() {
+            // k × bandpass has unity gain at the center
+            let band = k * self.band.next_sample(*sample, k, g).bandpass;
+            let level = self.follower.process(band);
+            self.gain = db_to_gain(-gain_reduction_db(to_db(level), self.threshold_db, self.ratio));
+            *sample -= (1.0 - self.gain) * band;
+        }
+    }
+
+    fn prepare(&mut self, sample_rate: f32, _max_block: usize) {
+        self.sample_rate = sample_rate;
+        self.follower.set_times(self.attack_secs, self.release_secs, sample_rate);
+    }
+}
+
+impl Modulatable for DynamicEqNode {
+    type Param = DynamicEqParam;
+
+    fn get_param(&self, param: Self::Param) -> f32 {
+        match param {
+            DynamicEqParam::Frequency => self.band.cutoff_hz,
+            DynamicEqParam::Threshold => self.threshold_db,
+            DynamicEqParam::Ratio => self.ratio,
+        }
+    }
+
+    fn apply_modulation(&mut self, param: Self::Param, base: f32, modulation: f32) {
+        match param {
+            DynamicEqParam::Frequency => {
+                self.band.set_cutoff((base + modulation).clamp(20.0, 20_000.0));
+            }
+            DynamicEqParam::Threshold => {
+                self.threshold_db = (base + modulation).min(0.0);
+            }
+            DynamicEqParam::Ratio => {
+                self.ratio = (base + modulation).max(1.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::meter::peak;
+
+    fn tone(hz: f32, amplitude: f32, sample_rate: f32) -> Vec<f32> {
+        (0..sample_rate as usize)
+            .map(|i| amplitude * (std::f32::consts::TAU * hz * i as f32 / sample_rate).sin())
+            .collect()
+    }
+
+    #[test]
+    fn cuts_the_band_only_when_it_is_loud() {
+        let sample_rate = 48_000.0;
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 100.0);
+        let settled = |signal: &[f32]| peak(&signal[24_000..]);
+
+        // Quiet tone at the center: below threshold, left alone
+        let mut quiet = tone(1_000.0, 0.02, sample_rate);
+        DynamicEqNode::new(1_000.0, -20.0, 4.0).render_block(&mut quiet, &ctx);
+        assert!((settled(&quiet) - 0.02).abs() < 1e-3);
+
+        // Loud tone at the center: 14 dB over at 4:1 → ~10.5 dB cut
+        let mut loud = tone(1_000.0, 0.5, sample_rate);
+        let mut eq = DynamicEqNode::new(1_000.0, -20.0, 4.0);
+        eq.render_block(&mut loud, &ctx);
+        assert!((to_db(settled(&loud)) - (-16.5)).abs() < 1.0, "{} dB", to_db(settled(&loud)));
+        assert!((eq.gain_db() + 10.5).abs() < 1.0);
+
+        // Loud tone far from the band: barely touched
+        let mut other = tone(100.0, 0.5, sample_rate);
+        DynamicEqNode::new(4_000.0, -20.0, 4.0).with_q(4.0).render_block(&mut other, &ctx);
+        assert!(settled(&other) > 0.49);
+    }
+}
@@ -96,6 +96,12 @@ impl EnvNode {
     }
 }
 
+impl Default for EnvNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GraphNode for EnvNode {
     fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
         self.env.render(out, ctx);
@@ -116,6 +122,10 @@ impl GraphNode for EnvNode {
     fn get_envelope_level(&self) -> Option<f32> {
         Some(self.env.level())
     }
+
+    fn get_envelope_state(&self) -> Option<EnvelopeState> {
+        Some(self.env.state())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,167 @@
+use crate::dsp::distortion::soft_clip;
+use crate::dsp::filter::SVFilter;
+use crate::dsp::smooth::{SmoothedParam, DEFAULT_SMOOTHING_SECS};
+use crate::graph::node::{GraphNode, Modulatable, RenderCtx};
+use crate::MAX_BLOCK_SIZE;
+
+/*
+Exciter Node
+============
+
+Adds brightness that wasn't in the recording. An EQ can only boost the
+highs a sound already has; a dull sample or a dark pad has little up
+there to boost. An exciter (the Aphex "Aural Exciter" trick) makes new
+high harmonics from the top of the signal and mixes a little of them back
+in:
+
+  Input ──┬─────────────────────────────────────────(+)──→ Output
+          │                                          ↑
+          └→ [Highpass] → [Saturate] → [Highpass] → × amount
+
+  1. Highpass at `frequency_hz` keeps only the upper band (the part that
+     should sparkle), so the lows and mids never distort
+  2. Saturation generates harmonics of that band, an octave and more up
+  3. A second highpass removes the low intermodulation the saturation
+     adds, leaving only "air"
+
+Because the harmonics follow the signal, the added sheen moves with the
+performance instead of sitting there like hiss.
+
+Parameters
+----------
+
+Frequency (1000 - 8000 Hz):
+  Where the excited band starts. Lower = more presence and bite,
+  higher = just air and shimmer.
+
+Amount (0.0 - 1.0):
+  How much of the generated harmonics is added. 0.1 - 0.3 is usually
+  plenty; the effect is heard as "clearer" long before it sounds
+  distorted.
+
+Example usage:
+
+  // Dull sampled snare cuts through the mix
+  let snare = SamplerNode::new(samples, 44_100.0).through(ExciterNode::new(3000.0, 0.3));
+
+  // Air on a pad
+  let pad = voices::pad().through(ExciterNode::new(6000.0, 0.2));
+*/
+
+/// Drive into the saturator (high enough that quiet highs still bloom)
+const EXCITER_DRIVE: f32 = 4.0;
+
+/// Parameters that can be modulated
+#[derive(Clone, Copy, Debug)]
+pub enum ExciterParam {
+    /// Start of the excited band (Hz)
+    Frequency,
+    /// Level of added harmonics (0.0 - 1.0)
+    Amount,
+}
+
+/// Harmonic exciter: blends saturated highs back into the signal
+pub struct ExciterNode {
+    pre_filter: SVFilter,
+    post_filter: SVFilter,
+    amount: SmoothedParam,
+    sample_rate: f32,
+    /// The generated harmonics for the current block (pre-allocated)
+    band: Vec<f32>,
+}
+
+impl ExciterNode {
+    /// Create an exciter
+    ///
+    /// - `frequency_hz`: the band above this is excited
+    /// - `amount`: 0.0 (off) to 1.0 of generated harmonics added
+    pub fn new(frequency_hz: f32, amount: f32) -> Self {
+        let frequency_hz = frequency_hz.clamp(20.0, 20_000.0);
+        Self {
+            pre_filter: SVFilter::highpass(frequency_hz),
+            post_filter: SVFilter::highpass(frequency_hz),
+            amount: SmoothedParam::new(amount.clamp(0.0, 1.0)),
+            sample_rate: 48_000.0,
+            band: vec![0.0; MAX_BLOCK_SIZE],
+        }
+    }
+}
+
+impl GraphNode for ExciterNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        self.sample_rate = ctx.sample_rate;
+
+        for chunk in out.chunks_mut(MAX_BLOCK_SIZE) {
+            let band = &mut self.band[..chunk.len()];
+            band.copy_from_slice(chunk);
+            self.pre_filter.render(band, ctx);
+            for sample in band.iter_mut() {
+                *sample = soft_clip(*sample, EXCITER_DRIVE);
+            }
+            self.post_filter.render(band, ctx);
+
+            for (sample, &harmonics) in chunk.iter_mut().zip(band.iter()) {
+                *sample += harmonics * self.amount.next_value();
+            }
+        }
+    }
+
+    fn prepare(&mut self, sample_rate: f32, _max_block: usize) {
+        self.sample_rate = sample_rate;
+    }
+}
+
+impl Modulatable for ExciterNode {
+    type Param = ExciterParam;
+
+    fn get_param(&self, param: Self::Param) -> f32 {
+        match param {
+            ExciterParam::Frequency => self.pre_filter.cutoff_hz,
+            ExciterParam::Amount => self.amount.target(),
+        }
+    }
+
+    fn apply_modulation(&mut self, param: Self::Param, base: f32, modulation: f32) {
+        match param {
+            ExciterParam::Frequency => {
+                let frequency = (base + modulation).clamp(20.0, 20_000.0);
+                self.pre_filter.set_cutoff(frequency);
+                self.post_filter.set_cutoff(frequency);
+            }
+            ExciterParam::Amount => {
+                let amount = (base + modulation).clamp(0.0, 1.0);
+                self.amount.set_target(amount, DEFAULT_SMOOTHING_SECS, self.sample_rate);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::analysis::harmonics::HarmonicAnalysis;
+
+    #[test]
+    fn adds_harmonics_above_the_band_and_leaves_lows_alone() {
+        let sample_rate = 48_000.0;
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 100.0);
+        let sine = |hz: f32| -> Vec<f32> {
+            (0..48_000)
+                .map(|i| 0.5 * (std::f32::consts::TAU * hz * i as f32 / sample_rate).sin())
+                .collect()
+        };
+
+        // A 2 kHz tone gains a 3rd harmonic at 6 kHz
+        let mut bright = sine(2_000.0);
+        ExciterNode::new(1_500.0, 0.5).render_block(&mut bright, &ctx);
+        let analysis = HarmonicAnalysis::of(&bright[4_800..], sample_rate, 2_000.0);
+        assert!(analysis.amplitudes[2] > 0.01, "{:?}", &analysis.amplitudes[..4]);
+
+        // A 100 Hz tone is far below the band: barely touched
+        let dry = sine(100.0);
+        let mut low = dry.clone();
+        ExciterNode::new(3_000.0, 0.5).render_block(&mut low, &ctx);
+        let diff = dry.iter().zip(&low).fold(0.0f32, |m, (a, b)| m.max((a - b).abs()));
+        assert!(diff < 0.01, "low tone changed by {diff}");
+    }
+}
@@ -1,9 +1,20 @@
 use crate::graph::{
     amplify::{Amplify, Gain},
+    chorus::ChorusNode,
+    delay::DelayNode,
+    distortion::DistortionNode,
+    filter::FilterNode,
     mix::Mix,
     modulate::Modulate,
     node::{GraphNode, Modulatable},
+    portamento::Portamento,
+    reverb::ReverbNode,
+    slew::SlewNode,
+    split::Split,
     through::Through,
+    tremolo::TremoloNode,
+    vibrato::Vibrato,
+    vocoder::Vocoder,
 };
 
 pub trait NodeExt: GraphNode + Sized {
@@ -23,6 +34,21 @@ pub trait NodeExt: GraphNode + Sized {
         Through::new(self, filter)
     }
 
+    /// Glide between notes over `glide_secs` (see `Portamento`)
+    fn portamento(self, glide_secs: f32) -> Portamento<Self> {
+        Portamento::new(self, glide_secs)
+    }
+
+    /// Pitch vibrato: LFO rate (Hz), depth (cents) and onset delay (ms) (see `Vibrato`)
+    fn vibrato(self, rate_hz: f32, depth_cents: f32, onset_delay_ms: f32) -> Vibrato<Self> {
+        Vibrato::new(self, rate_hz, depth_cents, onset_delay_ms)
+    }
+
+    /// Play this voice below `split_note` and `upper` from it up
+    fn split<U: GraphNode>(self, split_note: u8, upper: U) -> Split<Self, U> {
+        Split::new(self, split_note, upper)
+    }
+
     fn modulate<M: GraphNode>(self, lfo: M, param: Self::Param, depth: f32) -> Modulate<Self, M>
     where
         Self: Modulatable,
@@ -33,6 +59,80 @@ pub trait NodeExt: GraphNode + Sized {
     fn mix<M: GraphNode>(self, source: M, balance: f32) -> Mix<Self, M> {
         Mix::new(self, source, balance)
     }
+
+    // Effect shorthands: each is `.through(<Node>::...)` with common defaults.
+    // Reach for the explicit node when you need its other settings.
+
+    /// Low-pass filter at `cutoff_hz` (`.through(FilterNode::lowpass(..))`)
+    fn lowpass(self, cutoff_hz: f32) -> Through<Self, FilterNode> {
+        self.through(FilterNode::lowpass(cutoff_hz))
+    }
+
+    /// High-pass filter at `cutoff_hz` (`.through(FilterNode::highpass(..))`)
+    fn highpass(self, cutoff_hz: f32) -> Through<Self, FilterNode> {
+        self.through(FilterNode::highpass(cutoff_hz))
+    }
+
+    /// Band-pass filter centered on `cutoff_hz` (`.through(FilterNode::bandpass(..))`)
+    fn bandpass(self, cutoff_hz: f32) -> Through<Self, FilterNode> {
+        self.through(FilterNode::bandpass(cutoff_hz))
+    }
+
+    /// Echo: delay time, feedback (0-0.95) and dry/wet mix (`.through(DelayNode::new(..))`)
+    fn delay(self, delay_ms: f32, feedback: f32, mix: f32) -> Through<Self, DelayNode> {
+        self.through(DelayNode::new(delay_ms, feedback, mix))
+    }
+
+    /// Small-room reverb with dry/wet `mix` (`.through(ReverbNode::room(..))`)
+    fn reverb(self, mix: f32) -> Through<Self, ReverbNode> {
+        self.through(ReverbNode::room(mix))
+    }
+
+    /// Chorus: LFO rate (Hz), depth (ms) and dry/wet mix (`.through(ChorusNode::new(..))`)
+    fn chorus(self, rate_hz: f32, depth_ms: f32, mix: f32) -> Through<Self, ChorusNode> {
+        self.through(ChorusNode::new(rate_hz, depth_ms, mix))
+    }
+
+    /// Sine tremolo: LFO rate (Hz) and depth (0-1) (`.through(TremoloNode::sine(..))`)
+    fn tremolo(self, rate_hz: f32, depth: f32) -> Through<Self, TremoloNode> {
+        self.through(TremoloNode::sine(rate_hz, depth))
+    }
+
+    /// Vocode this node (the carrier) with `modulator`'s band levels (see `Vocoder`)
+    fn vocode<M: GraphNode>(self, modulator: M, bands: usize) -> Vocoder<Self, M> {
+        Vocoder::new(self, modulator, bands)
+    }
+
+    /// Limit how fast the signal rises and falls (`.through(SlewNode::new(rise, fall))`)
+    fn slew(self, rise_secs: f32, fall_secs: f32) -> Through<Self, SlewNode> {
+        self.through(SlewNode::new(rise_secs, fall_secs))
+    }
+
+    /// Fully wet soft-clip saturation (`.through(DistortionNode::soft(drive, 1.0))`)
+    fn distort(self, drive: f32) -> Through<Self, DistortionNode> {
+        self.through(DistortionNode::soft(drive, 1.0))
+    }
 }
 
 impl<T: GraphNode> NodeExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::analysis::null_test::{probe_signal, process};
+    use crate::graph::oscillator::OscNode;
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    #[test]
+    fn shorthand_matches_explicit_chain() {
+        let input = probe_signal(SAMPLE_RATE, 4_800);
+        let mut sugar = OscNode::sine().lowpass(800.0).delay(20.0, 0.3, 0.4).distort(2.0);
+        let mut explicit = OscNode::sine()
+            .through(FilterNode::lowpass(800.0))
+            .through(DelayNode::new(20.0, 0.3, 0.4))
+            .through(DistortionNode::soft(2.0, 1.0));
+
+        assert_eq!(process(&mut sugar, &input, SAMPLE_RATE), process(&mut explicit, &input, SAMPLE_RATE));
+    }
+}
@@ -1,6 +1,11 @@
 use crate::{
-    dsp::filter::SVFilter,
-    graph::node::{GraphNode, Modulatable},
+    dsp::{
+        filter::SVFilter,
+        smooth::{SmoothedParam, DEFAULT_SMOOTHING_SECS},
+    },
+    dsp::modulate::apply_modulation,
+    graph::node::{GraphNode, Modulatable, NodeCommand, RenderCtx},
+    MAX_BLOCK_SIZE,
 };
 
 /*
@@ -73,6 +78,24 @@ Example usage:
   let filter = OscNode::sawtooth()
       .through(FilterNode::lowpass(500.0));
   // (Apply envelope modulation to sweep the cutoff)
+
+  // Filter FM: an audio-rate oscillator moves the cutoff every sample
+  let growl = OscNode::sawtooth()
+      .through(FilterNode::lowpass(800.0).with_cutoff_fm(OscNode::sine(), 600.0));
+
+
+Audio-Rate Cutoff Modulation
+----------------------------
+
+`.modulate()` updates the cutoff once per block, which is right for LFOs
+and envelopes but smears an audio-rate modulator into its average (zero).
+`with_cutoff_fm` renders the modulator into a buffer and sets the cutoff
+for every sample:
+
+    cutoff[n] = base + modulator[n] × depth
+
+The sidebands this creates give growls, vowel-like buzz, and metallic
+textures. It costs one `tan` per sample, so it's opt-in.
 */
 
 #[derive(Clone, Copy, Debug)]
@@ -85,47 +108,36 @@ pub struct FilterNode {
     filter: SVFilter,
     base_cutoff: f32,
     base_resonance: f32,
+    /// Modulated cutoff in octaves (log2 Hz), ramped so block-rate changes don't zipper
+    log2_cutoff: SmoothedParam,
+    sample_rate: f32,
 }
 
 impl FilterNode {
-    pub fn lowpass(cutoff_hz: f32) -> Self {
-        let filter = SVFilter::lowpass(cutoff_hz);
-
-        FilterNode {
+    fn new(filter: SVFilter, cutoff_hz: f32) -> Self {
+        Self {
             filter,
             base_cutoff: cutoff_hz,
             base_resonance: 0.0,
+            log2_cutoff: SmoothedParam::new(cutoff_hz.log2()),
+            sample_rate: 48_000.0,
         }
     }
 
-    pub fn highpass(cutoff_hz: f32) -> Self {
-        let filter = SVFilter::highpass(cutoff_hz);
+    pub fn lowpass(cutoff_hz: f32) -> Self {
+        Self::new(SVFilter::lowpass(cutoff_hz), cutoff_hz)
+    }
 
-        FilterNode {
-            filter,
-            base_cutoff: cutoff_hz,
-            base_resonance: 0.0,
-        }
+    pub fn highpass(cutoff_hz: f32) -> Self {
+        Self::new(SVFilter::highpass(cutoff_hz), cutoff_hz)
     }
 
     pub fn bandpass(cutoff_hz: f32) -> Self {
-        let filter = SVFilter::bandpass(cutoff_hz);
-
-        FilterNode {
-            filter,
-            base_cutoff: cutoff_hz,
-            base_resonance: 0.0,
-        }
+        Self::new(SVFilter::bandpass(cutoff_hz), cutoff_hz)
     }
 
     pub fn notch(cutoff_hz: f32) -> Self {
-        let filter = SVFilter::notch(cutoff_hz);
-
-        FilterNode {
-            filter,
-            base_cutoff: cutoff_hz,
-            base_resonance: 0.0,
-        }
+        Self::new(SVFilter::notch(cutoff_hz), cutoff_hz)
     }
 
     /// Set the resonance (Q factor) for this filter
@@ -145,6 +157,33 @@ impl FilterNode {
         self
     }
 
+    /// Drive the cutoff from an audio-rate signal, updated every sample
+    ///
+    /// `depth_hz` scales the modulator (typically ±1.0) around the base
+    /// cutoff; the result is clamped to 20 Hz-20 kHz. Block-rate `.modulate()`
+    /// on the returned node still moves the base cutoff.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Cutoff swings ±600 Hz at the note's pitch
+    /// FilterNode::lowpass(800.0).with_cutoff_fm(OscNode::sine(), 600.0)
+    /// ```
+    pub fn with_cutoff_fm<M: GraphNode>(self, modulator: M, depth_hz: f32) -> FilterFm<M> {
+        FilterFm {
+            filter: self,
+            modulator,
+            depth_hz,
+            cutoff_buffer: vec![0.0; MAX_BLOCK_SIZE],
+        }
+    }
+
+    /// Magnitude response (dB) at `freq_hz` for the current cutoff and resonance
+    ///
+    /// See `dsp::analysis::response` to plot a whole curve.
+    pub fn magnitude_db(&self, freq_hz: f32, sample_rate: f32) -> f32 {
+        self.filter.magnitude_db(freq_hz, sample_rate)
+    }
+
     // Test-only getters to verify modulation behavior
     #[cfg(test)]
     pub fn get_base_cutoff(&self) -> f32 {
@@ -172,7 +211,9 @@ impl Modulatable for FilterNode {
         match param {
             FilterParam::Cutoff => {
                 self.base_cutoff = base;
-                self.filter.set_cutoff(final_value.clamp(20.0, 20_000.0));
+                let cutoff = final_value.clamp(20.0, 20_000.0);
+                self.log2_cutoff
+                    .set_target(cutoff.log2(), DEFAULT_SMOOTHING_SECS, self.sample_rate);
             }
             FilterParam::Resonance => {
                 self.base_resonance = base;
@@ -183,7 +224,150 @@ impl Modulatable for FilterNode {
 }
 
 impl GraphNode for FilterNode {
-    fn render_block(&mut self, out: &mut [f32], ctx: &super::node::RenderCtx) {
-        self.filter.render(out, ctx);
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        self.sample_rate = ctx.sample_rate;
+        self.filter.render_smoothed(out, ctx, &mut self.log2_cutoff);
+    }
+
+    fn prepare(&mut self, sample_rate: f32, _max_block: usize) {
+        self.sample_rate = sample_rate;
+    }
+}
+
+/// A `FilterNode` whose cutoff follows an audio-rate modulator
+///
+/// Built with `FilterNode::with_cutoff_fm`.
+pub struct FilterFm<M> {
+    filter: FilterNode,
+    modulator: M,
+    depth_hz: f32,
+    /// Modulator output, then the per-sample cutoff (no allocation in render)
+    cutoff_buffer: Vec<f32>,
+}
+
+impl<M: GraphNode> GraphNode for FilterFm<M> {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        let filter = &mut self.filter;
+        filter.sample_rate = ctx.sample_rate;
+
+        let cutoffs = &mut self.cutoff_buffer[..out.len()];
+        cutoffs.fill(0.0);
+        self.modulator.render_block(cutoffs, ctx);
+
+        // Base cutoff still ramps (in octaves) when block-rate modulation moves it
+        for cutoff in cutoffs.iter_mut() {
+            let base = filter.log2_cutoff.next_value().exp2();
+            *cutoff = apply_modulation(base, *cutoff, self.depth_hz).clamp(20.0, 20_000.0);
+        }
+
+        filter.filter.render_cutoff_buffer(out, ctx, cutoffs);
+    }
+
+    fn prepare(&mut self, sample_rate: f32, max_block: usize) {
+        self.cutoff_buffer.resize(max_block, 0.0);
+        self.filter.prepare(sample_rate, max_block);
+        self.modulator.prepare(sample_rate, max_block);
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.modulator.seed(seed);
+    }
+
+    fn note_on(&mut self, ctx: &RenderCtx) {
+        self.modulator.note_on(ctx);
+    }
+
+    fn note_off(&mut self, ctx: &RenderCtx) {
+        self.modulator.note_off(ctx);
+    }
+
+    fn command(&mut self, command: NodeCommand) {
+        self.modulator.command(command);
+    }
+}
+
+impl<M: GraphNode> Modulatable for FilterFm<M> {
+    type Param = FilterParam;
+
+    fn get_param(&self, param: Self::Param) -> f32 {
+        self.filter.get_param(param)
+    }
+
+    fn apply_modulation(&mut self, param: Self::Param, base: f32, modulation: f32) {
+        self.filter.apply_modulation(param, base, modulation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::analysis::null_test;
+    use crate::graph::node::RenderCtx;
+
+    #[test]
+    fn test_open_lowpass_keeps_level() {
+        // Cutoff far above the probe's highest partial (3.3 kHz)
+        null_test::assert_unity_gain(FilterNode::lowpass(20_000.0), 48_000.0, 0.01, 0.5);
+    }
+
+    #[test]
+    fn test_open_highpass_keeps_level() {
+        // Cutoff far below the probe's lowest partial (220 Hz)
+        null_test::assert_unity_gain(FilterNode::highpass(10.0), 48_000.0, 0.05, 0.5);
+    }
+
+    #[test]
+    fn test_cutoff_modulation_ramps_without_zipper() {
+        let ctx = RenderCtx::from_freq(48_000.0, 440.0, 1.0);
+        let mut filter = FilterNode::lowpass(200.0);
+        filter.prepare(48_000.0, 1);
+
+        // Block-rate jump 200 Hz → 5 kHz, then watch the response one sample at a time
+        filter.apply_modulation(FilterParam::Cutoff, 200.0, 4_800.0);
+        let response: Vec<f32> = (0..480)
+            .map(|_| {
+                filter.render_block(&mut [0.0], &ctx);
+                filter.magnitude_db(2_000.0, 48_000.0)
+            })
+            .collect();
+
+        let max_step = response.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0, f32::max);
+        assert!(max_step < 0.5, "largest response step {max_step} dB");
+        let settled = FilterNode::lowpass(5_000.0).magnitude_db(2_000.0, 48_000.0);
+        assert!((response[response.len() - 1] - settled).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_zero_depth_fm_matches_plain_filter() {
+        use crate::graph::oscillator::OscNode;
+
+        let ctx = RenderCtx::from_freq(48_000.0, 440.0, 1.0);
+        let input: Vec<f32> = (0..256).map(|i| (i as f32 * 0.37).sin()).collect();
+
+        let mut plain = input.clone();
+        FilterNode::lowpass(800.0).render_block(&mut plain, &ctx);
+        let mut fm = input.clone();
+        FilterNode::lowpass(800.0).with_cutoff_fm(OscNode::sine(), 0.0).render_block(&mut fm, &ctx);
+
+        assert_eq!(plain, fm);
+    }
+
+    #[test]
+    fn test_audio_rate_fm_is_not_averaged_away() {
+        use crate::graph::oscillator::OscNode;
+
+        // A 440 Hz modulator averages to ~0 over a block, so block-rate
+        // modulation would leave the cutoff where it was
+        let ctx = RenderCtx::from_freq(48_000.0, 440.0, 1.0);
+        let input: Vec<f32> = (0..512).map(|i| if i % 40 < 20 { 0.5 } else { -0.5 }).collect();
+
+        let mut plain = input.clone();
+        FilterNode::lowpass(800.0).render_block(&mut plain, &ctx);
+        let mut fm = input.clone();
+        FilterNode::lowpass(800.0).with_cutoff_fm(OscNode::sine(), 600.0).render_block(&mut fm, &ctx);
+
+        let diff = plain.iter().zip(&fm).map(|(a, b)| (a - b).abs()).fold(0.0, f32::max);
+        assert!(diff > 0.05, "FM changed the output by only {diff}");
+        assert!(fm.iter().all(|s| s.is_finite()));
     }
 }
@@ -116,7 +116,7 @@ mod tests {
 
         for &sample in &buffer {
             assert!(
-                sample >= -1.0 && sample <= 1.0,
+                (-1.0..=1.0).contains(&sample),
                 "LFO sine sample {} out of range [-1.0, 1.0]",
                 sample
             );
@@ -134,7 +134,7 @@ mod tests {
 
         for &sample in &buffer {
             assert!(
-                sample >= -1.0 && sample <= 1.0,
+                (-1.0..=1.0).contains(&sample),
                 "LFO triangle sample {} out of range [-1.0, 1.0]",
                 sample
             );
@@ -151,7 +151,7 @@ mod tests {
 
         for &sample in &buffer {
             assert!(
-                sample >= -1.0 && sample <= 1.0,
+                (-1.0..=1.0).contains(&sample),
                 "LFO square sample {} out of range [-1.0, 1.0]",
                 sample
             );
@@ -168,7 +168,7 @@ mod tests {
 
         for &sample in &buffer {
             assert!(
-                sample >= -1.0 && sample <= 1.0,
+                (-1.0..=1.0).contains(&sample),
                 "LFO saw sample {} out of range [-1.0, 1.0]",
                 sample
             );
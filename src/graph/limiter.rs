@@ -0,0 +1,130 @@
+use crate::dsp::limiter::{LookaheadLimiter, DEFAULT_LOOKAHEAD_SECS, DEFAULT_RELEASE_SECS};
+use crate::graph::node::{GraphNode, Modulatable, RenderCtx};
+
+/*
+Limiter Node
+============
+
+A brick-wall limiter for one voice or bus: nothing gets past the ceiling,
+inter-sample peaks included. See `dsp/limiter.rs` for how it works.
+
+  // Keep a resonant acid line from ever passing -3 dBTP
+  let acid = voices::bass().through(LimiterNode::new(-3.0));
+
+The node delays its output by the lookahead (3 ms by default) and reports
+it through `latency_samples`, so the runtime knows the track runs late.
+For the whole mix, use `Saavy::limiter` instead.
+
+
+Parameters
+----------
+
+Ceiling (dB, ≤ 0):
+  Highest true peak let through. -1 dB leaves headroom for lossy encoders.
+
+Lookahead (1 - 5 ms, `with_lookahead`):
+  How long the gain has to fade down. Longer is smoother on bass-heavy
+  material but adds latency.
+
+Release (`with_release`, default 100 ms):
+  How fast the gain recovers after a peak. Short = louder but can pump.
+*/
+
+/// Parameters that can be modulated
+#[derive(Clone, Copy, Debug)]
+pub enum LimiterParam {
+    /// Highest true peak let through (dB)
+    Ceiling,
+}
+
+/// Lookahead true-peak limiter (see module docs)
+pub struct LimiterNode {
+    limiter: LookaheadLimiter,
+    ceiling_db: f32,
+    lookahead_secs: f32,
+    release_secs: f32,
+    sample_rate: f32,
+}
+
+impl LimiterNode {
+    /// Limit to `ceiling_db` true peak with the default lookahead and release
+    pub fn new(ceiling_db: f32) -> Self {
+        let ceiling_db = ceiling_db.min(0.0);
+        Self {
+            limiter: LookaheadLimiter::new(ceiling_db, DEFAULT_LOOKAHEAD_SECS, DEFAULT_RELEASE_SECS, 48_000.0),
+            ceiling_db,
+            lookahead_secs: DEFAULT_LOOKAHEAD_SECS,
+            release_secs: DEFAULT_RELEASE_SECS,
+            sample_rate: 48_000.0,
+        }
+    }
+
+    /// Set the lookahead (clamped to 1 - 5 ms); this is the node's latency
+    pub fn with_lookahead(mut self, lookahead_secs: f32) -> Self {
+        self.lookahead_secs = lookahead_secs.clamp(0.001, 0.005);
+        self.limiter.configure(self.lookahead_secs, self.release_secs, self.sample_rate);
+        self
+    }
+
+    /// Set how fast the gain recovers after a peak
+    pub fn with_release(mut self, release_secs: f32) -> Self {
+        self.release_secs = release_secs.max(0.0);
+        self.limiter.configure(self.lookahead_secs, self.release_secs, self.sample_rate);
+        self
+    }
+}
+
+impl GraphNode for LimiterNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        if ctx.sample_rate != self.sample_rate {
+            self.prepare(ctx.sample_rate, out.len());
+        }
+        self.limiter.render(out);
+    }
+
+    fn prepare(&mut self, sample_rate: f32, _max_block: usize) {
+        self.sample_rate = sample_rate;
+        self.limiter.configure(self.lookahead_secs, self.release_secs, sample_rate);
+    }
+
+    fn latency_samples(&self) -> usize {
+        self.limiter.latency_samples()
+    }
+}
+
+impl Modulatable for LimiterNode {
+    type Param = LimiterParam;
+
+    fn get_param(&self, param: Self::Param) -> f32 {
+        match param {
+            LimiterParam::Ceiling => self.ceiling_db,
+        }
+    }
+
+    fn apply_modulation(&mut self, param: Self::Param, base: f32, modulation: f32) {
+        match param {
+            LimiterParam::Ceiling => {
+                self.ceiling_db = (base + modulation).min(0.0);
+                self.limiter.set_ceiling_db(self.ceiling_db);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::extensions::NodeExt;
+    use crate::graph::oscillator::OscNode;
+
+    #[test]
+    fn reports_its_lookahead_through_containers() {
+        let mut node = LimiterNode::new(-1.0).with_lookahead(0.002);
+        node.prepare(48_000.0, 256);
+        assert_eq!(node.latency_samples(), 96);
+
+        let mut chain = OscNode::sine().through(node).through(LimiterNode::new(-1.0));
+        chain.prepare(48_000.0, 256);
+        assert_eq!(chain.latency_samples(), 96 + 144);
+    }
+}
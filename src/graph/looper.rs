@@ -0,0 +1,363 @@
+use crate::{
+    dsp::smooth::SmoothedParam,
+    graph::node::{GraphNode, Modulatable, NodeCommand, RenderCtx},
+};
+
+/*
+Looper Node
+===========
+
+A looper pedal: record a few seconds of whatever runs through it, then
+play that back on repeat underneath the live signal. Overdub more on top
+and the layers pile up - the classic way to build an ambient drone live
+out of a single voice.
+
+  // An 8-second looper on the pad track
+  let pad = voices::pad().through(LooperNode::new(8.0).with_overdub_gain(0.8));
+
+The live signal always passes through; the loop is added on top:
+
+  Input ──┬──────────────────────────────→ (+) ──→ Output
+          │                                 ↑
+          └──→ [Loop buffer] ──→ × level ───┘
+                    ↑    │
+                    └────┘ overdub: loop × overdub gain + input
+
+
+Commands
+--------
+
+The looper is driven by `NodeCommand::Looper`, which the runtime sends to
+every track on `ControlMessage::Looper` (keys L / O / P / X / C in the UI):
+
+  Record    Capture the next `loop_secs` of input, then start looping
+            automatically - in time with the moment recording began.
+  Overdub   Add the input to the loop as it plays. The existing layers
+            are scaled by the overdub gain every pass, so old material
+            slowly fades while new material builds up.
+  Play      Stop overdubbing (or resume a stopped loop).
+  Stop      Fade the loop out; the recording is kept for `Play`.
+  Clear     Fade the loop out and forget it.
+
+Starting, stopping and re-recording all fade the loop's level over the
+crossfade time, so commands never click.
+
+
+Crossfaded Seams
+----------------
+
+A loop's end rarely matches its start: a drone that was rising when
+recording stopped jumps back down when the loop wraps - a click every
+pass. So recording runs `crossfade_secs` PAST the loop length, and the
+first moments of every pass blend the start with that extra tail:
+
+  recorded   |←──────────── loop ─────────────→|← tail →|
+  playback   |╱ start fades in                 |
+             |╲ tail (the audio that followed  |
+             |  the end) fades out             |
+
+At the seam the tail picks up exactly where the end left off, so the
+wrap is continuous. The blend is linear: the two sides are the same
+sound moments apart, so they add up in phase. 20 ms suits drones; use
+longer crossfades for slowly evolving pads.
+
+
+Parameters
+----------
+
+Loop Length (seconds, at construction):
+  0.1 - 60 s. The buffer is allocated in `prepare` for the sample rate;
+  until then (or if blocks arrive at another rate) audio passes through.
+
+Overdub Gain (0.0 - 1.0, `with_overdub_gain`, modulatable):
+  How much of the existing loop survives each overdub pass.
+  1.0 = layers stack forever, 0.8 = old layers fade over a few passes,
+  0.0 = every pass replaces the last.
+
+Crossfade (seconds, `with_crossfade`):
+  Length of the seam blend and of the start/stop fades. Default 20 ms.
+*/
+
+/// Longest loop a looper can hold
+const MAX_LOOP_SECS: f32 = 60.0;
+/// Shortest loop a looper can hold
+const MIN_LOOP_SECS: f32 = 0.1;
+/// Default seam crossfade and level fade
+const DEFAULT_CROSSFADE_SECS: f32 = 0.02;
+
+/// What a looper should do next
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LooperCommand {
+    /// Record a new loop (replacing the old one), then play it
+    Record,
+    /// Layer the input onto the playing loop
+    Overdub,
+    /// Play the loop without recording (ends an overdub)
+    Play,
+    /// Fade the loop out, keeping it
+    Stop,
+    /// Fade the loop out and forget it
+    Clear,
+}
+
+/// What a looper is doing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LooperState {
+    /// No loop recorded
+    Empty,
+    /// Capturing the input
+    Recording,
+    /// Playing the loop
+    Playing,
+    /// Playing the loop and layering the input onto it
+    Overdubbing,
+    /// Loop kept but silent
+    Stopped,
+}
+
+/// Parameters that can be modulated
+#[derive(Clone, Copy, Debug)]
+pub enum LooperParam {
+    /// Share of the loop kept per overdub pass (0.0 - 1.0)
+    OverdubGain,
+}
+
+/// Record-and-loop effect with crossfaded seams and overdubbing (see module docs)
+pub struct LooperNode {
+    loop_secs: f32,
+    crossfade_secs: f32,
+    overdub_gain: f32,
+    /// Loop plus crossfade tail
+    buffer: Vec<f32>,
+    /// Samples in one pass of the loop
+    loop_len: usize,
+    /// Samples in the crossfade tail (at most `loop_len`)
+    fade_len: usize,
+    state: LooperState,
+    /// Write position while recording (0 - loop_len + fade_len)
+    record_pos: usize,
+    /// Read position in the loop (0 - loop_len)
+    play_pos: usize,
+    /// Loop playback level, faded by commands
+    level: SmoothedParam,
+    /// Rate the buffer was sized for (0 = not prepared)
+    sample_rate: f32,
+}
+
+impl LooperNode {
+    /// Loop `loop_secs` seconds of input (0.1 - 60)
+    pub fn new(loop_secs: f32) -> Self {
+        Self {
+            loop_secs: loop_secs.clamp(MIN_LOOP_SECS, MAX_LOOP_SECS),
+            crossfade_secs: DEFAULT_CROSSFADE_SECS,
+            overdub_gain: 1.0,
+            buffer: Vec::new(),
+            loop_len: 1,
+            fade_len: 0,
+            state: LooperState::Empty,
+            record_pos: 0,
+            play_pos: 0,
+            level: SmoothedParam::new(0.0),
+            sample_rate: 0.0,
+        }
+    }
+
+    /// Keep `gain` of the loop on every overdub pass (0.0 - 1.0)
+    pub fn with_overdub_gain(mut self, gain: f32) -> Self {
+        self.overdub_gain = gain.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Blend the loop seam (and fade commands) over `secs`
+    pub fn with_crossfade(mut self, secs: f32) -> Self {
+        self.crossfade_secs = secs.clamp(0.0, self.loop_secs);
+        // Re-sized by the next `prepare`
+        self.sample_rate = 0.0;
+        self
+    }
+
+    /// What the looper is doing
+    pub fn state(&self) -> LooperState {
+        self.state
+    }
+
+    /// Size the buffer for `sample_rate` (drops any recorded loop; allocates)
+    fn allocate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        self.loop_len = ((self.loop_secs * sample_rate) as usize).max(1);
+        self.fade_len = ((self.crossfade_secs * sample_rate) as usize).min(self.loop_len);
+        self.buffer.clear();
+        self.buffer.resize(self.loop_len + self.fade_len, 0.0);
+        self.state = LooperState::Empty;
+        self.record_pos = 0;
+        self.play_pos = 0;
+        self.level.snap(0.0);
+    }
+
+    /// Apply a command (fades take the crossfade time)
+    pub fn handle(&mut self, command: LooperCommand) {
+        let fade = self.crossfade_secs;
+        let (state, level) = match (command, self.state) {
+            (LooperCommand::Record, _) => {
+                // The old loop keeps fading out while the new one is written
+                self.record_pos = 0;
+                (LooperState::Recording, 0.0)
+            }
+            (LooperCommand::Overdub, LooperState::Playing | LooperState::Stopped) => (LooperState::Overdubbing, 1.0),
+            (LooperCommand::Play, LooperState::Overdubbing | LooperState::Stopped) => (LooperState::Playing, 1.0),
+            (LooperCommand::Stop, LooperState::Playing | LooperState::Overdubbing) => (LooperState::Stopped, 0.0),
+            // An unfinished recording has nothing to keep
+            (LooperCommand::Stop, LooperState::Recording) | (LooperCommand::Clear, _) => (LooperState::Empty, 0.0),
+            _ => return,
+        };
+        self.state = state;
+        self.level.set_target(level, fade, self.sample_rate);
+    }
+
+    /// Loop sample at `pos`, blending in the tail over the first `fade_len` samples
+    #[inline]
+    fn read(&self, pos: usize) -> f32 {
+        if pos < self.fade_len {
+            let w = pos as f32 / self.fade_len as f32;
+            self.buffer[pos] * w + self.buffer[self.loop_len + pos] * (1.0 - w)
+        } else {
+            self.buffer[pos]
+        }
+    }
+}
+
+impl GraphNode for LooperNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        // Never allocate here: unprepared (or at the wrong rate) it passes audio through
+        if ctx.sample_rate != self.sample_rate {
+            return;
+        }
+
+        for sample in out.iter_mut() {
+            let input = *sample;
+            let looped = self.read(self.play_pos);
+            let level = self.level.next_value();
+
+            match self.state {
+                LooperState::Recording => {
+                    self.buffer[self.record_pos] = input;
+                    self.record_pos += 1;
+                    if self.record_pos == self.buffer.len() {
+                        // Loop from where recording started, one tail past the top
+                        self.state = LooperState::Playing;
+                        self.play_pos = self.fade_len % self.loop_len;
+                        self.level.set_target(1.0, self.crossfade_secs, self.sample_rate);
+                        *sample = input;
+                        continue;
+                    }
+                }
+                LooperState::Overdubbing => {
+                    // Bake the blend in: both sides of the seam take the new layer
+                    let layered = looped * self.overdub_gain + input;
+                    self.buffer[self.play_pos] = layered;
+                    if self.play_pos < self.fade_len {
+                        self.buffer[self.loop_len + self.play_pos] = layered;
+                    }
+                }
+                _ => {}
+            }
+
+            *sample = input + looped * level;
+            self.play_pos += 1;
+            if self.play_pos == self.loop_len {
+                self.play_pos = 0;
+            }
+        }
+    }
+
+    fn prepare(&mut self, sample_rate: f32, _max_block: usize) {
+        if sample_rate != self.sample_rate {
+            self.allocate(sample_rate);
+        }
+    }
+
+    fn command(&mut self, command: NodeCommand) {
+        if let NodeCommand::Looper(command) = command {
+            self.handle(command);
+        }
+    }
+}
+
+impl Modulatable for LooperNode {
+    type Param = LooperParam;
+
+    fn get_param(&self, param: Self::Param) -> f32 {
+        match param {
+            LooperParam::OverdubGain => self.overdub_gain,
+        }
+    }
+
+    fn apply_modulation(&mut self, param: Self::Param, base: f32, modulation: f32) {
+        match param {
+            LooperParam::OverdubGain => self.overdub_gain = (base + modulation).clamp(0.0, 1.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 1_000.0;
+
+    fn render(looper: &mut LooperNode, input: impl Fn(usize) -> f32, len: usize) -> Vec<f32> {
+        let ctx = RenderCtx::from_freq(SAMPLE_RATE, 440.0, 100.0);
+        let mut out: Vec<f32> = (0..len).map(input).collect();
+        for block in out.chunks_mut(64) {
+            looper.render_block(block, &ctx);
+        }
+        out
+    }
+
+    #[test]
+    fn loops_a_recording_with_a_continuous_seam() {
+        // 1 s loop with a 100 ms seam at 1 kHz, fed a rising ramp
+        let mut looper = LooperNode::new(1.0).with_crossfade(0.1);
+        looper.prepare(SAMPLE_RATE, 64);
+        looper.command(NodeCommand::Looper(LooperCommand::Record));
+        let ramp = |i: usize| i as f32 / 1_000.0;
+        render(&mut looper, ramp, 1_100);
+        assert_eq!(looper.state(), LooperState::Playing);
+
+        // Two silent passes: the loop alone, the ramp's wrap blended with no jump
+        let out = render(&mut looper, |_| 0.0, 2_000);
+        let settled = &out[100..];
+        let steepest = settled.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0f32, f32::max);
+        assert!(steepest < 0.02, "step {steepest}");
+        // In time with the recording: the pass restarts 900 samples in
+        assert!((out[899] - 0.999).abs() < 0.02 && out[905] < 0.99, "{} {}", out[899], out[905]);
+    }
+
+    #[test]
+    fn overdub_layers_and_stop_fades_out() {
+        let mut looper = LooperNode::new(0.5).with_overdub_gain(0.5);
+        looper.prepare(SAMPLE_RATE, 64);
+        looper.command(NodeCommand::Looper(LooperCommand::Record));
+        render(&mut looper, |_| 1.0, 520);
+
+        // One overdub pass of 1.0 on top: 0.5 × 1 + 1 = 1.5
+        looper.command(NodeCommand::Looper(LooperCommand::Overdub));
+        render(&mut looper, |_| 1.0, 500);
+        looper.command(NodeCommand::Looper(LooperCommand::Play));
+        let out = render(&mut looper, |_| 0.0, 500);
+        assert!(out[100..].iter().all(|&s| (s - 1.5).abs() < 1e-4), "{}", out[100]);
+
+        looper.command(NodeCommand::Looper(LooperCommand::Stop));
+        let out = render(&mut looper, |_| 0.0, 100);
+        assert!(out[0] > 1.0 && out[50] == 0.0, "fades, then silent");
+    }
+
+    #[test]
+    fn unprepared_looper_passes_audio_through() {
+        let mut looper = LooperNode::new(0.5);
+        looper.command(NodeCommand::Looper(LooperCommand::Record));
+        let out = render(&mut looper, |i| i as f32, 200);
+        assert!(out.iter().enumerate().all(|(i, &s)| s == i as f32));
+        assert!(looper.buffer.is_empty(), "nothing allocated on the audio thread");
+    }
+}
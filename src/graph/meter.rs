@@ -0,0 +1,183 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use crate::dsp::meter::{self, LufsMeter, RmsMeter};
+use crate::graph::node::{GraphNode, RenderCtx};
+
+/*
+Meter Node
+==========
+
+A pass-through tap that measures the signal flowing through it. Audio is
+left untouched; the readings land in a shared `MeterReadings` that any
+thread (usually the UI) can read without locking.
+
+  [Source] ──→ [MeterNode] ──→ output (unchanged)
+                   │
+                   └──→ MeterReadings { peak, rms, momentary, short_term }
+
+
+Usage
+-----
+
+  let meter = MeterNode::new();
+  let readings = meter.readings();        // grab the handle BEFORE moving the node
+
+  let voice = OscNode::sawtooth()
+      .through(FilterNode::lowpass(1200.0))
+      .through(meter);
+
+  // later, on the UI thread
+  let lufs = readings.momentary_lufs();
+
+
+What Gets Measured
+------------------
+
+  peak        true-peak estimate of the last block (linear)
+  rms         300ms sliding RMS (linear)
+  momentary   400ms LUFS
+  short_term  3s LUFS
+
+See `dsp/meter.rs` for how each one is computed and why they differ.
+
+The meters allocate their windows in `prepare`, so rendering itself stays
+realtime-safe. Until `prepare` runs at the block's sample rate the node
+passes audio through without measuring it.
+*/
+
+/// Latest readings published by a `MeterNode` (f32 bits in atomics)
+#[derive(Debug)]
+pub struct MeterReadings {
+    peak: AtomicU32,
+    rms: AtomicU32,
+    momentary: AtomicU32,
+    short_term: AtomicU32,
+}
+
+impl Default for MeterReadings {
+    fn default() -> Self {
+        let silence = meter::SILENCE_LUFS.to_bits();
+        Self {
+            peak: AtomicU32::new(0),
+            rms: AtomicU32::new(0),
+            momentary: AtomicU32::new(silence),
+            short_term: AtomicU32::new(silence),
+        }
+    }
+}
+
+impl MeterReadings {
+    /// True-peak estimate of the most recent block (linear)
+    pub fn peak(&self) -> f32 {
+        f32::from_bits(self.peak.load(Ordering::Relaxed))
+    }
+
+    /// Windowed RMS level (linear)
+    pub fn rms(&self) -> f32 {
+        f32::from_bits(self.rms.load(Ordering::Relaxed))
+    }
+
+    /// Momentary (400ms) loudness in LUFS
+    pub fn momentary_lufs(&self) -> f32 {
+        f32::from_bits(self.momentary.load(Ordering::Relaxed))
+    }
+
+    /// Short-term (3s) loudness in LUFS
+    pub fn short_term_lufs(&self) -> f32 {
+        f32::from_bits(self.short_term.load(Ordering::Relaxed))
+    }
+}
+
+/// Pass-through node that publishes level and loudness readings.
+pub struct MeterNode {
+    readings: Arc<MeterReadings>,
+    /// Built for a specific sample rate in `prepare`
+    meters: Option<(f32, RmsMeter, LufsMeter)>,
+}
+
+impl MeterNode {
+    pub fn new() -> Self {
+        Self {
+            readings: Arc::new(MeterReadings::default()),
+            meters: None,
+        }
+    }
+
+    /// Shared handle to the readings (clone it before moving the node into a graph)
+    pub fn readings(&self) -> Arc<MeterReadings> {
+        Arc::clone(&self.readings)
+    }
+
+}
+
+impl Default for MeterNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphNode for MeterNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        // Built only in `prepare`: never allocate on the audio thread
+        let Some((_, rms, lufs)) = self.meters.as_mut().filter(|(sr, _, _)| *sr == ctx.sample_rate) else {
+            return;
+        };
+
+        rms.process(out);
+        lufs.process(out);
+
+        let readings = &self.readings;
+        readings.peak.store(meter::true_peak(out).to_bits(), Ordering::Relaxed);
+        readings.rms.store(rms.level().to_bits(), Ordering::Relaxed);
+        readings.momentary.store(lufs.momentary().to_bits(), Ordering::Relaxed);
+        readings.short_term.store(lufs.short_term().to_bits(), Ordering::Relaxed);
+    }
+
+    fn prepare(&mut self, sample_rate: f32, _max_block: usize) {
+        if !matches!(self.meters, Some((sr, _, _)) if sr == sample_rate) {
+            self.meters = Some((
+                sample_rate,
+                RmsMeter::new(sample_rate, 0.3),
+                LufsMeter::new(sample_rate),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{extensions::NodeExt, oscillator::OscNode};
+
+    #[test]
+    fn passes_audio_through_unchanged() {
+        let ctx = RenderCtx::from_freq(48_000.0, 440.0, 1.0);
+        let mut plain = OscNode::sine();
+        let mut metered = OscNode::sine().through(MeterNode::new());
+
+        let (mut a, mut b) = (vec![0.0; 256], vec![0.0; 256]);
+        plain.render_block(&mut a, &ctx);
+        metered.render_block(&mut b, &ctx);
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn publishes_readings() {
+        let ctx = RenderCtx::from_freq(48_000.0, 1000.0, 1.0);
+        let meter = MeterNode::new();
+        let readings = meter.readings();
+        let mut node = OscNode::sine().through(meter);
+        node.prepare(48_000.0, 480);
+
+        let mut buffer = vec![0.0; 480];
+        for _ in 0..100 {
+            node.render_block(&mut buffer, &ctx);
+        }
+
+        assert!((readings.peak() - 1.0).abs() < 0.05);
+        assert!((readings.rms() - std::f32::consts::FRAC_1_SQRT_2).abs() < 0.01);
+        assert!((readings.momentary_lufs() + 3.01).abs() < 0.2);
+    }
+}
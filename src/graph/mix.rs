@@ -1,4 +1,8 @@
-use crate::{dsp::mix::mix_in_place, graph::node::GraphNode, MAX_BLOCK_SIZE};
+use crate::{
+    dsp::{envelope::EnvelopeState, rng::Rng, simd::mix_in_place},
+    graph::node::{GraphNode, NodeCommand},
+    MAX_BLOCK_SIZE,
+};
 
 /*
 Mix Node
@@ -97,6 +101,17 @@ impl<A: GraphNode, B: GraphNode> GraphNode for Mix<A, B> {
         mix_in_place(out, b_out, self.balance);
     }
 
+    fn prepare(&mut self, sample_rate: f32, max_block: usize) {
+        self.b_buffer.resize(max_block, 0.0);
+        self.source_a.prepare(sample_rate, max_block);
+        self.source_b.prepare(sample_rate, max_block);
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.source_a.seed(Rng::derive(seed, 0));
+        self.source_b.seed(Rng::derive(seed, 1));
+    }
+
     fn note_on(&mut self, ctx: &super::node::RenderCtx) {
         self.source_a.note_on(ctx);
         self.source_b.note_on(ctx);
@@ -107,10 +122,19 @@ impl<A: GraphNode, B: GraphNode> GraphNode for Mix<A, B> {
         self.source_b.note_off(ctx);
     }
 
+    fn command(&mut self, command: NodeCommand) {
+        self.source_a.command(command);
+        self.source_b.command(command);
+    }
+
     fn is_active(&self) -> bool {
         self.source_a.is_active() || self.source_b.is_active()
     }
 
+    fn latency_samples(&self) -> usize {
+        self.source_a.latency_samples().max(self.source_b.latency_samples())
+    }
+
     fn get_envelope_level(&self) -> Option<f32> {
         match (
             self.source_a.get_envelope_level(),
@@ -122,6 +146,15 @@ impl<A: GraphNode, B: GraphNode> GraphNode for Mix<A, B> {
             (None, None) => None,
         }
     }
+
+    fn get_envelope_state(&self) -> Option<EnvelopeState> {
+        // The louder envelope, matching `get_envelope_level`
+        match (self.source_a.get_envelope_level(), self.source_b.get_envelope_level()) {
+            (Some(a), Some(b)) if b > a => self.source_b.get_envelope_state(),
+            (None, Some(_)) => self.source_b.get_envelope_state(),
+            _ => self.source_a.get_envelope_state(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -145,6 +178,19 @@ mod tests {
         mixed.render_block(&mut buffer, &ctx);
     }
 
+    #[test]
+    fn test_mix_prepare_allows_larger_blocks() {
+        // Blocks beyond MAX_BLOCK_SIZE need prepare to size the internal buffer
+        let mut mixed = OscNode::sine().mix(OscNode::sawtooth(), 0.5);
+        mixed.prepare(48000.0, MAX_BLOCK_SIZE * 2);
+
+        let mut buffer = vec![0.0; MAX_BLOCK_SIZE * 2];
+        let ctx = RenderCtx::from_freq(48000.0, 440.0, 1.0);
+        mixed.render_block(&mut buffer, &ctx);
+
+        assert!(buffer[MAX_BLOCK_SIZE..].iter().any(|&s| s.abs() > 0.0));
+    }
+
     #[test]
     fn test_mix_equal_balance() {
         // Test 50/50 mix produces expected result
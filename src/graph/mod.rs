@@ -6,24 +6,37 @@
 //! clear, chainable API.
 
 // Re-export core types for convenience
-pub use node::{GraphNode, RenderCtx};
+pub use node::{GraphNode, NodeCommand, RenderCtx, Transport};
+pub use telemetry::Telemetry;
 
 /// Multiply two signals together (amplitude or ring modulation).
 pub mod amplify;
+/// Multi-hit one-shot envelope (hand claps).
+pub mod burst;
 /// Chorus effect - modulated delay for thickening.
 pub mod chorus;
 /// Feedback delay effect with realtime-safe modulation.
 pub mod delay;
 /// Waveshaping distortion (soft, hard, foldback).
 pub mod distortion;
+/// Dynamic EQ band that cuts only while the band is over a threshold.
+pub mod dynamic_eq;
 /// Envelope generator node exposing ADSR state.
 pub mod envelope;
+/// Harmonic exciter - saturated highs blended back in for brightness.
+pub mod exciter;
 /// Fluent combinators (`.amplify()`, `.mix()`, etc.).
 pub mod extensions;
 /// Topology-preserving filter node with multiple responses.
 pub mod filter;
 /// Low frequency oscillators for parameter modulation.
 pub mod lfo;
+/// Lookahead brick-wall limiter that reports its latency.
+pub mod limiter;
+/// Record-and-loop effect with crossfaded seams and overdubbing.
+pub mod looper;
+/// Pass-through level/loudness meter with shared readings.
+pub mod meter;
 /// Linear wet/dry mixing for parallel graphs.
 pub mod mix;
 /// Connect modulation sources to node parameters.
@@ -32,7 +45,39 @@ pub mod modulate;
 pub mod node;
 /// Audio-band oscillators and noise sources.
 pub mod oscillator;
+/// Oscillator with a per-sample pitch drop (kicks, toms).
+pub mod pitch_envelope;
+/// Glide between note frequencies for any voice.
+pub mod portamento;
+/// Held-note pressure (sequence expression) as a modulation source.
+pub mod pressure;
+/// Smoothed random modulation (sample & hold with slew).
+pub mod random;
 /// Reverb effect - room/hall simulation.
 pub mod reverb;
+/// Tempo-synced reverse delay (backwards segments).
+pub mod reverse;
+/// Leslie-style rotary speaker (horn and drum AM + Doppler).
+pub mod rotary;
+/// Sample & hold on audio, free-running or tempo-synced.
+pub mod sample_hold;
+/// Sample playback with tempo-synced time-stretch.
+pub mod sampler;
+/// Slew limiter for control signals and audio.
+pub mod slew;
+/// Keyboard split: different voices below and above a note.
+pub mod split;
+/// Tempo-synced step LFO for rhythmic modulation and gates.
+pub mod step_lfo;
+/// One telemetry interface (level, state, note, peak) for voices and tracks.
+pub mod telemetry;
 /// Serial chaining of two nodes (source → effect).
 pub mod through;
+/// Tremolo effect - LFO amplitude modulation with selectable shape.
+pub mod tremolo;
+/// Pitch vibrato in cents with delayed onset for any voice.
+pub mod vibrato;
+/// Channel vocoder: one node's band levels shape another.
+pub mod vocoder;
+/// West-coast wavefolder with modulatable fold depth and symmetry.
+pub mod wavefolder;
@@ -1,6 +1,10 @@
 use crate::{
-    dsp::modulate::block_average,
-    graph::node::{GraphNode, Modulatable, RenderCtx},
+    dsp::{
+        envelope::EnvelopeState,
+        modulate::{block_average, shape_modulator, ModCurve},
+        rng::Rng,
+    },
+    graph::node::{GraphNode, Modulatable, NodeCommand, RenderCtx},
     MAX_BLOCK_SIZE,
 };
 
@@ -68,6 +72,20 @@ Quick rule: controlling volume? Use `.amplify()`. Controlling any other
 parameter? Use `.modulate()`.
 
 
+Inverting and Curving a Route
+-----------------------------
+
+Every route can flip its source or bend its response:
+
+  // The envelope CLOSES the filter on each note: 3000 Hz down to 800 Hz
+  let pluck = FilterNode::lowpass(3000.0)
+      .modulate(EnvNode::adsr(0.0, 0.3, 0.0, 0.1), FilterParam::Cutoff, 2200.0)
+      .inverted()
+      .with_curve(ModCurve::Exponential);
+
+See `dsp/modulate.rs` for the curve shapes.
+
+
 Depth Gotcha
 ------------
 
@@ -98,6 +116,8 @@ where
     lfo: L,               // The modulation source (e.g., LfoNode)
     param: S::Param,      // Which parameter to modulate (e.g., FilterParam::Cutoff)
     depth: f32,           // Modulation amount (scales LFO output)
+    curve: ModCurve,      // Response curve applied before the depth
+    inverted: bool,       // Flip the modulator's polarity
     lfo_buffer: Vec<f32>, // Temp buffer for LFO output
 }
 
@@ -112,9 +132,23 @@ where
             lfo,
             param,
             depth,
+            curve: ModCurve::Linear,
+            inverted: false,
             lfo_buffer: vec![0.0; MAX_BLOCK_SIZE],
         }
     }
+
+    /// Flip the modulator: rises in the source push the parameter down
+    pub fn inverted(mut self) -> Self {
+        self.inverted = true;
+        self
+    }
+
+    /// Bend the modulator's response before the depth is applied
+    pub fn with_curve(mut self, curve: ModCurve) -> Self {
+        self.curve = curve;
+        self
+    }
 }
 
 impl<S, L> GraphNode for Modulate<S, L>
@@ -133,7 +167,7 @@ where
 
         // Calculate and apply modulation
         let base_value = self.source.get_param(self.param);
-        let modulation = lfo_avg * self.depth;
+        let modulation = shape_modulator(lfo_avg, self.curve, self.inverted) * self.depth;
         self.source
             .apply_modulation(self.param, base_value, modulation);
 
@@ -141,6 +175,17 @@ where
         self.source.render_block(out, ctx);
     }
 
+    fn prepare(&mut self, sample_rate: f32, max_block: usize) {
+        self.lfo_buffer.resize(max_block, 0.0);
+        self.source.prepare(sample_rate, max_block);
+        self.lfo.prepare(sample_rate, max_block);
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.source.seed(Rng::derive(seed, 0));
+        self.lfo.seed(Rng::derive(seed, 1));
+    }
+
     fn note_on(&mut self, ctx: &RenderCtx) {
         self.source.note_on(ctx);
         self.lfo.note_on(ctx);
@@ -151,13 +196,26 @@ where
         self.lfo.note_off(ctx);
     }
 
+    fn command(&mut self, command: NodeCommand) {
+        self.source.command(command);
+        self.lfo.command(command);
+    }
+
     fn is_active(&self) -> bool {
         self.source.is_active()
     }
 
+    fn latency_samples(&self) -> usize {
+        self.source.latency_samples()
+    }
+
     fn get_envelope_level(&self) -> Option<f32> {
         self.source.get_envelope_level()
     }
+
+    fn get_envelope_state(&self) -> Option<EnvelopeState> {
+        self.source.get_envelope_state()
+    }
 }
 
 #[cfg(test)]
@@ -229,6 +287,44 @@ mod tests {
         filter.note_off(&ctx);
     }
 
+    #[test]
+    fn test_inverted_curved_route() {
+        /// Records the last modulation it was given
+        struct Probe(f32);
+        impl GraphNode for Probe {
+            fn render_block(&mut self, out: &mut [f32], _ctx: &RenderCtx) {
+                out.fill(0.0);
+            }
+        }
+        impl Modulatable for Probe {
+            type Param = ();
+            fn get_param(&self, _param: ()) -> f32 {
+                0.0
+            }
+            fn apply_modulation(&mut self, _param: (), _base: f32, modulation: f32) {
+                self.0 = modulation;
+            }
+        }
+        /// Constant modulator
+        struct Dc(f32);
+        impl GraphNode for Dc {
+            fn render_block(&mut self, out: &mut [f32], _ctx: &RenderCtx) {
+                out.fill(self.0);
+            }
+        }
+
+        let ctx = RenderCtx::from_freq(48000.0, 440.0, 1.0);
+        let mut buffer = vec![0.0; 64];
+        let mut route = Probe(0.0).modulate(Dc(0.5), (), 1000.0).inverted();
+        route.render_block(&mut buffer, &ctx);
+        assert_eq!(route.source.0, -500.0);
+
+        let mut route = route.with_curve(ModCurve::Exponential);
+        route.render_block(&mut buffer, &ctx);
+        let expected = -1000.0 * shape_modulator(0.5, ModCurve::Exponential, false);
+        assert!((route.source.0 - expected).abs() < 1e-3);
+    }
+
     #[test]
     fn test_multiple_modulations() {
         // Test chaining modulations (modulate cutoff, then resonance - if we could)
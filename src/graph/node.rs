@@ -1,8 +1,51 @@
-/// Convert MIDI note number to frequency in Hz.
-/// A4 = 440 Hz = MIDI note 69
-#[inline]
-fn midi_note_to_freq(note: u8) -> f32 {
-    440.0 * 2.0_f32.powf((note as f32 - 69.0) / 12.0)
+use crate::dsp::{envelope::EnvelopeState, tuning::midi_to_hz};
+use crate::graph::looper::LooperCommand;
+
+/// Song position and tempo at the start of a block
+///
+/// Filled in by the runtime so nodes can lock to the beat (tempo-synced
+/// LFOs and delays, bar-length effects) without knowing about sequencers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transport {
+    /// Tempo in quarter notes per minute
+    pub bpm: f64,
+    /// Ticks per quarter note
+    pub ppq: u32,
+    /// Position in ticks from the start of the loop (fractional)
+    pub tick: f64,
+    /// Length of one bar in ticks
+    pub bar_ticks: u32,
+    /// Whether the sequencer is running (a paused transport doesn't move)
+    pub playing: bool,
+}
+
+impl Transport {
+    /// Position in quarter-note beats
+    pub fn beat(&self) -> f64 {
+        self.tick / self.ppq.max(1) as f64
+    }
+
+    /// Progress through the current bar, 0.0 - 1.0
+    pub fn bar_phase(&self) -> f64 {
+        (self.tick / self.bar_ticks.max(1) as f64).fract()
+    }
+
+    /// Samples in one quarter note
+    pub fn samples_per_beat(&self, sample_rate: f32) -> f64 {
+        sample_rate as f64 * 60.0 / self.bpm
+    }
+
+    /// The transport `frames` samples later (unchanged while paused)
+    pub fn advanced(self, frames: usize, sample_rate: f32) -> Self {
+        if !self.playing {
+            return self;
+        }
+        let ticks = frames as f64 / self.samples_per_beat(sample_rate) * self.ppq as f64;
+        Self {
+            tick: self.tick + ticks,
+            ..self
+        }
+    }
 }
 
 /// Context passed to graph nodes during rendering
@@ -12,23 +55,29 @@ fn midi_note_to_freq(note: u8) -> f32 {
 /// - frequency: Pitch to render (Hz)
 /// - velocity: Intensity/loudness (0.0-127.0, MIDI-style)
 /// - time: Current playback time in seconds
+/// - transport: Tempo and song position, when rendered by the runtime
+/// - pressure: Held-note expression (0.0-127.0, aftertouch-style)
 pub struct RenderCtx {
     pub sample_rate: f32,
     pub frequency: f32,
     pub velocity: f32,
     pub time: f64,
+    pub transport: Option<Transport>,
+    pub pressure: f32,
 }
 
 impl RenderCtx {
     /// Create context from MIDI note (keyboard/sequencer use case)
     pub fn from_note(sample_rate: f32, note: u8, velocity: f32) -> Self {
-        let frequency = midi_note_to_freq(note);
+        let frequency = midi_to_hz(note as f32);
 
         Self {
             sample_rate,
             frequency,
             velocity,
             time: 0.0,
+            transport: None,
+            pressure: 0.0,
         }
     }
 
@@ -39,8 +88,28 @@ impl RenderCtx {
             frequency,
             velocity,
             time: 0.0,
+            transport: None,
+            pressure: 0.0,
         }
     }
+
+    /// Attach the song position (the runtime does this for every block)
+    pub fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+}
+
+/// A command from outside the note stream (UI keys, control code)
+///
+/// Sent to a whole graph with `GraphNode::command`; nodes act on the
+/// commands meant for them and ignore the rest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeCommand {
+    /// Drive every `LooperNode` in the graph
+    Looper(LooperCommand),
+    /// Freeze (true) or thaw (false) every `ReverbNode` in the graph
+    ReverbFreeze(bool),
 }
 
 /// Trait for nodes that support parameter modulation
@@ -58,6 +127,30 @@ pub trait Modulatable: Send {
 pub trait GraphNode: Send {
     fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx);
 
+    /// Configure the node for a sample rate and maximum block size
+    ///
+    /// Called once by the host before rendering (never from the audio
+    /// callback), so nodes may allocate here. After `prepare`, `render_block`
+    /// must accept any block up to `max_block` samples without allocating.
+    /// Nodes that skip this fall back to `MAX_BLOCK_SIZE` and the render
+    /// context's sample rate. Containers forward to their children.
+    ///
+    /// Default implementation does nothing (stateless nodes).
+    fn prepare(&mut self, _sample_rate: f32, _max_block: usize) {
+        // Default: do nothing
+    }
+
+    /// Give stochastic nodes (noise, random modulation) their random stream
+    ///
+    /// Containers pass `Rng::derive(seed, i)` to their i-th child so every
+    /// source in a graph gets an independent, reproducible stream. Called
+    /// by the host alongside `prepare`.
+    ///
+    /// Default implementation does nothing (deterministic nodes).
+    fn seed(&mut self, _seed: u64) {
+        // Default: do nothing
+    }
+
     /// Triggered when a note starts
     ///
     /// Default implementation does nothing (passthrough nodes).
@@ -72,16 +165,37 @@ pub trait GraphNode: Send {
         // Default: do nothing
     }
 
+    /// Respond to a command from outside the note stream
+    ///
+    /// Containers forward to all their children. Default implementation
+    /// does nothing (nodes without commands).
+    fn command(&mut self, _command: NodeCommand) {
+        // Default: do nothing
+    }
+
     fn get_envelope_level(&self) -> Option<f32> {
         None
     }
 
+    /// Stage of the envelope reported by `get_envelope_level`
+    fn get_envelope_state(&self) -> Option<EnvelopeState> {
+        None
+    }
+
     /// Check if this node is still producing sound
     ///
     /// Used by voice management to know when a voice can be freed.
     fn is_active(&self) -> bool {
         true
     }
+
+    /// Samples this node delays its input by (lookahead, FFT framing)
+    ///
+    /// Containers report the latency of their longest path, so the runtime
+    /// can line tracks up. Default implementation reports none.
+    fn latency_samples(&self) -> usize {
+        0
+    }
 }
 
 /// Allow boxed graph nodes to be used as graph nodes (for dynamic dispatch)
@@ -90,6 +204,14 @@ impl GraphNode for Box<dyn GraphNode> {
         (**self).render_block(out, ctx)
     }
 
+    fn prepare(&mut self, sample_rate: f32, max_block: usize) {
+        (**self).prepare(sample_rate, max_block)
+    }
+
+    fn seed(&mut self, seed: u64) {
+        (**self).seed(seed)
+    }
+
     fn note_on(&mut self, ctx: &RenderCtx) {
         (**self).note_on(ctx)
     }
@@ -98,11 +220,23 @@ impl GraphNode for Box<dyn GraphNode> {
         (**self).note_off(ctx)
     }
 
+    fn command(&mut self, command: NodeCommand) {
+        (**self).command(command)
+    }
+
     fn get_envelope_level(&self) -> Option<f32> {
         (**self).get_envelope_level()
     }
 
+    fn get_envelope_state(&self) -> Option<EnvelopeState> {
+        (**self).get_envelope_state()
+    }
+
     fn is_active(&self) -> bool {
         (**self).is_active()
     }
+
+    fn latency_samples(&self) -> usize {
+        (**self).latency_samples()
+    }
 }
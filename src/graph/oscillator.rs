@@ -1,5 +1,7 @@
-use crate::dsp::oscillator::OscillatorBlock;
-use crate::graph::node::{GraphNode, Modulatable, RenderCtx};
+use crate::dsp::oscillator::{OscillatorBlock, PhaseMode};
+use crate::dsp::tuning::cents_to_ratio;
+use crate::graph::node::{GraphNode, Modulatable, NodeCommand, RenderCtx};
+use crate::MAX_BLOCK_SIZE;
 
 /*
 Audio Oscillator
@@ -63,6 +65,24 @@ Example usage:
   let voice = OscNode::sawtooth()
       .through(FilterNode::lowpass(2000.0))
       .amplify(EnvNode::adsr(0.01, 0.1, 0.7, 0.3));
+
+
+Audio-Rate FM
+-------------
+
+`.modulate()` moves the frequency once per block - fine for vibrato, but
+an audio-rate modulator averages away to nothing. `with_fm` renders the
+modulator into a buffer and bends the frequency every sample:
+
+    frequency[n] = f × (1 + depth × modulator[n])
+
+A modulator at r times the carrier's pitch adds sidebands at f ± k·r·f,
+with a modulation index of depth / r for a full-scale modulator. Put an
+envelope on the modulator and the index - the brightness - follows it:
+
+  // DX-style bell: the partials fade faster than the tone
+  let modulator = OscNode::sine().amplify(EnvNode::adsr(0.001, 0.3, 0.1, 0.2));
+  let bell = OscNode::sine().with_fm(modulator, 2.0);
 */
 
 pub struct OscNode {
@@ -155,10 +175,47 @@ impl OscNode {
         self.detune_cents = cents;
         self
     }
+
+    /// Set what happens to the phase when a note starts.
+    ///
+    /// The default, `PhaseMode::FreeRunning`, lets the phase carry over
+    /// between notes. Use `Reset` for identical, punchy attacks (kicks, bass)
+    /// and `Random` for analog-style looseness in layered oscillators.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Every kick starts at the zero crossing
+    /// OscNode::sine().with_frequency(50.0).with_phase(PhaseMode::Reset)
+    /// ```
+    pub fn with_phase(mut self, mode: PhaseMode) -> Self {
+        self.osc.set_phase_mode(mode);
+        self
+    }
+
+    /// Frequency-modulate from an audio-rate signal, updated every sample
+    ///
+    /// The frequency becomes `f × (1 + depth × modulator)`, so `depth` is
+    /// the peak deviation as a multiple of the carrier's frequency. Block-rate
+    /// `.modulate()` on the returned node still moves the carrier.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Modulator an octave up, index 1
+    /// OscNode::sine().with_fm(OscNode::sine().with_detune(1200.0), 2.0)
+    /// ```
+    pub fn with_fm<M: GraphNode>(self, modulator: M, depth: f32) -> OscFm<M> {
+        OscFm {
+            osc: self,
+            modulator,
+            depth,
+            fm_buffer: vec![0.0; MAX_BLOCK_SIZE],
+        }
+    }
 }
 
-impl GraphNode for OscNode {
-    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+impl OscNode {
+    /// Frequency this block plays at: fixed or from the note, then detuned
+    fn block_frequency(&self, ctx: &RenderCtx) -> f32 {
         // Determine base frequency: fixed or from note
         let base_freq = if self.base_frequency.is_some() {
             self.current_frequency
@@ -167,20 +224,35 @@ impl GraphNode for OscNode {
         };
 
         // Apply detune: frequency * 2^(cents/1200)
-        let final_freq = if self.detune_cents != 0.0 {
-            base_freq * 2.0_f32.powf(self.detune_cents / 1200.0)
+        if self.detune_cents != 0.0 {
+            base_freq * cents_to_ratio(self.detune_cents)
         } else {
             base_freq
-        };
+        }
+    }
+
+    /// Render with the block frequency scaled by `ratio()` every sample
+    pub(crate) fn render_scaled(&mut self, out: &mut [f32], ctx: &RenderCtx, mut ratio: impl FnMut() -> f32) {
+        let frequency = self.block_frequency(ctx);
+        self.osc.render_swept(out, ctx.sample_rate, || frequency * ratio());
+    }
+}
 
+impl GraphNode for OscNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
         let modified_ctx = RenderCtx {
-            frequency: final_freq,
+            frequency: self.block_frequency(ctx),
             ..*ctx
         };
         self.osc.render(out, &modified_ctx);
     }
 
+    fn seed(&mut self, seed: u64) {
+        self.osc.set_seed(seed);
+    }
+
     fn note_on(&mut self, _ctx: &RenderCtx) {
+        self.osc.note_on();
         // Reset current_frequency to base on note-on (important for modulation)
         if let Some(base) = self.base_frequency {
             self.current_frequency = base;
@@ -212,6 +284,66 @@ impl Modulatable for OscNode {
     }
 }
 
+/// An `OscNode` whose frequency follows an audio-rate modulator
+///
+/// Built with `OscNode::with_fm`.
+pub struct OscFm<M> {
+    osc: OscNode,
+    modulator: M,
+    depth: f32,
+    /// Modulator output (no allocation in render)
+    fm_buffer: Vec<f32>,
+}
+
+impl<M: GraphNode> GraphNode for OscFm<M> {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        let modulation = &mut self.fm_buffer[..out.len()];
+        modulation.fill(0.0);
+        self.modulator.render_block(modulation, ctx);
+
+        let depth = self.depth;
+        let mut modulation = modulation.iter();
+        self.osc
+            .render_scaled(out, ctx, || 1.0 + depth * modulation.next().copied().unwrap_or(0.0));
+    }
+
+    fn prepare(&mut self, sample_rate: f32, max_block: usize) {
+        self.fm_buffer.resize(max_block, 0.0);
+        self.osc.prepare(sample_rate, max_block);
+        self.modulator.prepare(sample_rate, max_block);
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.osc.seed(seed);
+        self.modulator.seed(seed);
+    }
+
+    fn note_on(&mut self, ctx: &RenderCtx) {
+        self.osc.note_on(ctx);
+        self.modulator.note_on(ctx);
+    }
+
+    fn note_off(&mut self, ctx: &RenderCtx) {
+        self.modulator.note_off(ctx);
+    }
+
+    fn command(&mut self, command: NodeCommand) {
+        self.modulator.command(command);
+    }
+}
+
+impl<M: GraphNode> Modulatable for OscFm<M> {
+    type Param = OscParam;
+
+    fn get_param(&self, param: Self::Param) -> f32 {
+        self.osc.get_param(param)
+    }
+
+    fn apply_modulation(&mut self, param: Self::Param, base: f32, modulation: f32) {
+        self.osc.apply_modulation(param, base, modulation);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::graph::node::RenderCtx;
@@ -240,4 +372,28 @@ mod tests {
             "expected {expected}, got {actual}"
         );
     }
+
+    #[test]
+    fn zero_depth_fm_matches_plain_oscillator() {
+        let ctx = RenderCtx::from_note(48_000.0, 69, 100.0);
+        let mut plain = vec![0.0; 256];
+        OscNode::sine().render_block(&mut plain, &ctx);
+        let mut fm = vec![0.0; 256];
+        OscNode::sine().with_fm(OscNode::sine(), 0.0).render_block(&mut fm, &ctx);
+
+        assert_eq!(plain, fm);
+    }
+
+    #[test]
+    fn audio_rate_fm_is_not_averaged_away() {
+        let ctx = RenderCtx::from_note(48_000.0, 69, 100.0);
+        let mut plain = vec![0.0; 256];
+        OscNode::sine().render_block(&mut plain, &ctx);
+        let mut fm = vec![0.0; 256];
+        OscNode::sine().with_fm(OscNode::sine(), 2.0).render_block(&mut fm, &ctx);
+
+        let diff = plain.iter().zip(&fm).map(|(a, b)| (a - b).abs()).fold(0.0, f32::max);
+        assert!(diff > 0.5, "FM changed the output by only {diff}");
+        assert!(fm.iter().all(|s| s.is_finite() && s.abs() <= 1.0));
+    }
 }
@@ -0,0 +1,108 @@
+use crate::{
+    dsp::pitch_envelope::PitchEnvelope,
+    graph::{
+        node::{GraphNode, RenderCtx},
+        oscillator::OscNode,
+    },
+};
+
+/*
+Pitch Envelope Node
+===================
+
+An oscillator whose pitch drops on every note-on: the core of a synthesized
+kick or tom.
+
+  // 150 Hz falling to 50 Hz, most of the drop in the first 20 ms
+  let body = OscNode::sine()
+      .with_frequency(50.0)
+      .pitch_env(3.0, 1.0, 0.08)
+      .with_curve(6.0)
+      .amplify(EnvNode::adsr(0.001, 0.15, 0.0, 0.05));
+
+The ratios multiply the oscillator's own frequency (fixed, or the note's),
+so detune and `with_frequency` still apply. The sweep is computed every
+sample, which keeps the fastest drops smooth where `.modulate()` (updated
+once per block) would step.
+
+See `dsp/pitch_envelope.rs` for the curve math.
+*/
+
+/// An oscillator with a one-shot pitch drop on each note-on
+pub struct PitchEnvNode {
+    pub osc: OscNode,
+    pub env: PitchEnvelope,
+}
+
+impl PitchEnvNode {
+    pub fn new(osc: OscNode, start_ratio: f32, end_ratio: f32, decay_secs: f32) -> Self {
+        Self {
+            osc,
+            env: PitchEnvelope::new(start_ratio, end_ratio, decay_secs),
+        }
+    }
+
+    /// Bend the drop: 0 = even in pitch, 4-8 = analog kick
+    pub fn with_curve(mut self, curve: f32) -> Self {
+        self.env = self.env.with_curve(curve);
+        self
+    }
+}
+
+impl OscNode {
+    /// Drop the pitch from `start_ratio` to `end_ratio` times the frequency on each note
+    pub fn pitch_env(self, start_ratio: f32, end_ratio: f32, decay_secs: f32) -> PitchEnvNode {
+        PitchEnvNode::new(self, start_ratio, end_ratio, decay_secs)
+    }
+}
+
+impl GraphNode for PitchEnvNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        let env = &mut self.env;
+        self.osc.render_scaled(out, ctx, || env.next_ratio(ctx.sample_rate));
+    }
+
+    fn prepare(&mut self, sample_rate: f32, max_block: usize) {
+        self.osc.prepare(sample_rate, max_block);
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.osc.seed(seed);
+    }
+
+    fn note_on(&mut self, ctx: &RenderCtx) {
+        self.osc.note_on(ctx);
+        self.env.trigger();
+    }
+
+    fn note_off(&mut self, ctx: &RenderCtx) {
+        self.osc.note_off(ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Zero crossings in `buffer` (two per cycle)
+    fn crossings(buffer: &[f32]) -> usize {
+        buffer.windows(2).filter(|w| (w[0] < 0.0) != (w[1] < 0.0)).count()
+    }
+
+    #[test]
+    fn sweep_settles_on_the_oscillator_frequency() {
+        let ctx = RenderCtx::from_freq(48_000.0, 440.0, 100.0);
+        let mut node = OscNode::sine().with_frequency(100.0).pitch_env(4.0, 1.0, 0.05).with_curve(6.0);
+        node.note_on(&ctx);
+
+        // 50 ms of drop, then 100 ms at 100 Hz
+        let mut drop = vec![0.0; 2_400];
+        let mut body = vec![0.0; 4_800];
+        node.render_block(&mut drop, &ctx);
+        node.render_block(&mut body, &ctx);
+
+        // 100 Hz alone would cross 10 times in 50 ms
+        assert!(crossings(&drop) > 11, "{} crossings in the drop", crossings(&drop));
+        assert!((crossings(&body) as i32 - 20).abs() <= 1, "{} crossings at 100 Hz", crossings(&body));
+    }
+}
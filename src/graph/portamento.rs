@@ -0,0 +1,205 @@
+use crate::{
+    dsp::{
+        envelope::EnvelopeState,
+        smooth::SmoothedParam,
+        tuning::{hz_to_midi, midi_to_hz},
+    },
+    graph::node::{GraphNode, NodeCommand, RenderCtx},
+};
+
+/*
+Portamento Node
+===============
+
+Glides the pitch from one note to the next instead of jumping. Wraps a
+voice and smooths the frequency everything inside it sees:
+
+  let lead = voices::lead().portamento(0.08);   // 80 ms glide between notes
+
+       ctx.frequency (steps at each note)
+             ↓
+     ┌───────────────┐
+     │  Portamento   │   ramps toward each new frequency
+     └───────┬───────┘
+             ↓   smoothed frequency
+     ┌───────────────┐
+     │     voice     │   oscillators, key-tracked filters, ...
+     └───────────────┘
+
+
+Constant Time, Even Across Octaves
+----------------------------------
+
+The glide is linear in semitones (log frequency), like an analog synth's
+glide circuit: every glide takes `glide_secs`, whether it moves a half
+step or two octaves, and sounds even the whole way. A glide that is
+interrupted by another note restarts from wherever the pitch is.
+
+The first note after creation starts on its own pitch; every later note
+glides from the previous one, even after a rest.
+
+How It Works
+------------
+
+The node compares each block's frequency to the one it is heading for. A
+change (a new note, or a track's slide) becomes the new glide target.
+While gliding it renders in 32-sample chunks and updates the frequency
+between them, which is far finer than the ear can follow at glide speeds.
+
+This is independent of `Track`'s per-event slides: those glide only
+where the pattern asks, portamento glides into every note.
+*/
+
+/// Samples per frequency update while gliding
+const GLIDE_CHUNK: usize = 32;
+
+/// Glides the frequency seen by `source` between notes
+pub struct Portamento<N> {
+    pub source: N,
+    /// Seconds each glide takes
+    pub glide_secs: f32,
+    /// Sounding pitch in (fractional) MIDI notes
+    pitch: SmoothedParam,
+    /// Frequency the current glide is heading for (0 before the first note)
+    target_freq: f32,
+}
+
+impl<N> Portamento<N> {
+    pub fn new(source: N, glide_secs: f32) -> Self {
+        Self {
+            source,
+            glide_secs: glide_secs.max(0.0),
+            pitch: SmoothedParam::new(0.0),
+            target_freq: 0.0,
+        }
+    }
+
+    /// Head for `frequency`, gliding unless this is the first note
+    fn retarget(&mut self, frequency: f32, sample_rate: f32) {
+        if frequency == self.target_freq || frequency <= 0.0 {
+            return;
+        }
+        let pitch = hz_to_midi(frequency);
+        if self.target_freq == 0.0 || self.glide_secs == 0.0 {
+            self.pitch.snap(pitch);
+        } else {
+            self.pitch.set_target(pitch, self.glide_secs, sample_rate);
+        }
+        self.target_freq = frequency;
+    }
+}
+
+impl<N: GraphNode> GraphNode for Portamento<N> {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        self.retarget(ctx.frequency, ctx.sample_rate);
+
+        if !self.pitch.is_smoothing() {
+            let ctx = RenderCtx {
+                frequency: midi_to_hz(self.pitch.value()),
+                ..*ctx
+            };
+            self.source.render_block(out, &ctx);
+            return;
+        }
+
+        let mut transport = ctx.transport;
+        for chunk in out.chunks_mut(GLIDE_CHUNK) {
+            let chunk_ctx = RenderCtx {
+                frequency: midi_to_hz(self.pitch.value()),
+                transport,
+                ..*ctx
+            };
+            self.source.render_block(chunk, &chunk_ctx);
+            for _ in 0..chunk.len() {
+                self.pitch.next_value();
+            }
+            transport = transport.map(|t| t.advanced(chunk.len(), ctx.sample_rate));
+        }
+    }
+
+    fn prepare(&mut self, sample_rate: f32, max_block: usize) {
+        self.source.prepare(sample_rate, max_block);
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.source.seed(seed);
+    }
+
+    fn note_on(&mut self, ctx: &RenderCtx) {
+        self.retarget(ctx.frequency, ctx.sample_rate);
+        // The voice starts on the pitch the glide starts from
+        let ctx = RenderCtx {
+            frequency: midi_to_hz(self.pitch.value()),
+            ..*ctx
+        };
+        self.source.note_on(&ctx);
+    }
+
+    fn note_off(&mut self, ctx: &RenderCtx) {
+        self.source.note_off(ctx);
+    }
+
+    fn command(&mut self, command: NodeCommand) {
+        self.source.command(command);
+    }
+
+    fn is_active(&self) -> bool {
+        self.source.is_active()
+    }
+
+    fn latency_samples(&self) -> usize {
+        self.source.latency_samples()
+    }
+
+    fn get_envelope_level(&self) -> Option<f32> {
+        self.source.get_envelope_level()
+    }
+
+    fn get_envelope_state(&self) -> Option<EnvelopeState> {
+        self.source.get_envelope_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::extensions::NodeExt;
+    use crate::sequencing::notes::*;
+
+    /// Records the frequency of every chunk it renders
+    struct Probe(Vec<f32>);
+    impl GraphNode for Probe {
+        fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+            out.fill(0.0);
+            self.0.push(ctx.frequency);
+        }
+    }
+
+    #[test]
+    fn glides_in_constant_time_between_notes() {
+        let sample_rate = 48_000.0;
+        let mut node = Probe(Vec::new()).portamento(0.016); // 768 samples
+        let mut out = [0.0; 256];
+
+        let c4 = RenderCtx::from_note(sample_rate, C4, 100.0);
+        node.note_on(&c4);
+        node.render_block(&mut out, &c4);
+        assert_eq!(node.source.0, vec![c4.frequency], "first note doesn't glide");
+
+        let c5 = RenderCtx::from_note(sample_rate, C5, 100.0);
+        node.note_on(&c5);
+        node.source.0.clear();
+        for _ in 0..3 {
+            node.render_block(&mut out, &c5);
+        }
+
+        let seen = &node.source.0;
+        assert!(seen.windows(2).all(|w| w[1] > w[0]), "rising: {seen:?}");
+        // Halfway through the glide is halfway in pitch: F#4
+        let halfway = seen[384 / GLIDE_CHUNK];
+        assert!((hz_to_midi(halfway) - 66.0).abs() < 0.1, "{halfway} Hz");
+
+        node.render_block(&mut out, &c5);
+        assert!((node.source.0.last().unwrap() - c5.frequency).abs() < 1e-2);
+    }
+}
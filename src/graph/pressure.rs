@@ -0,0 +1,42 @@
+use crate::graph::node::{GraphNode, RenderCtx};
+
+/*
+Pressure Node
+=============
+
+Turns a held note's pressure into a modulation signal, so sequenced notes
+can swell, open up, or fade while they sound - what aftertouch does on a
+keyboard.
+
+Pressure comes from the sequence: a note with an expression ramp
+(`slot::swell(C4, 20, 127)`, or `NoteSlot::with_expression`) has its
+track ramp `RenderCtx::pressure` from start to end over the note. This
+node outputs that pressure scaled to 0.0 - 1.0:
+
+  // Crescendo: each note grows from quiet to full over its length
+  let pad = voices::pad().amplify(PressureNode::new());
+
+  // Pressure opens the filter by up to 3 kHz
+  let brass = OscNode::sawtooth()
+      .through(FilterNode::lowpass(800.0).modulate(PressureNode::new(), FilterParam::Cutoff, 3000.0));
+
+Notes without an expression ramp have zero pressure, so a route that
+should rest at full level (an `.amplify()` crescendo) only suits
+patterns whose notes all carry one.
+*/
+
+/// Modulation source following the note's pressure (see module docs)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PressureNode;
+
+impl PressureNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl GraphNode for PressureNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        out.fill(ctx.pressure / 127.0);
+    }
+}
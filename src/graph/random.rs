@@ -0,0 +1,119 @@
+use crate::{
+    dsp::{
+        rng::{Rng, DEFAULT_SEED},
+        smooth::SmoothedParam,
+    },
+    graph::node::{GraphNode, RenderCtx},
+};
+
+/*
+Random Modulation Node
+======================
+
+Sample & hold with slew: picks a new random value every `interval_secs`
+and glides to it over `slew_secs`. Where an LFO repeats, this wanders,
+which is what makes analog gear sound alive:
+
+  value
+    1 ┤     ╱‾‾‾╲
+      ┤‾‾‾‾╱     ╲        ╱‾‾‾‾‾
+    0 ┤           ╲______╱
+   -1 ┤
+      └─────┴─────┴─────┴─────→ time
+       │←───→│ interval
+             │←→│ slew
+
+  // Cutoff drifting ±300 Hz, a new target every 250 ms
+  let drift = RandomNode::new(0.25, 0.2);
+  let pad = FilterNode::lowpass(1500.0).modulate(drift, FilterParam::Cutoff, 300.0);
+
+  slew = 0              classic stepped sample & hold (bleeps, computer sounds)
+  slew < interval       steps with rounded edges
+  slew = interval       a continuous random walk (organic drift)
+
+Output is bipolar, -1.0 to 1.0, like `LfoNode`. The node free-runs: notes
+don't restart it.
+
+
+Seeding
+-------
+
+Values come from the node's own `Rng`, reseeded through `GraphNode::seed`
+like noise oscillators, so a seeded arrangement drifts the same way on
+every render. See `dsp/rng.rs`.
+*/
+
+/// Smoothed random modulation source (see module docs)
+pub struct RandomNode {
+    /// Seconds between new random targets
+    pub interval_secs: f32,
+    /// Seconds to glide to each target (0 = hard steps)
+    pub slew_secs: f32,
+    rng: Rng,
+    value: SmoothedParam,
+    /// Samples until the next target
+    countdown: usize,
+}
+
+impl RandomNode {
+    pub fn new(interval_secs: f32, slew_secs: f32) -> Self {
+        Self {
+            interval_secs: interval_secs.max(1e-3),
+            slew_secs: slew_secs.max(0.0),
+            rng: Rng::new(DEFAULT_SEED),
+            value: SmoothedParam::new(0.0),
+            countdown: 0,
+        }
+    }
+}
+
+impl GraphNode for RandomNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        for sample in out.iter_mut() {
+            if self.countdown == 0 {
+                let target = self.rng.next_bipolar();
+                self.value.set_target(target, self.slew_secs, ctx.sample_rate);
+                self.countdown = ((self.interval_secs * ctx.sample_rate) as usize).max(1);
+            }
+            self.countdown -= 1;
+            *sample = self.value.next_value();
+        }
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_to_new_targets_and_slews_between_them() {
+        let sample_rate = 1_000.0;
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 100.0);
+        let render = |slew, seed| {
+            let mut node = RandomNode::new(0.1, slew);
+            node.seed(seed);
+            let mut out = vec![0.0; 1_000];
+            node.render_block(&mut out, &ctx);
+            out
+        };
+
+        // Sample & hold: ten flat 100 ms steps
+        let held = render(0.0, 7);
+        assert!(held.iter().all(|v| (-1.0..1.0).contains(v)));
+        for step in held.chunks(100) {
+            assert!(step.iter().all(|&v| v == step[0]));
+        }
+
+        // Slewed: no jump bigger than a full swing spread over 50 ms
+        let slewed = render(0.05, 7);
+        assert!(slewed.windows(2).all(|w| (w[1] - w[0]).abs() <= 2.0 / 50.0 + 1e-6));
+        assert_eq!(slewed[99], held[0], "lands on the same target");
+
+        assert_eq!(render(0.05, 7), slewed, "seeded");
+        assert_ne!(render(0.05, 8), slewed);
+    }
+}
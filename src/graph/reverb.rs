@@ -1,6 +1,9 @@
+use crate::dsp::filter::SVFilter;
 use crate::dsp::mix::blend_dry_wet;
-use crate::dsp::reverb::SchroederReverb;
-use crate::graph::node::{GraphNode, Modulatable, RenderCtx};
+use crate::dsp::pitch_shift::PitchShifter;
+use crate::dsp::reverb::{EarlyReflections, SchroederReverb};
+use crate::dsp::spring::SpringReverb;
+use crate::graph::node::{GraphNode, Modulatable, NodeCommand, RenderCtx};
 
 /*
 Reverb Node
@@ -49,8 +52,80 @@ Example usage:
   // Huge ambient reverb
   let ambient = OscNode::sine()
       .through(ReverbNode::new(1.0, 0.3, 0.7));
+
+
+Shimmer
+-------
+
+`ReverbNode::shimmer(mix)` feeds the reverb's own output, shifted up an
+octave, back into its input. Every pass through the loop climbs another
+octave and decays a little more, so held notes bloom into a high, glassy
+halo - the classic ambient pad sound:
+
+  Input ──(+)──→ [Reverb] ──┬──→ Output
+           ↑                │
+           └── [+12 st] ←───┘   × feedback
+
+The loop is highpassed (shifting can't move DC or rumble up, so they
+would build up instead), and the feedback shrinks as the room grows so
+the loop always dies away. Damping darkens each octave so the halo fades
+out rather than piling up at the top of the spectrum. See
+`dsp/pitch_shift.rs`.
+
+  let pad = voices::pad().through(ReverbNode::shimmer(0.5));
+
+
+Spring
+------
+
+`ReverbNode::spring(mix)` swaps the room for a spring tank: a few
+distinct, chirping repeats instead of a smooth wash. Snares and rimshots
+"boing", dub chords drip, guitar-amp twang for a lo-fi beat. Room size
+sets how long the repeats ring and damping how fast they darken. See
+`dsp/spring.rs`.
+
+  let snare = voices::snare().through(ReverbNode::spring(0.4));
+
+
+Early Reflections
+-----------------
+
+`with_early_reflections(level)` adds the first distinct bounces of the
+room ahead of the tail: eight taps between ~4 and ~35 ms (spread further
+apart as the room grows). They give the reverb a sense of distance and
+size - a sound with strong early reflections sits further back in the
+mix - without making the tail any longer. See `dsp/reverb.rs`.
+
+  // Drums placed at the back of a mid-sized room
+  let toms = voices::tom().through(ReverbNode::room(0.3).with_early_reflections(0.8));
+
+
+Freeze
+------
+
+Freezing the reverb holds its tail forever: the combs stop losing
+anything (feedback 1.0, no damping) and new input no longer goes in, so
+the wash that was ringing at that moment hangs in the air unchanged while
+the dry signal plays on over it. Play a chord, freeze, and it becomes a
+pad to solo over; unfreeze and it decays away as usual. Works for every
+flavor (room, shimmer, spring).
+
+  // Gate the freeze with the bar: hold on the last beat of every bar
+  let swell = voices::pad().through(ReverbNode::hall(0.6).modulate(
+      StepLfoNode::new(&[0.0, 0.0, 0.0, 1.0], Duration::QUARTER), ReverbParam::Freeze, 1.0));
+
+In the runtime, `ControlMessage::ReverbFreeze` (key F in the UI) freezes
+or thaws every reverb in the arrangement.
 */
 
+/// Pitch shift applied on each pass of the shimmer loop
+const SHIMMER_SEMITONES: f32 = 12.0;
+/// Shifted signal fed back into the reverb, relative to what the combs let
+/// decay each pass: bigger rooms ring longer, so they get less feedback
+const SHIMMER_FEEDBACK: f32 = 2.5;
+/// Highpass on the feedback (shifting leaves DC and rumble where they are)
+const SHIMMER_HIGHPASS_HZ: f32 = 300.0;
+
 /// Parameters that can be modulated
 #[derive(Clone, Copy, Debug)]
 pub enum ReverbParam {
@@ -60,18 +135,38 @@ pub enum ReverbParam {
     Damping,
     /// Dry/wet mix (0.0 = dry, 1.0 = wet)
     Mix,
+    /// Freeze the tail (0.5 and above = frozen)
+    Freeze,
 }
 
 /// Schroeder reverb effect
 ///
-/// Delay buffers are pre-allocated at construction (RT-safe). On first render,
-/// the delay times are configured using the sample rate from `RenderCtx`.
+/// Delay buffers are pre-allocated at construction (RT-safe). Delay times are
+/// configured in `prepare`, or on first render from `RenderCtx` if the host
+/// never called it.
 pub struct ReverbNode {
     reverb: SchroederReverb,
     room_size: f32,
     damping: f32,
     mix: f32,
     configured: bool,
+    shimmer: Option<Shimmer>,
+    /// Spring tank used instead of `reverb` (see module docs)
+    spring: Option<SpringReverb>,
+    /// First bounces added ahead of the tail, with their level
+    early: Option<(EarlyReflections, f32)>,
+    /// Unmodulated freeze setting (1.0 = frozen)
+    base_freeze: f32,
+}
+
+/// Octave-up feedback loop around the reverb (see module docs)
+struct Shimmer {
+    shifter: PitchShifter,
+    highpass: SVFilter,
+    /// Highpass coefficient for the current sample rate
+    highpass_g: f32,
+    /// Previous wet output, fed back through the shifter
+    last_wet: f32,
 }
 
 impl ReverbNode {
@@ -82,9 +177,9 @@ impl ReverbNode {
     /// - `mix`: 0.0 (dry) to 1.0 (wet)
     ///
     /// Buffers are pre-allocated (no allocation in audio thread).
-    /// Delay times are configured on first render using ctx.sample_rate.
+    /// Delay times are configured by `prepare` (or on first render).
     pub fn new(room_size: f32, damping: f32, mix: f32) -> Self {
-        // Pre-allocate with default sample rate (will be reconfigured on first render)
+        // Pre-allocate with default sample rate (reconfigured in prepare/first render)
         let mut reverb = SchroederReverb::new(48000.0);
         reverb.set_room_size(room_size);
         reverb.set_damping(damping);
@@ -95,6 +190,10 @@ impl ReverbNode {
             damping: damping.clamp(0.0, 1.0),
             mix: mix.clamp(0.0, 1.0),
             configured: false,
+            shimmer: None,
+            spring: None,
+            early: None,
+            base_freeze: 0.0,
         }
     }
 
@@ -112,26 +211,115 @@ impl ReverbNode {
     pub fn plate(mix: f32) -> Self {
         Self::new(0.85, 0.3, mix)
     }
+
+    /// Create a spring reverb (chirping "boing" repeats, amp/dub style)
+    pub fn spring(mix: f32) -> Self {
+        let mut reverb = Self::new(0.5, 0.3, mix);
+        let mut spring = SpringReverb::new(48000.0);
+        spring.set_decay(reverb.room_size);
+        spring.set_damping(reverb.damping);
+        reverb.spring = Some(spring);
+        reverb
+    }
+
+    /// Create a shimmer reverb (long hall with an octave-up feedback loop)
+    pub fn shimmer(mix: f32) -> Self {
+        let mut reverb = Self::new(0.8, 0.5, mix);
+        reverb.shimmer = Some(Shimmer {
+            shifter: PitchShifter::new(SHIMMER_SEMITONES, 48000.0),
+            highpass: SVFilter::highpass(SHIMMER_HIGHPASS_HZ),
+            highpass_g: SVFilter::compute_g(SHIMMER_HIGHPASS_HZ, 48000.0),
+            last_wet: 0.0,
+        });
+        reverb
+    }
+
+    /// Hold the current tail forever (true) or let it decay again (false)
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.base_freeze = frozen as u8 as f32;
+        self.freeze(frozen);
+    }
+
+    fn freeze(&mut self, frozen: bool) {
+        self.reverb.set_frozen(frozen);
+        if let Some(spring) = &mut self.spring {
+            spring.set_frozen(frozen);
+        }
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.reverb.is_frozen()
+    }
+
+    /// Add early reflections at `level` (0.0 - 1.0) ahead of the tail
+    pub fn with_early_reflections(mut self, level: f32) -> Self {
+        self.early = Some((EarlyReflections::new(self.room_size, 48000.0), level.clamp(0.0, 1.0)));
+        self
+    }
+}
+
+/// Shimmer loop gain for a room size (see `SHIMMER_FEEDBACK`)
+fn shimmer_feedback(room_size: f32) -> f32 {
+    SHIMMER_FEEDBACK * (1.0 - SchroederReverb::comb_feedback(room_size))
 }
 
 impl GraphNode for ReverbNode {
     fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
         // Configure delay times for actual sample rate on first render (RT-safe)
         if !self.configured {
-            self.reverb.configure(ctx.sample_rate);
-            self.configured = true;
+            self.prepare(ctx.sample_rate, out.len());
         }
 
+        // Frozen: nothing new goes in, the held tail rings on
+        let input_gain = if self.is_frozen() { 0.0 } else { 1.0 };
         for sample in out.iter_mut() {
             let dry = *sample;
-            let wet = self.reverb.process(dry);
+            let input = dry * input_gain;
+            let early = match &mut self.early {
+                Some((early, level)) => early.process(input) * *level,
+                None => 0.0,
+            };
+            let wet = early + match &mut self.shimmer {
+                Some(shimmer) => {
+                    // k = 2: no resonance
+                    let feedback = shimmer.highpass.next_sample(shimmer.last_wet, 2.0, shimmer.highpass_g).highpass;
+                    let shifted = shimmer.shifter.process(feedback);
+                    shimmer.last_wet = self.reverb.process(input + shifted * shimmer_feedback(self.room_size) * input_gain);
+                    shimmer.last_wet
+                }
+                None => match &mut self.spring {
+                    Some(spring) => spring.process(input),
+                    None => self.reverb.process(input),
+                },
+            };
             *sample = blend_dry_wet(dry, wet, self.mix);
         }
     }
 
+    fn prepare(&mut self, sample_rate: f32, _max_block: usize) {
+        self.reverb.configure(sample_rate);
+        if let Some(spring) = &mut self.spring {
+            spring.configure(sample_rate);
+        }
+        if let Some((early, _)) = &mut self.early {
+            early.configure(sample_rate);
+        }
+        if let Some(shimmer) = &mut self.shimmer {
+            shimmer.shifter.configure(sample_rate);
+            shimmer.highpass_g = SVFilter::compute_g(SHIMMER_HIGHPASS_HZ, sample_rate);
+        }
+        self.configured = true;
+    }
+
     fn note_on(&mut self, _ctx: &RenderCtx) {
         // Don't reset reverb on note-on - we want the tail to continue
     }
+
+    fn command(&mut self, command: NodeCommand) {
+        if let NodeCommand::ReverbFreeze(frozen) = command {
+            self.set_frozen(frozen);
+        }
+    }
 }
 
 impl Modulatable for ReverbNode {
@@ -142,6 +330,7 @@ impl Modulatable for ReverbNode {
             ReverbParam::RoomSize => self.room_size,
             ReverbParam::Damping => self.damping,
             ReverbParam::Mix => self.mix,
+            ReverbParam::Freeze => self.base_freeze,
         }
     }
 
@@ -150,14 +339,30 @@ impl Modulatable for ReverbNode {
             ReverbParam::RoomSize => {
                 self.room_size = (base + modulation).clamp(0.0, 1.0);
                 self.reverb.set_room_size(self.room_size);
+                if let Some(spring) = &mut self.spring {
+                    spring.set_decay(self.room_size);
+                }
+                if let Some((early, _)) = &mut self.early {
+                    early.set_room_size(self.room_size);
+                }
             }
             ReverbParam::Damping => {
                 self.damping = (base + modulation).clamp(0.0, 1.0);
                 self.reverb.set_damping(self.damping);
+                if let Some(spring) = &mut self.spring {
+                    spring.set_damping(self.damping);
+                }
             }
             ReverbParam::Mix => {
                 self.mix = (base + modulation).clamp(0.0, 1.0);
             }
+            ReverbParam::Freeze => {
+                self.base_freeze = base;
+                let frozen = base + modulation >= 0.5;
+                if frozen != self.is_frozen() {
+                    self.freeze(frozen);
+                }
+            }
         }
     }
 }
@@ -165,6 +370,7 @@ impl Modulatable for ReverbNode {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dsp::analysis::{harmonics::HarmonicAnalysis, null_test};
 
     fn test_ctx() -> RenderCtx {
         RenderCtx::from_note(48000.0, 60, 100.0)
@@ -212,4 +418,109 @@ mod tests {
         assert!(room.room_size < hall.room_size);
         assert!(hall.room_size < plate.room_size);
     }
+
+    #[test]
+    fn test_dry_reverb_nulls() {
+        null_test::assert_null(ReverbNode::hall(0.0), 48_000.0);
+    }
+
+    #[test]
+    fn test_spring_repeats_at_its_transit_time() {
+        let mut spring = ReverbNode::spring(1.0);
+        spring.prepare(48_000.0, 4_800);
+        let mut buffer = vec![0.0; 4_800];
+        buffer[0] = 1.0;
+        spring.render_block(&mut buffer, &test_ctx());
+
+        // Nothing until the first trip along the springs, then the boing
+        let peak = |s: &[f32]| s.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+        assert!(peak(&buffer[1..1_700]) < 1e-6);
+        assert!(peak(&buffer[1_700..2_400]) > 0.01);
+    }
+
+    #[test]
+    fn test_early_reflections_arrive_before_the_tail() {
+        let render = |mut reverb: ReverbNode| {
+            reverb.prepare(48_000.0, 2_400);
+            let mut buffer = vec![0.0; 2_400];
+            buffer[0] = 1.0;
+            reverb.render_block(&mut buffer, &test_ctx());
+            buffer
+        };
+        let peak = |s: &[f32]| s.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+
+        // The combs need ~30 ms; the first bounce comes back at ~5 ms
+        let plain = render(ReverbNode::hall(1.0));
+        let early = render(ReverbNode::hall(1.0).with_early_reflections(1.0));
+        assert!(peak(&plain[1..1_400]) < 1e-6);
+        let first = early.iter().skip(1).position(|x| x.abs() > 0.1).map(|i| i + 1);
+        assert_eq!(first, Some((4.3f32 * 1.1 * 48.0) as usize));
+        assert!(peak(&early[1..1_400]) > 0.3);
+    }
+
+    #[test]
+    fn test_freeze_holds_the_tail_and_ignores_new_input() {
+        let mut reverb = ReverbNode::hall(1.0);
+        reverb.prepare(48_000.0, 4_800);
+        let ctx = test_ctx();
+        let rms = |s: &[f32]| (s.iter().map(|x| x * x).sum::<f32>() / s.len() as f32).sqrt();
+
+        // A burst of noise-ish input, then freeze once the tail has built up
+        let mut burst: Vec<f32> = (0..4_800).map(|i| ((i * 7_919) % 101) as f32 / 50.0 - 1.0).collect();
+        reverb.render_block(&mut burst, &ctx);
+        reverb.command(NodeCommand::ReverbFreeze(true));
+        let mut held = vec![0.0; 4_800];
+        reverb.render_block(&mut held, &ctx);
+
+        // Ten seconds later it's still there, and loud input didn't pile on
+        for _ in 0..100 {
+            let mut block = vec![0.5; 4_800];
+            reverb.render_block(&mut block, &ctx);
+        }
+        reverb.set_frozen(false);
+        let mut later = vec![0.0; 4_800];
+        reverb.render_block(&mut later, &ctx);
+        let ratio = rms(&later) / rms(&held);
+        assert!(ratio > 0.7 && ratio < 1.2, "held level moved: {ratio}");
+        assert_eq!(reverb.get_param(ReverbParam::Freeze), 0.0);
+
+        // Thawed, it decays again
+        for _ in 0..100 {
+            let mut block = vec![0.0; 4_800];
+            reverb.render_block(&mut block, &ctx);
+        }
+        let mut gone = vec![0.0; 4_800];
+        reverb.render_block(&mut gone, &ctx);
+        assert!(rms(&gone) < 1e-3 * rms(&held));
+    }
+
+    #[test]
+    fn test_shimmer_adds_octave_and_dies_away() {
+        let sample_rate = 48_000.0;
+        let ctx = RenderCtx::from_note(sample_rate, 60, 100.0);
+        let run = |mut reverb: ReverbNode| {
+            reverb.prepare(sample_rate, 1_000);
+            let mut out: Vec<f32> = (0..48_000)
+                .map(|i| (std::f32::consts::TAU * 400.0 * i as f32 / sample_rate).sin())
+                .collect();
+            out.resize(48_000 * 10, 0.0);
+            for block in out.chunks_mut(1_000) {
+                reverb.render_block(block, &ctx);
+            }
+            out
+        };
+        let plain = run(ReverbNode::new(0.8, 0.5, 1.0));
+        let shimmer = run(ReverbNode::shimmer(1.0));
+
+        let octave = |signal: &[f32]| {
+            let analysis = HarmonicAnalysis::of(signal, sample_rate, 400.0);
+            analysis.amplitudes[1] / analysis.amplitudes[0]
+        };
+        // A second into the note the halo has climbed an octave
+        assert!(octave(&shimmer[24_000..48_000]) > 10.0 * octave(&plain[24_000..48_000]));
+
+        // ...and the loop still dies away
+        let tail_peak = shimmer[48_000 * 9..].iter().fold(0.0f32, |m, x| m.max(x.abs()));
+        assert!(tail_peak < 1e-3, "tail peak {tail_peak}");
+    }
 }
@@ -0,0 +1,252 @@
+use crate::{
+    dsp::smooth::{SmoothedParam, DEFAULT_SMOOTHING_SECS},
+    graph::node::{GraphNode, Modulatable, RenderCtx},
+    sequencing::Duration,
+    MAX_DELAY_SAMPLES,
+};
+
+/*
+Reverse Delay
+=============
+
+Records the input one segment at a time (a note value, often a bar) and plays each
+finished segment backwards while the next one records. Everything comes
+out reversed, one segment late - the swelling, sucked-in sound of tape
+played backwards, used for transitions and textures:
+
+  segment    │   1   │   2   │   3   │
+  input      │ A→→→→ │ B→→→→ │ C→→→→ │
+  output     │       │ ←←←←A │ ←←←←B │
+
+  // Half-wet reversed pad, one bar at a time
+  let pad = voices::pad().through(ReverseNode::new(Duration::WHOLE, 0.5));
+
+  // Quarter-note reverse stutter
+  let fx = ReverseNode::new(Duration::QUARTER, 1.0);
+
+
+Sync
+----
+
+Segments line up with the transport: a one-bar segment flips exactly on
+each downbeat and follows tempo changes. Without a transport (offline
+tests, a bare `GraphNode`), or while paused, segments keep running at the
+last tempo seen (120 BPM until one is).
+
+
+Crossfade
+---------
+
+A reversed segment starts with the end of the recording, which rarely
+lines up with where the previous segment stopped. Each segment fades in
+and out over `crossfade_secs` (10 ms default) to hide the seam; longer
+fades (`with_crossfade(0.2)`) turn the flips into soft swells.
+
+
+Buffer
+------
+
+Two buffers of MAX_DELAY_SAMPLES each (~4 seconds at 48kHz: a 4/4 bar at
+60 BPM). Segments longer than the buffer play back only what fit; size it
+with `with_max_seconds` for slow tempos or long segments. Allocated at
+construction, so rendering stays realtime-safe.
+*/
+
+/// Tempo used until a transport reports one
+const DEFAULT_BPM: f64 = 120.0;
+/// Default fade at each end of a reversed segment
+const DEFAULT_CROSSFADE_SECS: f32 = 0.01;
+
+/// Tempo-synced reverse delay (see module docs)
+pub struct ReverseNode {
+    /// Segment length in quarter-note beats
+    segment_beats: f64,
+    /// Fade in/out at the ends of each reversed segment
+    pub crossfade_secs: f32,
+    mix: SmoothedParam,
+    sample_rate: f32,
+
+    /// Segment being recorded
+    record: Vec<f32>,
+    recorded: usize,
+    /// Previous segment, played backwards
+    playback: Vec<f32>,
+    playback_len: usize,
+
+    /// Position in beats, kept for running without a transport
+    beat: f64,
+    bpm: f64,
+    /// Index of the segment being recorded
+    segment: i64,
+}
+
+impl ReverseNode {
+    /// Reverse every `segment` note value, with a dry/wet `mix` (0.0 - 1.0)
+    pub fn new(segment: Duration, mix: f32) -> Self {
+        Self::with_buffer(segment, mix, MAX_DELAY_SAMPLES)
+    }
+
+    /// Size each buffer for `max_secs` at `sample_rate` (see module docs)
+    pub fn with_max_seconds(mut self, max_secs: f32, sample_rate: f32) -> Self {
+        let capacity = ((max_secs.max(0.0) * sample_rate).ceil() as usize).max(1);
+        self.record = vec![0.0; capacity];
+        self.playback = vec![0.0; capacity];
+        self.recorded = 0;
+        self.playback_len = 0;
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Fade each reversed segment in and out over `secs`
+    pub fn with_crossfade(mut self, secs: f32) -> Self {
+        self.crossfade_secs = secs.max(0.0);
+        self
+    }
+
+    fn with_buffer(segment: Duration, mix: f32, capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            segment_beats: (4.0 * segment.numerator as f64 / segment.denominator.max(1) as f64)
+                .max(1e-6),
+            crossfade_secs: DEFAULT_CROSSFADE_SECS,
+            mix: SmoothedParam::new(mix.clamp(0.0, 1.0)),
+            sample_rate: 48_000.0,
+            record: vec![0.0; capacity],
+            recorded: 0,
+            playback: vec![0.0; capacity],
+            playback_len: 0,
+            beat: 0.0,
+            bpm: DEFAULT_BPM,
+            segment: 0,
+        }
+    }
+
+    /// Finish the recording and start playing it backwards
+    fn flip(&mut self, segment: i64) {
+        std::mem::swap(&mut self.record, &mut self.playback);
+        self.playback_len = self.recorded;
+        self.recorded = 0;
+        self.segment = segment;
+    }
+}
+
+impl GraphNode for ReverseNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        self.sample_rate = ctx.sample_rate;
+        if let Some(transport) = ctx.transport {
+            self.bpm = transport.bpm;
+            if transport.playing {
+                self.beat = transport.beat();
+            }
+        }
+        let beats_per_sample = self.bpm / (60.0 * ctx.sample_rate as f64);
+        let segment_samples = self.segment_beats / beats_per_sample;
+        let fade_samples = (self.crossfade_secs * ctx.sample_rate).max(1.0);
+
+        for sample in out.iter_mut() {
+            let position = self.beat / self.segment_beats;
+            let segment = position.floor() as i64;
+            if segment != self.segment {
+                self.flip(segment);
+            }
+
+            // Samples into this segment = samples back from the recording's end
+            let offset = (position.fract() * segment_samples) as usize;
+            let wet = if offset < self.playback_len {
+                let remaining = (self.playback_len - offset) as f32;
+                let fade = ((offset + 1) as f32 / fade_samples)
+                    .min(remaining / fade_samples)
+                    .min(1.0);
+                self.playback[self.playback_len - 1 - offset] * fade
+            } else {
+                0.0
+            };
+
+            if self.recorded < self.record.len() {
+                self.record[self.recorded] = *sample;
+                self.recorded += 1;
+            }
+
+            let mix = self.mix.next_value();
+            *sample = *sample * (1.0 - mix) + wet * mix;
+            self.beat += beats_per_sample;
+        }
+    }
+
+    fn prepare(&mut self, sample_rate: f32, _max_block: usize) {
+        self.sample_rate = sample_rate;
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ReverseParam {
+    Mix,
+}
+
+impl Modulatable for ReverseNode {
+    type Param = ReverseParam;
+
+    fn get_param(&self, param: Self::Param) -> f32 {
+        match param {
+            ReverseParam::Mix => self.mix.target(),
+        }
+    }
+
+    fn apply_modulation(&mut self, param: Self::Param, base: f32, modulation: f32) {
+        match param {
+            ReverseParam::Mix => {
+                let mix = (base + modulation).clamp(0.0, 1.0);
+                self.mix
+                    .set_target(mix, DEFAULT_SMOOTHING_SECS, self.sample_rate);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Transport;
+
+    #[test]
+    fn plays_each_segment_backwards_one_segment_late() {
+        // 120 BPM at 1 kHz: a quarter note is 500 samples
+        let sample_rate = 1_000.0;
+        let transport = Transport {
+            bpm: 120.0,
+            ppq: 480,
+            tick: 0.0,
+            bar_ticks: 1920,
+            playing: true,
+        };
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 100.0).with_transport(transport);
+        let mut reverse = ReverseNode::new(Duration::QUARTER, 1.0).with_crossfade(0.0);
+
+        let mut out: Vec<f32> = (0..1_000).map(|i| i as f32).collect();
+        reverse.render_block(&mut out, &ctx);
+
+        assert!(
+            out[..500].iter().all(|&v| v == 0.0),
+            "first segment is recording"
+        );
+        assert_eq!(out[500], 499.0);
+        assert_eq!(out[999], 0.0);
+        assert!(out[500..].windows(2).all(|w| w[1] == w[0] - 1.0));
+    }
+
+    #[test]
+    fn crossfade_and_mix_keep_segment_edges_quiet() {
+        let sample_rate = 1_000.0;
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 100.0);
+        // Free-runs at 120 BPM without a transport
+        let mut reverse = ReverseNode::new(Duration::QUARTER, 0.5).with_crossfade(0.05);
+
+        let mut out = vec![1.0; 1_000];
+        reverse.render_block(&mut out, &ctx);
+
+        assert_eq!(out[499], 0.5, "dry half only while recording");
+        assert!(out[500] < 0.52, "reversed segment fades in from silence");
+        assert_eq!(out[750], 1.0);
+        assert!(out[999] < 0.52, "and fades out at its end");
+    }
+}
@@ -0,0 +1,261 @@
+use std::f32::consts::TAU;
+
+use crate::dsp::delay::DelayLine;
+use crate::dsp::filter::SVFilter;
+use crate::graph::node::{GraphNode, Modulatable, RenderCtx};
+
+/*
+Rotary Speaker Node
+===================
+
+The Leslie speaker: the sound of a tonewheel organ. Inside the cabinet a
+crossover splits the signal between a spinning HORN (highs) and a
+spinning DRUM (lows). As each one turns toward you and away:
+
+  AM   it gets louder and quieter (the mouth points at you, then away)
+  FM   its pitch rises and falls (Doppler: the source moves toward you,
+       then away)
+
+  Input ──→ [Crossover 800 Hz] ──┬── highs → Horn (fast, small) ──┐
+                                 └── lows  → Drum (slow, big)  ───┴─→ Output
+
+The horn and drum spin at different rates, so the two wobbles drift
+against each other instead of pulsing together - the swirl.
+
+
+Slow and Fast
+-------------
+
+The speed switch ("chorale" / "tremolo") is the Leslie's performance
+control. The rotors are heavy and don't change speed instantly: the horn
+gets there in about a second, the drum takes several. That speed-up and
+slow-down is a big part of the sound.
+
+              slow      fast      time to change speed
+  Horn        0.8 Hz    6.7 Hz    ~0.7 s
+  Drum        0.7 Hz    5.7 Hz    ~4 s
+
+Speed (0.0 - 1.0):
+  0 = slow, 1 = fast. Modulatable, so a step LFO or an automation lane can
+  flip it. The rotors ramp toward the new speed on their own.
+
+Example usage:
+
+  let organ = voices::organ().through(RotaryNode::new());
+
+  // Kick into fast at the chorus
+  let organ = voices::organ().through(RotaryNode::new().with_speed(1.0));
+
+A real cabinet also throws the horn around the room from left to right.
+The output here is mono, so only the AM and FM are modelled.
+
+
+How the Doppler Works
+---------------------
+
+Each rotor reads its signal from a short delay whose length swings with
+the rotation. A delay that's shrinking plays the audio back faster (pitch
+up); one that's growing plays it slower (pitch down). The horn is further
+from the axis, so its swing - and its pitch shift - is larger.
+*/
+
+/// Crossover between drum and horn
+const CROSSOVER_HZ: f32 = 800.0;
+/// Longest rotor delay (center plus swing), with room to spare
+const MAX_ROTOR_DELAY_SECS: f32 = 0.004;
+
+/// One spinning speaker: rates, ramp time and how much it modulates
+struct RotorSpec {
+    slow_hz: f32,
+    fast_hz: f32,
+    /// Time constant of speed changes
+    inertia_secs: f32,
+    /// Level dip when facing away (0 - 1)
+    am_depth: f32,
+    /// Center of the Doppler delay
+    center_ms: f32,
+    /// Swing of the Doppler delay
+    swing_ms: f32,
+}
+
+const HORN: RotorSpec = RotorSpec {
+    slow_hz: 0.8,
+    fast_hz: 6.7,
+    inertia_secs: 0.7,
+    am_depth: 0.5,
+    center_ms: 1.5,
+    swing_ms: 0.35,
+};
+
+const DRUM: RotorSpec = RotorSpec {
+    slow_hz: 0.7,
+    fast_hz: 5.7,
+    inertia_secs: 4.0,
+    am_depth: 0.3,
+    center_ms: 1.5,
+    swing_ms: 0.15,
+};
+
+/// Parameters that can be modulated
+#[derive(Clone, Copy, Debug)]
+pub enum RotaryParam {
+    /// 0.0 = slow (chorale), 1.0 = fast (tremolo)
+    Speed,
+}
+
+/// A rotor's moving state
+struct Rotor {
+    spec: &'static RotorSpec,
+    delay: DelayLine,
+    /// Current rotation speed (ramps toward the target)
+    rate_hz: f32,
+    phase: f32,
+}
+
+impl Rotor {
+    fn new(spec: &'static RotorSpec, phase: f32) -> Self {
+        Self {
+            spec,
+            delay: DelayLine::with_max_seconds(MAX_ROTOR_DELAY_SECS, 192_000.0),
+            rate_hz: spec.slow_hz,
+            phase,
+        }
+    }
+
+    /// Per-sample step of the speed ramp toward its target
+    fn inertia_coef(&self, sample_rate: f32) -> f32 {
+        1.0 - (-1.0 / (self.spec.inertia_secs * sample_rate)).exp()
+    }
+
+    /// `coef` is `inertia_coef` at this sample rate, computed once per block
+    #[inline]
+    fn process(&mut self, input: f32, speed: f32, coef: f32, sample_rate: f32) -> f32 {
+        let spec = self.spec;
+        let target = spec.slow_hz + speed * (spec.fast_hz - spec.slow_hz);
+        self.rate_hz += coef * (target - self.rate_hz);
+
+        // Facing the listener at phase 0: loudest, delay shrinking (pitch up)
+        let (sin, cos) = (TAU * self.phase).sin_cos();
+        let delay_ms = spec.center_ms - spec.swing_ms * sin;
+        self.delay.write(input);
+        let doppler = self.delay.read_interpolated(delay_ms * sample_rate / 1000.0);
+        let gain = 1.0 - spec.am_depth * 0.5 * (1.0 - cos);
+
+        self.phase = (self.phase + self.rate_hz / sample_rate).fract();
+        doppler * gain
+    }
+}
+
+/// Leslie-style rotary speaker (see module docs)
+pub struct RotaryNode {
+    crossover: SVFilter,
+    horn: Rotor,
+    drum: Rotor,
+    /// 0.0 = slow, 1.0 = fast
+    speed: f32,
+}
+
+impl RotaryNode {
+    /// Rotary speaker starting on the slow setting
+    pub fn new() -> Self {
+        Self {
+            crossover: SVFilter::lowpass(CROSSOVER_HZ),
+            horn: Rotor::new(&HORN, 0.0),
+            // Start the drum a quarter turn off so the two don't pulse together
+            drum: Rotor::new(&DRUM, 0.25),
+            speed: 0.0,
+        }
+    }
+
+    /// Start at `speed` (0.0 = slow, 1.0 = fast), rotors already up to speed
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed.clamp(0.0, 1.0);
+        for rotor in [&mut self.horn, &mut self.drum] {
+            rotor.rate_hz = rotor.spec.slow_hz + self.speed * (rotor.spec.fast_hz - rotor.spec.slow_hz);
+        }
+        self
+    }
+
+    /// Current horn and drum rotation speeds in Hz, for displays
+    pub fn rotor_rates(&self) -> (f32, f32) {
+        (self.horn.rate_hz, self.drum.rate_hz)
+    }
+}
+
+impl Default for RotaryNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphNode for RotaryNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        let sample_rate = ctx.sample_rate;
+        // Butterworth crossover; lows + highs rebuild the input exactly
+        let k = std::f32::consts::SQRT_2;
+        let g = SVFilter::compute_g(CROSSOVER_HZ, sample_rate);
+        let horn_coef = self.horn.inertia_coef(sample_rate);
+        let drum_coef = self.drum.inertia_coef(sample_rate);
+
+        for sample in out.iter_mut() {
+            let lows = self.crossover.next_sample(*sample, k, g).lowpass;
+            let highs = *sample - lows;
+            *sample = self.horn.process(highs, self.speed, horn_coef, sample_rate)
+                + self.drum.process(lows, self.speed, drum_coef, sample_rate);
+        }
+    }
+}
+
+impl Modulatable for RotaryNode {
+    type Param = RotaryParam;
+
+    fn get_param(&self, param: Self::Param) -> f32 {
+        match param {
+            RotaryParam::Speed => self.speed,
+        }
+    }
+
+    fn apply_modulation(&mut self, param: Self::Param, base: f32, modulation: f32) {
+        match param {
+            RotaryParam::Speed => {
+                self.speed = (base + modulation).clamp(0.0, 1.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotors_ramp_to_fast_at_their_own_pace() {
+        let sample_rate = 48_000.0;
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 100.0);
+        let mut rotary = RotaryNode::new();
+        rotary.apply_modulation(RotaryParam::Speed, 1.0, 0.0);
+
+        // One second in: the horn is nearly there, the drum still spinning up
+        let mut buffer = vec![0.0; 48_000];
+        rotary.render_block(&mut buffer, &ctx);
+        let (horn, drum) = rotary.rotor_rates();
+        assert!(horn > 0.7 * HORN.fast_hz, "horn at {horn} Hz");
+        assert!(drum < 0.5 * DRUM.fast_hz, "drum at {drum} Hz");
+    }
+
+    #[test]
+    fn horn_swirls_the_level_of_high_notes() {
+        let sample_rate = 48_000.0;
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 100.0);
+        let mut rotary = RotaryNode::new().with_speed(1.0);
+
+        // A 3 kHz tone goes through the horn, pulsing at its fast rate
+        let mut buffer: Vec<f32> = (0..24_000).map(|i| (TAU * 3_000.0 * i as f32 / sample_rate).sin()).collect();
+        rotary.render_block(&mut buffer, &ctx);
+        let cycle_peaks: Vec<f32> =
+            buffer[4_800..].chunks(16).map(|c| c.iter().fold(0.0f32, |m, s| m.max(s.abs()))).collect();
+        let loudest = cycle_peaks.iter().fold(0.0f32, |m, &p| m.max(p));
+        let quietest = cycle_peaks.iter().fold(f32::MAX, |m, &p| m.min(p));
+        assert!(loudest > 0.9 && quietest < 0.6, "{quietest} - {loudest}");
+    }
+}
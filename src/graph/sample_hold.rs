@@ -0,0 +1,193 @@
+use crate::{
+    graph::node::{GraphNode, Modulatable, RenderCtx},
+    sequencing::Duration,
+};
+
+/*
+Sample & Hold Node
+==================
+
+Grabs the audio's value on every clock tick and holds it until the next
+one, turning any sound into a staircase:
+
+  input    ╱‾╲    ╱‾╲            output   ┌─┐
+          ╱   ╲  ╱   ╲                  ┌─┘ └┐  ┌─┐
+         ╱     ╲╱     ╲               ──┘    └──┘ └──
+           │ │ │ │ │ │ │ ticks
+
+At a few kHz it's a downsampler: aliasing, whine, 80s sampler grit. At a
+few hundred Hz the pitch dissolves into buzzing robotic steps, and
+synced to the tempo it chops a sound into rhythmic frozen slices.
+
+  // Free-running: 2 kHz hold clock, swept by an LFO for a "talking" robot
+  let robot = voices::lead()
+      .through(SampleHoldNode::new(2_000.0).modulate(LfoNode::sine(0.5), SampleHoldParam::Rate, 1_500.0));
+
+  // Tempo-synced: freeze a new slice of the pad every 16th note
+  let frozen = voices::pad().through(SampleHoldNode::synced(Duration::SIXTEENTH));
+
+
+Clock
+-----
+
+Free-running (`new`):
+  Ticks at `rate_hz` (modulatable as `SampleHoldParam::Rate`, 1 Hz - the
+  sample rate). Unlike decimating by a fixed whole factor, the rate
+  can move smoothly, so sweeping it sweeps the aliasing.
+
+Synced (`synced`):
+  Ticks on every note value of the transport, like `StepLfoNode`: locked
+  to the grid, paused with the sequencer (the held value freezes).
+  Without a transport it free-runs at 120 BPM. `Rate` doesn't apply.
+*/
+
+/// Tempo used when no transport is available
+const FREE_RUN_BPM: f64 = 120.0;
+
+/// Parameters that can be modulated
+#[derive(Clone, Copy, Debug)]
+pub enum SampleHoldParam {
+    /// Hold clock rate in Hz (free-running mode)
+    Rate,
+}
+
+/// What ticks the hold
+#[derive(Clone, Copy, Debug)]
+enum HoldClock {
+    /// A fixed rate in Hz
+    Free { rate_hz: f32 },
+    /// Every `step_beats` quarter notes of the transport
+    Synced { step_beats: f64 },
+}
+
+/// Sample-and-hold effect on audio (see module docs)
+pub struct SampleHoldNode {
+    clock: HoldClock,
+    /// Position in clock periods; a tick is every whole number
+    position: f64,
+    held: f32,
+}
+
+impl SampleHoldNode {
+    /// Hold the audio `rate_hz` times a second
+    pub fn new(rate_hz: f32) -> Self {
+        Self {
+            clock: HoldClock::Free { rate_hz: rate_hz.max(1.0) },
+            position: 0.0,
+            held: 0.0,
+        }
+    }
+
+    /// Hold the audio once per `step` note value of the transport
+    pub fn synced(step: Duration) -> Self {
+        Self {
+            clock: HoldClock::Synced {
+                step_beats: (4.0 * step.numerator as f64 / step.denominator.max(1) as f64).max(1e-6),
+            },
+            position: 0.0,
+            held: 0.0,
+        }
+    }
+}
+
+impl GraphNode for SampleHoldNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        let (start, periods_per_sample) = match self.clock {
+            HoldClock::Free { rate_hz } => (self.position, (rate_hz.min(ctx.sample_rate) / ctx.sample_rate) as f64),
+            HoldClock::Synced { step_beats } => match ctx.transport {
+                Some(transport) if !transport.playing => (transport.beat() / step_beats, 0.0),
+                Some(transport) => (
+                    transport.beat() / step_beats,
+                    1.0 / (step_beats * transport.samples_per_beat(ctx.sample_rate)),
+                ),
+                None => {
+                    let samples_per_beat = ctx.sample_rate as f64 * 60.0 / FREE_RUN_BPM;
+                    (self.position, 1.0 / (step_beats * samples_per_beat))
+                }
+            },
+        };
+        // The first sample of a block can land on a tick the last one ended before
+        let mut tick = if start.fract() == 0.0 { start - 1.0 } else { start.floor() };
+
+        for (i, sample) in out.iter_mut().enumerate() {
+            // Multiplied rather than accumulated, so ticks land on the exact frame
+            let position = start + i as f64 * periods_per_sample;
+            if position.floor() > tick {
+                tick = position.floor();
+                self.held = *sample;
+            }
+            *sample = self.held;
+        }
+        let end = start + out.len() as f64 * periods_per_sample;
+        // Free-running keeps only the phase (avoids precision loss over long runs)
+        self.position = match self.clock {
+            HoldClock::Free { .. } => end - tick,
+            HoldClock::Synced { .. } => end,
+        };
+    }
+}
+
+impl Modulatable for SampleHoldNode {
+    type Param = SampleHoldParam;
+
+    fn get_param(&self, param: Self::Param) -> f32 {
+        match (param, self.clock) {
+            (SampleHoldParam::Rate, HoldClock::Free { rate_hz }) => rate_hz,
+            (SampleHoldParam::Rate, HoldClock::Synced { .. }) => 0.0,
+        }
+    }
+
+    fn apply_modulation(&mut self, param: Self::Param, base: f32, modulation: f32) {
+        match (param, &mut self.clock) {
+            (SampleHoldParam::Rate, HoldClock::Free { rate_hz }) => *rate_hz = (base + modulation).max(1.0),
+            (SampleHoldParam::Rate, HoldClock::Synced { .. }) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Transport;
+
+    fn ramp(len: usize) -> Vec<f32> {
+        (0..len).map(|i| i as f32).collect()
+    }
+
+    #[test]
+    fn free_clock_holds_every_period() {
+        let ctx = RenderCtx::from_freq(48_000.0, 440.0, 100.0);
+        let mut node = SampleHoldNode::new(1_000.0);
+        // Two blocks, so the hold carries across the boundary
+        let mut out = ramp(256);
+        node.render_block(&mut out[..100], &ctx);
+        node.render_block(&mut out[100..], &ctx);
+
+        // 48 samples per tick: a staircase of the input's values
+        for (i, &value) in out.iter().enumerate() {
+            assert_eq!(value, (i / 48 * 48) as f32, "sample {i}");
+        }
+    }
+
+    #[test]
+    fn synced_clock_ticks_on_the_grid() {
+        let sample_rate = 48_000.0;
+        // 120 BPM: a 16th is 6 000 samples; start half-way into one
+        let transport = Transport {
+            bpm: 120.0,
+            ppq: 480,
+            tick: 60.0,
+            bar_ticks: 1920,
+            playing: true,
+        };
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 100.0).with_transport(transport);
+        let mut node = SampleHoldNode::synced(Duration::SIXTEENTH);
+        let mut out = ramp(12_000);
+        node.render_block(&mut out, &ctx);
+
+        // Holds what came in first, then the input 3 000 samples in (the next 16th)
+        assert!(out[..3_000].iter().all(|&v| v == 0.0));
+        assert!(out[3_000..9_000].iter().all(|&v| v == 3_000.0));
+        assert!(out[9_000..].iter().all(|&v| v == 9_000.0));
+    }
+}
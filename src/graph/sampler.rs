@@ -0,0 +1,155 @@
+use crate::{
+    dsp::stretch::{GrainStretcher, DEFAULT_GRAIN_SECS},
+    graph::node::{GraphNode, RenderCtx},
+};
+
+/*
+Sampler
+=======
+
+Plays back a recording - a drum hit, a vocal chop, a whole drum loop -
+instead of synthesizing a waveform. Each note-on restarts the sample from
+the top.
+
+One-Shot vs Loop
+----------------
+
+  SamplerNode::new(..)            One-shot: plays to the end, ignores
+                                  note-off. Drum hits.
+  SamplerNode::new(..).looped()   Loops while the note is held, stops on
+                                  note-off. Sustained loops and textures.
+
+
+Tempo Sync
+----------
+
+A drum loop recorded at 100 BPM sounds wrong in a 120 BPM session: its
+bar lasts 2.4 s while the session's lasts 2.0 s. `tempo_sync` time-stretches
+playback by session_bpm / loop_bpm WITHOUT changing pitch (see
+`dsp/stretch.rs`), so the loop lines up with the sequencer:
+
+  // A 100 BPM breakbeat, retriggered every bar at 120 BPM
+  let breaks = SamplerNode::new(samples, 44_100.0).tempo_sync(100.0, 120.0);
+  Saavy::new()
+      .bpm(120.0)
+      .track("breaks", pattern!(4/4 => [C4]), breaks)
+
+Stretching is limited to 0.5×-2×; beyond that the grains become audible.
+Retriggering the loop every bar keeps it locked to the grid even if the
+recorded tempo is slightly off.
+*/
+
+/// Stretch factors `tempo_sync` and `with_speed` are clamped to
+const SPEED_RANGE: (f32, f32) = (0.5, 2.0);
+
+/// Plays a recorded sample, optionally time-stretched to the session tempo
+pub struct SamplerNode {
+    sample: Vec<f32>,
+    /// Sample rate the recording was made at
+    source_rate: f32,
+    stretcher: GrainStretcher,
+    looping: bool,
+    playing: bool,
+}
+
+impl SamplerNode {
+    /// One-shot sampler for mono `sample` recorded at `sample_rate`
+    pub fn new(sample: Vec<f32>, sample_rate: f32) -> Self {
+        Self {
+            sample,
+            source_rate: sample_rate,
+            stretcher: GrainStretcher::new((DEFAULT_GRAIN_SECS * 48_000.0) as usize),
+            looping: false,
+            playing: false,
+        }
+    }
+
+    /// Loop while the note is held instead of playing once
+    pub fn looped(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+
+    /// Stretch a loop recorded at `loop_bpm` to play in time at `session_bpm`
+    ///
+    /// Pitch is unchanged. The ratio is clamped to 0.5×-2×.
+    pub fn tempo_sync(self, loop_bpm: f32, session_bpm: f32) -> Self {
+        self.with_speed(session_bpm / loop_bpm)
+    }
+
+    /// Play `speed` times faster (or slower) without changing pitch
+    ///
+    /// Clamped to 0.5×-2×.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.set_speed(speed);
+        self
+    }
+
+    /// Change the stretch factor while playing (clamped to 0.5×-2×)
+    pub fn set_speed(&mut self, speed: f32) {
+        self.stretcher.set_speed(speed.clamp(SPEED_RANGE.0, SPEED_RANGE.1));
+    }
+}
+
+impl GraphNode for SamplerNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        if !self.playing {
+            out.fill(0.0);
+            return;
+        }
+
+        self.stretcher.set_rate(self.source_rate, ctx.sample_rate);
+        self.stretcher.render(&self.sample, out, self.looping);
+
+        if !self.looping && self.stretcher.position() >= self.sample.len() as f64 {
+            self.playing = false;
+        }
+    }
+
+    fn prepare(&mut self, sample_rate: f32, _max_block: usize) {
+        // Grain length in output frames depends on the device rate (allocates)
+        let speed = self.stretcher.speed();
+        self.stretcher = GrainStretcher::new((DEFAULT_GRAIN_SECS * sample_rate) as usize);
+        self.stretcher.set_speed(speed);
+    }
+
+    fn note_on(&mut self, _ctx: &RenderCtx) {
+        self.stretcher.reset();
+        self.playing = true;
+    }
+
+    fn note_off(&mut self, _ctx: &RenderCtx) {
+        // One-shots ring out; loops stop when the key is released
+        if self.looping {
+            self.playing = false;
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.playing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tempo_sync_shortens_one_shot_playback() {
+        let ctx = RenderCtx::from_freq(48_000.0, 440.0, 1.0);
+        // One bar at 100 BPM (2.4 s) of non-silent audio
+        let bar = vec![0.5; 115_200];
+        let mut sampler = SamplerNode::new(bar, 48_000.0).tempo_sync(100.0, 120.0);
+        sampler.prepare(48_000.0, 512);
+        sampler.note_on(&ctx);
+
+        // At 120 BPM the bar should last 2.0 s
+        let mut frames = 0;
+        let mut block = [0.0; 480];
+        while sampler.is_active() {
+            sampler.render_block(&mut block, &ctx);
+            frames += block.len();
+        }
+        assert_eq!(frames, 96_000);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::dsp::slew::SlewLimiter;
+use crate::graph::node::{GraphNode, Modulatable, RenderCtx};
+
+/*
+Slew Node
+=========
+
+Limits how fast whatever runs through it may rise and fall (see
+`dsp/slew.rs`). The same node works on control signals and on audio:
+
+  // Smooth a square LFO into a ramped, "bouncy" wobble
+  let wobble = LfoNode::square(2.0).slew(0.05, 0.05);
+  let bass = FilterNode::lowpass(800.0).modulate(wobble, FilterParam::Cutoff, 600.0);
+
+  // Stepped random that glides up fast and drifts down slowly
+  let drift = RandomNode::new(0.25, 0.0).slew(0.02, 0.3);
+
+  // Lo-fi grit on audio: loud highs get turned into triangles
+  let grit = voices::lead().slew(0.0005, 0.0005);
+
+
+Parameters
+----------
+
+Rise / Fall (seconds per full-scale change, 0 = unlimited):
+  Time to move by 1.0 upward / downward.
+  Control signals: 10 ms - 1 s. Audio: 0.1 - 2 ms.
+*/
+
+/// Parameters that can be modulated
+#[derive(Clone, Copy, Debug)]
+pub enum SlewParam {
+    /// Seconds to rise by 1.0
+    Rise,
+    /// Seconds to fall by 1.0
+    Fall,
+}
+
+/// Rise/fall rate limiter for control or audio signals (see module docs)
+pub struct SlewNode {
+    slew: SlewLimiter,
+    rise_secs: f32,
+    fall_secs: f32,
+    sample_rate: f32,
+}
+
+impl SlewNode {
+    /// Limit rises to `rise_secs` and falls to `fall_secs` per unit change
+    pub fn new(rise_secs: f32, fall_secs: f32) -> Self {
+        let (rise_secs, fall_secs) = (rise_secs.max(0.0), fall_secs.max(0.0));
+        Self {
+            slew: SlewLimiter::new(rise_secs, fall_secs, 48_000.0),
+            rise_secs,
+            fall_secs,
+            sample_rate: 48_000.0,
+        }
+    }
+}
+
+impl GraphNode for SlewNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        if ctx.sample_rate != self.sample_rate {
+            self.prepare(ctx.sample_rate, out.len());
+        }
+        self.slew.render(out);
+    }
+
+    fn prepare(&mut self, sample_rate: f32, _max_block: usize) {
+        self.sample_rate = sample_rate;
+        self.slew.set_times(self.rise_secs, self.fall_secs, sample_rate);
+    }
+}
+
+impl Modulatable for SlewNode {
+    type Param = SlewParam;
+
+    fn get_param(&self, param: Self::Param) -> f32 {
+        match param {
+            SlewParam::Rise => self.rise_secs,
+            SlewParam::Fall => self.fall_secs,
+        }
+    }
+
+    fn apply_modulation(&mut self, param: Self::Param, base: f32, modulation: f32) {
+        match param {
+            SlewParam::Rise => self.rise_secs = (base + modulation).max(0.0),
+            SlewParam::Fall => self.fall_secs = (base + modulation).max(0.0),
+        }
+        self.slew.set_times(self.rise_secs, self.fall_secs, self.sample_rate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::extensions::NodeExt;
+    use crate::graph::lfo::LfoNode;
+
+    #[test]
+    fn square_lfo_becomes_a_ramped_wave() {
+        let sample_rate = 48_000.0;
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 100.0);
+        // 1 Hz square, 100 ms per unit: -1 → 1 takes 200 ms
+        let mut node = LfoNode::square(1.0).slew(0.1, 0.1);
+        let mut out = vec![0.0; 48_000];
+        node.render_block(&mut out, &ctx);
+
+        let steepest = out.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0f32, f32::max);
+        assert!(steepest <= 1.0 / 4_800.0 + 1e-6, "step {steepest}");
+        assert!(out.iter().any(|&s| s > 0.999) && out.iter().any(|&s| s < -0.999), "still reaches both levels");
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{
+    dsp::{envelope::EnvelopeState, rng::Rng, tuning::hz_to_midi},
+    graph::node::{GraphNode, NodeCommand, RenderCtx},
+    MAX_BLOCK_SIZE,
+};
+
+/*
+Split Node
+==========
+
+A keyboard split: notes below the split point play one voice graph, the
+rest play another. One track can then perform a bass line in the left hand
+and a lead in the right without routing notes to separate tracks.
+
+  // Bass below C3, lead from C3 up
+  let keys = voices::bass().split(C3, voices::lead());
+
+       note < C3          note >= C3
+     ┌───────────┐      ┌───────────┐
+     │   lower   │      │   upper   │
+     └─────┬─────┘      └─────┬─────┘
+           └──────── + ───────┘
+                     ↓
+                   output
+
+
+Which Side Plays
+----------------
+
+The note comes from the note-on frequency (rounded to the nearest MIDI
+note), so slides and pitch changes after the note-on never switch sides.
+Each note-on goes to one side only. When the hand crosses the split, the
+side that was playing gets a note-off and rings out its release while the
+new side starts, so tails are never cut. The released side keeps
+rendering at its own note-on pitch, not the new note's.
+
+Envelope Gotcha
+---------------
+
+A side keeps rendering until it reports `is_active() == false`, which
+normally means its envelope finished. Give each side its own envelope:
+
+  ✓ bass().split(C3, lead())                               // both enveloped
+  ✗ OscNode::sine().split(C3, lead()).amplify(env)         // env shared by both sides
+*/
+
+/// Which side of the split a note plays on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    Lower,
+    Upper,
+}
+
+/// Plays `lower` below `split_note` and `upper` from it up
+pub struct Split<L, U> {
+    /// Voice for notes below the split
+    pub lower: L,
+    /// Voice for notes at or above the split
+    pub upper: U,
+    /// First MIDI note played by `upper`
+    pub split_note: u8,
+    /// Side of the most recent note-on
+    current: Option<Side>,
+    /// Sides that got a note-on and haven't finished sounding
+    sounding: [bool; 2],
+    /// Note-on frequency of each side, for rendering a released tail
+    frequencies: [f32; 2],
+    /// Pre-allocated buffer for the second side
+    buffer: Vec<f32>,
+}
+
+impl<L, U> Split<L, U> {
+    pub fn new(lower: L, split_note: u8, upper: U) -> Self {
+        Self {
+            lower,
+            upper,
+            split_note,
+            current: None,
+            sounding: [false; 2],
+            frequencies: [0.0; 2],
+            buffer: vec![0.0; MAX_BLOCK_SIZE],
+        }
+    }
+
+    fn side_for(&self, frequency: f32) -> Side {
+        let note = hz_to_midi(frequency);
+        if note.round() < self.split_note as f32 {
+            Side::Lower
+        } else {
+            Side::Upper
+        }
+    }
+}
+
+impl<L: GraphNode, U: GraphNode> Split<L, U> {
+    /// Context for rendering `side`: the block's own for the playing side,
+    /// the side's note-on pitch for a released one
+    fn side_ctx(&self, side: Side, ctx: &RenderCtx) -> RenderCtx {
+        let frequency = if self.current == Some(side) {
+            ctx.frequency
+        } else {
+            self.frequencies[side as usize]
+        };
+        RenderCtx { frequency, ..*ctx }
+    }
+
+    fn note_off_side(&mut self, side: Side, ctx: &RenderCtx) {
+        match side {
+            Side::Lower => self.lower.note_off(ctx),
+            Side::Upper => self.upper.note_off(ctx),
+        }
+    }
+}
+
+impl<L: GraphNode, U: GraphNode> GraphNode for Split<L, U> {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        out.fill(0.0);
+        let [lower_sounding, upper_sounding] = self.sounding;
+
+        if lower_sounding {
+            let lower_ctx = self.side_ctx(Side::Lower, ctx);
+            self.lower.render_block(out, &lower_ctx);
+            self.sounding[0] = self.lower.is_active();
+        }
+        if upper_sounding {
+            let upper_ctx = self.side_ctx(Side::Upper, ctx);
+            let upper_out = &mut self.buffer[..out.len()];
+            upper_out.fill(0.0);
+            self.upper.render_block(upper_out, &upper_ctx);
+            for (sample, &upper) in out.iter_mut().zip(upper_out.iter()) {
+                *sample += upper;
+            }
+            self.sounding[1] = self.upper.is_active();
+        }
+    }
+
+    fn prepare(&mut self, sample_rate: f32, max_block: usize) {
+        self.buffer.resize(max_block, 0.0);
+        self.lower.prepare(sample_rate, max_block);
+        self.upper.prepare(sample_rate, max_block);
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.lower.seed(Rng::derive(seed, 0));
+        self.upper.seed(Rng::derive(seed, 1));
+    }
+
+    fn note_on(&mut self, ctx: &RenderCtx) {
+        let side = self.side_for(ctx.frequency);
+        // Crossing the split: release the other side and let it ring out
+        if let Some(previous) = self.current.filter(|&previous| previous != side) {
+            self.note_off_side(previous, ctx);
+        }
+        match side {
+            Side::Lower => self.lower.note_on(ctx),
+            Side::Upper => self.upper.note_on(ctx),
+        }
+        self.sounding[side as usize] = true;
+        self.frequencies[side as usize] = ctx.frequency;
+        self.current = Some(side);
+    }
+
+    fn note_off(&mut self, ctx: &RenderCtx) {
+        if let Some(side) = self.current {
+            self.note_off_side(side, ctx);
+        }
+    }
+
+    fn command(&mut self, command: NodeCommand) {
+        self.lower.command(command);
+        self.upper.command(command);
+    }
+
+    fn is_active(&self) -> bool {
+        self.sounding.iter().any(|&s| s)
+    }
+
+    fn latency_samples(&self) -> usize {
+        self.lower.latency_samples().max(self.upper.latency_samples())
+    }
+
+    fn get_envelope_level(&self) -> Option<f32> {
+        match self.current? {
+            Side::Lower => self.lower.get_envelope_level(),
+            Side::Upper => self.upper.get_envelope_level(),
+        }
+    }
+
+    fn get_envelope_state(&self) -> Option<EnvelopeState> {
+        match self.current? {
+            Side::Lower => self.lower.get_envelope_state(),
+            Side::Upper => self.upper.get_envelope_state(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{envelope::EnvNode, extensions::NodeExt, oscillator::OscNode};
+    use crate::sequencing::notes::*;
+
+    fn enveloped() -> impl GraphNode {
+        OscNode::sine().amplify(EnvNode::adsr(0.001, 0.01, 1.0, 0.01))
+    }
+
+    #[test]
+    fn notes_go_to_their_side_and_tails_ring_out() {
+        let mut keys = enveloped().split(C3, enveloped());
+        let mut out = [0.0; 256];
+
+        let low = RenderCtx::from_note(48_000.0, B2, 100.0);
+        keys.note_on(&low);
+        keys.render_block(&mut out, &low);
+        assert!(keys.lower.is_active());
+        assert_eq!(keys.upper.get_envelope_state(), Some(EnvelopeState::Idle));
+
+        // Crossing the split releases the lower side instead of cutting it
+        let high = RenderCtx::from_note(48_000.0, C3, 100.0);
+        keys.note_on(&high);
+        assert_eq!(keys.lower.get_envelope_state(), Some(EnvelopeState::Release));
+        keys.render_block(&mut out, &high);
+        assert!(out.iter().any(|&s| s.abs() > 0.0));
+
+        // Once the lower tail is done, only the upper side keeps the node active
+        for _ in 0..8 {
+            keys.render_block(&mut out, &high);
+        }
+        assert_eq!(keys.sounding, [false, true]);
+    }
+
+    #[test]
+    fn released_tail_keeps_its_pitch() {
+        let sample_rate = 48_000.0;
+        // Long release so the lower tail is still ringing
+        let mut keys = OscNode::sine()
+            .amplify(EnvNode::adsr(0.001, 0.01, 1.0, 1.0))
+            .split(C3, enveloped());
+        let mut out = vec![0.0; 4_800];
+
+        let low = RenderCtx::from_note(sample_rate, A2, 100.0);
+        keys.note_on(&low);
+        keys.render_block(&mut out[..480], &low);
+        let high = RenderCtx::from_note(sample_rate, A4, 100.0);
+        keys.note_on(&high);
+
+        // Render the lower side alone: still 110 Hz, not 440 Hz
+        keys.sounding[1] = false;
+        for block in out.chunks_mut(480) {
+            keys.render_block(block, &high);
+        }
+        let crossings = out.windows(2).filter(|w| w[0] <= 0.0 && w[1] > 0.0).count();
+        assert!((10..=12).contains(&crossings), "{crossings} cycles in 100 ms");
+    }
+}
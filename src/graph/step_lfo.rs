@@ -0,0 +1,132 @@
+use crate::{
+    dsp::step_lfo::StepShape,
+    graph::node::{GraphNode, RenderCtx},
+    sequencing::Duration,
+};
+
+/*
+Step LFO Node
+=============
+
+A tempo-synced step sequencer for parameters instead of notes. Each step
+lasts one note value and outputs its own level:
+
+  // Rhythmic filter: cutoff jumps every 16th note
+  let steps = StepLfoNode::new(&[1.0, -0.5, 0.2, -1.0], Duration::SIXTEENTH);
+  let pluck = OscNode::sawtooth()
+      .through(FilterNode::lowpass(1200.0).modulate(steps, FilterParam::Cutoff, 800.0));
+
+  // Trance gate: 0/1 steps into .amplify(), slightly glided to avoid clicks
+  let gate = StepLfoNode::new(&[1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 1.0], Duration::SIXTEENTH)
+      .with_glide(0.05);
+  let pad = voices::pad().amplify(gate);
+
+
+Sync
+----
+
+When the runtime renders the node, its position comes straight from the
+transport: step `n` starts exactly on the `n`th note value of the loop,
+pauses with the sequencer, and follows tempo changes. Rendered without a
+transport (offline tests, a bare `GraphNode`), it free-runs at 120 BPM
+from wherever it was.
+
+`.modulate()` reads one value per block (see `dsp/modulate.rs`), so for
+sharp gates prefer `.amplify()`, which follows the shape every sample.
+
+See `dsp/step_lfo.rs` for steps and glide.
+*/
+
+/// Tempo used when no transport is available
+const FREE_RUN_BPM: f64 = 120.0;
+
+/// Tempo-synced step-sequenced LFO (see module docs)
+pub struct StepLfoNode {
+    pub shape: StepShape,
+    /// Length of each step in quarter-note beats
+    step_beats: f64,
+    /// Position in steps, kept for free-running without a transport
+    position: f64,
+}
+
+impl StepLfoNode {
+    /// One step per `step` note value through `values`, with hard steps
+    pub fn new(values: &[f32], step: Duration) -> Self {
+        Self {
+            shape: StepShape::new(values),
+            step_beats: (4.0 * step.numerator as f64 / step.denominator.max(1) as f64).max(1e-6),
+            position: 0.0,
+        }
+    }
+
+    /// Glide into every step over this share of the step (0.0 - 1.0)
+    pub fn with_glide(mut self, glide: f32) -> Self {
+        self.shape.set_glide(glide);
+        self
+    }
+
+    /// Glide into step `index` only
+    pub fn with_step_glide(mut self, index: usize, glide: f32) -> Self {
+        self.shape.set_step_glide(index, glide);
+        self
+    }
+}
+
+impl GraphNode for StepLfoNode {
+    fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+        let (mut position, steps_per_sample) = match ctx.transport {
+            Some(transport) => {
+                let rate = if transport.playing {
+                    1.0 / (self.step_beats * transport.samples_per_beat(ctx.sample_rate))
+                } else {
+                    0.0
+                };
+                (transport.beat() / self.step_beats, rate)
+            }
+            None => {
+                let samples_per_beat = ctx.sample_rate as f64 * 60.0 / FREE_RUN_BPM;
+                (self.position, 1.0 / (self.step_beats * samples_per_beat))
+            }
+        };
+
+        for sample in out.iter_mut() {
+            *sample = self.shape.value_at(position);
+            position += steps_per_sample;
+        }
+        self.position = position;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Transport;
+
+    #[test]
+    fn steps_follow_the_transport() {
+        let sample_rate = 48_000.0;
+        let mut lfo = StepLfoNode::new(&[1.0, 0.0, 0.5, 0.0], Duration::EIGHTH);
+        // 120 BPM: an eighth note is 12 000 samples; start on the second step
+        let transport = Transport {
+            bpm: 120.0,
+            ppq: 480,
+            tick: 240.0,
+            bar_ticks: 1920,
+            playing: true,
+        };
+        let ctx = RenderCtx::from_freq(sample_rate, 440.0, 100.0).with_transport(transport);
+
+        let mut out = vec![0.0; 24_000];
+        lfo.render_block(&mut out, &ctx);
+        assert!(out[..12_000].iter().all(|&v| v == 0.0));
+        assert!(out[12_000..].iter().all(|&v| v == 0.5));
+
+        // Paused: holds the current step
+        let paused = RenderCtx::from_freq(sample_rate, 440.0, 100.0).with_transport(Transport {
+            playing: false,
+            ..transport
+        });
+        lfo.render_block(&mut out, &paused);
+        assert!(out.iter().all(|&v| v == 0.0));
+    }
+}
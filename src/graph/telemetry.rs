@@ -0,0 +1,78 @@
+use crate::dsp::envelope::EnvelopeState;
+use crate::graph::node::GraphNode;
+
+/*
+Voice Telemetry
+===============
+
+One read-only view of what a voice is doing, for UIs, meters and tests:
+
+  level   envelope level, 0.0 - 1.0
+  state   envelope stage (Idle, Attack, Decay, Sustain, Release)
+  note    MIDI note being played, if known
+  peak    peak output of the most recent audio, linear
+
+Every graph node gets it for free from `GraphNode::get_envelope_level` and
+`get_envelope_state`. A bare node never sees the note that triggered it
+and doesn't keep its output, so it reports no note and its envelope level
+as the peak. Tracks know both (see `Track`), and stay accurate while
+frozen: the graph is idle then, so level and peak come from the frozen
+audio itself.
+
+  let level = renderer.tracks()[0].level();
+  let node_level = voices::lead().level();   // same call on a bare voice
+*/
+
+/// What a voice is doing right now (see module docs)
+pub trait Telemetry {
+    /// Envelope level, 0.0 - 1.0
+    fn level(&self) -> f32;
+
+    /// Envelope stage
+    fn state(&self) -> EnvelopeState;
+
+    /// MIDI note being played
+    fn note(&self) -> Option<u8>;
+
+    /// Peak output of the most recent audio (linear)
+    fn peak(&self) -> f32;
+}
+
+impl<N: GraphNode + ?Sized> Telemetry for N {
+    fn level(&self) -> f32 {
+        self.get_envelope_level().unwrap_or(0.0)
+    }
+
+    fn state(&self) -> EnvelopeState {
+        self.get_envelope_state().unwrap_or(EnvelopeState::Idle)
+    }
+
+    fn note(&self) -> Option<u8> {
+        None
+    }
+
+    fn peak(&self) -> f32 {
+        Telemetry::level(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{envelope::EnvNode, extensions::NodeExt, node::RenderCtx, oscillator::OscNode};
+
+    #[test]
+    fn nested_voice_reports_its_envelope() {
+        let mut voice: Box<dyn GraphNode> = Box::new(OscNode::sawtooth().amplify(EnvNode::adsr(0.001, 0.01, 0.5, 0.1)));
+        assert_eq!(voice.state(), EnvelopeState::Idle);
+
+        let ctx = RenderCtx::from_note(48_000.0, 60, 100.0);
+        voice.note_on(&ctx);
+        let mut out = [0.0; 2048];
+        voice.render_block(&mut out, &ctx);
+
+        assert_eq!(voice.state(), EnvelopeState::Sustain);
+        assert!((Telemetry::level(&voice) - 0.5).abs() < 1e-3);
+        assert_eq!(voice.note(), None);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::graph::node::{GraphNode, RenderCtx};
+use crate::dsp::envelope::EnvelopeState;
+use crate::dsp::rng::Rng;
+use crate::graph::node::{GraphNode, NodeCommand, RenderCtx};
 
 /*
 Through Node
@@ -85,6 +87,16 @@ impl<S: GraphNode, E: GraphNode> GraphNode for Through<S, E> {
         self.effect.render_block(out, ctx);
     }
 
+    fn prepare(&mut self, sample_rate: f32, max_block: usize) {
+        self.source.prepare(sample_rate, max_block);
+        self.effect.prepare(sample_rate, max_block);
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.source.seed(Rng::derive(seed, 0));
+        self.effect.seed(Rng::derive(seed, 1));
+    }
+
     fn note_on(&mut self, ctx: &RenderCtx) {
         self.source.note_on(ctx);
         self.effect.note_on(ctx);
@@ -95,13 +107,26 @@ impl<S: GraphNode, E: GraphNode> GraphNode for Through<S, E> {
         self.effect.note_off(ctx);
     }
 
+    fn command(&mut self, command: NodeCommand) {
+        self.source.command(command);
+        self.effect.command(command);
+    }
+
     fn is_active(&self) -> bool {
         self.source.is_active() || self.effect.is_active()
     }
 
+    fn latency_samples(&self) -> usize {
+        self.source.latency_samples() + self.effect.latency_samples()
+    }
+
     fn get_envelope_level(&self) -> Option<f32> {
         self.source.get_envelope_level()
     }
+
+    fn get_envelope_state(&self) -> Option<EnvelopeState> {
+        self.source.get_envelope_state()
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 pub mod dsp;
 pub mod graph; // Composable audio graph nodes
+#[cfg(feature = "rt-check")]
+pub mod rt_check; // Allocation detector for realtime-safety tests
 pub mod runtime; // TUI application runtime
 pub mod sequencing; // Musical timing and patterns
 pub mod voices; // Pre-built voices (kick, snare, bass, lead)
@@ -7,3 +9,7 @@ pub mod voices; // Pre-built voices (kick, snare, bass, lead)
 pub const MAX_BLOCK_SIZE: usize = 2048;
 pub const MAX_DELAY_SAMPLES: usize = 192_000; // ~2 seconds at 96kHz, ~4 seconds at 48kHz
 pub(crate) const MIN_TIME: f32 = 1.0 / 48_000.0;
+
+#[cfg(all(test, feature = "rt-check"))]
+#[global_allocator]
+static ALLOC: rt_check::CountingAllocator = rt_check::CountingAllocator;
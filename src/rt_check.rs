@@ -0,0 +1,123 @@
+//! Realtime-safety checks: an allocation-counting global allocator.
+//!
+//! The audio callback must never allocate - `malloc` can take a lock or page
+//! in memory and blow the block deadline. Comments across the crate promise
+//! "REAL-TIME SAFE: No allocations"; this module turns that promise into a
+//! test failure.
+//!
+//! Enabled by the `rt-check` feature. Install the allocator in a test or
+//! binary crate, then wrap the code under test:
+//!
+//! ```ignore
+//! use saavy_dsp::rt_check::{assert_no_alloc, CountingAllocator};
+//!
+//! #[global_allocator]
+//! static ALLOC: CountingAllocator = CountingAllocator;
+//!
+//! let mut voice = voices::lead();
+//! assert_no_alloc(|| voice.render_block(&mut buffer, &ctx));
+//! ```
+//!
+//! Counting is per thread, so allocations on other threads (test harness,
+//! UI) never trip the check. The crate's own test build installs the
+//! allocator automatically when the feature is on.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    // Const-initialized so touching it never allocates
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Global allocator that counts allocations and frees on the current thread
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        bump();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        bump();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        bump();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        bump();
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[inline]
+fn bump() {
+    // try_with: the slot may already be gone during thread teardown
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+/// Allocations and frees seen on this thread so far
+pub fn allocation_count() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// Run `f` and panic if it allocated or freed memory on this thread
+///
+/// Only meaningful when `CountingAllocator` is the global allocator.
+pub fn assert_no_alloc<R>(f: impl FnOnce() -> R) -> R {
+    let before = allocation_count();
+    let result = f();
+    let allocations = allocation_count() - before;
+    assert!(allocations == 0, "{} allocation(s) in realtime code", allocations);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{GraphNode, RenderCtx};
+    use crate::voices;
+
+    #[test]
+    #[should_panic(expected = "allocation(s) in realtime code")]
+    fn detects_allocation() {
+        assert_no_alloc(|| std::hint::black_box(vec![0.0f32; 64]));
+    }
+
+    #[test]
+    fn voices_render_without_allocating() {
+        let all: Vec<Box<dyn GraphNode>> = vec![
+            Box::new(voices::kick()),
+            Box::new(voices::snare()),
+            Box::new(voices::hihat()),
+            Box::new(voices::openhat()),
+            Box::new(voices::clap()),
+            Box::new(voices::tom()),
+            Box::new(voices::crash()),
+            Box::new(voices::ride()),
+            Box::new(voices::bass()),
+            Box::new(voices::lead()),
+            Box::new(voices::pad()),
+            Box::new(voices::pluck()),
+        ];
+        let ctx = RenderCtx::from_note(48_000.0, 60, 100.0);
+        let mut buffer = vec![0.0; 512];
+
+        for mut voice in all {
+            voice.prepare(48_000.0, buffer.len());
+            assert_no_alloc(|| {
+                voice.note_on(&ctx);
+                for _ in 0..8 {
+                    voice.render_block(&mut buffer, &ctx);
+                }
+                voice.note_off(&ctx);
+                voice.render_block(&mut buffer, &ctx);
+            });
+        }
+    }
+}
@@ -4,29 +4,113 @@ use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use color_eyre::eyre::{eyre, Result as EyreResult, WrapErr};
 use rtrb::RingBuffer;
 use std::sync::{Arc, Mutex};
-
-use super::sequencer::Sequencer;
-use super::track::Track;
-use super::ui::{ControlMessage, TrackDynamicState, TrackStaticInfo, UiApp, UiStateInit, UiStateUpdate};
+use std::time::{Duration, Instant};
+
+use super::clock::{clock_bus, ClockEvent};
+use super::edits::{sequence_bus, SequenceReceiver};
+use super::monitor::CallbackMonitor;
+use super::params::{param_bus, ParamReceiver};
+use super::renderer::{OutputStage, Renderer};
+use super::tap::{audio_tap, TapWriter};
+use super::track::{LoopTail, Track};
+use super::ui::{
+    ControlMessage, TrackDynamicState, TrackStaticInfo, UiApp, UiStateInit, UiStateUpdate, MAX_UI_TRACKS,
+};
 
 use crate::{
+    dsp::{
+        denormal::DenormalGuard,
+        limiter::{LookaheadLimiter, DEFAULT_LOOKAHEAD_SECS, DEFAULT_RELEASE_SECS},
+        rng::DEFAULT_SEED,
+    },
     graph::GraphNode,
-    sequencing::{Pattern, PatternChain, Sequence},
+    sequencing::{self, Key, Pattern, PatternChain, Sequence},
     MAX_BLOCK_SIZE,
 };
 
 /// Ring buffer capacity for audio samples (enough for ~340ms at 48kHz)
 const AUDIO_RING_SIZE: usize = 16384;
+/// Ring buffer capacity for bar/beat events (a few seconds even at high tempos)
+const CLOCK_RING_SIZE: usize = 64;
+/// How often the clock thread checks for bar/beat events
+const CLOCK_POLL: Duration = Duration::from_millis(1);
 /// Ring buffer capacity for UI state updates
 const STATE_RING_SIZE: usize = 32;
 /// Ring buffer capacity for control messages
 const CONTROL_RING_SIZE: usize = 64;
+/// Ring buffer capacity for parameter changes
+const PARAM_RING_SIZE: usize = 256;
+/// Ring buffer capacity for step-edited sequences (each way)
+const SEQUENCE_RING_SIZE: usize = 32;
+
+/// Problems `Saavy::validate` finds in an arrangement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// No tracks were added
+    NoTracks,
+    /// Two tracks share a name (the UI and MIDI export can't tell them apart)
+    DuplicateTrackName(String),
+    /// A track's pattern contains no notes
+    EmptySequence(String),
+    /// More tracks than the UI can display
+    TooManyTracks { count: usize, max: usize },
+    /// `freeze`, `unpitched`, `loop_tail`, `legato`, `duck` or `output` named a track that doesn't exist
+    UnknownTrack(String),
+    /// A track's sequence was built at a different PPQ than the arrangement
+    PpqMismatch { track: String, ppq: u32, expected: u32 },
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::NoTracks => write!(f, "No tracks: add at least one with .track(name, pattern, voice)"),
+            ConfigError::DuplicateTrackName(name) => write!(f, "Duplicate track name \"{}\": track names must be unique", name),
+            ConfigError::EmptySequence(name) => write!(f, "Track \"{}\" has no notes: its pattern is empty or all rests", name),
+            ConfigError::TooManyTracks { count, max } => {
+                write!(f, "Too many tracks: {} given, the UI shows at most {}", count, max)
+            }
+            ConfigError::UnknownTrack(name) => write!(f, "Unknown track \"{}\": no track has that name", name),
+            ConfigError::PpqMismatch { track, ppq, expected } => write!(
+                f,
+                "Track \"{}\" uses {} PPQ but the arrangement uses {}: call .ppq() before adding tracks",
+                track, ppq, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
 
 /// Main application builder
 pub struct Saavy {
     bpm: f64,
     ppq: u32,
+    block_size: usize,
+    seed: u64,
+    output_stage: OutputStage,
+    /// Ceiling of the lookahead limiter after the output stage, if any
+    limiter: Option<f32>,
     tracks: Vec<Track>,
+    /// Names of tracks to pre-render before playback
+    frozen: Vec<String>,
+    /// Per-track loop-point handling, applied when the renderer is built
+    loop_tails: Vec<(String, LoopTail)>,
+    /// Mono-legato tracks and their glide times: (track, glide s)
+    legatos: Vec<(String, f32)>,
+    /// Ducking routes: (trigger, target, amount dB, attack s, release s)
+    ducks: Vec<(String, String, f32, f32, f32)>,
+    /// Tracks sent to their own device channel pair: (track, pair)
+    outputs: Vec<(String, usize)>,
+    /// Key every track is moved into (patterns are written in C)
+    key: Option<Key>,
+    /// Tracks `key` leaves alone
+    unpitched: Vec<String>,
+    /// Global groove: share of each `swing_base` pair given to the first note
+    swing: f32,
+    swing_base: sequencing::Duration,
+    monitor: Arc<CallbackMonitor>,
+    /// Called on the clock thread for every bar and beat during `run`
+    clock_handlers: Vec<Box<dyn FnMut(ClockEvent) + Send>>,
 }
 
 impl Saavy {
@@ -35,7 +119,22 @@ impl Saavy {
         Self {
             bpm: 120.0,
             ppq: 480,
+            block_size: MAX_BLOCK_SIZE,
+            seed: DEFAULT_SEED,
+            output_stage: OutputStage::Off,
+            limiter: None,
             tracks: Vec::new(),
+            frozen: Vec::new(),
+            loop_tails: Vec::new(),
+            legatos: Vec::new(),
+            ducks: Vec::new(),
+            outputs: Vec::new(),
+            key: None,
+            unpitched: Vec::new(),
+            swing: 0.5,
+            swing_base: sequencing::Duration::EIGHTH,
+            monitor: Arc::new(CallbackMonitor::new()),
+            clock_handlers: Vec::new(),
         }
     }
 
@@ -45,6 +144,104 @@ impl Saavy {
         self
     }
 
+    /// Set the tick resolution in pulses per quarter note (default 480)
+    ///
+    /// Patterns are converted to ticks as tracks are added, so call this
+    /// first. Higher values place odd tuplets (5s, 7s) and nested
+    /// subdivisions closer to their exact positions; 960 divides evenly by
+    /// 2, 3, 4, 5, 6 and 8 per beat.
+    pub fn ppq(mut self, ppq: u32) -> Self {
+        self.ppq = ppq.max(1);
+        self
+    }
+
+    /// Play the whole arrangement in `key`
+    ///
+    /// Write patterns with C as the tonic; every note is transposed to the
+    /// key's root and snapped to its scale (see `sequencing::key`). Tracks
+    /// marked `unpitched` (drums) are left alone.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // The same riff, now in A minor
+    /// Saavy::new().key(Key::Am).track("lead", pattern!(4/4 => [C4, E4, G4, C5]), voices::lead())
+    /// ```
+    pub fn key(mut self, key: Key) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Leave a track out of `key` (drums, one-shots, anything unpitched)
+    pub fn unpitched(mut self, name: &str) -> Self {
+        self.unpitched.push(name.to_string());
+        self
+    }
+
+    /// Swing every track: delay each off-beat 8th (see `swing_base`)
+    ///
+    /// `amount` is the share of each pair taken by the first note: 0.5 is
+    /// straight (the default), 0.57 a light groove, ~0.67 a triplet shuffle,
+    /// 0.75 the maximum. Applied through `offset_ticks`, so patterns stay on
+    /// the straight grid.
+    pub fn swing(mut self, amount: f32) -> Self {
+        self.swing = amount;
+        self
+    }
+
+    /// Note value that `swing` pairs up (default `Duration::EIGHTH`)
+    ///
+    /// Use `Duration::SIXTEENTH` for 16th-note swing.
+    pub fn swing_base(mut self, base: sequencing::Duration) -> Self {
+        self.swing_base = base;
+        self
+    }
+
+    /// Set the maximum block size rendered per graph call
+    ///
+    /// Device buffers larger than this are split into several blocks.
+    /// Smaller blocks tighten modulation and parameter-update granularity
+    /// at the cost of more per-block overhead. Defaults to `MAX_BLOCK_SIZE`.
+    pub fn block_size(mut self, frames: usize) -> Self {
+        self.block_size = frames.max(1);
+        self
+    }
+
+    /// Set the master seed for all randomness (noise, humanization)
+    ///
+    /// Every track derives its own stream from this seed, so the same seed
+    /// always renders the same audio. Defaults to `dsp::rng::DEFAULT_SEED`.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the safety stage on the summed output (clamp, soft clip, 1/√n scaling)
+    ///
+    /// Off by default. Many tracks playing at once can exceed ±1.0; pick
+    /// a stage to keep the device or WAV file from clipping digitally.
+    pub fn output_stage(mut self, stage: OutputStage) -> Self {
+        self.output_stage = stage;
+        self
+    }
+
+    /// Brick-wall limit the output to `ceiling_db` true peak
+    ///
+    /// A lookahead limiter (see `dsp::limiter`) after the output stage:
+    /// live, on the master mix and each output pair, 3 ms late; offline
+    /// renders are limited as a whole and shifted back into time, so a
+    /// bounce never exceeds the ceiling, even between samples.
+    pub fn limiter(mut self, ceiling_db: f32) -> Self {
+        self.limiter = Some(ceiling_db.min(0.0));
+        self
+    }
+
+    /// Shared callback monitor (overrun counts, deadline load)
+    ///
+    /// Clone before `run` to poll xrun statistics from another thread.
+    pub fn callback_monitor(&self) -> Arc<CallbackMonitor> {
+        self.monitor.clone()
+    }
+
     /// Add a track with a pattern and audio node
     ///
     /// Each track is monophonic (one voice). For polyphony, create multiple tracks.
@@ -59,8 +256,289 @@ impl Saavy {
         self
     }
 
+    /// Add a track that plays `fill` in place of the last bar(s) of every
+    /// `every_n_bars` bars of `pattern`
+    ///
+    /// The runtime loops the whole cycle, so a one-bar groove with a one-bar
+    /// fill every 4 bars plays groove, groove, groove, fill - no chain math.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Saavy::new().track_with_variations(
+    ///     "drums",
+    ///     pattern!(4/4 => [C2, _, C2, _]),
+    ///     pattern!(4/4 => [C2, [C2, C2], [C2, C2, C2], C2]),
+    ///     4,
+    ///     voices::kick(),
+    /// )
+    /// ```
+    pub fn track_with_variations<N: GraphNode + 'static>(
+        mut self,
+        name: &str,
+        pattern: impl IntoSequence,
+        fill: impl IntoSequence,
+        every_n_bars: u32,
+        node: N,
+    ) -> Self {
+        let fill = fill.into_sequence(self.ppq);
+        let sequence = pattern.into_sequence(self.ppq).with_fill(&fill, every_n_bars);
+        self.tracks.push(Track::new(name, sequence, node));
+        self
+    }
+
+    /// Freeze a track: pre-render its loop and play back the audio
+    ///
+    /// The track's graph runs offline once, before playback starts, and the
+    /// audio callback then only copies samples - useful for expensive voices.
+    /// Frozen tracks ignore their note events; the pattern must loop as-is.
+    pub fn freeze(mut self, name: &str) -> Self {
+        self.frozen.push(name.to_string());
+        self
+    }
+
+    /// Duck `target` by `amount_db` every time `trigger` plays a note
+    ///
+    /// The classic kick-pumps-the-bass effect, driven by the sequencer's
+    /// note events instead of a sidechain compressor. The dip reaches full
+    /// depth after `attack_secs` and recovers over `release_secs`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Bass drops 9 dB on every kick and swells back over 150 ms
+    /// Saavy::new()
+    ///     .track("kick", kicks, voices::kick())
+    ///     .track("bass", bassline, voices::bass())
+    ///     .duck("kick", "bass", 9.0, 0.005, 0.15)
+    /// ```
+    pub fn duck(mut self, trigger: &str, target: &str, amount_db: f32, attack_secs: f32, release_secs: f32) -> Self {
+        self.ducks
+            .push((trigger.to_string(), target.to_string(), amount_db, attack_secs, release_secs));
+        self
+    }
+
+    /// Choose what a track does with notes sounding when the pattern loops
+    ///
+    /// By default (`LoopTail::Release`) they get a note-off at the loop
+    /// point. `Carry` holds them into the next pass; `Fade` releases them
+    /// and fades the track out.
+    pub fn loop_tail(mut self, name: &str, loop_tail: LoopTail) -> Self {
+        self.loop_tails.push((name.to_string(), loop_tail));
+        self
+    }
+
+    /// Play a track mono-legato: overlapping or touching notes glide over
+    /// `glide_secs` instead of retriggering (see `Track::set_legato`)
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Tied notes slur like a programmed bassline; leave gaps to re-pluck
+    /// Saavy::new()
+    ///     .track("bass", bassline, voices::bass())
+    ///     .legato("bass", 0.06)
+    /// ```
+    pub fn legato(mut self, name: &str, glide_secs: f32) -> Self {
+        self.legatos.push((name.to_string(), glide_secs));
+        self
+    }
+
+    /// Send a track to device channel pair `pair` instead of the master mix
+    ///
+    /// Pair `n` is output channels `2n` and `2n + 1`, so pair 0 is the
+    /// first stereo output. Those channels then carry only the tracks routed
+    /// to them; every other channel still gets the master mix. `run` fails
+    /// if the device has too few channels for a pair.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Drums on outputs 3/4 of an audio interface, everything else on 1/2
+    /// Saavy::new()
+    ///     .track("kick", kicks, voices::kick())
+    ///     .track("bass", bassline, voices::bass())
+    ///     .output("kick", 1)
+    /// ```
+    pub fn output(mut self, name: &str, pair: usize) -> Self {
+        self.outputs.push((name.to_string(), pair));
+        self
+    }
+
+    /// Call `handler` at every bar and beat boundary while `run` plays
+    ///
+    /// Handlers run in order on a separate thread (never the audio thread),
+    /// within about a millisecond of the audio callback rendering the
+    /// boundary. `ClockEvent::at` is when it will be heard, so a handler
+    /// driving lights can wait until then to stay in sync with the speakers.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Saavy::new()
+    ///     .track("kick", kicks, voices::kick())
+    ///     .on_clock(|event| {
+    ///         if event.kind == ClockKind::Bar {
+    ///             println!("bar {}", event.bar + 1);
+    ///         }
+    ///     })
+    /// ```
+    pub fn on_clock(mut self, handler: impl FnMut(ClockEvent) + Send + 'static) -> Self {
+        self.clock_handlers.push(Box::new(handler));
+        self
+    }
+
+    /// Check the arrangement for mistakes that `run` would silently accept
+    ///
+    /// Reports the first problem found: no tracks, duplicate track names,
+    /// a track whose pattern has no notes, a configured name with no track, or
+    /// more than `MAX_UI_TRACKS` tracks.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.tracks.is_empty() {
+            return Err(ConfigError::NoTracks);
+        }
+        for (i, track) in self.tracks.iter().enumerate() {
+            if self.tracks[..i].iter().any(|t| t.name == track.name) {
+                return Err(ConfigError::DuplicateTrackName(track.name.clone()));
+            }
+            if track.sequence.events.iter().all(|e| e.note.is_none()) {
+                return Err(ConfigError::EmptySequence(track.name.clone()));
+            }
+            if track.sequence.ppq != self.ppq {
+                return Err(ConfigError::PpqMismatch {
+                    track: track.name.clone(),
+                    ppq: track.sequence.ppq,
+                    expected: self.ppq,
+                });
+            }
+        }
+        let mut configured = self
+            .frozen
+            .iter()
+            .chain(&self.unpitched)
+            .chain(self.loop_tails.iter().map(|(name, _)| name))
+            .chain(self.legatos.iter().map(|(name, _)| name))
+            .chain(self.ducks.iter().flat_map(|(trigger, target, ..)| [trigger, target]))
+            .chain(self.outputs.iter().map(|(name, _)| name));
+        if let Some(name) = configured.find(|name| !self.tracks.iter().any(|t| &t.name == *name)) {
+            return Err(ConfigError::UnknownTrack(name.clone()));
+        }
+        if self.tracks.len() > MAX_UI_TRACKS {
+            return Err(ConfigError::TooManyTracks {
+                count: self.tracks.len(),
+                max: MAX_UI_TRACKS,
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate the arrangement, then run it
+    ///
+    /// Like `run`, but refuses to start if `validate` finds a problem.
+    pub fn try_run(self) -> EyreResult<()> {
+        self.validate()?;
+        self.run()
+    }
+
+    /// Render the arrangement offline, faster than realtime
+    ///
+    /// Uses the same renderer as the audio callback. The callback splits
+    /// each device buffer into block-sized chunks (the last one shorter),
+    /// and short blocks render the same audio as full ones, so the result
+    /// matches what `run` plays for any device buffer size, odd ones
+    /// included. Returns `seconds` of mono samples (the pattern loops).
+    pub fn render_offline(mut self, sample_rate: f32, seconds: f32) -> Vec<f32> {
+        self.arrange_tracks();
+        let mut renderer = self.build_renderer(sample_rate);
+        // Render past the end by the track latency, then drop it from the front
+        let latency = renderer.latency_samples();
+        let mut out = vec![0.0; (seconds.max(0.0) * sample_rate) as usize + latency];
+
+        let _denormal_guard = DenormalGuard::new();
+        renderer.render(&mut out);
+        out.drain(..latency);
+        self.limit_bounce(&mut out, sample_rate);
+        out
+    }
+
+    /// Render `seconds` of the arrangement, then let every tail ring out
+    ///
+    /// Like `render_offline`, but instead of cutting off at `seconds` the
+    /// sequence stops there and rendering continues until the mix falls
+    /// below `threshold` (linear peak, e.g. 0.001 for -60 dB), for at most
+    /// `max_tail_secs` more.
+    pub fn render_offline_with_tail(mut self, sample_rate: f32, seconds: f32, threshold: f32, max_tail_secs: f32) -> Vec<f32> {
+        self.arrange_tracks();
+        let mut renderer = self.build_renderer(sample_rate);
+        let latency = renderer.latency_samples();
+        let mut out = vec![0.0; (seconds.max(0.0) * sample_rate) as usize + latency];
+
+        let _denormal_guard = DenormalGuard::new();
+        renderer.render(&mut out);
+        out.extend(renderer.render_until_silent(threshold, max_tail_secs));
+        out.drain(..latency);
+        self.limit_bounce(&mut out, sample_rate);
+        out
+    }
+
+    /// Run an offline render through the `limiter`, compensating its latency
+    fn limit_bounce(&self, out: &mut Vec<f32>, sample_rate: f32) {
+        let Some(ceiling_db) = self.limiter else {
+            return;
+        };
+        let mut limiter = LookaheadLimiter::new(ceiling_db, DEFAULT_LOOKAHEAD_SECS, DEFAULT_RELEASE_SECS, sample_rate);
+        let latency = limiter.latency_samples();
+        // Flush the last `latency` samples out, then drop the leading delay
+        out.resize(out.len() + latency, 0.0);
+        limiter.render(out);
+        out.drain(..latency);
+    }
+
+    /// Apply the global key (except to unpitched tracks) and swing to every sequence
+    fn arrange_tracks(&mut self) {
+        for track in self.tracks.iter_mut() {
+            if let Some(key) = self.key.filter(|_| !self.unpitched.contains(&track.name)) {
+                track.sequence.apply_key(key);
+            }
+            if self.swing != 0.5 {
+                track.apply_swing(self.swing, self.swing_base);
+            }
+        }
+    }
+
+    /// Move the tracks into a renderer with loop tails, legato, ducks, output routes and freezes applied (allocates)
+    fn build_renderer(&mut self, sample_rate: f32) -> Renderer {
+        let mut tracks = std::mem::take(&mut self.tracks);
+        for (name, loop_tail) in &self.loop_tails {
+            for track in tracks.iter_mut().filter(|t| &t.name == name) {
+                track.set_loop_tail(*loop_tail);
+            }
+        }
+        for (name, glide_secs) in &self.legatos {
+            for track in tracks.iter_mut().filter(|t| &t.name == name) {
+                track.set_legato(Some(*glide_secs));
+            }
+        }
+        let mut renderer = Renderer::new(tracks, self.bpm, self.ppq, sample_rate, self.block_size)
+            .with_seed(self.seed)
+            .with_output_stage(self.output_stage);
+
+        let index_of = |renderer: &Renderer, name: &str| renderer.tracks().iter().position(|t| t.name == name);
+        for &(ref trigger, ref target, amount_db, attack_secs, release_secs) in &self.ducks {
+            if let (Some(trigger), Some(target)) = (index_of(&renderer, trigger), index_of(&renderer, target)) {
+                renderer = renderer.with_duck(trigger, target, amount_db, attack_secs, release_secs);
+            }
+        }
+        for &(ref name, pair) in &self.outputs {
+            if let Some(index) = index_of(&renderer, name) {
+                renderer = renderer.with_output_pair(index, pair);
+            }
+        }
+        for name in &self.frozen {
+            if let Some(index) = index_of(&renderer, name) {
+                renderer.freeze_track(index);
+            }
+        }
+        renderer
+    }
+
     /// Run the application (takes over, plays audio)
-    pub fn run(self) -> EyreResult<()> {
+    pub fn run(mut self) -> EyreResult<()> {
         // Set up audio
         let host = cpal::default_host();
         let device = host
@@ -72,6 +550,18 @@ impl Saavy {
 
         let sample_rate = config.sample_rate().0 as f32;
         let channels = config.channels() as usize;
+        if let Some((name, pair)) = self.outputs.iter().find(|(_, pair)| 2 * pair + 1 >= channels) {
+            return Err(eyre!(
+                "track \"{}\" is routed to channels {}-{}, but the output device has {}",
+                name,
+                2 * pair + 1,
+                2 * pair + 2,
+                channels
+            ));
+        }
+
+        // Key and swing first, so the UI shows the notes that play
+        self.arrange_tracks();
 
         // Calculate total duration and build static track info for UI (sent once, can allocate)
         let mut total_ticks = 0u32;
@@ -88,122 +578,149 @@ impl Saavy {
                         .iter()
                         .filter_map(|e| e.note.map(|_| (e.tick_offset, e.duration_ticks)))
                         .collect(),
+                    sequence: track.sequence.clone(),
                 }
             })
             .collect();
 
-        let num_tracks = self.tracks.len().min(8) as u8;
+        let num_tracks = self.tracks.len().min(MAX_UI_TRACKS) as u8;
 
         // Create ring buffers for audio↔UI communication
-        let (audio_tx, audio_rx) = RingBuffer::<f32>::new(AUDIO_RING_SIZE);
+        let (audio_tx, audio_rx) = audio_tap(AUDIO_RING_SIZE);
         let (state_tx, state_rx) = RingBuffer::<UiStateUpdate>::new(STATE_RING_SIZE);
         let (control_tx, control_rx) = RingBuffer::<ControlMessage>::new(CONTROL_RING_SIZE);
+        let (param_tx, param_rx) = param_bus(PARAM_RING_SIZE);
+        let (sequence_tx, sequence_rx) = sequence_bus(SEQUENCE_RING_SIZE);
 
         // Static UI state (sent once at init, never changes)
         let static_state = UiStateInit::new(self.bpm, self.ppq, total_ticks, sample_rate, tracks_static);
 
-        // Create sequencer
-        let sequencer = Sequencer::new(self.bpm, self.ppq, sample_rate as f64, self.tracks.len());
+        // Prepare nodes and build the sequencer before audio starts (may allocate)
+        let mut renderer = self.build_renderer(sample_rate);
+        if let Some(ceiling_db) = self.limiter {
+            renderer = renderer.with_limiter(ceiling_db, DEFAULT_LOOKAHEAD_SECS);
+        }
+        if !self.clock_handlers.is_empty() {
+            let (clock_tx, mut clock_rx) = clock_bus(CLOCK_RING_SIZE);
+            renderer = renderer.with_clock(clock_tx);
+            let mut handlers = std::mem::take(&mut self.clock_handlers);
+            // Ends when the audio state (and its sender) is dropped
+            std::thread::spawn(move || {
+                while !clock_rx.is_closed() {
+                    clock_rx.drain(|event| handlers.iter_mut().for_each(|handler| handler(event)));
+                    std::thread::sleep(CLOCK_POLL);
+                }
+            });
+        }
+        let mut render_buf = vec![0.0f32; renderer.block_size()];
 
         // Wrap in Arc<Mutex> for sharing with audio thread
         let state = Arc::new(Mutex::new(AudioState {
-            tracks: self.tracks,
-            sequencer,
-            sample_rate,
+            renderer,
             num_tracks,
             audio_tx,
             state_tx,
             control_rx,
+            param_rx,
+            sequence_rx,
         }));
-        state.lock().unwrap().sequencer.set_total_ticks(total_ticks);
 
         // Set up audio stream
         let state_clone = state.clone();
-        let mut render_buf = vec![0.0f32; MAX_BLOCK_SIZE];
-        let mut track_buf = vec![0.0f32; MAX_BLOCK_SIZE];
+        let monitor = self.monitor.clone();
 
         let stream = device.build_output_stream(
             &config.into(),
-            move |data: &mut [f32], _| {
+            move |data: &mut [f32], info: &cpal::OutputCallbackInfo| {
+                // Timestamp first so the measurement covers the whole callback
+                let callback_start = Instant::now();
+                // Flush denormals to zero for this callback (restored on drop)
+                let _denormal_guard = DenormalGuard::new();
                 let mut state = state_clone.lock().unwrap();
                 let total_frames = data.len() / channels;
-                let mut frames_written = 0;
 
                 // Destructure to allow simultaneous mutable borrows
                 let AudioState {
-                    tracks,
-                    sequencer,
-                    sample_rate,
+                    renderer,
                     num_tracks,
                     audio_tx,
                     state_tx,
                     control_rx,
+                    param_rx,
+                    sequence_rx,
                 } = &mut *state;
-                let sample_rate = *sample_rate;
                 let num_tracks = *num_tracks;
 
                 // Process control messages from UI
                 while let Ok(msg) = control_rx.pop() {
-                    match msg {
-                        ControlMessage::TogglePlayback => sequencer.toggle(),
-                        ControlMessage::Reset => sequencer.reset(),
-                    }
+                    renderer.handle_control(msg);
                 }
 
-                while frames_written < total_frames {
-                    let frames_remaining = total_frames - frames_written;
-                    let frames_to_render = frames_remaining.min(MAX_BLOCK_SIZE);
+                // Apply parameter changes at the block boundary (ramped per sample)
+                param_rx.drain(|change| renderer.apply_param(change));
 
-                    // Process sequencer (triggers note events)
-                    sequencer.process_block(frames_to_render, tracks, sample_rate);
+                // Step edits wait on their tracks for the next loop point
+                sequence_rx.drain(|track, sequence| renderer.queue_sequence(track, sequence));
 
-                    // Clear render buffer
-                    let block = &mut render_buf[..frames_to_render];
-                    block.fill(0.0);
+                // Device latency: time between this callback and the samples hitting the DAC
+                let timestamp = info.timestamp();
+                let output_latency = timestamp.playback.duration_since(&timestamp.callback).unwrap_or_default();
 
-                    // Render and mix all tracks
-                    for track in tracks.iter_mut() {
-                        let tbuf = &mut track_buf[..frames_to_render];
-                        tbuf.fill(0.0);
-                        track.render(tbuf, sample_rate);
-
-                        // Mix into main buffer
-                        for (out, &sample) in block.iter_mut().zip(tbuf.iter()) {
-                            *out += sample;
-                        }
-                    }
+                let mut rendered = 0;
+                for frames in data.chunks_mut(renderer.block_size() * channels) {
+                    let frames_to_render = frames.len() / channels;
+                    let block = &mut render_buf[..frames_to_render];
+                    // Stamp bar/beat events with when this block is heard
+                    let block_delay = Duration::from_secs_f64(rendered as f64 / sample_rate as f64);
+                    renderer.set_block_time(callback_start + output_latency + block_delay);
+                    renderer.render_block(block);
+                    rendered += frames_to_render;
 
                     // Copy to output (mono to all channels)
-                    let out_off = frames_written * channels;
-                    for (i, &s) in block.iter().enumerate() {
-                        for ch in 0..channels {
-                            data[out_off + i * channels + ch] = s;
-                        }
+                    for (frame, &s) in frames.chunks_mut(channels).zip(block.iter()) {
+                        frame.fill(s);
                     }
 
-                    // Push audio samples to UI (non-blocking, drop on overflow)
-                    for &sample in &block[..frames_to_render] {
-                        let _ = audio_tx.push(sample);
+                    // Routed tracks replace the master on their channel pairs,
+                    // and join it in the UI tap
+                    for (pair, bus) in renderer.output_buses() {
+                        for ((frame, &s), master) in frames.chunks_mut(channels).zip(bus).zip(block.iter_mut()) {
+                            frame[2 * pair..2 * pair + 2].fill(s);
+                            *master += s;
+                        }
                     }
 
-                    frames_written += frames_to_render;
+                    // Tap the block for the UI (non-blocking, drop on overflow)
+                    audio_tx.write_slice(block);
                 }
 
+                // Sequences swapped out at the loop point go back to be freed
+                sequence_rx.retire(|| renderer.take_retired());
+
                 // Push UI state update (once per callback, allocation-free)
-                let mut track_states = [TrackDynamicState::default(); 8];
-                for (i, track) in tracks.iter().enumerate().take(8) {
+                let mut track_states = [TrackDynamicState::default(); MAX_UI_TRACKS];
+                for (i, track) in renderer.tracks().iter().enumerate().take(MAX_UI_TRACKS) {
                     track_states[i] = TrackDynamicState {
                         is_active: track.is_active(),
-                        envelope_level: track.envelope_level().unwrap_or(0.0),
-                        current_note: track.current_note().unwrap_or(0),
                     };
                 }
 
+                let output_latency_micros = output_latency.as_micros() as u32;
+
+                // Deadline: the callback must finish within one buffer period
+                let elapsed = callback_start.elapsed();
+                let budget = Duration::from_secs_f64(total_frames as f64 / sample_rate as f64);
+                monitor.record(elapsed, budget);
+
+                let sequencer = renderer.sequencer();
                 let ui_update = UiStateUpdate {
                     tick_position: sequencer.tick_position(),
                     is_playing: sequencer.is_playing(),
                     track_states,
                     num_tracks,
+                    buffer_frames: total_frames as u32,
+                    callback_micros: elapsed.as_micros() as u32,
+                    output_latency_micros,
                 };
                 let _ = state_tx.push(ui_update);
             },
@@ -215,7 +732,7 @@ impl Saavy {
 
         // Initialize terminal and run TUI
         let mut terminal = ratatui::init();
-        let mut ui = UiApp::new(audio_rx, state_rx, control_tx, static_state);
+        let mut ui = UiApp::new(audio_rx, state_rx, control_tx, param_tx, sequence_tx, self.monitor, static_state);
         let result = ui.run(&mut terminal);
         ratatui::restore();
 
@@ -231,13 +748,13 @@ impl Default for Saavy {
 
 /// Shared audio state
 struct AudioState {
-    tracks: Vec<Track>,
-    sequencer: Sequencer,
-    sample_rate: f32,
+    renderer: Renderer,
     num_tracks: u8,
-    audio_tx: rtrb::Producer<f32>,
+    audio_tx: TapWriter,
     state_tx: rtrb::Producer<UiStateUpdate>,
     control_rx: rtrb::Consumer<ControlMessage>,
+    param_rx: ParamReceiver,
+    sequence_rx: SequenceReceiver,
 }
 
 /// Trait for types that can be converted to a Sequence
@@ -262,3 +779,74 @@ impl IntoSequence for Sequence {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequencing::notes::*;
+    use crate::voices;
+
+    fn beat() -> Pattern {
+        Pattern::four_four(vec![C2.into()])
+    }
+
+    #[test]
+    fn valid_arrangement_passes() {
+        let app = Saavy::new().track("kick", beat(), voices::kick()).track("bass", beat(), voices::bass());
+        assert_eq!(app.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_duplicate_names() {
+        let app = Saavy::new().track("kick", beat(), voices::kick()).track("kick", beat(), voices::kick());
+        assert_eq!(app.validate(), Err(ConfigError::DuplicateTrackName("kick".into())));
+    }
+
+    #[test]
+    fn rejects_empty_patterns() {
+        let rests = Pattern::four_four(vec![crate::sequencing::PatternSlot::Rest; 4]);
+        let app = Saavy::new().track("silence", rests, voices::lead());
+        assert_eq!(app.validate(), Err(ConfigError::EmptySequence("silence".into())));
+        assert_eq!(Saavy::new().validate(), Err(ConfigError::NoTracks));
+    }
+
+    #[test]
+    fn rejects_freezing_unknown_tracks() {
+        let app = Saavy::new().track("kick", beat(), voices::kick()).freeze("snare");
+        assert_eq!(app.validate(), Err(ConfigError::UnknownTrack("snare".into())));
+    }
+
+    #[test]
+    fn rejects_ppq_set_after_tracks() {
+        let app = Saavy::new().track("kick", beat(), voices::kick()).ppq(960);
+        assert_eq!(
+            app.validate(),
+            Err(ConfigError::PpqMismatch { track: "kick".into(), ppq: 480, expected: 960 })
+        );
+        assert!(Saavy::new().ppq(960).track("kick", beat(), voices::kick()).validate().is_ok());
+    }
+
+    #[test]
+    fn key_moves_melodic_tracks_only() {
+        let riff = || Pattern::four_four(vec![C4.into(), E4.into()]);
+        let mut app = Saavy::new()
+            .key(Key::Am)
+            .track("lead", riff(), voices::lead())
+            .track("snare", riff(), voices::snare())
+            .unpitched("snare");
+        app.arrange_tracks();
+
+        let notes = |track: &Track| track.sequence.events.iter().map(|e| e.note).collect::<Vec<_>>();
+        assert_eq!(notes(&app.tracks[0]), vec![Some(A3), Some(C4)]);
+        assert_eq!(notes(&app.tracks[1]), vec![Some(C4), Some(E4)]);
+    }
+
+    #[test]
+    fn rejects_more_tracks_than_the_ui_shows() {
+        let app = (0..=MAX_UI_TRACKS).fold(Saavy::new(), |app, i| app.track(&format!("t{i}"), beat(), voices::kick()));
+        let err = app.validate().unwrap_err();
+
+        assert_eq!(err, ConfigError::TooManyTracks { count: 9, max: 8 });
+        assert!(err.to_string().contains("at most 8"));
+    }
+}
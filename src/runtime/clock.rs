@@ -0,0 +1,105 @@
+//! Clock bus - bar and beat boundaries from the audio thread
+//!
+//! The renderer notices every beat (quarter note) and bar boundary the
+//! sequencer crosses and pushes a `ClockEvent` down one SPSC ring, stamped
+//! with its exact frame and, when the host provides one, the wall-clock
+//! time the boundary reaches the speakers. Control code drains the ring to
+//! flash lights, drive visuals, or time pattern swaps. `Saavy::on_clock`
+//! wraps this in a callback run on its own thread.
+
+use std::time::Instant;
+
+use rtrb::{Consumer, Producer, RingBuffer};
+
+/// Which boundary a `ClockEvent` marks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockKind {
+    /// First beat of a bar (not also sent as a `Beat`)
+    Bar,
+    /// Any other beat
+    Beat,
+}
+
+/// A bar or beat boundary crossed by the sequencer
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClockEvent {
+    pub kind: ClockKind,
+    /// Bar within the loop, from 0
+    pub bar: u32,
+    /// Beat within the bar, from 0
+    pub beat: u32,
+    /// Sequencer position in ticks
+    pub tick: u32,
+    /// Frames rendered since the renderer was built, up to the boundary
+    pub frame: u64,
+    /// When the boundary is heard, if the host set a block time
+    /// (see `Renderer::set_block_time`)
+    pub at: Option<Instant>,
+}
+
+/// Create a clock bus with room for `capacity` undrained events
+pub fn clock_bus(capacity: usize) -> (ClockSender, ClockReceiver) {
+    let (tx, rx) = RingBuffer::new(capacity);
+    (ClockSender { tx }, ClockReceiver { rx })
+}
+
+/// Audio-thread end of the clock bus
+pub struct ClockSender {
+    tx: Producer<ClockEvent>,
+}
+
+impl ClockSender {
+    /// Queue an event. Returns false if the bus is full (the event is dropped).
+    /// REAL-TIME SAFE: No allocations in this function.
+    pub fn send(&mut self, event: ClockEvent) -> bool {
+        self.tx.push(event).is_ok()
+    }
+}
+
+/// Control-thread end of the clock bus
+pub struct ClockReceiver {
+    rx: Consumer<ClockEvent>,
+}
+
+impl ClockReceiver {
+    /// Hand every pending event to `handle`, oldest first
+    pub fn drain(&mut self, mut handle: impl FnMut(ClockEvent)) {
+        while let Ok(event) = self.rx.pop() {
+            handle(event);
+        }
+    }
+
+    /// Whether the sending end has been dropped (no more events will come)
+    pub fn is_closed(&self) -> bool {
+        self.rx.is_abandoned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bus_delivers_events_in_order() {
+        let (mut tx, mut rx) = clock_bus(2);
+        let event = |beat| ClockEvent {
+            kind: ClockKind::Beat,
+            bar: 0,
+            beat,
+            tick: beat * 480,
+            frame: beat as u64 * 24_000,
+            at: None,
+        };
+        assert!(tx.send(event(1)));
+        assert!(tx.send(event(2)));
+        assert!(!tx.send(event(3)), "full bus drops");
+
+        let mut beats = Vec::new();
+        rx.drain(|e| beats.push(e.beat));
+        assert_eq!(beats, vec![1, 2]);
+
+        assert!(!rx.is_closed());
+        drop(tx);
+        assert!(rx.is_closed());
+    }
+}
@@ -0,0 +1,81 @@
+//! Duck - event-driven "sidechain" ducking between tracks
+//!
+//! The classic pump (bass dips every time the kick hits) is usually done
+//! with a compressor listening to the kick's audio. We already know exactly
+//! when the kick plays - the sequencer fires it - so the dip can be a simple
+//! gain envelope started by the trigger track's note-ons. No analysis, no
+//! lookahead, and it works the same for a kick with a slow attack.
+
+use crate::dsp::smooth::SmoothedParam;
+
+/// Gain envelope that dips one track whenever another triggers a note
+pub(crate) struct Duck {
+    /// Index of the track whose note-ons start the dip
+    pub trigger: usize,
+    /// Index of the track that gets quieter
+    pub target: usize,
+    /// Gain at the bottom of the dip (linear)
+    floor: f32,
+    attack_secs: f32,
+    release_secs: f32,
+    gain: SmoothedParam,
+    /// Heading down toward `floor`; the release starts when it gets there
+    attacking: bool,
+}
+
+impl Duck {
+    /// Dip `target` by `amount_db` over `attack_secs`, recovering over `release_secs`
+    pub fn new(trigger: usize, target: usize, amount_db: f32, attack_secs: f32, release_secs: f32) -> Self {
+        Self {
+            trigger,
+            target,
+            floor: 10.0f32.powf(-amount_db.abs() / 20.0),
+            attack_secs,
+            release_secs,
+            gain: SmoothedParam::new(1.0),
+            attacking: false,
+        }
+    }
+
+    /// Start a dip from wherever the gain is now
+    pub fn trigger(&mut self, sample_rate: f32) {
+        self.gain.set_target(self.floor, self.attack_secs, sample_rate);
+        self.attacking = true;
+    }
+
+    /// Apply the envelope to the target track's buffer
+    ///
+    /// REAL-TIME SAFE: No allocations in this function.
+    pub fn apply(&mut self, buffer: &mut [f32], sample_rate: f32) {
+        if !self.attacking && !self.gain.is_smoothing() {
+            // Fully recovered (gain is 1.0)
+            return;
+        }
+        for sample in buffer.iter_mut() {
+            if self.attacking && !self.gain.is_smoothing() {
+                self.attacking = false;
+                self.gain.set_target(1.0, self.release_secs, sample_rate);
+            }
+            *sample *= self.gain.next_value();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dips_to_floor_then_recovers() {
+        let mut duck = Duck::new(0, 1, 12.0, 0.001, 0.01);
+        duck.trigger(1000.0);
+
+        let mut buffer = vec![1.0; 20];
+        duck.apply(&mut buffer, 1000.0);
+
+        // 12 dB down after the 1-sample attack, back to unity 10 samples later
+        assert!((buffer[0] - 0.251).abs() < 1e-3, "floor {}", buffer[0]);
+        assert!(buffer[1..11].windows(2).all(|w| w[1] > w[0]));
+        assert_eq!(buffer[11..], [1.0; 9]);
+    }
+}
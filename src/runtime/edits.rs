@@ -0,0 +1,114 @@
+//! Sequence bus - hands edited sequences to the audio thread and back
+//!
+//! A sequence owns a `Vec`, so building one allocates and dropping one
+//! frees. Both must stay off the audio thread. The control side builds the
+//! new sequence and sends it down one SPSC ring. The audio thread queues it
+//! on its track, which swaps it in at the next loop point (see
+//! `Track::queue_sequence`). The sequence it replaces comes back up a second
+//! ring and is dropped by the control side.
+
+use rtrb::{Consumer, Producer, RingBuffer};
+
+use crate::sequencing::Sequence;
+
+/// A replacement sequence for one track
+pub struct SequenceEdit {
+    /// Index of the track to edit
+    pub track: usize,
+    pub sequence: Sequence,
+}
+
+/// Create a sequence bus with room for `capacity` edits in flight each way
+pub fn sequence_bus(capacity: usize) -> (SequenceSender, SequenceReceiver) {
+    let (edit_tx, edit_rx) = RingBuffer::new(capacity);
+    let (retired_tx, retired_rx) = RingBuffer::new(capacity);
+    (
+        SequenceSender { edit_tx, retired_rx },
+        SequenceReceiver { edit_rx, retired_tx },
+    )
+}
+
+/// Control-thread end of the sequence bus
+pub struct SequenceSender {
+    edit_tx: Producer<SequenceEdit>,
+    retired_rx: Consumer<Sequence>,
+}
+
+impl SequenceSender {
+    /// Queue a new sequence for `track`. Returns false if the bus is full.
+    pub fn send(&mut self, track: usize, sequence: Sequence) -> bool {
+        self.edit_tx.push(SequenceEdit { track, sequence }).is_ok()
+    }
+
+    /// Drop sequences the audio thread has finished with (frees memory here)
+    pub fn collect_garbage(&mut self) {
+        while self.retired_rx.pop().is_ok() {}
+    }
+}
+
+/// Audio-thread end of the sequence bus
+pub struct SequenceReceiver {
+    edit_rx: Consumer<SequenceEdit>,
+    retired_tx: Producer<Sequence>,
+}
+
+impl SequenceReceiver {
+    /// Hand pending edits to `queue`, which returns any sequence it displaced
+    ///
+    /// Edits are only taken while the return ring has room, so a displaced
+    /// sequence is never dropped here.
+    /// REAL-TIME SAFE: No allocations in this function.
+    pub fn drain(&mut self, mut queue: impl FnMut(usize, Sequence) -> Option<Sequence>) {
+        while self.retired_tx.slots() > 0 {
+            let Ok(edit) = self.edit_rx.pop() else {
+                break;
+            };
+            if let Some(displaced) = queue(edit.track, edit.sequence) {
+                let _ = self.retired_tx.push(displaced);
+            }
+        }
+    }
+
+    /// Send replaced sequences from `take` back to be freed, while there's room
+    /// REAL-TIME SAFE: No allocations in this function.
+    pub fn retire(&mut self, mut take: impl FnMut() -> Option<Sequence>) {
+        while self.retired_tx.slots() > 0 {
+            let Some(sequence) = take() else {
+                break;
+            };
+            let _ = self.retired_tx.push(sequence);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequencing::{notes::*, Pattern};
+
+    #[test]
+    fn displaced_sequences_come_back() {
+        let (mut tx, mut rx) = sequence_bus(1);
+        let sequence = || Pattern::four_four(vec![C4.into()]).to_sequence(480);
+        assert!(tx.send(0, sequence()));
+        assert!(!tx.send(0, sequence()), "bus holds one edit");
+
+        let mut queued = None;
+        rx.drain(|track, sequence| {
+            assert_eq!(track, 0);
+            queued.replace(sequence)
+        });
+        assert!(queued.is_some());
+
+        // The return ring is full until the control side collects
+        let mut old = Some(sequence());
+        rx.retire(|| old.take());
+        let mut another = Some(sequence());
+        rx.retire(|| another.take());
+        assert!(another.is_some(), "kept on the audio side while the ring is full");
+
+        tx.collect_garbage();
+        rx.retire(|| another.take());
+        assert!(another.is_none());
+    }
+}
@@ -16,10 +16,41 @@
 //!         .run()
 //! }
 //! ```
+//!
+//! # Without the TUI
+//!
+//! `Saavy` is a thin wrapper over the pieces below, which can drive your own
+//! audio callback (or an offline render) directly:
+//!
+//! - [`Track`]: one monophonic voice playing a `Sequence`
+//! - [`Sequencer`]: sample-accurate transport that fires tracks' note events
+//! - [`Renderer`]: owns both and mixes the tracks to mono, block by block
+//!
+//! ```ignore
+//! use saavy_dsp::{runtime::{Renderer, Track}, sequencing::*, voices};
+//!
+//! let lead = Track::new("lead", pattern!(4/4 => [C4, E4, G4, C5]).to_sequence(480), voices::lead());
+//! let mut renderer = Renderer::new(vec![lead], 120.0, 480, 48_000.0, 256);
+//!
+//! // In the audio callback, one block at a time:
+//! renderer.render_block(&mut block);
+//! ```
 
 mod app;
+mod clock;
+mod duck;
+mod edits;
+mod monitor;
+mod params;
+mod renderer;
 mod sequencer;
+mod tap;
 mod track;
 mod ui;
 
-pub use app::{IntoSequence, Saavy};
+pub use app::{ConfigError, IntoSequence, Saavy};
+pub use clock::{clock_bus, ClockEvent, ClockKind, ClockReceiver, ClockSender};
+pub use monitor::{CallbackMonitor, CallbackStats};
+pub use renderer::{OutputStage, Renderer};
+pub use sequencer::Sequencer;
+pub use track::{LoopTail, Track};
@@ -0,0 +1,115 @@
+//! Callback monitor - lock-free xrun (overrun) detection
+//!
+//! Every audio callback has a deadline: it must fill its buffer before the
+//! device finishes playing the previous one. `buffer_frames / sample_rate`
+//! is the budget; a callback that takes longer causes an audible dropout
+//! (an "xrun").
+//!
+//! The audio thread calls `record` once per callback. Any other thread can
+//! `snapshot` the counters at any time - everything is a relaxed atomic, so
+//! neither side ever blocks.
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Shared callback timing counters (audio thread writes, anyone reads)
+#[derive(Debug, Default)]
+pub struct CallbackMonitor {
+    /// Callbacks recorded
+    callbacks: AtomicU64,
+    /// Callbacks that ran past their deadline
+    overruns: AtomicU64,
+    /// Duration of the most recent callback (microseconds)
+    last_micros: AtomicU32,
+    /// Most recent callback time as a fraction of its budget (per mille)
+    last_load_permille: AtomicU32,
+    /// Highest load seen since creation or the last `reset_worst` (per mille)
+    worst_load_permille: AtomicU32,
+}
+
+/// A point-in-time copy of the monitor counters
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CallbackStats {
+    pub callbacks: u64,
+    pub overruns: u64,
+    pub last_micros: u32,
+    /// Most recent callback time as a percentage of its budget
+    pub last_load_percent: f32,
+    /// Worst callback time as a percentage of its budget
+    pub worst_load_percent: f32,
+}
+
+impl CallbackMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one callback that took `elapsed` out of a `budget`
+    ///
+    /// REAL-TIME SAFE: relaxed atomic stores only.
+    pub fn record(&self, elapsed: Duration, budget: Duration) {
+        let load_permille = if budget.is_zero() {
+            0
+        } else {
+            (elapsed.as_secs_f64() / budget.as_secs_f64() * 1000.0).min(u32::MAX as f64) as u32
+        };
+
+        self.callbacks.fetch_add(1, Ordering::Relaxed);
+        if elapsed > budget {
+            self.overruns.fetch_add(1, Ordering::Relaxed);
+        }
+        self.last_micros
+            .store(elapsed.as_micros().min(u32::MAX as u128) as u32, Ordering::Relaxed);
+        self.last_load_permille.store(load_permille, Ordering::Relaxed);
+        self.worst_load_permille.fetch_max(load_permille, Ordering::Relaxed);
+    }
+
+    /// Read the current counters
+    pub fn snapshot(&self) -> CallbackStats {
+        CallbackStats {
+            callbacks: self.callbacks.load(Ordering::Relaxed),
+            overruns: self.overruns.load(Ordering::Relaxed),
+            last_micros: self.last_micros.load(Ordering::Relaxed),
+            last_load_percent: self.last_load_permille.load(Ordering::Relaxed) as f32 / 10.0,
+            worst_load_percent: self.worst_load_permille.load(Ordering::Relaxed) as f32 / 10.0,
+        }
+    }
+
+    /// Clear the worst-case load (e.g. after the stream settles)
+    pub fn reset_worst(&self) {
+        self.worst_load_permille.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUDGET: Duration = Duration::from_millis(10);
+
+    #[test]
+    fn counts_overruns() {
+        let monitor = CallbackMonitor::new();
+        monitor.record(Duration::from_millis(2), BUDGET);
+        monitor.record(Duration::from_millis(12), BUDGET);
+        monitor.record(Duration::from_millis(5), BUDGET);
+
+        let stats = monitor.snapshot();
+        assert_eq!(stats.callbacks, 3);
+        assert_eq!(stats.overruns, 1);
+        assert_eq!(stats.last_micros, 5_000);
+        assert_eq!(stats.last_load_percent, 50.0);
+        assert_eq!(stats.worst_load_percent, 120.0);
+    }
+
+    #[test]
+    fn reset_worst_keeps_counts() {
+        let monitor = CallbackMonitor::new();
+        monitor.record(Duration::from_millis(9), BUDGET);
+        monitor.reset_worst();
+
+        let stats = monitor.snapshot();
+        assert_eq!(stats.worst_load_percent, 0.0);
+        assert_eq!(stats.callbacks, 1);
+    }
+}
@@ -0,0 +1,87 @@
+//! Parameter bus - realtime-safe parameter changes for the audio thread
+//!
+//! Any control layer (the TUI today, MIDI or OSC later) sends `ParamChange`
+//! messages through one SPSC ring. The audio callback drains the ring at the
+//! start of each block and retargets a `SmoothedParam` (see `dsp::smooth`),
+//! which ramps to the new value so changes don't click.
+
+use rtrb::{Consumer, Producer, RingBuffer};
+
+/// Identifies a runtime parameter
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamId {
+    /// Linear gain applied to the summed output
+    MasterGain,
+}
+
+/// A single parameter change: set `id` to `value` over `ramp_secs`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParamChange {
+    pub id: ParamId,
+    pub value: f32,
+    /// Ramp duration in seconds (0 = jump immediately)
+    pub ramp_secs: f32,
+}
+
+impl ParamChange {
+    /// Change `id` to `value` with a linear ramp
+    pub fn new(id: ParamId, value: f32, ramp_secs: f32) -> Self {
+        Self { id, value, ramp_secs }
+    }
+}
+
+/// Create a parameter bus with room for `capacity` pending changes
+pub fn param_bus(capacity: usize) -> (ParamSender, ParamReceiver) {
+    let (tx, rx) = RingBuffer::new(capacity);
+    (ParamSender { tx }, ParamReceiver { rx })
+}
+
+/// Control-thread end of the parameter bus
+pub struct ParamSender {
+    tx: Producer<ParamChange>,
+}
+
+impl ParamSender {
+    /// Queue a change. Returns false if the bus is full (the change is dropped).
+    pub fn send(&mut self, change: ParamChange) -> bool {
+        self.tx.push(change).is_ok()
+    }
+}
+
+/// Audio-thread end of the parameter bus
+pub struct ParamReceiver {
+    rx: Consumer<ParamChange>,
+}
+
+impl ParamReceiver {
+    /// Hand every pending change to `apply`, oldest first.
+    /// REAL-TIME SAFE: No allocations in this function.
+    pub fn drain(&mut self, mut apply: impl FnMut(ParamChange)) {
+        while let Ok(change) = self.rx.pop() {
+            apply(change);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bus_delivers_changes_in_order() {
+        let (mut tx, mut rx) = param_bus(4);
+        assert!(tx.send(ParamChange::new(ParamId::MasterGain, 0.5, 0.0)));
+        assert!(tx.send(ParamChange::new(ParamId::MasterGain, 0.25, 0.0)));
+
+        let mut received = Vec::new();
+        rx.drain(|change| received.push(change.value));
+        assert_eq!(received, vec![0.5, 0.25]);
+    }
+
+    #[test]
+    fn full_bus_drops_changes() {
+        let (mut tx, _rx) = param_bus(1);
+        assert!(tx.send(ParamChange::new(ParamId::MasterGain, 0.5, 0.0)));
+        assert!(!tx.send(ParamChange::new(ParamId::MasterGain, 0.25, 0.0)));
+    }
+}
@@ -0,0 +1,1046 @@
+//! Renderer - tracks driven by the sequencer, mixed to mono
+//!
+//! The cpal callback and `Saavy::render_offline` both render through
+//! `Renderer::render_block`, so an offline render is sample-for-sample what
+//! the device would have played (given the same block boundaries and the
+//! same control messages).
+
+use std::time::{Duration, Instant};
+
+use crate::dsp::analysis::tail;
+use crate::dsp::delay::DelayLine;
+use crate::dsp::distortion::soft_limit;
+use crate::dsp::limiter::{LookaheadLimiter, DEFAULT_RELEASE_SECS};
+use crate::dsp::rng::{Rng, DEFAULT_SEED};
+use crate::dsp::smooth::SmoothedParam;
+use crate::graph::{NodeCommand, Transport};
+use crate::sequencing::Sequence;
+
+use super::clock::{ClockEvent, ClockKind, ClockSender};
+use super::duck::Duck;
+use super::params::{ParamChange, ParamId};
+use super::sequencer::Sequencer;
+use super::track::Track;
+use super::ui::ControlMessage;
+
+/// Tick distance treated as zero when finding beat boundaries
+const TICK_EPSILON: f64 = 1e-6;
+
+/// Knee of `OutputStage::SoftClip`: samples below this pass untouched
+const SOFT_CLIP_KNEE: f32 = 0.8;
+
+/// Peak below which a track's block doesn't count as a voice (-80 dBFS)
+const VOICE_FLOOR: f32 = 1e-4;
+
+/// Ramp time for `OutputStage::VoiceScaling` as voices come and go
+const VOICE_SCALING_RAMP_SECS: f32 = 0.02;
+
+/// Safety stage applied to the summed output so hosts never see > ±1.0
+///
+/// Every track is one voice, so a busy arrangement (or many tracks used as
+/// polyphony) can easily sum past full scale.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputStage {
+    /// No processing - the raw sum (may exceed ±1.0)
+    #[default]
+    Off,
+    /// Clamp to ±1.0. Transparent until it clips, then harsh.
+    HardClamp,
+    /// Transparent below 0.8, then rounds peaks off smoothly toward ±1.0
+    SoftClip,
+    /// Scale the sum by 1/√n for the n tracks sounding in each block,
+    /// ramped over 20 ms as n changes. Keeps uncorrelated voices at a
+    /// constant loudness; correlated peaks can still exceed ±1.0.
+    VoiceScaling,
+}
+
+/// A device channel pair fed by routed tracks instead of the master mix
+struct OutputBus {
+    /// Channel pair index: pair `n` is device channels `2n` and `2n + 1`
+    pair: usize,
+    buffer: Vec<f32>,
+    /// The bus's own copy of the master limiter, if one is set
+    limiter: Option<LookaheadLimiter>,
+}
+
+/// Delay that lines a track up with the most latent one
+struct Compensation {
+    delay: DelayLine,
+    samples: usize,
+}
+
+/// Owns the tracks, sequencer, and output gain for one arrangement
+pub struct Renderer {
+    tracks: Vec<Track>,
+    sequencer: Sequencer,
+    master_gain: SmoothedParam,
+    output_stage: OutputStage,
+    /// 1/√n for the tracks sounding, ramped (`OutputStage::VoiceScaling` only)
+    voice_scale: SmoothedParam,
+    /// Lookahead limiter after the output stage (see `with_limiter`)
+    limiter: Option<LookaheadLimiter>,
+    sample_rate: f32,
+    block_size: usize,
+    /// Scratch buffer for each track's output before mixing
+    track_buf: Vec<f32>,
+    /// Per track: delay making up the difference to `track_latency`
+    compensation: Vec<Option<Compensation>>,
+    /// Latency of the most latent track; every track is heard this late
+    track_latency: usize,
+    /// Track-to-track ducking driven by note-ons
+    ducks: Vec<Duck>,
+    /// Per track: index into `buses`, or `None` for the master mix
+    routes: Vec<Option<usize>>,
+    /// Separate outputs for routed tracks
+    buses: Vec<OutputBus>,
+    /// Master gain ramp for the current block, shared by master and buses
+    gain_buf: Vec<f32>,
+    /// Frames in the last rendered block
+    block_len: usize,
+    /// Where bar and beat boundaries are reported, if anywhere
+    clock: Option<ClockSender>,
+    /// Bar length for clock events (the first track's time signature)
+    clock_bar_ticks: u32,
+    /// Frames rendered since creation
+    frames_rendered: u64,
+    /// When the next block starts playing, if the host reports it
+    block_time: Option<Instant>,
+}
+
+impl Renderer {
+    /// Prepare tracks for `sample_rate` / `block_size` and build the sequencer
+    ///
+    /// Tracks are seeded from `DEFAULT_SEED`; see `with_seed`.
+    /// Allocates - call before audio starts.
+    pub fn new(mut tracks: Vec<Track>, bpm: f64, ppq: u32, sample_rate: f32, block_size: usize) -> Self {
+        let block_size = block_size.max(1);
+        for (index, track) in tracks.iter_mut().enumerate() {
+            track.prepare(sample_rate, block_size);
+            track.seed(Rng::derive(DEFAULT_SEED, index as u64));
+        }
+
+        // Nodes know their latency once prepared; delay the rest to match
+        let track_latency = tracks.iter().map(Track::latency_samples).max().unwrap_or(0);
+        let compensation = tracks
+            .iter()
+            .map(|track| {
+                let samples = track_latency - track.latency_samples();
+                (samples > 0).then(|| Compensation {
+                    delay: DelayLine::with_capacity(samples + 1),
+                    samples,
+                })
+            })
+            .collect();
+
+        let total_ticks = tracks.iter().map(|t| t.sequence.total_ticks).max().unwrap_or(0);
+        let clock_bar_ticks = tracks.first().map_or(4 * ppq, |t| t.sequence.bar_ticks());
+        let track_count = tracks.len();
+        let mut sequencer = Sequencer::new(bpm, ppq, sample_rate as f64, tracks.len());
+        sequencer.set_total_ticks(total_ticks);
+
+        Self {
+            tracks,
+            sequencer,
+            master_gain: SmoothedParam::new(1.0),
+            output_stage: OutputStage::Off,
+            voice_scale: SmoothedParam::new(1.0),
+            limiter: None,
+            sample_rate,
+            block_size,
+            track_buf: vec![0.0; block_size],
+            compensation,
+            track_latency,
+            ducks: Vec::new(),
+            routes: vec![None; track_count],
+            buses: Vec::new(),
+            gain_buf: vec![0.0; block_size],
+            block_len: 0,
+            clock: None,
+            clock_bar_ticks,
+            frames_rendered: 0,
+            block_time: None,
+        }
+    }
+
+    /// Reseed every track's random stream from a master seed
+    ///
+    /// Track `i` gets `Rng::derive(seed, i)`, so renders with the same seed
+    /// are bit-identical and different tracks never share noise.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        for (index, track) in self.tracks.iter_mut().enumerate() {
+            track.seed(Rng::derive(seed, index as u64));
+        }
+        self
+    }
+
+    /// Choose the safety stage applied after the master gain
+    pub fn with_output_stage(mut self, stage: OutputStage) -> Self {
+        self.output_stage = stage;
+        self
+    }
+
+    /// Limit the master mix (and every output bus) to `ceiling_db` true peak
+    ///
+    /// A lookahead limiter after the output stage (see `dsp::limiter`).
+    /// The output runs `lookahead_secs` (1 - 5 ms) late; see
+    /// `latency_samples`.
+    pub fn with_limiter(mut self, ceiling_db: f32, lookahead_secs: f32) -> Self {
+        let lookahead_secs = lookahead_secs.clamp(0.001, 0.005);
+        let limiter = LookaheadLimiter::new(ceiling_db, lookahead_secs, DEFAULT_RELEASE_SECS, self.sample_rate);
+        for bus in self.buses.iter_mut() {
+            bus.limiter = Some(limiter.clone());
+        }
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// Duck track `target` by `amount_db` whenever track `trigger` plays a note
+    ///
+    /// The dip takes `attack_secs` to reach full depth, then recovers over
+    /// `release_secs`. It follows the sequencer's note-ons rather than the
+    /// trigger's audio, so it's exact and costs nothing while idle.
+    pub fn with_duck(mut self, trigger: usize, target: usize, amount_db: f32, attack_secs: f32, release_secs: f32) -> Self {
+        self.ducks.push(Duck::new(trigger, target, amount_db, attack_secs, release_secs));
+        self
+    }
+
+    /// Send track `index` to device channel pair `pair` instead of the master mix
+    ///
+    /// Pair `n` is channels `2n` and `2n + 1` (the track is mono, so both get
+    /// the same signal). Master gain and the output stage still apply. Read
+    /// the routed audio after each block with `output_buses`.
+    pub fn with_output_pair(mut self, index: usize, pair: usize) -> Self {
+        let bus = match self.buses.iter().position(|bus| bus.pair == pair) {
+            Some(bus) => bus,
+            None => {
+                self.buses.push(OutputBus {
+                    pair,
+                    buffer: vec![0.0; self.block_size],
+                    limiter: self.limiter.clone(),
+                });
+                self.buses.len() - 1
+            }
+        };
+        self.routes[index] = Some(bus);
+        self
+    }
+
+    /// Report every bar and beat boundary the sequencer crosses to `clock`
+    ///
+    /// Bars follow the first track's time signature; beats are quarter
+    /// notes. See `runtime::clock`.
+    pub fn with_clock(mut self, clock: ClockSender) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Set when the next block will be heard, to stamp its clock events
+    ///
+    /// The audio callback calls this before each `render_block` with the
+    /// callback time plus output latency. Offline renders leave it unset.
+    pub fn set_block_time(&mut self, at: Instant) {
+        self.block_time = Some(at);
+    }
+
+    /// Audio of each routed channel pair for the last block: (pair, samples)
+    pub fn output_buses(&self) -> impl Iterator<Item = (usize, &[f32])> {
+        self.buses.iter().map(|bus| (bus.pair, &bus.buffer[..self.block_len]))
+    }
+
+    /// Samples the output runs late: the most latent track plus the master limiter
+    ///
+    /// Tracks whose graphs report latency (`GraphNode::latency_samples`,
+    /// e.g. a `LimiterNode`) would sound late against the rest, so every
+    /// other track is delayed to match the most latent one, measured when
+    /// the renderer is built. Ducks follow note-ons and are not delayed.
+    pub fn latency_samples(&self) -> usize {
+        self.track_latency + self.limiter.as_ref().map_or(0, LookaheadLimiter::latency_samples)
+    }
+
+    /// Largest block `render_block` accepts
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    pub fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    pub fn sequencer(&self) -> &Sequencer {
+        &self.sequencer
+    }
+
+    /// Pre-render a track's loop and play it back instead of its graph
+    ///
+    /// The track is rendered alone for two passes of the loop and the second
+    /// is kept, so release tails that ring past the loop point wrap into its
+    /// start exactly as they do live. Playback then costs a copy per block.
+    ///
+    /// Allocates and renders two loops' worth of audio - call before the
+    /// stream starts, not from the audio callback.
+    pub fn freeze_track(&mut self, index: usize) {
+        let mut solo = self.sequencer.solo();
+        let track = &mut self.tracks[index];
+        track.unfreeze();
+
+        let mut audio = vec![0.0; solo.loop_frames()];
+        for _pass in 0..2 {
+            render_solo(&mut solo, track, &mut audio, self.block_size, self.sample_rate);
+        }
+
+        track.freeze(audio);
+    }
+
+    /// Render what frozen track `index`'s graph plays after a stop here (allocates)
+    ///
+    /// Replays the loop as `freeze_track` does, up to the current position,
+    /// then releases the notes and renders until the tail dies away. Like
+    /// the frozen loop itself, free-running state (oscillator phase) can
+    /// differ from what the live graph would have reached.
+    fn frozen_tail(&mut self, index: usize, threshold: f32, max_seconds: f32) -> Vec<f32> {
+        let mut solo = self.sequencer.solo();
+        let stop_frame = self.sequencer.loop_frame();
+        let (block_size, sample_rate) = (self.block_size, self.sample_rate);
+        let track = &mut self.tracks[index];
+        let Some(audio) = track.unfreeze() else {
+            return Vec::new();
+        };
+
+        let mut replay = vec![0.0; solo.loop_frames() + stop_frame];
+        render_solo(&mut solo, track, &mut replay, block_size, sample_rate);
+        solo.stop(std::slice::from_mut(track), sample_rate);
+        let tail = tail::render_until_silent(
+            |block| render_solo(&mut solo, track, block, block_size, sample_rate),
+            sample_rate,
+            block_size,
+            threshold,
+            max_seconds,
+        );
+
+        track.freeze(audio);
+        tail
+    }
+
+    /// Return a frozen track to live rendering through its graph
+    ///
+    /// Drops the frozen audio (deallocates).
+    pub fn unfreeze_track(&mut self, index: usize) {
+        self.tracks[index].unfreeze();
+    }
+
+    /// Replace track `index`'s sequence at the next loop point
+    ///
+    /// See `Track::queue_sequence`. Returns a displaced, never-played
+    /// sequence for the caller to drop off the audio thread.
+    pub fn queue_sequence(&mut self, index: usize, sequence: Sequence) -> Option<Sequence> {
+        self.tracks[index].queue_sequence(sequence)
+    }
+
+    /// Take one sequence replaced at a loop point, if any track has one
+    pub fn take_retired(&mut self) -> Option<Sequence> {
+        self.tracks.iter_mut().find_map(Track::take_retired)
+    }
+
+    /// Apply a transport command from the UI
+    pub(crate) fn handle_control(&mut self, msg: ControlMessage) {
+        match msg {
+            ControlMessage::TogglePlayback => self.sequencer.toggle(),
+            ControlMessage::Reset => self.sequencer.reset(),
+            ControlMessage::SeekToTick(tick) => self.sequencer.seek(tick, &mut self.tracks, self.sample_rate),
+            ControlMessage::Looper(command) => self.command_tracks(NodeCommand::Looper(command)),
+            ControlMessage::ReverbFreeze(frozen) => self.command_tracks(NodeCommand::ReverbFreeze(frozen)),
+            // Thawed, not unfrozen: the audio thread mustn't free the loops
+            ControlMessage::UnfreezeTracks => self.tracks.iter_mut().for_each(Track::thaw),
+        }
+    }
+
+    /// Send a command to every track's graph
+    fn command_tracks(&mut self, command: NodeCommand) {
+        for track in self.tracks.iter_mut() {
+            track.command(command);
+        }
+    }
+
+    /// Retarget a parameter (ramped per sample from the next block)
+    pub(crate) fn apply_param(&mut self, change: ParamChange) {
+        match change.id {
+            ParamId::MasterGain => self.master_gain.set_target(change.value, change.ramp_secs, self.sample_rate),
+        }
+    }
+
+    /// Render one block of mono output (at most `block_size` frames)
+    ///
+    /// REAL-TIME SAFE: No allocations in this function.
+    pub fn render_block(&mut self, block: &mut [f32]) {
+        self.render_block_with(block, false);
+    }
+
+    /// Render one block, optionally summing the output buses into `block`
+    ///
+    /// With `fold_buses` the buses join the main mix before the master gain,
+    /// output stage and limiter, so the fold-down is limited as a whole.
+    fn render_block_with(&mut self, block: &mut [f32], fold_buses: bool) {
+        debug_assert!(block.len() <= self.block_size);
+        block.fill(0.0);
+        self.block_len = block.len();
+        for bus in self.buses.iter_mut() {
+            bus.buffer.fill(0.0);
+        }
+
+        // Render in segments between sequencer events so notes
+        // start on their exact frame within the block
+        let mut offset = 0;
+        // Most tracks heard in any one segment
+        let mut voices = 0;
+        while offset < block.len() {
+            // Frozen tracks follow the transport; paused means silent
+            let loop_frame = self.sequencer.is_playing().then(|| self.sequencer.loop_frame());
+            // Position at the start of the segment, before `advance` moves it
+            let transport = self.sequencer.transport(0);
+            let segment_len = self.sequencer.advance(block.len() - offset, &mut self.tracks, self.sample_rate);
+            let segment = &mut block[offset..offset + segment_len];
+
+            // Note-ons fired by `advance` start their ducks on this frame
+            for duck in self.ducks.iter_mut() {
+                if self.tracks[duck.trigger].triggered() {
+                    duck.trigger(self.sample_rate);
+                }
+            }
+            self.tracks.iter_mut().for_each(Track::clear_trigger);
+
+            // Render and mix all tracks
+            let mut sounding = 0;
+            for (index, track) in self.tracks.iter_mut().enumerate() {
+                let tbuf = &mut self.track_buf[..segment_len];
+                tbuf.fill(0.0);
+                if track.is_frozen() {
+                    match loop_frame {
+                        Some(frame) => track.play_frozen(tbuf, frame),
+                        None => track.play_frozen_tail(tbuf),
+                    }
+                } else {
+                    let transport = Transport {
+                        bar_ticks: track.sequence.bar_ticks(),
+                        ..transport
+                    };
+                    track.render(tbuf, self.sample_rate, Some(transport));
+                }
+                if let Some(compensation) = self.compensation[index].as_mut() {
+                    for sample in tbuf.iter_mut() {
+                        *sample = compensation.delay.next_sample(*sample, compensation.samples);
+                    }
+                }
+                for duck in self.ducks.iter_mut().filter(|duck| duck.target == index) {
+                    duck.apply(tbuf, self.sample_rate);
+                }
+                track.meter(tbuf, self.sample_rate);
+                if tbuf.iter().any(|s| s.abs() > VOICE_FLOOR) {
+                    sounding += 1;
+                }
+
+                // Mix into main buffer, or the track's output bus
+                let mix = match self.routes[index] {
+                    Some(bus) => &mut self.buses[bus].buffer[offset..offset + segment_len],
+                    None => &mut *segment,
+                };
+                for (out, &sample) in mix.iter_mut().zip(tbuf.iter()) {
+                    *out += sample;
+                }
+            }
+
+            voices = voices.max(sounding);
+            self.send_clock(transport, offset, segment_len);
+            offset += segment_len;
+        }
+        self.frames_rendered += block.len() as u64;
+        self.block_time = None;
+
+        let scale_voices = self.output_stage == OutputStage::VoiceScaling;
+        if scale_voices {
+            let scale = 1.0 / (voices.max(1) as f32).sqrt();
+            if scale != self.voice_scale.target() {
+                self.voice_scale.set_target(scale, VOICE_SCALING_RAMP_SECS, self.sample_rate);
+            }
+        }
+
+        if fold_buses {
+            for bus in &self.buses {
+                for (out, &sample) in block.iter_mut().zip(&bus.buffer) {
+                    *out += sample;
+                }
+            }
+        }
+
+        if fold_buses || self.buses.is_empty() {
+            self.master_gain.apply(block);
+            if scale_voices {
+                self.voice_scale.apply(block);
+            }
+            apply_output_stage(self.output_stage, block);
+            if let Some(limiter) = self.limiter.as_mut() {
+                limiter.render(block);
+            }
+            return;
+        }
+
+        // The same gain ramp for the master mix and every bus
+        let len = block.len();
+        let gain = &mut self.gain_buf[..len];
+        gain.fill(1.0);
+        self.master_gain.apply(gain);
+        if scale_voices {
+            self.voice_scale.apply(gain);
+        }
+        let buses = self.buses.iter_mut().map(|bus| (&mut bus.buffer[..len], bus.limiter.as_mut()));
+        for (out, limiter) in std::iter::once((block, self.limiter.as_mut())).chain(buses) {
+            for (sample, &g) in out.iter_mut().zip(gain.iter()) {
+                *sample *= g;
+            }
+            apply_output_stage(self.output_stage, out);
+            if let Some(limiter) = limiter {
+                limiter.render(out);
+            }
+        }
+    }
+
+    /// Report a bar or beat boundary inside a segment that started at `from`
+    ///
+    /// Segments are never longer than a block, so at most one beat lands
+    /// in each. REAL-TIME SAFE: No allocations in this function.
+    fn send_clock(&mut self, from: Transport, offset: usize, segment_len: usize) {
+        let Some(clock) = self.clock.as_mut() else {
+            return;
+        };
+        if !from.playing {
+            return;
+        }
+
+        let ppq = from.ppq.max(1) as f64;
+        let samples_per_tick = from.samples_per_beat(self.sample_rate) / ppq;
+        // Positions within float error of a beat count as on it
+        let boundary = ((from.tick - TICK_EPSILON) / ppq).ceil() * ppq;
+        let frames_away = ((boundary - from.tick) * samples_per_tick - TICK_EPSILON).ceil().max(0.0) as usize;
+        // The loop point is reported as tick 0 when the sequencer wraps
+        if frames_away >= segment_len || boundary >= self.sequencer.total_ticks() as f64 {
+            return;
+        }
+
+        let tick = boundary as u32;
+        let bar_ticks = self.clock_bar_ticks.max(1);
+        let frame = offset + frames_away;
+        // Latency delays what's heard, not the sequencer
+        let heard = frame + self.track_latency + self.limiter.as_ref().map_or(0, LookaheadLimiter::latency_samples);
+        clock.send(ClockEvent {
+            kind: if tick.is_multiple_of(bar_ticks) { ClockKind::Bar } else { ClockKind::Beat },
+            bar: tick / bar_ticks,
+            beat: (tick % bar_ticks) / from.ppq.max(1),
+            tick,
+            frame: self.frames_rendered + frame as u64,
+            at: self
+                .block_time
+                .map(|at| at + Duration::from_secs_f64(heard as f64 / self.sample_rate as f64)),
+        });
+    }
+
+    /// Render any length of output as consecutive `block_size` blocks
+    ///
+    /// Routed tracks are summed back into `out` ahead of the output stage
+    /// and limiter, so an offline render always contains every track and
+    /// the fold-down stays under the ceiling. `output_buses` is not
+    /// meaningful after this call.
+    pub fn render(&mut self, out: &mut [f32]) {
+        let block_size = self.block_size;
+        for block in out.chunks_mut(block_size) {
+            self.render_block_with(block, true);
+        }
+    }
+
+    /// Stop the sequence and render until every tail has died away (allocates)
+    ///
+    /// Held notes are released and the sequencer pauses, then blocks are
+    /// rendered until the mix stays below `threshold` (linear peak) or
+    /// `max_seconds` pass. Use after `render` for offline bounces so reverb
+    /// and delay tails aren't cut off; see `dsp::analysis::tail`.
+    pub fn render_until_silent(&mut self, threshold: f32, max_seconds: f32) -> Vec<f32> {
+        // Frozen loops end at the loop point; ring out as their graphs would
+        if self.sequencer.is_playing() {
+            for index in 0..self.tracks.len() {
+                if self.tracks[index].is_frozen() {
+                    let tail = self.frozen_tail(index, threshold, max_seconds);
+                    self.tracks[index].set_frozen_tail(tail);
+                }
+            }
+        }
+        self.sequencer.stop(&mut self.tracks, self.sample_rate);
+        let (sample_rate, block_size) = (self.sample_rate, self.block_size);
+        tail::render_until_silent(|block| self.render(block), sample_rate, block_size, threshold, max_seconds)
+    }
+}
+
+/// Render one track alone from a solo sequencer (see `Sequencer::solo`)
+///
+/// Blocks of `block_size` split at events, as `render_block` does.
+fn render_solo(solo: &mut Sequencer, track: &mut Track, out: &mut [f32], block_size: usize, sample_rate: f32) {
+    for block in out.chunks_mut(block_size) {
+        let mut offset = 0;
+        while offset < block.len() {
+            let transport = solo.transport(track.sequence.bar_ticks());
+            let frames = solo.advance(block.len() - offset, std::slice::from_mut(track), sample_rate);
+            let segment = &mut block[offset..offset + frames];
+            segment.fill(0.0);
+            track.render(segment, sample_rate, Some(transport));
+            offset += frames;
+        }
+    }
+}
+
+fn apply_output_stage(stage: OutputStage, block: &mut [f32]) {
+    match stage {
+        // Voice scaling is a gain, applied with the master gain
+        OutputStage::Off | OutputStage::VoiceScaling => {}
+        OutputStage::HardClamp => block.iter_mut().for_each(|s| *s = s.clamp(-1.0, 1.0)),
+        OutputStage::SoftClip => block.iter_mut().for_each(|s| *s = soft_limit(*s, SOFT_CLIP_KNEE)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::Saavy;
+    use crate::sequencing::{notes::*, Pattern};
+    use crate::voices;
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    fn tracks() -> Vec<Track> {
+        let pattern = Pattern::four_four(vec![C4.into(), E4.into(), G4.into(), C5.into()]);
+        vec![
+            Track::new("lead", pattern.to_sequence(480), voices::lead()),
+            Track::new("kick", Pattern::four_four(vec![C2.into()]).to_sequence(480), voices::kick()),
+        ]
+    }
+
+    #[test]
+    fn offline_render_matches_callback_blocks() {
+        // Offline: one call
+        let mut offline = Renderer::new(tracks(), 120.0, 480, SAMPLE_RATE, 256);
+        let mut expected = vec![0.0; 48_000];
+        offline.render(&mut expected);
+
+        // Realtime: device callbacks of odd sizes, each split into
+        // `block_size` chunks the way the cpal callback does
+        let mut realtime = Renderer::new(tracks(), 120.0, 480, SAMPLE_RATE, 256);
+        let mut actual = vec![0.0; 48_000];
+        let mut callbacks = [441, 97, 1024, 300, 5].into_iter().cycle();
+        let mut rest = &mut actual[..];
+        while !rest.is_empty() {
+            let frames = callbacks.next().unwrap().min(rest.len());
+            let (callback, tail) = rest.split_at_mut(frames);
+            for block in callback.chunks_mut(realtime.block_size()) {
+                realtime.render_block(block);
+            }
+            rest = tail;
+        }
+
+        assert_eq!(expected, actual);
+        assert!(expected.iter().any(|&s| s.abs() > 0.01), "render should not be silent");
+    }
+
+    #[test]
+    fn frozen_track_plays_back_its_live_loop() {
+        let lead = || vec![tracks().remove(0)];
+        let loop_frames = 96_000; // one 4/4 bar at 120 BPM
+
+        // Live: the second pass includes tails wrapped from the first
+        let mut live = Renderer::new(lead(), 120.0, 480, SAMPLE_RATE, 256);
+        let mut expected = vec![0.0; 2 * loop_frames];
+        live.render(&mut expected);
+
+        let mut frozen = Renderer::new(lead(), 120.0, 480, SAMPLE_RATE, 256);
+        frozen.freeze_track(0);
+        assert!(frozen.tracks()[0].is_frozen());
+        let mut actual = vec![0.0; loop_frames];
+        frozen.render(&mut actual);
+
+        let max_diff = expected[loop_frames..].iter().zip(&actual).map(|(a, b)| (a - b).abs()).fold(0.0, f32::max);
+        assert!(max_diff < 1e-3, "frozen audio differs by {max_diff}");
+
+        // Odd block sizes read the frozen loop frame for frame, across the loop point too
+        let mut odd = Renderer::new(lead(), 120.0, 480, SAMPLE_RATE, 256);
+        odd.freeze_track(0);
+        let mut odd_blocks = vec![0.0; loop_frames + 5_000];
+        for block in odd_blocks.chunks_mut(97) {
+            odd.render_block(block);
+        }
+        let mut even = vec![0.0; 5_000];
+        frozen.render(&mut even);
+        assert_eq!(&odd_blocks[..loop_frames], &actual[..]);
+        assert_eq!(&odd_blocks[loop_frames..], &even[..]);
+
+        frozen.handle_control(ControlMessage::UnfreezeTracks);
+        let mut live_again = vec![0.0; loop_frames];
+        frozen.render(&mut live_again);
+        assert!(!frozen.tracks()[0].is_frozen());
+        assert!(live_again.iter().any(|&s| s.abs() > 0.01), "unfrozen track should render");
+    }
+
+    #[test]
+    fn frozen_track_keeps_reporting_telemetry() {
+        use crate::graph::Telemetry;
+
+        let lead = || vec![tracks().remove(0)];
+        let mut live = Renderer::new(lead(), 120.0, 480, SAMPLE_RATE, 256);
+        let mut frozen = Renderer::new(lead(), 120.0, 480, SAMPLE_RATE, 256);
+        frozen.freeze_track(0);
+
+        // 0.1 s into the first quarter note
+        let mut block = vec![0.0; 4800];
+        live.render(&mut block);
+        frozen.render(&mut block);
+
+        let (live, frozen) = (&live.tracks()[0], &frozen.tracks()[0]);
+        assert_eq!(frozen.note(), Some(C4));
+        assert_eq!(frozen.note(), live.note());
+        assert!(frozen.is_active());
+        assert!(frozen.level() > 0.0);
+        assert!((frozen.peak() - live.peak()).abs() < 0.02, "{} vs {}", frozen.peak(), live.peak());
+    }
+
+    #[test]
+    fn nodes_see_the_transport() {
+        use crate::graph::{GraphNode, RenderCtx};
+        use std::sync::{Arc, Mutex};
+
+        /// Records the transport of every block it renders
+        struct Probe(Arc<Mutex<Vec<Transport>>>);
+        impl GraphNode for Probe {
+            fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+                out.fill(0.0);
+                self.0.lock().unwrap().extend(ctx.transport);
+            }
+        }
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sequence = crate::pattern!(3/4 => [C4, C4, C4]).to_sequence(480);
+        let probe = Track::new("probe", sequence, Probe(seen.clone()));
+        let mut renderer = Renderer::new(vec![probe], 120.0, 480, SAMPLE_RATE, 256);
+        let mut out = vec![0.0; 36_000]; // 1.5 beats at 120 BPM
+        renderer.render(&mut out);
+
+        let seen = seen.lock().unwrap();
+        let first = seen.first().unwrap();
+        assert_eq!((first.bpm, first.bar_ticks, first.tick), (120.0, 1440, 0.0));
+        assert!(first.playing);
+
+        // The last block starts within 256 frames of beat 1.5
+        let last = seen.last().unwrap();
+        assert!(last.beat() < 1.5 && last.beat() > 1.4, "beat {}", last.beat());
+        assert!((last.advanced(12_000, SAMPLE_RATE).beat() - last.beat() - 0.5).abs() < 1e-9);
+        assert!((last.bar_phase() - last.beat() / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn kick_ducks_the_pad() {
+        use crate::graph::oscillator::OscNode;
+        use crate::sequencing::PatternSlot;
+
+        // A steady sine pad, and a kick on beats 2 and 4
+        let pad = Track::new("pad", Pattern::four_four(vec![C4.into()]).to_sequence(480), OscNode::sine());
+        let kick = Track::new("kick", Pattern::four_four(vec![PatternSlot::Rest, C2.into(), PatternSlot::Rest, C2.into()]).to_sequence(480), voices::kick());
+        let mut renderer = Renderer::new(vec![pad, kick], 120.0, 480, SAMPLE_RATE, 256).with_duck(1, 0, 12.0, 0.0, 0.1);
+        // Silence the kick's audio; its note-ons still duck
+        renderer.tracks[1].freeze(Vec::new());
+
+        let mut out = vec![0.0; 48_000];
+        renderer.render(&mut out);
+        let peak = |range: std::ops::Range<usize>| out[range].iter().fold(0.0f32, |m, s| m.max(s.abs()));
+
+        assert!(peak(20_000..24_000) > 0.99, "full level before the kick");
+        assert!(peak(24_000..24_150) < 0.3, "-12 dB right after the kick");
+        assert!(peak(30_000..34_000) > 0.99, "recovered after the release");
+    }
+
+    #[test]
+    fn saavy_render_offline_length() {
+        let out = Saavy::new()
+            .bpm(120.0)
+            .track("lead", Pattern::four_four(vec![C4.into()]), voices::lead())
+            .render_offline(SAMPLE_RATE, 0.5);
+
+        assert_eq!(out.len(), 24_000);
+        assert!(out.iter().all(|s| s.is_finite()));
+    }
+
+    #[test]
+    fn tail_rings_out_after_the_sequence_stops() {
+        use crate::graph::{envelope::EnvNode, extensions::NodeExt, oscillator::OscNode};
+
+        // A whole-bar note with a 200 ms release, stopped mid-note
+        let pad = OscNode::sine().amplify(EnvNode::adsr(0.001, 0.01, 1.0, 0.2));
+        let track = Track::new("pad", Pattern::four_four(vec![C4.into()]).to_sequence(480), pad);
+        let mut renderer = Renderer::new(vec![track], 120.0, 480, SAMPLE_RATE, 256);
+        let mut out = vec![0.0; 24_000];
+        renderer.render(&mut out);
+
+        let tail = renderer.render_until_silent(0.001, 5.0);
+        let secs = tail.len() as f32 / SAMPLE_RATE;
+        assert!(tail[..256].iter().any(|s| s.abs() > 0.5), "released, not cut");
+        assert!(secs > 0.2 && secs < 0.35, "{secs} s of tail");
+        assert!(!renderer.sequencer.is_playing());
+    }
+
+    #[test]
+    fn frozen_tail_rings_out_like_the_live_graph() {
+        use crate::dsp::oscillator::PhaseMode;
+        use crate::graph::{envelope::EnvNode, extensions::NodeExt, oscillator::OscNode};
+
+        // Phase reset on each note: free-running phases drift from pass to pass
+        let pad = || {
+            let voice = OscNode::sine()
+                .with_phase(PhaseMode::Reset)
+                .amplify(EnvNode::adsr(0.001, 0.01, 1.0, 0.2));
+            vec![Track::new("pad", Pattern::four_four(vec![C4.into()]).to_sequence(480), voice)]
+        };
+        let mut live = Renderer::new(pad(), 120.0, 480, SAMPLE_RATE, 256);
+        let mut frozen = Renderer::new(pad(), 120.0, 480, SAMPLE_RATE, 256);
+        frozen.freeze_track(0);
+
+        // Stopped mid-note, half a second into the second pass (the frozen loop)
+        let mut out = vec![0.0; 96_000 + 24_000];
+        live.render(&mut out);
+        frozen.render(&mut out);
+        let expected = live.render_until_silent(0.001, 5.0);
+        let actual = frozen.render_until_silent(0.001, 5.0);
+
+        assert!(actual[..256].iter().any(|s| s.abs() > 0.5), "frozen tail cut at the stop");
+        assert_eq!(expected.len(), actual.len());
+        let max_diff = expected.iter().zip(&actual).map(|(a, b)| (a - b).abs()).fold(0.0, f32::max);
+        assert!(max_diff < 1e-3, "frozen tail differs by {max_diff}");
+        assert!(frozen.tracks()[0].is_frozen());
+    }
+
+    #[test]
+    fn clock_reports_beats_on_their_frames() {
+        use crate::runtime::clock::{clock_bus, ClockKind};
+
+        // One 4/4 bar at 120 BPM: a beat every 24 000 frames, looping
+        let track = Track::new("lead", Pattern::four_four(vec![C4.into()]).to_sequence(480), voices::lead());
+        let (tx, mut rx) = clock_bus(16);
+        let mut renderer = Renderer::new(vec![track], 120.0, 480, SAMPLE_RATE, 256).with_clock(tx);
+        let mut out = vec![0.0; 100_000];
+        renderer.render(&mut out);
+
+        let mut events = Vec::new();
+        rx.drain(|e| events.push(e));
+        let beats: Vec<_> = events.iter().map(|e| (e.kind, e.beat)).collect();
+        assert_eq!(
+            beats,
+            vec![
+                (ClockKind::Bar, 0),
+                (ClockKind::Beat, 1),
+                (ClockKind::Beat, 2),
+                (ClockKind::Beat, 3),
+                (ClockKind::Bar, 0),
+            ]
+        );
+        for (event, frame) in events.iter().zip((0..).step_by(24_000)) {
+            assert!(event.frame.abs_diff(frame) <= 1, "beat at {} not {frame}", event.frame);
+            assert_eq!(event.at, None, "no block time offline");
+        }
+    }
+
+    #[test]
+    fn seed_controls_noise() {
+        let noisy = || vec![Track::new("hats", Pattern::four_four(vec![C4.into(); 4]).to_sequence(480), voices::hihat())];
+        let render = |seed| {
+            let mut renderer = Renderer::new(noisy(), 120.0, 480, SAMPLE_RATE, 256).with_seed(seed);
+            let mut out = vec![0.0; 4800];
+            renderer.render(&mut out);
+            out
+        };
+
+        assert_eq!(render(1), render(1));
+        assert_ne!(render(1), render(2));
+    }
+
+    #[test]
+    fn tracks_get_independent_streams() {
+        let hats = || Track::new("hats", Pattern::four_four(vec![C4.into()]).to_sequence(480), voices::hihat());
+        let mut renderer = Renderer::new(vec![hats(), hats()], 120.0, 480, SAMPLE_RATE, 256);
+        let mut buffers = [vec![0.0; 256], vec![0.0; 256]];
+
+        renderer.sequencer.advance(1, &mut renderer.tracks, SAMPLE_RATE);
+        for (track, buf) in renderer.tracks.iter_mut().zip(buffers.iter_mut()) {
+            track.render(buf, SAMPLE_RATE, None);
+        }
+        assert_ne!(buffers[0], buffers[1], "identical voices should not play identical noise");
+    }
+
+    fn stack_of_voices(count: usize, stage: OutputStage) -> Vec<f32> {
+        // A stack of simultaneous notes a whole tone apart, one track per voice
+        let tracks = (0..count as u8)
+            .map(|i| Track::new("voice", Pattern::four_four(vec![(48 + 2 * i).into()]).to_sequence(480), voices::lead()))
+            .collect();
+        let mut renderer = Renderer::new(tracks, 120.0, 480, SAMPLE_RATE, 256).with_output_stage(stage);
+        let mut out = vec![0.0; 24_000];
+        renderer.render(&mut out);
+        out
+    }
+
+    fn sixteen_voices(stage: OutputStage) -> Vec<f32> {
+        stack_of_voices(16, stage)
+    }
+
+    #[test]
+    fn output_stage_tames_sixteen_voices() {
+        let raw = sixteen_voices(OutputStage::Off);
+        let raw_peak = raw.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!(raw_peak > 1.0, "16 voices should overshoot without a safety stage ({raw_peak})");
+
+        for stage in [OutputStage::HardClamp, OutputStage::SoftClip] {
+            let out = sixteen_voices(stage);
+            assert!(out.iter().all(|s| s.abs() <= 1.0), "{stage:?} let a sample past full scale");
+        }
+
+        // 1/√16 once the 20 ms ramp from the first block has settled
+        let scaled = sixteen_voices(OutputStage::VoiceScaling);
+        for (s, r) in scaled.iter().zip(&raw).skip(1_300) {
+            assert!((s - r / 4.0).abs() < 1e-6);
+        }
+
+        // A lone voice is scaled by the voices sounding, not the track count
+        let solo = stack_of_voices(1, OutputStage::VoiceScaling);
+        assert_eq!(solo, stack_of_voices(1, OutputStage::Off));
+    }
+
+    #[test]
+    fn limiter_holds_the_ceiling_and_bounces_in_time() {
+        use crate::dsp::meter::{to_db, true_peak};
+
+        let stack = || (0..16).map(|i| Pattern::four_four(vec![(48 + 2 * i).into()]));
+        let tracks = stack().map(|p| Track::new("voice", p.to_sequence(480), voices::lead())).collect();
+        let mut live = Renderer::new(tracks, 120.0, 480, SAMPLE_RATE, 256).with_limiter(-1.0, 0.003);
+        assert_eq!(live.latency_samples(), 144);
+        let mut out = vec![0.0; 24_000];
+        live.render(&mut out);
+        assert!(to_db(true_peak(&out)) <= -0.95, "{} dBTP", to_db(true_peak(&out)));
+
+        // The bounce is limited too, but starts on time
+        let bounce = stack()
+            .enumerate()
+            .fold(Saavy::new(), |app, (i, p)| app.track(&format!("v{i}"), p, voices::lead()))
+            .limiter(-1.0)
+            .render_offline(SAMPLE_RATE, 0.5);
+        let first = |audio: &[f32]| audio.iter().position(|s| s.abs() > 1e-6);
+        assert_eq!(bounce.len(), 24_000);
+        assert_eq!(first(&bounce), first(&sixteen_voices(OutputStage::Off)));
+        assert!(to_db(true_peak(&bounce)) <= -0.95);
+    }
+
+    #[test]
+    fn folded_down_buses_stay_under_the_ceiling() {
+        use crate::dsp::meter::{to_db, true_peak};
+
+        // Half the stack on each of two buses, each with its own limiter copy
+        let tracks = (0..16)
+            .map(|i| Pattern::four_four(vec![(48 + 2 * i).into()]).to_sequence(480))
+            .map(|sequence| Track::new("voice", sequence, voices::lead()))
+            .collect();
+        let mut renderer = (0..16)
+            .fold(Renderer::new(tracks, 120.0, 480, SAMPLE_RATE, 256), |r, i| r.with_output_pair(i, 1 + i % 2))
+            .with_limiter(-1.0, 0.003);
+        let mut out = vec![0.0; 24_000];
+        renderer.render(&mut out);
+
+        assert!(to_db(true_peak(&out)) <= -0.95, "{} dBTP", to_db(true_peak(&out)));
+    }
+
+    #[test]
+    fn latent_track_is_lined_up_with_the_rest() {
+        use crate::graph::{extensions::NodeExt, limiter::LimiterNode, oscillator::OscNode};
+
+        // The same note on two tracks, one through a 3 ms lookahead limiter
+        let sequence = || Pattern::four_four(vec![C4.into()]).to_sequence(480);
+        let plain = Track::new("plain", sequence(), OscNode::sine());
+        let limited = Track::new("limited", sequence(), OscNode::sine().through(LimiterNode::new(0.0)));
+        let mut renderer = Renderer::new(vec![plain, limited], 120.0, 480, SAMPLE_RATE, 256).with_output_pair(1, 1);
+        assert_eq!(renderer.latency_samples(), 144);
+
+        // The plain track (master mix) waits for the limited one (its own bus)
+        let (mut master, mut bus) = (Vec::new(), Vec::new());
+        for _ in 0..4 {
+            let mut block = vec![0.0; 256];
+            renderer.render_block(&mut block);
+            master.extend_from_slice(&block);
+            bus.extend_from_slice(renderer.output_buses().next().unwrap().1);
+        }
+        let first = |audio: &[f32]| audio.iter().position(|s| s.abs() > 1e-6);
+        assert!(first(&master) >= Some(144), "{:?}", first(&master));
+        assert_eq!(first(&master), first(&bus));
+    }
+
+    #[test]
+    fn slide_glides_to_target_pitch() {
+        use crate::graph::oscillator::OscNode;
+        use crate::sequencing::pattern::slot;
+
+        // One beat (24_000 samples) sliding C3 → C4 over the first half
+        let mut sequence = Pattern::four_four(vec![slot::slide(C3, C4)]).to_sequence(480);
+        sequence.events[0].slide.as_mut().unwrap().glide_ticks = 240;
+        let track = Track::new("303", sequence, OscNode::sine());
+        let mut renderer = Renderer::new(vec![track], 120.0, 480, SAMPLE_RATE, 256);
+        let mut out = vec![0.0; 24_000];
+        renderer.render(&mut out);
+
+        let freq = |window: &[f32]| {
+            let crossings = window.windows(2).filter(|w| w[0] <= 0.0 && w[1] > 0.0).count();
+            crossings as f32 * SAMPLE_RATE / window.len() as f32
+        };
+        let start = freq(&out[..2_400]);
+        let end = freq(&out[14_400..]);
+        assert!(start < 180.0, "start {start} Hz");
+        assert!((end - 261.6).abs() < 6.0, "end {end} Hz");
+    }
+
+    #[test]
+    fn master_gain_scales_output() {
+        let mut unity = Renderer::new(tracks(), 120.0, 480, SAMPLE_RATE, 256);
+        let mut half = Renderer::new(tracks(), 120.0, 480, SAMPLE_RATE, 256);
+        half.apply_param(ParamChange::new(ParamId::MasterGain, 0.5, 0.0));
+
+        let (mut a, mut b) = (vec![0.0; 4096], vec![0.0; 4096]);
+        unity.render(&mut a);
+        half.render(&mut b);
+
+        for (x, y) in a.iter().zip(&b) {
+            assert!((x * 0.5 - y).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn routed_track_leaves_the_master_mix() {
+        let kick_only = || vec![tracks().remove(1)];
+        let mut routed = Renderer::new(tracks(), 120.0, 480, SAMPLE_RATE, 256).with_output_pair(1, 1);
+        let mut lead = Renderer::new(vec![tracks().remove(0)], 120.0, 480, SAMPLE_RATE, 256);
+        let mut kick = Renderer::new(kick_only(), 120.0, 480, SAMPLE_RATE, 256);
+
+        let (mut master, mut lead_block, mut kick_block) = (vec![0.0; 256], vec![0.0; 256], vec![0.0; 256]);
+        routed.render_block(&mut master);
+        lead.render_block(&mut lead_block);
+        kick.render_block(&mut kick_block);
+
+        assert_eq!(master, lead_block, "kick is not in the master mix");
+        let buses: Vec<_> = routed.output_buses().collect();
+        assert_eq!(buses, vec![(1, kick_block.as_slice())]);
+
+        // Offline renders fold the buses back in
+        let mut offline = Renderer::new(tracks(), 120.0, 480, SAMPLE_RATE, 256).with_output_pair(1, 1);
+        let mut plain = Renderer::new(tracks(), 120.0, 480, SAMPLE_RATE, 256);
+        let (mut a, mut b) = (vec![0.0; 4096], vec![0.0; 4096]);
+        offline.render(&mut a);
+        plain.render(&mut b);
+        for (x, y) in a.iter().zip(&b) {
+            assert!((x - y).abs() < 1e-6);
+        }
+    }
+}
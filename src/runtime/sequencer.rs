@@ -3,7 +3,8 @@
 //! The Sequencer runs in the audio thread and converts tick-based
 //! pattern timing into sample-accurate note events.
 
-use super::track::Track;
+use super::track::{LoopTail, Track};
+use crate::graph::Transport;
 
 /// Playback state for a single track
 struct TrackPlayback {
@@ -25,10 +26,41 @@ impl TrackPlayback {
         self.event_index = 0;
         self.active_notes.clear();
     }
+
+    /// Start the next pass of the loop, handling notes still sounding
+    ///
+    /// REAL-TIME SAFE: No allocations in this function.
+    fn wrap(&mut self, track: &mut Track, total_ticks: u32, sample_rate: f32) {
+        self.event_index = 0;
+        match track.loop_tail() {
+            LoopTail::Carry => {
+                // Shift end ticks into the new pass; release what ends here
+                let mut i = 0;
+                while i < self.active_notes.len() {
+                    let (note, end_tick) = self.active_notes[i];
+                    if end_tick > total_ticks {
+                        self.active_notes[i].1 = end_tick - total_ticks;
+                        i += 1;
+                    } else {
+                        track.note_off(note, sample_rate);
+                        self.active_notes.swap_remove(i);
+                    }
+                }
+            }
+            LoopTail::Release | LoopTail::Fade(_) => {
+                for &(note, _) in &self.active_notes {
+                    track.note_off(note, sample_rate);
+                }
+                self.active_notes.clear();
+                if let LoopTail::Fade(fade_secs) = track.loop_tail() {
+                    track.fade_out(fade_secs, sample_rate);
+                }
+            }
+        }
+    }
 }
 
 /// Sample-accurate sequencer that drives multiple tracks
-#[allow(dead_code)] // Fields retained for TUI display
 pub struct Sequencer {
     /// Tempo in beats per minute
     bpm: f64,
@@ -38,6 +70,9 @@ pub struct Sequencer {
     sample_rate: f64,
     /// Current position in ticks (fractional for sub-tick accuracy)
     tick_position: f64,
+    /// Current position in whole frames since the loop start (exact, unlike
+    /// converting `tick_position` back to frames)
+    frame_position: usize,
     /// Samples per tick (computed from bpm, ppq, sample_rate)
     samples_per_tick: f64,
     /// Per-track playback state
@@ -60,6 +95,7 @@ impl Sequencer {
             ppq,
             sample_rate,
             tick_position: 0.0,
+            frame_position: 0,
             samples_per_tick,
             track_states: (0..num_tracks).map(|_| TrackPlayback::new()).collect(),
             playing: true,
@@ -81,10 +117,16 @@ impl Sequencer {
         self.total_ticks = total_ticks;
     }
 
+    /// Loop length in ticks
+    pub fn total_ticks(&self) -> u32 {
+        self.total_ticks
+    }
+
     /// Set BPM (can be called at any time)
     pub fn set_bpm(&mut self, bpm: f64) {
         self.bpm = bpm;
         self.samples_per_tick = Self::compute_samples_per_tick(bpm, self.ppq, self.sample_rate);
+        self.frame_position = (self.tick_position * self.samples_per_tick).round() as usize;
     }
 
     /// Get current tick position
@@ -92,99 +134,228 @@ impl Sequencer {
         self.tick_position as u32
     }
 
-    /// Process one block of samples, triggering note events on tracks
+    /// Tempo and position for nodes, with bars `bar_ticks` long
+    pub fn transport(&self, bar_ticks: u32) -> Transport {
+        Transport {
+            bpm: self.bpm,
+            ppq: self.ppq,
+            tick: self.tick_position,
+            bar_ticks,
+            playing: self.playing,
+        }
+    }
+
+    /// Current position as a frame offset from the start of the loop
+    ///
+    /// Counted frame by frame as `advance` runs, so consecutive blocks
+    /// neither skip nor repeat a frame.
+    pub fn loop_frame(&self) -> usize {
+        self.frame_position
+    }
+
+    /// Length of one pass through the loop in frames
+    ///
+    /// `advance` always stops on the loop point, so a full pass renders
+    /// exactly this many frames.
+    pub fn loop_frames(&self) -> usize {
+        (self.total_ticks as f64 * self.samples_per_tick).ceil() as usize
+    }
+
+    /// A fresh one-track sequencer at tick 0 with this one's timing
     ///
-    /// This should be called from the audio callback before rendering tracks.
+    /// Same tempo, resolution, and loop length; used to render a track alone.
+    pub fn solo(&self) -> Self {
+        let mut solo = Self::new(self.bpm, self.ppq, self.sample_rate, 1);
+        solo.set_total_ticks(self.total_ticks);
+        solo
+    }
+
+    /// Advance playback by up to `max_frames`, stopping at the next event
+    ///
+    /// Events due at the current position fire first, then time advances to
+    /// the frame where the next note-on, note-off, or loop point lands (or
+    /// `max_frames`, whichever is sooner). Returns the number of frames
+    /// advanced - the caller renders exactly that many frames before calling
+    /// again, so every event starts on its own frame instead of snapping to
+    /// the start of the audio block.
+    ///
+    /// Always returns at least one frame. When paused, returns `max_frames`.
     /// REAL-TIME SAFE: No allocations in this function.
-    pub fn process_block(&mut self, block_size: usize, tracks: &mut [Track], sample_rate: f32) {
-        if !self.playing {
-            return;
+    pub fn advance(&mut self, max_frames: usize, tracks: &mut [Track], sample_rate: f32) -> usize {
+        if !self.playing || self.total_ticks == 0 {
+            return max_frames;
         }
 
-        // Process each sample in the block
-        for _ in 0..block_size {
-            let current_tick = self.tick_position as u32;
+        self.fire_due_events(tracks, sample_rate);
 
-            // Process each track
-            for (track_idx, track) in tracks.iter_mut().enumerate() {
-                if track_idx >= self.track_states.len() {
-                    continue;
+        let loop_frames_left = self.loop_frames().saturating_sub(self.frame_position);
+        let frames = self.frames_until_next_event(tracks).min(loop_frames_left).clamp(1, max_frames.max(1));
+        self.tick_position += frames as f64 / self.samples_per_tick;
+        self.frame_position += frames;
+
+        // Handle looping (on the exact frame: accumulated ticks can fall a hair short)
+        if self.tick_position >= self.total_ticks as f64 || self.frame_position >= self.loop_frames() {
+            if self.looping {
+                self.tick_position = 0.0;
+                self.frame_position = 0;
+                // Notes sounding at the loop point follow each track's LoopTail,
+                // then edited sequences take over for the new pass
+                for (track, state) in tracks.iter_mut().zip(self.track_states.iter_mut()) {
+                    state.wrap(track, self.total_ticks, sample_rate);
+                    track.swap_queued();
                 }
+            } else {
+                self.playing = false;
+            }
+        }
 
-                let state = &mut self.track_states[track_idx];
+        frames
+    }
 
-                // Process note-offs FIRST - this is critical!
-                // If we did note-ons first, a new note starting at the same tick
-                // as an old note ending would have its attack clobbered by the release.
-                let mut i = 0;
-                while i < state.active_notes.len() {
-                    let (note, end_tick) = state.active_notes[i];
-                    if current_tick >= end_tick {
-                        track.note_off(note, sample_rate);
-                        // swap_remove is O(1) and doesn't allocate
-                        state.active_notes.swap_remove(i);
-                        // Don't increment i - the swapped element needs checking
+    /// Trigger every note-off and note-on due at the current tick
+    fn fire_due_events(&mut self, tracks: &mut [Track], sample_rate: f32) {
+        let current_tick = self.tick_position as u32;
+
+        for (track, state) in tracks.iter_mut().zip(self.track_states.iter_mut()) {
+            // Legato tracks tie a note ending now into one starting now
+            let note_on_due = track.sequence.events.get(state.event_index).is_some_and(|e| {
+                e.note.is_some() && e.tick_offset.saturating_add_signed(e.offset_ticks) <= current_tick
+            });
+            let tie = track.legato().is_some() && note_on_due;
+            let mut tied = false; // a note ending now was left sounding for the tie
+
+            // Process note-offs FIRST - this is critical!
+            // If we did note-ons first, a new note starting at the same tick
+            // as an old note ending would have its attack clobbered by the release.
+            let mut i = 0;
+            while i < state.active_notes.len() {
+                let (note, end_tick) = state.active_notes[i];
+                if current_tick >= end_tick {
+                    if tie {
+                        tied = true;
                     } else {
-                        i += 1;
+                        track.note_off(note, sample_rate);
                     }
+                    // swap_remove is O(1) and doesn't allocate
+                    state.active_notes.swap_remove(i);
+                    // Don't increment i - the swapped element needs checking
+                } else {
+                    i += 1;
                 }
+            }
 
-                // Process note-on events - extract data first, then trigger
-                // (avoids borrow conflict between sequence and note_on)
-                loop {
-                    if state.event_index >= track.sequence.events.len() {
-                        break;
-                    }
+            // Process note-on events - extract data first, then trigger
+            // (avoids borrow conflict between sequence and note_on)
+            while let Some(event) = track.sequence.events.get(state.event_index) {
+                let event_tick = event.tick_offset.saturating_add_signed(event.offset_ticks);
+                if event_tick > current_tick {
+                    break;
+                }
 
-                    let event = &track.sequence.events[state.event_index];
-                    let event_tick = event.tick_offset.saturating_add_signed(event.offset_ticks);
+                // Extract event data before any mutable operations
+                let note = event.note;
+                let velocity = event.velocity;
+                let duration = event.duration_ticks;
+                let slide = event.slide;
+                let expression = event.expression;
+                state.event_index += 1;
 
-                    if event_tick > current_tick {
-                        break;
+                // Now trigger note-on if this event has a note
+                if let Some(n) = note {
+                    let end_tick = current_tick + duration;
+                    match track.legato() {
+                        // Still holding a note: glide to the new one, last note wins
+                        Some(glide_secs)
+                            if (tied || !state.active_notes.is_empty()) && track.current_note().is_some() =>
+                        {
+                            state.active_notes.clear();
+                            track.legato_to(n, glide_secs, sample_rate);
+                        }
+                        _ => track.note_on(n, velocity, sample_rate),
                     }
-
-                    // Extract event data before any mutable operations
-                    let note = event.note;
-                    let velocity = event.velocity;
-                    let duration = event.duration_ticks;
-                    state.event_index += 1;
-
-                    // Now trigger note-on if this event has a note
-                    if let Some(n) = note {
-                        let end_tick = current_tick + duration;
-                        track.note_on(n, velocity, sample_rate);
-                        // Push to pre-allocated vec (capacity reserved in TrackPlayback::new)
-                        state.active_notes.push((n, end_tick));
+                    if let Some(slide) = slide {
+                        let glide_secs = (slide.glide_ticks as f64 * self.samples_per_tick) as f32 / sample_rate;
+                        track.slide_to(slide.target, glide_secs, sample_rate);
                     }
+                    if let Some(expression) = expression {
+                        let ramp_secs = (duration as f64 * self.samples_per_tick) as f32 / sample_rate;
+                        track.express(expression.start, expression.end, ramp_secs, sample_rate);
+                    }
+                    // Push to pre-allocated vec (capacity reserved in TrackPlayback::new)
+                    state.active_notes.push((n, end_tick));
                 }
             }
+        }
+    }
 
-            // Advance time
-            self.tick_position += 1.0 / self.samples_per_tick;
+    /// Frames from the current position to the next pending event or loop point
+    fn frames_until_next_event(&self, tracks: &[Track]) -> usize {
+        let mut next_tick = self.total_ticks;
 
-            // Handle looping
-            if self.tick_position >= self.total_ticks as f64 {
-                if self.looping {
-                    self.tick_position = 0.0;
-                    // Reset all track states (clear doesn't deallocate)
-                    for state in &mut self.track_states {
-                        state.reset();
-                    }
-                } else {
-                    self.playing = false;
-                }
+        for (track, state) in tracks.iter().zip(self.track_states.iter()) {
+            if let Some(event) = track.sequence.events.get(state.event_index) {
+                next_tick = next_tick.min(event.tick_offset.saturating_add_signed(event.offset_ticks));
+            }
+            for &(_, end_tick) in &state.active_notes {
+                next_tick = next_tick.min(end_tick);
             }
         }
+
+        // An event fires on the first frame whose position reaches its tick
+        let ticks_away = next_tick as f64 - self.tick_position;
+        (ticks_away * self.samples_per_tick).ceil().max(0.0) as usize
     }
 
     /// Reset playback to the beginning
     pub fn reset(&mut self) {
         self.tick_position = 0.0;
+        self.frame_position = 0;
         for state in &mut self.track_states {
             state.reset();
         }
     }
 
+    /// Jump to a tick position
+    ///
+    /// Sounding notes are released (so nothing hangs across the jump) and each
+    /// track's event index is rebuilt to the first event at or after `tick`.
+    /// Positions past the end clamp to the last tick.
+    /// REAL-TIME SAFE: No allocations in this function.
+    pub fn seek(&mut self, tick: u32, tracks: &mut [Track], sample_rate: f32) {
+        let tick = tick.min(self.total_ticks.saturating_sub(1));
+
+        self.release_all(tracks, sample_rate);
+        for (track, state) in tracks.iter_mut().zip(self.track_states.iter_mut()) {
+            // Events are sorted by effective trigger tick (see Track::new)
+            state.event_index = track
+                .sequence
+                .events
+                .partition_point(|e| e.tick_offset.saturating_add_signed(e.offset_ticks) < tick);
+        }
+
+        self.tick_position = tick as f64;
+        self.frame_position = (self.tick_position * self.samples_per_tick).round() as usize;
+    }
+
+    /// Release every sounding note and pause
+    ///
+    /// Tracks keep rendering their release tails; no new notes start until
+    /// `play`. REAL-TIME SAFE: No allocations in this function.
+    pub fn stop(&mut self, tracks: &mut [Track], sample_rate: f32) {
+        self.release_all(tracks, sample_rate);
+        self.playing = false;
+    }
+
+    /// Send note-off for every note the sequencer is holding
+    fn release_all(&mut self, tracks: &mut [Track], sample_rate: f32) {
+        for (track, state) in tracks.iter_mut().zip(self.track_states.iter_mut()) {
+            for &(note, _) in &state.active_notes {
+                track.note_off(note, sample_rate);
+            }
+            state.active_notes.clear();
+        }
+    }
+
     /// Start playback
     pub fn play(&mut self) {
         self.playing = true;
@@ -205,3 +376,242 @@ impl Sequencer {
         self.playing
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::envelope::EnvNode;
+    use crate::sequencing::{notes::*, Pattern};
+
+    const PPQ: u32 = 480;
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    fn setup() -> (Sequencer, Vec<Track>) {
+        let pattern = Pattern::four_four(vec![C4.into(), E4.into(), G4.into(), C5.into()]).repeat(2);
+        let track = Track::new("lead", pattern.to_sequence(PPQ), EnvNode::adsr(0.001, 0.1, 0.8, 0.1));
+        let mut sequencer = Sequencer::new(120.0, PPQ, SAMPLE_RATE as f64, 1);
+        sequencer.set_total_ticks(track.sequence.total_ticks);
+        (sequencer, vec![track])
+    }
+
+    // 120 BPM at 480 PPQ and 48kHz: 50 samples per tick, 24_000 per beat
+    const SAMPLES_PER_BEAT: usize = 24_000;
+
+    #[test]
+    fn advance_stops_at_next_event() {
+        let (mut sequencer, mut tracks) = setup();
+
+        // Note-on fires at frame 0, then time runs up to the next beat
+        let frames = sequencer.advance(usize::MAX, &mut tracks, SAMPLE_RATE);
+        assert_eq!(frames, SAMPLES_PER_BEAT);
+        assert_eq!(sequencer.track_states[0].active_notes.len(), 1);
+        assert_eq!(sequencer.tick_position(), 480);
+    }
+
+    #[test]
+    fn advance_respects_max_frames() {
+        let (mut sequencer, mut tracks) = setup();
+
+        let mut elapsed = 0;
+        while elapsed < SAMPLES_PER_BEAT - 64 {
+            elapsed += sequencer.advance(64, &mut tracks, SAMPLE_RATE);
+        }
+
+        // Last partial block ends exactly on the beat boundary
+        assert_eq!(sequencer.advance(64, &mut tracks, SAMPLE_RATE), SAMPLES_PER_BEAT - elapsed);
+        assert_eq!(sequencer.track_states[0].event_index, 1);
+    }
+
+    /// One bar: a rest, then C4 held to the loop point, released over `release` s
+    fn held_at_loop_point(release: f32) -> (Sequencer, Vec<Track>) {
+        use crate::sequencing::PatternSlot;
+
+        let pattern = Pattern::four_four(vec![PatternSlot::Rest, C4.into()]);
+        let track = Track::new("pad", pattern.to_sequence(PPQ), EnvNode::adsr(0.001, 0.1, 0.8, release));
+        let mut sequencer = Sequencer::new(120.0, PPQ, SAMPLE_RATE as f64, 1);
+        sequencer.set_total_ticks(track.sequence.total_ticks);
+        (sequencer, vec![track])
+    }
+
+    /// Drive the sequencer and tracks like the renderer, returning the output
+    fn run(sequencer: &mut Sequencer, tracks: &mut [Track], frames: usize) -> Vec<f32> {
+        let mut out = vec![0.0; frames];
+        let mut offset = 0;
+        while offset < frames {
+            let len = sequencer.advance((frames - offset).min(256), tracks, SAMPLE_RATE);
+            tracks[0].render(&mut out[offset..offset + len], SAMPLE_RATE, None);
+            offset += len;
+        }
+        out
+    }
+
+    #[test]
+    fn loop_point_releases_held_notes() {
+        let (mut sequencer, mut tracks) = held_at_loop_point(0.01);
+
+        // One loop plus most of the opening rest
+        run(&mut sequencer, &mut tracks, SAMPLES_PER_BEAT * 4 + 20_000);
+        assert!(!tracks[0].is_active(), "note should release at the loop point, not hang through the rest");
+    }
+
+    #[test]
+    fn carry_holds_notes_across_the_loop_point() {
+        let (mut sequencer, mut tracks) = held_at_loop_point(0.01);
+        tracks[0].set_loop_tail(LoopTail::Carry);
+        // Stretch C4 one beat past the end of the bar
+        tracks[0].sequence.events.last_mut().unwrap().duration_ticks += PPQ;
+
+        run(&mut sequencer, &mut tracks, SAMPLES_PER_BEAT * 4 + 100);
+        assert_eq!(sequencer.track_states[0].active_notes, vec![(C4, PPQ)]);
+        assert!(tracks[0].is_active());
+
+        run(&mut sequencer, &mut tracks, SAMPLES_PER_BEAT + 2_000);
+        assert!(sequencer.track_states[0].active_notes.is_empty());
+        assert!(!tracks[0].is_active());
+    }
+
+    #[test]
+    fn fade_silences_long_releases_at_the_loop_point() {
+        let (mut sequencer, mut tracks) = held_at_loop_point(2.0);
+        tracks[0].set_loop_tail(LoopTail::Fade(0.01));
+
+        let out = run(&mut sequencer, &mut tracks, SAMPLES_PER_BEAT * 4 + 2_400);
+        assert!(tracks[0].is_active(), "envelope is still releasing");
+        assert_eq!(out[out.len() - 1], 0.0);
+    }
+
+    #[test]
+    fn expression_ramps_pressure_over_the_note() {
+        use crate::graph::pressure::PressureNode;
+        use crate::sequencing::pattern::slot;
+
+        // A whole-bar swell from silence to full pressure
+        let pattern = Pattern::four_four(vec![slot::swell(C4, 0, 127)]);
+        let mut tracks = vec![Track::new("pad", pattern.to_sequence(PPQ), PressureNode::new())];
+        let mut sequencer = Sequencer::new(120.0, PPQ, SAMPLE_RATE as f64, 1);
+        sequencer.set_total_ticks(tracks[0].sequence.total_ticks);
+
+        let out = run(&mut sequencer, &mut tracks, SAMPLES_PER_BEAT * 4 - 32);
+        assert!(out.windows(2).all(|w| w[1] >= w[0]), "rising");
+        assert_eq!(out[0], 0.0);
+        assert!((out[SAMPLES_PER_BEAT * 2] - 0.5).abs() < 0.01);
+        assert!(out[out.len() - 1] > 0.99);
+    }
+
+    #[test]
+    fn legato_ties_touching_notes_into_one_glide() {
+        use crate::graph::{GraphNode, RenderCtx};
+        use crate::sequencing::PatternSlot;
+        use std::sync::{Arc, Mutex};
+
+        /// Counts note-ons and records the last frequency rendered
+        struct Probe(Arc<Mutex<(usize, f32)>>);
+        impl GraphNode for Probe {
+            fn render_block(&mut self, out: &mut [f32], ctx: &RenderCtx) {
+                out.fill(0.0);
+                self.0.lock().unwrap().1 = ctx.frequency;
+            }
+            fn note_on(&mut self, _ctx: &RenderCtx) {
+                self.0.lock().unwrap().0 += 1;
+            }
+        }
+
+        // C3 tied into E3, then a gap before G3
+        let seen = Arc::new(Mutex::new((0, 0.0)));
+        let pattern = Pattern::four_four(vec![C3.into(), E3.into(), PatternSlot::Rest, G3.into()]);
+        let mut tracks = vec![Track::new("bass", pattern.to_sequence(PPQ), Probe(seen.clone()))];
+        tracks[0].set_legato(Some(0.05));
+        let mut sequencer = Sequencer::new(120.0, PPQ, SAMPLE_RATE as f64, 1);
+        sequencer.set_total_ticks(tracks[0].sequence.total_ticks);
+
+        run(&mut sequencer, &mut tracks, SAMPLES_PER_BEAT * 2 - 100);
+        let (note_ons, freq) = *seen.lock().unwrap();
+        assert_eq!(note_ons, 1, "E3 glides from C3 without retriggering");
+        assert!((freq - 164.81).abs() < 0.1, "{freq} Hz");
+        assert_eq!(tracks[0].current_note(), Some(E3));
+
+        run(&mut sequencer, &mut tracks, SAMPLES_PER_BEAT * 2);
+        assert_eq!(seen.lock().unwrap().0, 2, "G3 after a rest retriggers");
+    }
+
+    #[test]
+    fn queued_sequence_takes_over_at_the_loop_point() {
+        let (mut sequencer, mut tracks) = setup();
+        let edited = Pattern::four_four(vec![G4.into()]).repeat(2).to_sequence(PPQ);
+        assert!(tracks[0].queue_sequence(edited).is_none());
+
+        // Halfway through the pass the old sequence is still playing
+        run(&mut sequencer, &mut tracks, SAMPLES_PER_BEAT * 4 + 100);
+        assert_eq!(tracks[0].current_note(), Some(C4));
+        assert_eq!(tracks[0].sequence.events.len(), 8);
+
+        run(&mut sequencer, &mut tracks, SAMPLES_PER_BEAT * 4);
+        assert_eq!(tracks[0].current_note(), Some(G4));
+        assert_eq!(tracks[0].sequence.events.len(), 2);
+        assert_eq!(tracks[0].take_retired().map(|s| s.events.len()), Some(8));
+    }
+
+    #[test]
+    fn advance_while_paused_consumes_whole_block() {
+        let (mut sequencer, mut tracks) = setup();
+        sequencer.pause();
+
+        assert_eq!(sequencer.advance(512, &mut tracks, SAMPLE_RATE), 512);
+        assert_eq!(sequencer.tick_position(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "rt-check")]
+    fn advance_and_seek_do_not_allocate() {
+        use crate::rt_check::assert_no_alloc;
+
+        let (mut sequencer, mut tracks) = setup();
+        let mut buffer = [0.0; 256];
+
+        assert_no_alloc(|| {
+            // Two full loops of the pattern, rendering like the audio callback
+            let mut elapsed = 0;
+            while elapsed < SAMPLES_PER_BEAT * 16 {
+                let frames = sequencer.advance(buffer.len(), &mut tracks, SAMPLE_RATE);
+                tracks[0].render(&mut buffer[..frames], SAMPLE_RATE, None);
+                elapsed += frames;
+            }
+            sequencer.seek(1920, &mut tracks, SAMPLE_RATE);
+        });
+    }
+
+    #[test]
+    fn seek_rebuilds_event_index() {
+        let (mut sequencer, mut tracks) = setup();
+
+        // Jump to bar 2: the first four events are skipped
+        sequencer.seek(1920, &mut tracks, SAMPLE_RATE);
+
+        assert_eq!(sequencer.tick_position(), 1920);
+        assert_eq!(sequencer.track_states[0].event_index, 4);
+    }
+
+    #[test]
+    fn seek_releases_sounding_notes() {
+        let (mut sequencer, mut tracks) = setup();
+
+        // Play into the first note
+        sequencer.advance(64, &mut tracks, SAMPLE_RATE);
+        assert_eq!(sequencer.track_states[0].active_notes.len(), 1);
+
+        sequencer.seek(0, &mut tracks, SAMPLE_RATE);
+
+        assert!(sequencer.track_states[0].active_notes.is_empty());
+        assert_eq!(sequencer.track_states[0].event_index, 0);
+    }
+
+    #[test]
+    fn seek_past_end_clamps() {
+        let (mut sequencer, mut tracks) = setup();
+
+        sequencer.seek(u32::MAX, &mut tracks, SAMPLE_RATE);
+
+        assert_eq!(sequencer.tick_position(), 3839);
+        assert_eq!(sequencer.track_states[0].event_index, 8);
+    }
+}
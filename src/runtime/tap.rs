@@ -0,0 +1,118 @@
+//! Audio tap - block-based sample ring for visualizers
+//!
+//! The audio thread writes whole blocks with one `write_slice` call, and the
+//! UI reads everything that arrived since its last frame into a fixed-size
+//! rolling window. Both sides move contiguous chunks (at most two memcpys
+//! around the ring's wrap point) instead of pushing and popping one sample
+//! at a time.
+
+use rtrb::{Consumer, Producer, RingBuffer};
+
+/// Create a tap that buffers up to `capacity` samples between reads
+pub fn audio_tap(capacity: usize) -> (TapWriter, TapReader) {
+    let (tx, rx) = RingBuffer::new(capacity);
+    (TapWriter { tx }, TapReader { rx })
+}
+
+/// Audio-thread end of the tap
+pub struct TapWriter {
+    tx: Producer<f32>,
+}
+
+impl TapWriter {
+    /// Write a block of samples. If the ring is nearly full, the samples that
+    /// don't fit are dropped (the UI just misses them). Returns the count written.
+    ///
+    /// REAL-TIME SAFE: No allocations in this function.
+    pub fn write_slice(&mut self, samples: &[f32]) -> usize {
+        let count = samples.len().min(self.tx.slots());
+        match self.tx.write_chunk_uninit(count) {
+            Ok(chunk) => chunk.fill_from_iter(samples[..count].iter().copied()),
+            Err(_) => 0,
+        }
+    }
+}
+
+/// UI end of the tap
+pub struct TapReader {
+    rx: Consumer<f32>,
+}
+
+impl TapReader {
+    /// Drain all pending samples into the rolling window `out`
+    ///
+    /// Existing contents shift left and the newest samples land at the end,
+    /// so `out` always holds the most recent `out.len()` samples. Returns
+    /// how many new samples arrived.
+    pub fn read_latest(&mut self, out: &mut [f32]) -> usize {
+        let available = self.rx.slots();
+        let Ok(chunk) = self.rx.read_chunk(available) else {
+            return 0;
+        };
+        let (first, second) = chunk.as_slices();
+        let window = out.len();
+
+        if available >= window {
+            // Only the newest `window` samples matter
+            let skip = available - window;
+            if skip < first.len() {
+                let head = &first[skip..];
+                out[..head.len()].copy_from_slice(head);
+                out[head.len()..].copy_from_slice(second);
+            } else {
+                out.copy_from_slice(&second[skip - first.len()..]);
+            }
+        } else {
+            out.copy_within(available.., 0);
+            let tail = &mut out[window - available..];
+            tail[..first.len()].copy_from_slice(first);
+            tail[first.len()..].copy_from_slice(second);
+        }
+
+        chunk.commit_all();
+        available
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_keeps_newest_samples() {
+        let (mut writer, mut reader) = audio_tap(16);
+        let mut window = [0.0; 4];
+
+        writer.write_slice(&[1.0, 2.0]);
+        assert_eq!(reader.read_latest(&mut window), 2);
+        assert_eq!(window, [0.0, 0.0, 1.0, 2.0]);
+
+        writer.write_slice(&[3.0, 4.0, 5.0, 6.0, 7.0]);
+        assert_eq!(reader.read_latest(&mut window), 5);
+        assert_eq!(window, [4.0, 5.0, 6.0, 7.0]);
+    }
+
+    #[test]
+    fn reads_across_ring_wrap() {
+        let (mut writer, mut reader) = audio_tap(8);
+        let mut window = [0.0; 6];
+
+        // Advance the ring so the next write wraps around the end
+        writer.write_slice(&[0.0; 6]);
+        reader.read_latest(&mut window);
+
+        writer.write_slice(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        reader.read_latest(&mut window);
+        assert_eq!(window, [0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn full_ring_drops_overflow() {
+        let (mut writer, mut reader) = audio_tap(4);
+        assert_eq!(writer.write_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]), 4);
+
+        let mut window = [0.0; 4];
+        reader.read_latest(&mut window);
+        assert_eq!(window, [1.0, 2.0, 3.0, 4.0]);
+    }
+}
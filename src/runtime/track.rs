@@ -4,10 +4,35 @@
 //! Polyphony is achieved by creating multiple tracks.
 
 use crate::{
-    graph::{GraphNode, RenderCtx},
-    sequencing::Sequence,
+    dsp::{envelope::EnvelopeState, meter, smooth::SmoothedParam, tuning::midi_to_hz},
+    graph::{GraphNode, NodeCommand, RenderCtx, Telemetry, Transport},
+    sequencing::{Duration, Sequence},
 };
 
+/// Samples per frequency update while a slide is gliding
+const GLIDE_CHUNK: usize = 32;
+
+/// Seconds for the peak reading to fall by a factor of e
+const PEAK_RELEASE_SECS: f32 = 0.3;
+
+/// Peak below which a frozen track counts as silent
+const SILENCE_PEAK: f32 = 1e-4;
+
+/// What happens to a note still sounding when the sequence loops
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LoopTail {
+    /// Send note-off at the loop point; the envelope releases naturally
+    #[default]
+    Release,
+    /// Keep the note held into the next pass for the rest of its duration
+    Carry,
+    /// Send note-off and fade the track out over this many seconds
+    ///
+    /// For voices with long releases that shouldn't bleed into the top of
+    /// the loop. The next note-on restores full level.
+    Fade(f32),
+}
+
 /// A monophonic track - one voice playing a sequence
 pub struct Track {
     /// Display name
@@ -20,6 +45,32 @@ pub struct Track {
     current_note: Option<u8>,
     /// Current velocity
     velocity: f32,
+    /// Sounding pitch in (fractional) MIDI notes, ramped during slides
+    pitch: SmoothedParam,
+    /// Held-note pressure (0-127), ramped by sequence expression
+    pressure: SmoothedParam,
+    /// Pre-rendered loop played back instead of the node (see `freeze`)
+    frozen: Option<Vec<f32>>,
+    /// Frozen audio set aside by `thaw`, freed by the next `freeze` or drop
+    thawed: Option<Vec<f32>>,
+    /// What the graph plays after a stop, while frozen (see `set_frozen_tail`)
+    frozen_tail: Option<Vec<f32>>,
+    /// Next frame of `frozen_tail` to play
+    tail_frame: usize,
+    /// Handling of notes sounding at the loop point
+    loop_tail: LoopTail,
+    /// Mono-legato glide time: overlapping notes glide instead of retriggering
+    legato: Option<f32>,
+    /// Output level, ramped to zero by `LoopTail::Fade`
+    fade: SmoothedParam,
+    /// A note-on arrived since the renderer last looked (drives ducking)
+    triggered: bool,
+    /// Sequence waiting to replace `sequence` at the next loop point
+    queued: Option<Sequence>,
+    /// Sequence replaced at the loop point, waiting to be freed off the audio thread
+    retired: Option<Sequence>,
+    /// Decaying peak of the track's output (see `meter`)
+    peak: f32,
 }
 
 impl Track {
@@ -29,12 +80,7 @@ impl Track {
         mut sequence: Sequence,
         node: N,
     ) -> Self {
-        // Sort events by effective trigger time (tick_offset + offset_ticks)
-        // This is necessary because negative offsets (swing/humanization) can
-        // cause events to fire earlier than their tick_offset suggests
-        sequence.events.sort_by_key(|e| {
-            e.tick_offset.saturating_add_signed(e.offset_ticks)
-        });
+        sort_by_trigger_tick(&mut sequence);
 
         Self {
             name: name.into(),
@@ -42,20 +88,156 @@ impl Track {
             node: Box::new(node),
             current_note: None,
             velocity: 0.0,
+            pitch: SmoothedParam::new(0.0),
+            pressure: SmoothedParam::new(0.0),
+            frozen: None,
+            thawed: None,
+            frozen_tail: None,
+            tail_frame: 0,
+            loop_tail: LoopTail::Release,
+            legato: None,
+            fade: SmoothedParam::new(1.0),
+            triggered: false,
+            queued: None,
+            retired: None,
+            peak: 0.0,
+        }
+    }
+
+    /// Choose how notes sounding at the loop point are handled
+    pub fn set_loop_tail(&mut self, loop_tail: LoopTail) {
+        self.loop_tail = loop_tail;
+    }
+
+    /// How notes sounding at the loop point are handled
+    pub fn loop_tail(&self) -> LoopTail {
+        self.loop_tail
+    }
+
+    /// Play legato: a note starting while another is held glides to it
+    ///
+    /// Instead of a note-off and a fresh note-on (retriggering envelopes),
+    /// the sounding voice glides to the new pitch over `glide_secs` and
+    /// keeps going. Notes count as held up to and including their last
+    /// tick, so back-to-back notes tie too; put a gap between notes to
+    /// retrigger. The newest note always wins (last-note priority).
+    /// `None` returns to retriggering every note.
+    pub fn set_legato(&mut self, glide_secs: Option<f32>) {
+        self.legato = glide_secs.map(|secs| secs.max(0.0));
+    }
+
+    /// Legato glide time, if the track plays legato
+    pub fn legato(&self) -> Option<f32> {
+        self.legato
+    }
+
+    /// Swing this track's sequence (see `Sequence::apply_swing`)
+    pub fn apply_swing(&mut self, amount: f32, base: Duration) {
+        self.sequence.apply_swing(amount, base);
+        sort_by_trigger_tick(&mut self.sequence);
+    }
+
+    /// Replace this track's sequence when the sequencer next loops
+    ///
+    /// Double-buffered so edits never land mid-pass: the sequencer swaps the
+    /// new sequence in at the loop point and the old one moves to
+    /// `take_retired`. Returns a previously queued sequence that never got
+    /// to play. `sequence` must already be sorted by trigger tick (tick plus
+    /// microtiming offset), as `Track::new` leaves it.
+    ///
+    /// REAL-TIME SAFE: moves the sequence, never allocates or frees.
+    pub fn queue_sequence(&mut self, sequence: Sequence) -> Option<Sequence> {
+        self.queued.replace(sequence)
+    }
+
+    /// Swap in the queued sequence (called by the sequencer at the loop point)
+    ///
+    /// Waits another pass if the last retired sequence hasn't been collected.
+    pub(crate) fn swap_queued(&mut self) {
+        if self.retired.is_none() {
+            if let Some(next) = self.queued.take() {
+                self.retired = Some(std::mem::replace(&mut self.sequence, next));
+            }
         }
     }
 
+    /// Take the sequence replaced at the last loop point, to drop off the audio thread
+    pub fn take_retired(&mut self) -> Option<Sequence> {
+        self.retired.take()
+    }
+
+    /// Configure the node for the device sample rate and block size
+    ///
+    /// Call before the audio stream starts (may allocate).
+    pub fn prepare(&mut self, sample_rate: f32, max_block: usize) {
+        self.node.prepare(sample_rate, max_block);
+    }
+
+    /// Samples the track's graph delays its sound by (see `GraphNode::latency_samples`)
+    ///
+    /// A frozen loop was rendered through the same graph, so it runs just as late.
+    pub fn latency_samples(&self) -> usize {
+        self.node.latency_samples()
+    }
+
+    /// Give this track's voice its own random stream
+    pub fn seed(&mut self, seed: u64) {
+        self.node.seed(seed);
+    }
+
     /// Trigger a note on this track
     pub fn note_on(&mut self, note: u8, velocity: u8, sample_rate: f32) {
+        self.triggered = true;
         self.current_note = Some(note);
+        // A frozen track's notes are already in its audio
+        if self.frozen.is_some() {
+            return;
+        }
         self.velocity = velocity as f32;
+        self.pitch.snap(note as f32);
+        self.pressure.snap(0.0);
+        self.fade.snap(1.0);
 
         let ctx = RenderCtx::from_note(sample_rate, note, self.velocity);
         self.node.note_on(&ctx);
     }
 
+    /// Move the held voice to `note` without retriggering (see `set_legato`)
+    pub fn legato_to(&mut self, note: u8, glide_secs: f32, sample_rate: f32) {
+        self.current_note = Some(note);
+        if self.frozen.is_some() {
+            return;
+        }
+        self.pitch.set_target(note as f32, glide_secs, sample_rate);
+    }
+
+    /// Glide the sounding note's pitch to `target` over `glide_secs`
+    ///
+    /// The glide is linear in semitones, so it sounds even across octaves.
+    pub fn slide_to(&mut self, target: u8, glide_secs: f32, sample_rate: f32) {
+        self.pitch.set_target(target as f32, glide_secs, sample_rate);
+    }
+
+    /// Ramp the sounding note's pressure from `start` to `end` (0-127) over `ramp_secs`
+    pub fn express(&mut self, start: u8, end: u8, ramp_secs: f32, sample_rate: f32) {
+        self.pressure.snap(start as f32);
+        self.pressure.set_target(end as f32, ramp_secs, sample_rate);
+    }
+
+    /// Ramp the track's output to silence over `fade_secs`
+    pub fn fade_out(&mut self, fade_secs: f32, sample_rate: f32) {
+        self.fade.set_target(0.0, fade_secs, sample_rate);
+    }
+
     /// Release the current note
     pub fn note_off(&mut self, note: u8, sample_rate: f32) {
+        // Frozen: nothing to release, just stop reporting the note
+        if self.frozen.is_some() {
+            if self.current_note == Some(note) {
+                self.current_note = None;
+            }
+            return;
+        }
         // Only release if it's the note we're playing
         if self.current_note == Some(note) {
             let ctx = RenderCtx::from_note(sample_rate, note, 0.0);
@@ -64,11 +246,41 @@ impl Track {
         }
     }
 
+    /// Send a command to the track's node (see `GraphNode::command`)
+    pub fn command(&mut self, command: NodeCommand) {
+        self.node.command(command);
+    }
+
     /// Render audio into the buffer
-    pub fn render(&mut self, out: &mut [f32], sample_rate: f32) {
-        if let Some(note) = self.current_note {
-            let ctx = RenderCtx::from_note(sample_rate, note, self.velocity);
-            self.node.render_block(out, &ctx);
+    ///
+    /// `transport` is the song position at the start of `out`, passed on to
+    /// the node in its `RenderCtx`.
+    pub fn render(&mut self, out: &mut [f32], sample_rate: f32, transport: Option<Transport>) {
+        if self.current_note.is_some() {
+            let velocity = self.velocity;
+            let ctx = |pitch: &SmoothedParam, pressure: &SmoothedParam, transport: Option<Transport>| RenderCtx {
+                transport,
+                pressure: pressure.value(),
+                ..RenderCtx::from_freq(sample_rate, midi_to_hz(pitch.value()), velocity)
+            };
+            if self.pitch.is_smoothing() || self.pressure.is_smoothing() {
+                // Gliding: update the frequency and pressure every few samples
+                let mut transport = transport;
+                for chunk in out.chunks_mut(GLIDE_CHUNK) {
+                    self.node.render_block(chunk, &ctx(&self.pitch, &self.pressure, transport));
+                    for _ in 0..chunk.len() {
+                        self.pitch.next_value();
+                        self.pressure.next_value();
+                    }
+                    transport = transport.map(|t| t.advanced(chunk.len(), sample_rate));
+                }
+            } else {
+                self.node.render_block(out, &ctx(&self.pitch, &self.pressure, transport));
+            }
+
+            if self.fade.is_smoothing() || self.fade.value() < 1.0 {
+                self.fade.apply(out);
+            }
 
             // Check if the node is done (envelope finished)
             if !self.node.is_active() {
@@ -80,13 +292,110 @@ impl Track {
         }
     }
 
+    /// Replace live rendering with a pre-rendered loop
+    ///
+    /// `audio` holds one loop, starting at tick 0. While frozen, note events
+    /// are ignored and `play_frozen` reads from the buffer instead of running
+    /// the graph. The sounding note (if any) is dropped.
+    pub fn freeze(&mut self, audio: Vec<f32>) {
+        self.current_note = None;
+        self.frozen = Some(audio);
+        self.thawed = None;
+        self.frozen_tail = None;
+    }
+
+    /// Return to live rendering, handing back the frozen audio
+    ///
+    /// The graph picks up again from the next note event.
+    pub fn unfreeze(&mut self) -> Option<Vec<f32>> {
+        self.frozen.take()
+    }
+
+    /// Return to live rendering from the audio thread
+    ///
+    /// Like `unfreeze`, but the audio is kept (not freed) until the next
+    /// `freeze` or until the track is dropped.
+    /// REAL-TIME SAFE: No allocations in this function.
+    pub fn thaw(&mut self) {
+        if let Some(audio) = self.frozen.take() {
+            self.thawed = Some(audio);
+        }
+    }
+
+    /// Check if this track plays pre-rendered audio
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.is_some()
+    }
+
+    /// Copy frozen audio starting at `loop_frame` into the buffer
+    ///
+    /// Frames past the end of the loop are silent.
+    pub fn play_frozen(&self, out: &mut [f32], loop_frame: usize) {
+        out.fill(0.0);
+        if let Some(audio) = &self.frozen {
+            let start = loop_frame.min(audio.len());
+            let end = (start + out.len()).min(audio.len());
+            out[..end - start].copy_from_slice(&audio[start..end]);
+        }
+    }
+
+    /// Audio the graph rings out with after the transport stops
+    ///
+    /// A frozen loop holds nothing past its end, so the renderer renders the
+    /// released notes' tails through the graph and plays them here while
+    /// stopped, from the start.
+    pub fn set_frozen_tail(&mut self, tail: Vec<f32>) {
+        self.frozen_tail = Some(tail);
+        self.tail_frame = 0;
+    }
+
+    /// Copy the next frames of the frozen tail into the buffer
+    ///
+    /// Silent once the tail (if any) has played out.
+    /// REAL-TIME SAFE: No allocations in this function.
+    pub fn play_frozen_tail(&mut self, out: &mut [f32]) {
+        out.fill(0.0);
+        if let Some(tail) = &self.frozen_tail {
+            let start = self.tail_frame.min(tail.len());
+            let end = (start + out.len()).min(tail.len());
+            out[..end - start].copy_from_slice(&tail[start..end]);
+            self.tail_frame = end;
+        }
+    }
+
+    /// Update the peak reading from a block of this track's final output
+    ///
+    /// Called by the renderer after ducking, for live and frozen tracks alike.
+    pub(crate) fn meter(&mut self, out: &[f32], sample_rate: f32) {
+        let decay = (-(out.len() as f32) / (PEAK_RELEASE_SECS * sample_rate)).exp();
+        self.peak = meter::peak(out).max(self.peak * decay);
+    }
+
+    /// Whether a note-on arrived since `clear_trigger`
+    pub(crate) fn triggered(&self) -> bool {
+        self.triggered
+    }
+
+    pub(crate) fn clear_trigger(&mut self) {
+        self.triggered = false;
+    }
+
     /// Check if this track is currently producing sound
     pub fn is_active(&self) -> bool {
+        if self.is_frozen() {
+            return self.state() != EnvelopeState::Idle;
+        }
         self.current_note.is_some() && self.node.is_active()
     }
 
     /// Get the envelope level (for visualization)
+    ///
+    /// `None` while frozen or if the voice has no envelope; `Telemetry::level`
+    /// always has a reading.
     pub fn envelope_level(&self) -> Option<f32> {
+        if self.is_frozen() {
+            return None;
+        }
         self.node.get_envelope_level()
     }
 
@@ -95,3 +404,43 @@ impl Track {
         self.current_note
     }
 }
+
+/// Frozen tracks report the frozen audio: level follows its peak, and the
+/// stage is Sustain while a note is held and Release while its tail rings.
+impl Telemetry for Track {
+    fn level(&self) -> f32 {
+        if self.is_frozen() {
+            return self.peak.min(1.0);
+        }
+        self.node.get_envelope_level().unwrap_or(0.0)
+    }
+
+    fn state(&self) -> EnvelopeState {
+        if self.is_frozen() {
+            return match (self.current_note, self.peak > SILENCE_PEAK) {
+                (Some(_), _) => EnvelopeState::Sustain,
+                (None, true) => EnvelopeState::Release,
+                (None, false) => EnvelopeState::Idle,
+            };
+        }
+        self.node.get_envelope_state().unwrap_or(EnvelopeState::Idle)
+    }
+
+    fn note(&self) -> Option<u8> {
+        self.current_note
+    }
+
+    fn peak(&self) -> f32 {
+        self.peak
+    }
+}
+
+/// Sort events by effective trigger time (tick_offset + offset_ticks)
+///
+/// This is necessary because offsets (swing/humanization) can cause events
+/// to fire earlier or later than their tick_offset suggests.
+pub(crate) fn sort_by_trigger_tick(sequence: &mut Sequence) {
+    sequence
+        .events
+        .sort_by_key(|e| e.tick_offset.saturating_add_signed(e.offset_ticks));
+}
@@ -4,6 +4,7 @@
 
 pub mod state;
 mod spectrum;
+mod steps;
 mod timeline;
 mod transport;
 mod waveform;
@@ -16,26 +17,54 @@ use ratatui::{
     DefaultTerminal, Frame,
 };
 use rtrb::Consumer;
+use std::sync::Arc;
 use std::time::Duration;
 
-pub use state::{ControlMessage, TrackDynamicState, TrackStaticInfo, UiStateInit, UiStateUpdate};
+use super::edits::SequenceSender;
+use super::monitor::CallbackMonitor;
+use super::params::{ParamChange, ParamId, ParamSender};
+use super::tap::TapReader;
+use crate::dsp::meter::LufsMeter;
+use crate::graph::looper::LooperCommand;
+use crate::sequencing::{midi, Sequence};
+
+pub use state::{ControlMessage, TrackDynamicState, TrackStaticInfo, UiStateInit, UiStateUpdate, MAX_UI_TRACKS};
 
 use spectrum::{render_spectrum, SpectrumAnalyzer};
+use steps::{render_step_editor, StepEditor};
 use timeline::render_timeline;
 use transport::{render_transport, AudioStats};
 use waveform::render_waveform;
 
 /// Audio visualization buffer size
 const VIS_BUFFER_SIZE: usize = 1024;
+/// File written by the MIDI export command (relative to the working directory)
+const MIDI_EXPORT_PATH: &str = "saavy.mid";
+/// Master volume change per key press (dB)
+const MASTER_GAIN_STEP_DB: f32 = 1.5;
+/// Master volume range (dB)
+const MASTER_GAIN_RANGE_DB: (f32, f32) = (-60.0, 6.0);
+/// Ramp time for master volume changes, long enough to avoid zipper noise
+const MASTER_GAIN_RAMP_SECS: f32 = 0.05;
+/// Velocity change per key press in the step editor
+const STEP_VELOCITY_STEP: i32 = 8;
 
 /// UI application state
 pub struct UiApp {
-    /// Ring buffer receiver for audio samples
-    audio_rx: Consumer<f32>,
+    /// Block-based tap of the audio output
+    audio_rx: TapReader,
     /// Ring buffer receiver for UI state updates (allocation-free)
     state_rx: Consumer<UiStateUpdate>,
     /// Ring buffer sender for control messages
     control_tx: rtrb::Producer<ControlMessage>,
+    /// Parameter bus sender for smoothed parameter changes
+    param_tx: ParamSender,
+    /// Sequence bus sender for step edits (applied at the next loop)
+    sequence_tx: SequenceSender,
+    /// Callback deadline monitor shared with the audio thread
+    monitor: Arc<CallbackMonitor>,
+    /// Master volume in dB (the audio thread receives linear gain)
+    master_gain_db: f32,
     /// Static state (set once at init, never changes)
     static_state: UiStateInit,
     /// Current dynamic state (updated from audio thread)
@@ -44,6 +73,14 @@ pub struct UiApp {
     audio_buffer: Vec<f32>,
     /// Spectrum analyzer for frequency visualization
     spectrum: SpectrumAnalyzer,
+    /// Loudness meter fed with every sample that arrives from the tap
+    loudness: LufsMeter,
+    /// Step editor, while edit mode is on
+    editor: Option<StepEditor>,
+    /// Last status message shown in the help bar (e.g. export result)
+    status: Option<String>,
+    /// Whether the reverbs were last told to freeze
+    reverb_frozen: bool,
     /// Whether the app should quit
     should_quit: bool,
 }
@@ -51,20 +88,32 @@ pub struct UiApp {
 impl UiApp {
     /// Create a new UI application
     pub fn new(
-        audio_rx: Consumer<f32>,
+        audio_rx: TapReader,
         state_rx: Consumer<UiStateUpdate>,
         control_tx: rtrb::Producer<ControlMessage>,
+        param_tx: ParamSender,
+        sequence_tx: SequenceSender,
+        monitor: Arc<CallbackMonitor>,
         static_state: UiStateInit,
     ) -> Self {
         let spectrum = SpectrumAnalyzer::new(VIS_BUFFER_SIZE, static_state.sample_rate);
+        let loudness = LufsMeter::new(static_state.sample_rate);
         Self {
             audio_rx,
             state_rx,
             control_tx,
+            param_tx,
+            sequence_tx,
+            monitor,
+            master_gain_db: 0.0,
             static_state,
             dynamic_state: UiStateUpdate::new(),
             audio_buffer: vec![0.0; VIS_BUFFER_SIZE],
             spectrum,
+            loudness,
+            editor: None,
+            status: None,
+            reverb_frozen: false,
             should_quit: false,
         }
     }
@@ -78,6 +127,9 @@ impl UiApp {
             // Poll for state updates
             self.poll_state();
 
+            // Free sequences replaced by step edits
+            self.sequence_tx.collect_garbage();
+
             // Draw the UI
             terminal.draw(|frame| self.render(frame))?;
 
@@ -94,21 +146,14 @@ impl UiApp {
         Ok(())
     }
 
-    /// Poll for new audio samples from ring buffer
+    /// Poll for new audio samples from the tap
     fn poll_audio(&mut self) {
-        // Read as many samples as available, keeping last VIS_BUFFER_SIZE
-        let mut new_samples = Vec::new();
-        while let Ok(sample) = self.audio_rx.pop() {
-            new_samples.push(sample);
-        }
-
-        if !new_samples.is_empty() {
-            // Append new samples and keep only the last VIS_BUFFER_SIZE
-            self.audio_buffer.extend(new_samples);
-            if self.audio_buffer.len() > VIS_BUFFER_SIZE {
-                let excess = self.audio_buffer.len() - VIS_BUFFER_SIZE;
-                self.audio_buffer.drain(0..excess);
-            }
+        // Rolling window: keeps the last VIS_BUFFER_SIZE samples
+        let arrived = self.audio_rx.read_latest(&mut self.audio_buffer);
+        if arrived > 0 {
+            // Only the new samples go to the loudness meter (the rest were measured last frame)
+            let fresh = arrived.min(self.audio_buffer.len());
+            self.loudness.process(&self.audio_buffer[self.audio_buffer.len() - fresh..]);
 
             // Update spectrum analyzer with current buffer
             self.spectrum.update(&self.audio_buffer);
@@ -125,6 +170,9 @@ impl UiApp {
 
     /// Handle keyboard input
     fn handle_key(&mut self, key: KeyCode) {
+        if self.editor.is_some() && self.handle_edit_key(key) {
+            return;
+        }
         match key {
             KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
                 self.should_quit = true;
@@ -135,10 +183,157 @@ impl UiApp {
             KeyCode::Char('r') | KeyCode::Char('R') => {
                 let _ = self.control_tx.push(ControlMessage::Reset);
             }
+            KeyCode::Left => {
+                let tick = self.static_state.previous_bar(self.dynamic_state.tick_position);
+                let _ = self.control_tx.push(ControlMessage::SeekToTick(tick));
+            }
+            KeyCode::Right => {
+                let tick = self.static_state.next_bar(self.dynamic_state.tick_position);
+                let _ = self.control_tx.push(ControlMessage::SeekToTick(tick));
+            }
+            KeyCode::Home => {
+                let _ = self.control_tx.push(ControlMessage::SeekToTick(0));
+            }
+            KeyCode::Char('l') | KeyCode::Char('L') => {
+                let _ = self.control_tx.push(ControlMessage::Looper(LooperCommand::Record));
+            }
+            KeyCode::Char('o') | KeyCode::Char('O') => {
+                let _ = self.control_tx.push(ControlMessage::Looper(LooperCommand::Overdub));
+            }
+            KeyCode::Char('p') | KeyCode::Char('P') => {
+                let _ = self.control_tx.push(ControlMessage::Looper(LooperCommand::Play));
+            }
+            KeyCode::Char('x') | KeyCode::Char('X') => {
+                let _ = self.control_tx.push(ControlMessage::Looper(LooperCommand::Stop));
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                let _ = self.control_tx.push(ControlMessage::Looper(LooperCommand::Clear));
+            }
+            KeyCode::Char('f') | KeyCode::Char('F') => {
+                self.reverb_frozen = !self.reverb_frozen;
+                let _ = self.control_tx.push(ControlMessage::ReverbFreeze(self.reverb_frozen));
+            }
+            KeyCode::Char('u') | KeyCode::Char('U') => {
+                let _ = self.control_tx.push(ControlMessage::UnfreezeTracks);
+            }
+            KeyCode::Char('m') | KeyCode::Char('M') => {
+                self.export_midi();
+            }
+            KeyCode::Char('e') | KeyCode::Char('E') if !self.static_state.tracks.is_empty() => {
+                self.editor = Some(StepEditor::new(&self.static_state));
+            }
+            KeyCode::Char('-') => self.nudge_master_gain(-MASTER_GAIN_STEP_DB),
+            KeyCode::Char('=') | KeyCode::Char('+') => self.nudge_master_gain(MASTER_GAIN_STEP_DB),
             _ => {}
         }
     }
 
+    /// Handle a key in step edit mode; returns false to fall through to the
+    /// normal bindings (transport, volume, quit)
+    fn handle_edit_key(&mut self, key: KeyCode) -> bool {
+        let Some(editor) = self.editor.as_mut() else {
+            return false;
+        };
+        let cursor = editor.cursor;
+        match key {
+            KeyCode::Esc | KeyCode::Char('e') | KeyCode::Char('E') => {
+                self.editor = None;
+                return true;
+            }
+            KeyCode::Up => editor.select_track(-1),
+            KeyCode::Down => editor.select_track(1),
+            KeyCode::Left => editor.move_cursor(-1),
+            KeyCode::Right => editor.move_cursor(1),
+            KeyCode::Enter => {
+                editor.grid().toggle(cursor);
+                self.commit_step_edit();
+            }
+            KeyCode::Char('[') => {
+                editor.grid().transpose(cursor, -1);
+                self.commit_step_edit();
+            }
+            KeyCode::Char(']') => {
+                editor.grid().transpose(cursor, 1);
+                self.commit_step_edit();
+            }
+            KeyCode::Char('{') => {
+                editor.grid().transpose(cursor, -12);
+                self.commit_step_edit();
+            }
+            KeyCode::Char('}') => {
+                editor.grid().transpose(cursor, 12);
+                self.commit_step_edit();
+            }
+            KeyCode::Char(',') => {
+                editor.grid().nudge_velocity(cursor, -STEP_VELOCITY_STEP);
+                self.commit_step_edit();
+            }
+            KeyCode::Char('.') => {
+                editor.grid().nudge_velocity(cursor, STEP_VELOCITY_STEP);
+                self.commit_step_edit();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Rebuild the edited track's sequence and send it to the audio thread
+    ///
+    /// The UI's copy (timeline, MIDI export) updates now; playback picks the
+    /// new sequence up at the next loop point.
+    fn commit_step_edit(&mut self) {
+        let Some(editor) = self.editor.as_ref() else {
+            return;
+        };
+        let track = &mut self.static_state.tracks[editor.track];
+        let sequence = editor.grids[editor.track].to_sequence(&track.sequence);
+
+        track.events = sequence
+            .events
+            .iter()
+            .filter_map(|e| e.note.map(|_| (e.tick_offset, e.duration_ticks)))
+            .collect();
+        track.sequence = sequence.clone();
+
+        self.status = Some(if self.sequence_tx.send(editor.track, sequence) {
+            format!("{}: edit plays from the next loop", track.name)
+        } else {
+            String::from("Sequence bus full, edit not sent")
+        });
+    }
+
+    /// Step the master volume and send the new gain over the parameter bus
+    fn nudge_master_gain(&mut self, delta_db: f32) {
+        let (min_db, max_db) = MASTER_GAIN_RANGE_DB;
+        self.master_gain_db = (self.master_gain_db + delta_db).clamp(min_db, max_db);
+
+        let gain = 10f32.powf(self.master_gain_db / 20.0);
+        let change = ParamChange::new(ParamId::MasterGain, gain, MASTER_GAIN_RAMP_SECS);
+        self.status = Some(if self.param_tx.send(change) {
+            format!("Master {:+.1} dB", self.master_gain_db)
+        } else {
+            String::from("Parameter bus full, change dropped")
+        });
+    }
+
+    /// Export all tracks to a Standard MIDI File at the current BPM
+    ///
+    /// Runs on the UI thread - file I/O never touches the audio callback.
+    fn export_midi(&mut self) {
+        let tracks: Vec<(&str, &Sequence)> = self
+            .static_state
+            .tracks
+            .iter()
+            .map(|t| (t.name.as_str(), &t.sequence))
+            .collect();
+        let bytes = midi::to_smf(&tracks, self.static_state.bpm, self.static_state.ppq);
+
+        self.status = Some(match std::fs::write(MIDI_EXPORT_PATH, bytes) {
+            Ok(()) => format!("Exported {} tracks to {}", tracks.len(), MIDI_EXPORT_PATH),
+            Err(err) => format!("MIDI export failed: {}", err),
+        });
+    }
+
     /// Render the UI
     fn render(&self, frame: &mut Frame) {
         let area = frame.area();
@@ -155,18 +350,30 @@ impl UiApp {
             .split(area);
 
         // Compute audio stats for transport display
-        let audio_stats = AudioStats::from_buffer(&self.audio_buffer);
+        let audio_stats = AudioStats::from_buffer(&self.audio_buffer, self.loudness.short_term());
 
         // Transport bar
-        render_transport(frame, chunks[0], &self.static_state, &self.dynamic_state, &audio_stats);
+        let callback_stats = self.monitor.snapshot();
+        render_transport(
+            frame,
+            chunks[0],
+            &self.static_state,
+            &self.dynamic_state,
+            &audio_stats,
+            &callback_stats,
+        );
 
-        // Timeline with pattern blocks
+        // Timeline with pattern blocks, or the step grid in edit mode
+        let title = if self.editor.is_some() { " Step Edit " } else { " Timeline " };
         let timeline_block = Block::default()
-            .title(" Timeline ")
+            .title(title)
             .borders(Borders::ALL);
         let timeline_inner = timeline_block.inner(chunks[1]);
         frame.render_widget(timeline_block, chunks[1]);
-        render_timeline(frame, timeline_inner, &self.static_state, &self.dynamic_state);
+        match &self.editor {
+            Some(editor) => render_step_editor(frame, timeline_inner, editor, &self.static_state, &self.dynamic_state),
+            None => render_timeline(frame, timeline_inner, &self.static_state, &self.dynamic_state),
+        }
 
         // Visualizers: waveform and spectrum side-by-side
         let viz_chunks = Layout::default()
@@ -180,10 +387,17 @@ impl UiApp {
         render_waveform(frame, viz_chunks[0], &self.audio_buffer);
         render_spectrum(frame, viz_chunks[1], self.spectrum.data());
 
-        // Help bar
-        let help = ratatui::widgets::Paragraph::new(
-            " [Q] Quit  [Space] Play/Pause  [R] Reset"
-        )
+        // Help bar (status message appended after the key hints)
+        let mut help_text = String::from(if self.editor.is_some() {
+            " [E/Esc] Done  [↑/↓] Track  [←/→] Step  [Enter] Toggle  [[/]] Pitch  [{/}] Octave  [,/.] Velocity  [Space] Play/Pause"
+        } else {
+            " [Q] Quit  [Space] Play/Pause  [R] Reset  [←/→] Bar  [Home] Start  [-/+] Volume  [M] Export MIDI  [E] Edit steps  [L/O/P/X/C] Loop rec/dub/play/stop/clear  [F] Freeze reverb  [U] Unfreeze tracks"
+        });
+        if let Some(status) = &self.status {
+            help_text.push_str("  |  ");
+            help_text.push_str(status);
+        }
+        let help = ratatui::widgets::Paragraph::new(help_text)
         .style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray));
         frame.render_widget(help, chunks[3]);
     }
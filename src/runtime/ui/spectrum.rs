@@ -1,6 +1,7 @@
 //! Spectrum analyzer widget
 //!
-//! FFT-based frequency spectrum visualization with log-spaced bins.
+//! Draws the log-binned output of `dsp::analysis::Spectrum`. Without the
+//! `rustfft` feature the panel stays empty.
 
 use ratatui::{
     layout::Rect,
@@ -9,141 +10,54 @@ use ratatui::{
     widgets::{Axis, Block, Borders, Chart, Dataset, GraphType},
     Frame,
 };
-use rustfft::{num_complex::Complex, Fft, FftPlanner};
-use std::sync::Arc;
 
 /// Number of frequency bins to display
+#[cfg(feature = "rustfft")]
 const SPECTRUM_BINS: usize = 48;
+/// Run the FFT every N UI frames (~30 Hz at the ~60 fps event loop)
+#[cfg(feature = "rustfft")]
+const UPDATE_INTERVAL: usize = 2;
 
-/// Spectrum analyzer with FFT processing
+/// Throttled spectrum for the UI
 pub struct SpectrumAnalyzer {
-    /// Hann window coefficients
-    window: Vec<f32>,
-    /// Frequency values for each bin (Hz)
-    freq_bins: Vec<f64>,
-    /// FFT bin indices corresponding to each frequency
-    bin_indices: Vec<usize>,
-    /// FFT processor
-    fft: Arc<dyn Fft<f32>>,
-    /// Scratch buffer for FFT computation
-    scratch: Vec<Complex<f32>>,
-    /// Current spectrum data: (frequency_hz, magnitude_db)
-    spectrum: Vec<(f64, f64)>,
+    #[cfg(feature = "rustfft")]
+    spectrum: crate::dsp::analysis::Spectrum,
     /// Frame counter for update throttling
+    #[cfg(feature = "rustfft")]
     frame_counter: usize,
-    /// Update every N frames
-    update_interval: usize,
 }
 
 impl SpectrumAnalyzer {
-    /// Create a new spectrum analyzer
-    ///
-    /// # Arguments
-    /// * `buffer_len` - FFT size (should match audio buffer length)
-    /// * `sample_rate` - Audio sample rate in Hz
+    /// Create a spectrum of `buffer_len` samples (the FFT size)
+    #[cfg_attr(not(feature = "rustfft"), allow(unused_variables))]
     pub fn new(buffer_len: usize, sample_rate: f32) -> Self {
-        let mut planner = FftPlanner::new();
-        let fft = planner.plan_fft_forward(buffer_len);
-
-        // Hann window - reduces spectral leakage
-        let window: Vec<f32> = (0..buffer_len)
-            .map(|i| {
-                if buffer_len > 1 {
-                    let denom = (buffer_len - 1) as f32;
-                    0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / denom).cos())
-                } else {
-                    1.0
-                }
-            })
-            .collect();
-
-        // Log-spaced frequency bins (20 Hz to Nyquist)
-        let mut freq_bins = Vec::with_capacity(SPECTRUM_BINS);
-        let mut bin_indices = Vec::with_capacity(SPECTRUM_BINS);
-        let max_freq = (sample_rate / 2.0).min(20_000.0).max(1.0);
-        let min_freq = 20.0f32.min(max_freq);
-        let ratio = if max_freq > min_freq {
-            (max_freq / min_freq) as f64
-        } else {
-            1.0
-        };
-        let half = buffer_len.saturating_div(2).max(1);
-
-        for i in 0..SPECTRUM_BINS {
-            let t = if SPECTRUM_BINS > 1 {
-                i as f64 / (SPECTRUM_BINS - 1) as f64
-            } else {
-                0.0
-            };
-            let freq = if ratio > 1.0 {
-                min_freq as f64 * ratio.powf(t)
-            } else {
-                min_freq as f64 + (max_freq as f64 - min_freq as f64) * t
-            };
-            let mut index = (freq * buffer_len as f64 / sample_rate as f64).round() as usize;
-            if index >= half {
-                index = half - 1;
-            }
-            freq_bins.push(freq);
-            bin_indices.push(index);
-        }
-
-        let scratch = vec![Complex::new(0.0, 0.0); buffer_len];
-        let spectrum = freq_bins.iter().map(|&f| (f, -120.0)).collect();
-
         Self {
-            window,
-            freq_bins,
-            bin_indices,
-            fft,
-            scratch,
-            spectrum,
+            #[cfg(feature = "rustfft")]
+            spectrum: crate::dsp::analysis::Spectrum::new(buffer_len, sample_rate, SPECTRUM_BINS),
+            #[cfg(feature = "rustfft")]
             frame_counter: 0,
-            update_interval: 1,
         }
     }
 
-    /// Update the spectrum from new audio samples
-    ///
-    /// Only processes if buffer length matches and update interval elapsed.
+    /// Update the spectrum from new audio samples (every `UPDATE_INTERVAL` calls)
+    #[cfg_attr(not(feature = "rustfft"), allow(unused_variables))]
     pub fn update(&mut self, buffer: &[f32]) {
-        if buffer.len() != self.window.len() {
-            return;
-        }
-
-        let should_update =
-            self.frame_counter % self.update_interval == 0 || self.spectrum.is_empty();
-        self.frame_counter = self.frame_counter.wrapping_add(1);
-
-        if !should_update {
-            return;
-        }
-
-        // Apply window and prepare for FFT
-        for (i, sample) in buffer.iter().enumerate() {
-            self.scratch[i].re = *sample * self.window[i];
-            self.scratch[i].im = 0.0;
-        }
-
-        // Compute FFT
-        self.fft.process(&mut self.scratch);
-
-        // Extract magnitudes at log-spaced frequencies
-        let half = (self.scratch.len() / 2).max(1);
-        for (i, &idx) in self.bin_indices.iter().enumerate() {
-            if let Some((freq, magnitude_db)) = self.spectrum.get_mut(i) {
-                let index = idx.min(half.saturating_sub(1));
-                let bin = self.scratch[index];
-                let power = (bin.re * bin.re + bin.im * bin.im).max(1e-12);
-                *freq = self.freq_bins[i];
-                *magnitude_db = 10.0 * (power as f64).log10();
+        #[cfg(feature = "rustfft")]
+        {
+            let should_update = self.frame_counter.is_multiple_of(UPDATE_INTERVAL);
+            self.frame_counter = self.frame_counter.wrapping_add(1);
+            if should_update {
+                self.spectrum.analyze(buffer);
             }
         }
     }
 
-    /// Get the current spectrum data
+    /// Current spectrum: (frequency_hz, magnitude_db)
     pub fn data(&self) -> &[(f64, f64)] {
-        &self.spectrum
+        #[cfg(feature = "rustfft")]
+        return self.spectrum.data();
+        #[cfg(not(feature = "rustfft"))]
+        &[]
     }
 }
 
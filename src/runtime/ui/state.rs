@@ -3,6 +3,9 @@
 //! Designed for real-time safety: static data is sent once at init,
 //! dynamic updates are allocation-free.
 
+use crate::graph::looper::LooperCommand;
+use crate::sequencing::Sequence;
+
 /// Commands sent from UI thread to audio thread
 #[derive(Clone, Copy, Debug)]
 pub enum ControlMessage {
@@ -10,6 +13,14 @@ pub enum ControlMessage {
     TogglePlayback,
     /// Reset to beginning
     Reset,
+    /// Jump to a tick position (sounding notes are released)
+    SeekToTick(u32),
+    /// Drive the loopers on every track
+    Looper(LooperCommand),
+    /// Freeze (true) or thaw (false) the reverbs on every track
+    ReverbFreeze(bool),
+    /// Return every frozen track to live rendering through its graph
+    UnfreezeTracks,
 }
 
 /// Static state sent once at initialization (can allocate)
@@ -34,8 +45,13 @@ pub struct TrackStaticInfo {
     pub name: String,
     /// Pattern events for timeline visualization (tick, duration)
     pub events: Vec<(u32, u32)>,
+    /// Full sequence (notes and velocities) for MIDI export
+    pub sequence: Sequence,
 }
 
+/// Tracks the TUI can display (extra tracks still play, unseen)
+pub const MAX_UI_TRACKS: usize = 8;
+
 /// Dynamic state update sent from audio thread (allocation-free, Copy)
 #[derive(Clone, Copy, Debug)]
 pub struct UiStateUpdate {
@@ -43,10 +59,16 @@ pub struct UiStateUpdate {
     pub tick_position: u32,
     /// Whether playback is active
     pub is_playing: bool,
-    /// Per-track dynamic state (fixed-size array for up to 8 tracks)
-    pub track_states: [TrackDynamicState; 8],
+    /// Per-track dynamic state (fixed-size array for up to `MAX_UI_TRACKS` tracks)
+    pub track_states: [TrackDynamicState; MAX_UI_TRACKS],
     /// Number of active tracks
     pub num_tracks: u8,
+    /// Frames in the last device callback (the actual buffer size)
+    pub buffer_frames: u32,
+    /// Time spent inside the last audio callback (microseconds)
+    pub callback_micros: u32,
+    /// Estimated output latency reported by the device (microseconds, 0 = unknown)
+    pub output_latency_micros: u32,
 }
 
 /// Dynamic state for a single track (Copy, no allocations)
@@ -54,10 +76,6 @@ pub struct UiStateUpdate {
 pub struct TrackDynamicState {
     /// Whether the track is currently producing sound
     pub is_active: bool,
-    /// Current envelope level (0.0-1.0)
-    pub envelope_level: f32,
-    /// Current note being played (0 = none, 1-127 = MIDI note)
-    pub current_note: u8,
 }
 
 impl UiStateInit {
@@ -71,6 +89,29 @@ impl UiStateInit {
             tracks,
         }
     }
+
+    /// Ticks per bar in the first track's time signature (4/4 with no tracks)
+    pub fn bar_ticks(&self) -> u32 {
+        self.tracks
+            .first()
+            .map_or(self.ppq * 4, |track| track.sequence.time_signature.bar_ticks(self.ppq))
+            .max(1)
+    }
+
+    /// Start of the bar after the one containing `tick`, wrapping to 0 past the end
+    pub fn next_bar(&self, tick: u32) -> u32 {
+        let next = (tick / self.bar_ticks() + 1) * self.bar_ticks();
+        if next >= self.total_ticks {
+            0
+        } else {
+            next
+        }
+    }
+
+    /// Start of the bar before the one containing `tick` (stays on the first bar)
+    pub fn previous_bar(&self, tick: u32) -> u32 {
+        (tick / self.bar_ticks()).saturating_sub(1) * self.bar_ticks()
+    }
 }
 
 impl UiStateUpdate {
@@ -79,8 +120,34 @@ impl UiStateUpdate {
         Self {
             tick_position: 0,
             is_playing: true,
-            track_states: [TrackDynamicState::default(); 8],
+            track_states: [TrackDynamicState::default(); MAX_UI_TRACKS],
             num_tracks: 0,
+            buffer_frames: 0,
+            callback_micros: 0,
+            output_latency_micros: 0,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequencing::{notes::*, Pattern};
+
+    #[test]
+    fn bars_follow_the_time_signature_and_wrap() {
+        let sequence = Pattern::three_four(vec![C4.into(), E4.into(), G4.into()]).repeat(2).to_sequence(480);
+        let track = TrackStaticInfo {
+            name: "waltz".into(),
+            events: Vec::new(),
+            sequence,
+        };
+        let state = UiStateInit::new(120.0, 480, 2880, 48_000.0, vec![track]);
+
+        assert_eq!(state.bar_ticks(), 1440);
+        assert_eq!(state.next_bar(100), 1440);
+        assert_eq!(state.next_bar(1500), 0, "Right on the last bar wraps to the first");
+        assert_eq!(state.previous_bar(1500), 0);
+        assert_eq!(state.previous_bar(100), 0);
+    }
+}
@@ -0,0 +1,389 @@
+//! Step editor - a track's sequence as a row of toggleable steps
+//!
+//! The grid is the sequence quantized to its own resolution: a sixteenth
+//! note, or finer if any note starts between sixteenths (triplets get a
+//! triplet grid), but never finer than `MAX_STEPS_PER_BEAT`. A step holds
+//! every note starting in it - chords included - and each keeps its
+//! original position, length, microtiming and slide until the step is
+//! toggled off, so a capped grid still round-trips. Steps toggled on last
+//! one step and take the nearest earlier note's pitch.
+//!
+//! Edits rebuild the whole sequence on the UI thread; the audio thread
+//! swaps it in at the next loop point (see `runtime::edits`).
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::{UiStateInit, UiStateUpdate};
+use crate::runtime::track::sort_by_trigger_tick;
+use crate::sequencing::{grid::gcd, note_name, Expression, Sequence, SequenceEvent, Slide};
+
+/// Pitch of a step toggled on with no earlier note to copy
+const DEFAULT_NOTE: u8 = 60;
+/// Velocity of a newly toggled step
+const DEFAULT_VELOCITY: u8 = 100;
+/// Finest grid: septuplets and the like would otherwise make a step a tick or two
+const MAX_STEPS_PER_BEAT: u32 = 12;
+/// Velocity glyphs, quietest to loudest
+const VELOCITY_GLYPHS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One note on the grid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub note: u8,
+    pub velocity: u8,
+    /// Ticks after the step's start (nonzero only on a capped grid)
+    position_ticks: u32,
+    duration_ticks: u32,
+    offset_ticks: i32,
+    slide: Option<Slide>,
+    expression: Option<Expression>,
+}
+
+/// A sequence as fixed-size steps, each empty or holding the notes starting in it
+#[derive(Clone, Debug)]
+pub struct StepGrid {
+    step_ticks: u32,
+    steps: Vec<Vec<Step>>,
+}
+
+impl StepGrid {
+    /// Quantize `sequence` to the finest grid (sixteenths or smaller) its notes start on
+    pub fn from_sequence(sequence: &Sequence) -> Self {
+        let finest = (sequence.ppq / MAX_STEPS_PER_BEAT).max(1);
+        let step_ticks = sequence
+            .events
+            .iter()
+            .filter(|e| e.note.is_some())
+            .fold((sequence.ppq / 4).max(1), |step, e| gcd(step, e.tick_offset))
+            .max(finest);
+
+        let mut steps = vec![Vec::new(); sequence.total_ticks.div_ceil(step_ticks) as usize];
+        for event in &sequence.events {
+            let (Some(note), Some(step)) = (event.note, steps.get_mut((event.tick_offset / step_ticks) as usize)) else {
+                continue;
+            };
+            step.push(Step {
+                note,
+                velocity: event.velocity,
+                position_ticks: event.tick_offset % step_ticks,
+                duration_ticks: event.duration_ticks,
+                offset_ticks: event.offset_ticks,
+                slide: event.slide,
+                expression: event.expression,
+            });
+        }
+
+        Self { step_ticks, steps }
+    }
+
+    /// Rebuild a sequence from the grid, keeping `template`'s meter and length
+    pub fn to_sequence(&self, template: &Sequence) -> Sequence {
+        let events: Vec<SequenceEvent> = self
+            .steps
+            .iter()
+            .enumerate()
+            .flat_map(|(i, notes)| {
+                notes.iter().map(move |step| SequenceEvent {
+                    tick_offset: i as u32 * self.step_ticks + step.position_ticks,
+                    duration_ticks: step.duration_ticks,
+                    note: Some(step.note),
+                    velocity: step.velocity,
+                    offset_ticks: step.offset_ticks,
+                    slide: step.slide,
+                    expression: step.expression,
+                })
+            })
+            .collect();
+        let mut sequence = Sequence {
+            time_signature: template.time_signature,
+            ppq: template.ppq,
+            events,
+            total_ticks: template.total_ticks,
+        };
+        // Swing and humanize can move a note past its neighbours, and the
+        // track plays events in trigger order
+        sort_by_trigger_tick(&mut sequence);
+        sequence
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn step_ticks(&self) -> u32 {
+        self.step_ticks
+    }
+
+    /// First note on a step, if any
+    pub fn get(&self, index: usize) -> Option<Step> {
+        self.notes(index).first().copied()
+    }
+
+    /// Every note on a step, in sequence order
+    pub fn notes(&self, index: usize) -> &[Step] {
+        self.steps.get(index).map_or(&[], Vec::as_slice)
+    }
+
+    /// Turn a step off, or on with the pitch of the nearest earlier note
+    pub fn toggle(&mut self, index: usize) {
+        if index >= self.steps.len() {
+            return;
+        }
+        let note = self.steps[..index]
+            .iter()
+            .rev()
+            .chain(self.steps[index..].iter())
+            .find_map(|notes| notes.first().map(|s| s.note))
+            .unwrap_or(DEFAULT_NOTE);
+
+        let notes = &mut self.steps[index];
+        if notes.is_empty() {
+            notes.push(Step {
+                note,
+                velocity: DEFAULT_VELOCITY,
+                position_ticks: 0,
+                duration_ticks: self.step_ticks,
+                offset_ticks: 0,
+                slide: None,
+                expression: None,
+            });
+        } else {
+            notes.clear();
+        }
+    }
+
+    /// Move a step's notes (and their slide targets) by `semitones`
+    pub fn transpose(&mut self, index: usize, semitones: i32) {
+        let shift = |note: u8| (note as i32 + semitones).clamp(0, 127) as u8;
+        for step in self.steps.get_mut(index).into_iter().flatten() {
+            step.note = shift(step.note);
+            if let Some(slide) = &mut step.slide {
+                slide.target = shift(slide.target);
+            }
+        }
+    }
+
+    /// Change a step's velocities by `delta` (kept within 1-127)
+    pub fn nudge_velocity(&mut self, index: usize, delta: i32) {
+        for step in self.steps.get_mut(index).into_iter().flatten() {
+            step.velocity = (step.velocity as i32 + delta).clamp(1, 127) as u8;
+        }
+    }
+}
+
+/// Step edit mode: which track and step the cursor is on
+pub struct StepEditor {
+    /// Selected track
+    pub track: usize,
+    /// Selected step
+    pub cursor: usize,
+    /// One grid per track, built when edit mode starts
+    pub grids: Vec<StepGrid>,
+}
+
+impl StepEditor {
+    pub fn new(static_state: &UiStateInit) -> Self {
+        Self {
+            track: 0,
+            cursor: 0,
+            grids: static_state
+                .tracks
+                .iter()
+                .map(|t| StepGrid::from_sequence(&t.sequence))
+                .collect(),
+        }
+    }
+
+    pub fn grid(&mut self) -> &mut StepGrid {
+        &mut self.grids[self.track]
+    }
+
+    /// Select the track `delta` rows away (wrapping), keeping the cursor in range
+    pub fn select_track(&mut self, delta: isize) {
+        let count = self.grids.len() as isize;
+        self.track = (self.track as isize + delta).rem_euclid(count.max(1)) as usize;
+        self.cursor = self.cursor.min(self.grids[self.track].len().saturating_sub(1));
+    }
+
+    /// Move the cursor `delta` steps (wrapping)
+    pub fn move_cursor(&mut self, delta: isize) {
+        let len = self.grids[self.track].len() as isize;
+        self.cursor = (self.cursor as isize + delta).rem_euclid(len.max(1)) as usize;
+    }
+}
+
+/// Render the selected track's steps with the cursor and playhead
+pub fn render_step_editor(
+    frame: &mut Frame,
+    area: Rect,
+    editor: &StepEditor,
+    static_state: &UiStateInit,
+    dynamic_state: &UiStateUpdate,
+) {
+    if area.height < 4 || area.width < 20 {
+        return;
+    }
+    let grid = &editor.grids[editor.track];
+    let mut lines = Vec::new();
+
+    // Track selector
+    let mut tabs = Vec::new();
+    for (i, track) in static_state.tracks.iter().enumerate() {
+        let style = if i == editor.track {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        tabs.push(Span::styled(format!(" {} ", track.name), style));
+        tabs.push(Span::raw(" "));
+    }
+    lines.push(Line::from(tabs));
+
+    // Scroll so the cursor stays in view
+    let visible = area.width as usize;
+    let first = editor
+        .cursor
+        .saturating_sub(visible / 2)
+        .min(grid.len().saturating_sub(visible));
+    let steps = first..grid.len().min(first + visible);
+
+    let ppq = static_state.ppq;
+    let playing_step = (dynamic_state.tick_position / grid.step_ticks()) as usize;
+
+    // Beat markers above the first step of each beat
+    let markers: String = steps
+        .clone()
+        .map(|i| {
+            let tick = i as u32 * grid.step_ticks();
+            if tick.is_multiple_of(ppq) {
+                '|'
+            } else {
+                ' '
+            }
+        })
+        .collect();
+    lines.push(Line::from(Span::styled(markers, Style::default().fg(Color::DarkGray))));
+
+    // Steps: velocity glyph for notes, dot for empty
+    let cells: Vec<Span> = steps
+        .clone()
+        .map(|i| {
+            let (ch, color) = match grid.get(i) {
+                Some(step) => (VELOCITY_GLYPHS[(step.velocity as usize * VELOCITY_GLYPHS.len() / 128).min(7)], Color::Cyan),
+                None => ('·', Color::DarkGray),
+            };
+            let mut style = Style::default().fg(if i == playing_step { Color::Yellow } else { color });
+            if i == editor.cursor {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            Span::styled(ch.to_string(), style)
+        })
+        .collect();
+    lines.push(Line::from(cells));
+
+    // Detail of the step under the cursor
+    let notes = grid.notes(editor.cursor);
+    let detail = match notes.first() {
+        Some(step) => {
+            let names: Vec<String> = notes.iter().map(|s| note_name(s.note)).collect();
+            format!("{}  vel {}", names.join(" "), step.velocity)
+        }
+        None => String::from("rest"),
+    };
+    lines.push(Line::from(format!("Step {}/{}  {}", editor.cursor + 1, grid.len(), detail)));
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequencing::{notes::*, Pattern};
+
+    const PPQ: u32 = 480;
+
+    #[test]
+    fn grid_round_trips_a_sequence() {
+        let sequence = Pattern::four_four(vec![C4.into(), E4.into(), G4.into(), C5.into()]).to_sequence(PPQ);
+        let grid = StepGrid::from_sequence(&sequence);
+
+        assert_eq!(grid.len(), 16);
+        assert_eq!(grid.get(4).map(|s| s.note), Some(E4));
+        assert_eq!(grid.to_sequence(&sequence).events, sequence.events);
+    }
+
+    #[test]
+    fn triplets_get_a_finer_grid() {
+        let sequence = crate::pattern!(4/4 => [[C4, C4, C4], _, E4, _]).to_sequence(PPQ);
+        let grid = StepGrid::from_sequence(&sequence);
+        // Triplet eighths are 160 ticks apart, sixteenths 120
+        assert_eq!(grid.step_ticks(), 40);
+        assert_eq!(grid.to_sequence(&sequence).events, sequence.events);
+    }
+
+    #[test]
+    fn edits_toggle_transpose_and_change_velocity() {
+        let sequence = Pattern::four_four(vec![C4.into(), E4.into(), G4.into(), C5.into()]).to_sequence(PPQ);
+        let mut grid = StepGrid::from_sequence(&sequence);
+
+        grid.toggle(6); // new step copies E4 from step 4
+        grid.transpose(6, 12);
+        grid.nudge_velocity(6, 200);
+        grid.toggle(0); // C4 off
+
+        let edited = grid.to_sequence(&sequence);
+        let notes: Vec<_> = edited.events.iter().map(|e| (e.tick_offset, e.note, e.velocity)).collect();
+        assert_eq!(
+            notes,
+            vec![(480, Some(E4), 100), (720, Some(E5), 127), (960, Some(G4), 100), (1440, Some(C5), 100)]
+        );
+        assert_eq!(edited.events[1].duration_ticks, 120);
+        assert_eq!(edited.total_ticks, sequence.total_ticks);
+    }
+
+    #[test]
+    fn every_note_on_a_step_is_kept() {
+        let mut sequence = Pattern::four_four(vec![C4.into(), E4.into(), G4.into(), C5.into()]).to_sequence(PPQ);
+        let mut chord_note = sequence.events[1].clone();
+        chord_note.note = Some(G4);
+        sequence.events.insert(2, chord_note);
+        let mut grid = StepGrid::from_sequence(&sequence);
+
+        assert_eq!(grid.notes(4).iter().map(|s| s.note).collect::<Vec<_>>(), vec![E4, G4]);
+        assert_eq!(grid.to_sequence(&sequence).events, sequence.events);
+
+        grid.transpose(4, 1);
+        let edited = grid.to_sequence(&sequence);
+        assert_eq!(edited.events[1].note, Some(F4));
+        assert_eq!(edited.events[2].note, Some(Gs4));
+    }
+
+    #[test]
+    fn swung_and_humanized_steps_keep_trigger_order() {
+        let mut sequence = Pattern::four_four(vec![C4.into(), E4.into(), G4.into(), C5.into()]).to_sequence(PPQ);
+        sequence.events[1].offset_ticks = 300; // E4 swung late, to tick 780
+        sequence.events[2].offset_ticks = -200; // G4 humanized early, to tick 760
+        sort_by_trigger_tick(&mut sequence);
+
+        let rebuilt = StepGrid::from_sequence(&sequence).to_sequence(&sequence);
+        let notes: Vec<_> = rebuilt.events.iter().filter_map(|e| e.note).collect();
+        assert_eq!(notes, [C4, G4, E4, C5]);
+        assert_eq!(rebuilt.events, sequence.events);
+    }
+
+    #[test]
+    fn septuplets_cap_the_grid_and_still_round_trip() {
+        let sequence = crate::pattern!(4/4 => [[C4, C4, C4, C4, C4, C4, C4], E4, _, _]).to_sequence(PPQ);
+        let grid = StepGrid::from_sequence(&sequence);
+
+        assert_eq!(grid.step_ticks(), PPQ / MAX_STEPS_PER_BEAT);
+        assert_eq!(grid.len(), 48);
+        assert_eq!(grid.to_sequence(&sequence).events, sequence.events);
+    }
+}
@@ -21,9 +21,8 @@ pub fn render_timeline(
         return;
     }
 
-    let ticks_per_beat = static_state.ppq;
-    let ticks_per_bar = ticks_per_beat * 4; // 4/4 time
-    let total_bars = (static_state.total_ticks + ticks_per_bar - 1) / ticks_per_bar;
+    let ticks_per_bar = static_state.bar_ticks();
+    let total_bars = static_state.total_ticks.div_ceil(ticks_per_bar);
 
     // Calculate how many characters per bar based on available width
     let track_label_width = 8u16;
@@ -1,4 +1,5 @@
-//! Transport bar widget - shows BPM, play state, position, and audio stats
+//! Transport bar widget - shows BPM, play state, position, audio stats, and
+//! device timing (buffer size, callback duration, output latency, xruns)
 
 use ratatui::{
     layout::Rect,
@@ -9,22 +10,65 @@ use ratatui::{
 };
 
 use super::{UiStateInit, UiStateUpdate};
+use crate::dsp::meter;
+use crate::runtime::monitor::CallbackStats;
 
 /// Audio statistics for display
 pub struct AudioStats {
     pub peak: f32,
     pub rms: f32,
+    /// Short-term loudness (LUFS), from the UI's running meter
+    pub lufs: f32,
 }
 
 impl AudioStats {
-    /// Compute audio stats from a buffer
-    pub fn from_buffer(buffer: &[f32]) -> Self {
-        if buffer.is_empty() {
-            return Self { peak: 0.0, rms: 0.0 };
+    /// Compute audio stats from a buffer plus the current loudness reading
+    pub fn from_buffer(buffer: &[f32], lufs: f32) -> Self {
+        Self {
+            peak: meter::true_peak(buffer),
+            rms: meter::rms(buffer),
+            lufs,
+        }
+    }
+}
+
+/// Device timing derived from audio thread telemetry
+pub struct DeviceTiming {
+    /// Frames per device callback
+    pub buffer_frames: u32,
+    /// Duration of one buffer at the device sample rate
+    pub buffer_ms: f32,
+    /// Time the last callback took to render
+    pub callback_ms: f32,
+    /// Callback time as a percentage of the buffer period (the realtime budget)
+    pub load_percent: f32,
+    /// Estimated output latency, if the host reports it
+    pub latency_ms: Option<f32>,
+}
+
+impl DeviceTiming {
+    /// Compute display values from raw telemetry
+    pub fn new(buffer_frames: u32, callback_micros: u32, latency_micros: u32, sample_rate: f32) -> Self {
+        let buffer_ms = if sample_rate > 0.0 {
+            buffer_frames as f32 * 1000.0 / sample_rate
+        } else {
+            0.0
+        };
+        let callback_ms = callback_micros as f32 / 1000.0;
+        let load_percent = if buffer_ms > 0.0 {
+            callback_ms / buffer_ms * 100.0
+        } else {
+            0.0
+        };
+        let latency_ms = (latency_micros > 0).then(|| latency_micros as f32 / 1000.0);
+
+        Self {
+            buffer_frames,
+            buffer_ms,
+            callback_ms,
+            load_percent,
+            latency_ms,
         }
-        let peak = buffer.iter().fold(0.0f32, |acc, &x| acc.max(x.abs()));
-        let rms = (buffer.iter().map(|&x| x * x).sum::<f32>() / buffer.len() as f32).sqrt();
-        Self { peak, rms }
     }
 }
 
@@ -35,6 +79,7 @@ pub fn render_transport(
     static_state: &UiStateInit,
     dynamic_state: &UiStateUpdate,
     audio_stats: &AudioStats,
+    callback_stats: &CallbackStats,
 ) {
     let block = Block::default()
         .title(" saavy ")
@@ -42,7 +87,7 @@ pub fn render_transport(
 
     // Calculate bar and beat from tick position
     let ticks_per_beat = static_state.ppq;
-    let ticks_per_bar = ticks_per_beat * 4; // Assuming 4/4 time
+    let ticks_per_bar = static_state.bar_ticks();
 
     let current_bar = dynamic_state.tick_position / ticks_per_bar + 1;
     let current_beat = (dynamic_state.tick_position % ticks_per_bar) / ticks_per_beat + 1;
@@ -54,6 +99,14 @@ pub fn render_transport(
     // Format sample rate nicely (e.g., 48000 -> "48kHz")
     let sample_rate_khz = static_state.sample_rate / 1000.0;
 
+    // Device timing: the callback must finish well inside one buffer period
+    let timing = DeviceTiming::new(
+        dynamic_state.buffer_frames,
+        dynamic_state.callback_micros,
+        dynamic_state.output_latency_micros,
+        static_state.sample_rate,
+    );
+
     let line = Line::from(vec![
         Span::styled(
             format!(" BPM: {:.0}  ", static_state.bpm),
@@ -81,7 +134,41 @@ pub fn render_transport(
             Style::default().fg(Color::DarkGray),
         ),
         Span::styled(
-            format!("Peak: {:.2}  RMS: {:.2}", audio_stats.peak, audio_stats.rms),
+            format!("Buf: {} ({:.1}ms)  ", timing.buffer_frames, timing.buffer_ms),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            format!("CB: {:.2}ms ({:.0}%)  ", timing.callback_ms, timing.load_percent),
+            Style::default().fg(if timing.load_percent > 80.0 {
+                Color::Red
+            } else {
+                Color::DarkGray
+            }),
+        ),
+        Span::styled(
+            format!("XRun: {} (max {:.0}%)  ", callback_stats.overruns, callback_stats.worst_load_percent),
+            Style::default().fg(if callback_stats.overruns > 0 {
+                Color::Red
+            } else {
+                Color::DarkGray
+            }),
+        ),
+        Span::styled(
+            match timing.latency_ms {
+                Some(ms) => format!("Lat: {:.1}ms  ", ms),
+                None => "Lat: --  ".to_string(),
+            },
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            if audio_stats.lufs > -70.0 {
+                format!(
+                    "Peak: {:.2}  RMS: {:.2}  {:.1} LUFS",
+                    audio_stats.peak, audio_stats.rms, audio_stats.lufs
+                )
+            } else {
+                format!("Peak: {:.2}  RMS: {:.2}  -- LUFS", audio_stats.peak, audio_stats.rms)
+            },
             Style::default().fg(Color::Magenta),
         ),
     ]);
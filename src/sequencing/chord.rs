@@ -0,0 +1,285 @@
+/*
+Chord Symbols
+=============
+
+Lead-sheet chord symbols expand to stacks of notes:
+
+    "C"      C E G          (root + quality, major by default)
+    "Am7"    A C E G
+    "G7b9"   G B D F Ab
+    "F/A"    A F A C        (slash: a bass note under the chord)
+
+A symbol is a ROOT (A-G, optionally # or b), a QUALITY looked up in the
+dictionary below (intervals in semitones above the root), and an optional
+"/bass" note. Common spellings are aliases: "m", "min" and "-" all mean
+minor; "maj7", "M7" and "Δ7" all mean major seventh.
+
+Inversions and Voicings
+-----------------------
+
+The dictionary gives close position: every note within an octave or so of
+the root. Two transforms reshape it:
+
+- INVERSION n moves the lowest note up an octave, n times:
+      C E G  →  E G C  (1st)  →  G C E  (2nd)
+
+- VOICING spreads the stack (after any inversion):
+      Drop2:  second-highest note down an octave   C E G B → G C E B
+      Drop3:  third-highest note down an octave    C E G B → E C G B
+      Spread: every other note up an octave        C E G   → C G E
+
+Tracks Are Monophonic
+---------------------
+
+A track plays one note at a time, so a chord needs one track per note.
+`chord::voices` turns a progression into one pattern per voice - the lowest
+notes of every chord, then the next lowest, and so on:
+
+    let parts = chord::voices(&["Cmaj7", "Am7", "Dm7", "G7"], 3)?;
+    let app = parts.into_iter().enumerate().fold(Saavy::new(), |app, (i, part)| {
+        app.track(&format!("pad{}", i + 1), part, voices::pad())
+    });
+*/
+
+use std::str::FromStr;
+
+use super::pattern::{Pattern, PatternSlot};
+
+/// Chord qualities: suffix → semitones above the root
+const QUALITIES: &[(&[&str], &[i32])] = &[
+    (&["", "maj", "M"], &[0, 4, 7]),
+    (&["m", "min", "-"], &[0, 3, 7]),
+    (&["dim", "°"], &[0, 3, 6]),
+    (&["aug", "+"], &[0, 4, 8]),
+    (&["sus2"], &[0, 2, 7]),
+    (&["sus4", "sus"], &[0, 5, 7]),
+    (&["5"], &[0, 7]),
+    (&["6"], &[0, 4, 7, 9]),
+    (&["m6", "min6"], &[0, 3, 7, 9]),
+    (&["7"], &[0, 4, 7, 10]),
+    (&["maj7", "M7", "Δ7", "Δ"], &[0, 4, 7, 11]),
+    (&["m7", "min7", "-7"], &[0, 3, 7, 10]),
+    (&["mMaj7", "mmaj7", "minmaj7"], &[0, 3, 7, 11]),
+    (&["m7b5", "min7b5", "ø", "ø7"], &[0, 3, 6, 10]),
+    (&["dim7", "°7"], &[0, 3, 6, 9]),
+    (&["aug7", "7#5", "+7"], &[0, 4, 8, 10]),
+    (&["7sus4", "7sus"], &[0, 5, 7, 10]),
+    (&["add9"], &[0, 4, 7, 14]),
+    (&["madd9"], &[0, 3, 7, 14]),
+    (&["9"], &[0, 4, 7, 10, 14]),
+    (&["maj9", "M9"], &[0, 4, 7, 11, 14]),
+    (&["m9", "min9"], &[0, 3, 7, 10, 14]),
+    (&["7b9"], &[0, 4, 7, 10, 13]),
+    (&["7#9"], &[0, 4, 7, 10, 15]),
+    (&["7#11"], &[0, 4, 7, 10, 18]),
+    (&["11"], &[0, 4, 7, 10, 14, 17]),
+    (&["m11", "min11"], &[0, 3, 7, 10, 14, 17]),
+    (&["13"], &[0, 4, 7, 10, 14, 21]),
+];
+
+/// How the notes of a chord are spread out
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Voicing {
+    /// As stacked by the dictionary (and inversion)
+    #[default]
+    Close,
+    /// Second-highest note down an octave
+    Drop2,
+    /// Third-highest note down an octave
+    Drop3,
+    /// Every other note, from the second lowest, up an octave
+    Spread,
+}
+
+/// Problems parsing a chord symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChordError {
+    /// The symbol (or the part after '/') doesn't start with a note letter A-G
+    InvalidRoot(String),
+    /// The quality isn't in the chord dictionary
+    UnknownQuality(String),
+}
+
+impl std::fmt::Display for ChordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChordError::InvalidRoot(symbol) => write!(f, "Invalid chord root in \"{}\": expected A-G with optional # or b", symbol),
+            ChordError::UnknownQuality(quality) => write!(f, "Unknown chord quality \"{}\"", quality),
+        }
+    }
+}
+
+impl std::error::Error for ChordError {}
+
+/// A parsed chord: root, intervals, and optional slash bass
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chord {
+    /// Pitch class of the root (0 = C ... 11 = B)
+    pub root: u8,
+    /// Semitones above the root, ascending
+    intervals: Vec<i32>,
+    /// Pitch class of a slash bass note, played below the chord
+    pub bass: Option<u8>,
+}
+
+impl Chord {
+    /// Parse a chord symbol such as "C", "F#m7", "Bbmaj9" or "G7b9/B"
+    pub fn parse(symbol: &str) -> Result<Self, ChordError> {
+        let (body, bass) = match symbol.rsplit_once('/') {
+            Some((body, bass)) => {
+                let (bass, rest) = parse_pitch_class(bass).ok_or_else(|| ChordError::InvalidRoot(symbol.to_string()))?;
+                if !rest.is_empty() {
+                    return Err(ChordError::InvalidRoot(symbol.to_string()));
+                }
+                (body, Some(bass))
+            }
+            None => (symbol, None),
+        };
+
+        let (root, quality) = parse_pitch_class(body).ok_or_else(|| ChordError::InvalidRoot(symbol.to_string()))?;
+        let intervals = QUALITIES
+            .iter()
+            .find(|(names, _)| names.contains(&quality))
+            .map(|(_, intervals)| intervals.to_vec())
+            .ok_or_else(|| ChordError::UnknownQuality(quality.to_string()))?;
+
+        Ok(Self { root, intervals, bass })
+    }
+
+    /// Move the lowest note up an octave, `n` times
+    pub fn inversion(mut self, n: usize) -> Self {
+        for _ in 0..n {
+            if let Some(lowest) = self.intervals.first_mut() {
+                *lowest += 12;
+            }
+            self.intervals.sort_unstable();
+        }
+        self
+    }
+
+    /// Spread the notes out (see `Voicing`)
+    pub fn voicing(mut self, voicing: Voicing) -> Self {
+        let len = self.intervals.len();
+        match voicing {
+            Voicing::Close => {}
+            Voicing::Drop2 if len >= 2 => self.intervals[len - 2] -= 12,
+            Voicing::Drop3 if len >= 3 => self.intervals[len - 3] -= 12,
+            Voicing::Spread => self.intervals.iter_mut().skip(1).step_by(2).for_each(|i| *i += 12),
+            _ => {}
+        }
+        self.intervals.sort_unstable();
+        self
+    }
+
+    /// MIDI notes with the root in `octave` (C4 = 60), lowest first
+    ///
+    /// A slash bass sits below the lowest chord note.
+    pub fn notes(&self, octave: i32) -> Vec<u8> {
+        let root = 12 * (octave + 1) + self.root as i32;
+        let mut notes: Vec<i32> = self.intervals.iter().map(|i| root + i).collect();
+        if let (Some(bass), Some(&lowest)) = (self.bass, notes.first()) {
+            // Highest note of the bass pitch class strictly below the chord
+            let below = lowest - 1;
+            notes.insert(0, below - (below - bass as i32).rem_euclid(12));
+        }
+        notes.into_iter().map(|n| n.clamp(0, 127) as u8).collect()
+    }
+}
+
+impl FromStr for Chord {
+    type Err = ChordError;
+
+    fn from_str(symbol: &str) -> Result<Self, Self::Err> {
+        Self::parse(symbol)
+    }
+}
+
+/// Split a leading note name (letter plus optional # or b) off `text`
+fn parse_pitch_class(text: &str) -> Option<(u8, &str)> {
+    let mut chars = text.chars();
+    let natural: i32 = match chars.next()? {
+        'C' => 0,
+        'D' => 2,
+        'E' => 4,
+        'F' => 5,
+        'G' => 7,
+        'A' => 9,
+        'B' => 11,
+        _ => return None,
+    };
+    let rest = chars.as_str();
+    let (shift, rest) = if let Some(rest) = rest.strip_prefix('#').or_else(|| rest.strip_prefix('♯')) {
+        (1, rest)
+    } else if let Some(rest) = rest.strip_prefix('b').or_else(|| rest.strip_prefix('♭')) {
+        (-1, rest)
+    } else {
+        (0, rest)
+    };
+    Some(((natural + shift).rem_euclid(12) as u8, rest))
+}
+
+/// Split a progression into one 4/4 pattern per voice, one chord per slot
+///
+/// Voice 0 plays the lowest note of every chord, voice 1 the next, and so
+/// on; chords with fewer notes rest in the upper voices. `"_"` is a rest
+/// for every voice. Roots sit in `octave`.
+pub fn voices(progression: &[&str], octave: i32) -> Result<Vec<Pattern>, ChordError> {
+    let chords = progression
+        .iter()
+        .map(|&symbol| match symbol {
+            "_" => Ok(Vec::new()),
+            symbol => Chord::parse(symbol).map(|chord| chord.notes(octave)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let voice_count = chords.iter().map(Vec::len).max().unwrap_or(0);
+    Ok((0..voice_count)
+        .map(|voice| {
+            let slots = chords
+                .iter()
+                .map(|notes| notes.get(voice).map_or(PatternSlot::Rest, |&note| note.into()))
+                .collect();
+            Pattern::four_four(slots)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequencing::notes::*;
+
+    #[test]
+    fn parses_roots_qualities_and_slash_bass() {
+        assert_eq!(Chord::parse("C").unwrap().notes(4), vec![C4, E4, G4]);
+        assert_eq!(Chord::parse("Cmin7").unwrap().notes(4), vec![C4, Eb4, G4, Bb4]);
+        assert_eq!(Chord::parse("G7b9").unwrap().notes(3), vec![G3, B3, D4, F4, Ab4]);
+        assert_eq!(Chord::parse("Bbmaj7").unwrap().notes(3), vec![Bb3, D4, F4, A4]);
+        assert_eq!("F#m".parse::<Chord>().unwrap().notes(4), vec![Fs4, A4, Cs5]);
+        assert_eq!(Chord::parse("F/A").unwrap().notes(4), vec![A3, F4, A4, C5]);
+
+        assert_eq!(Chord::parse("Hm"), Err(ChordError::InvalidRoot("Hm".into())));
+        assert_eq!(Chord::parse("Cwobble"), Err(ChordError::UnknownQuality("wobble".into())));
+    }
+
+    #[test]
+    fn inversions_and_voicings() {
+        let cmaj7 = || Chord::parse("Cmaj7").unwrap();
+        assert_eq!(cmaj7().inversion(1).notes(4), vec![E4, G4, B4, C5]);
+        assert_eq!(cmaj7().inversion(2).notes(4), vec![G4, B4, C5, E5]);
+        assert_eq!(cmaj7().voicing(Voicing::Drop2).notes(4), vec![G3, C4, E4, B4]);
+        assert_eq!(cmaj7().voicing(Voicing::Drop3).notes(4), vec![E3, C4, G4, B4]);
+        assert_eq!(Chord::parse("C").unwrap().voicing(Voicing::Spread).notes(4), vec![C4, G4, E5]);
+    }
+
+    #[test]
+    fn progression_splits_into_voices() {
+        let voices = voices(&["C", "Am7", "_", "G"], 4).unwrap();
+        assert_eq!(voices.len(), 4);
+
+        let notes = |p: &Pattern| p.to_sequence(480).events.iter().map(|e| e.note.unwrap()).collect::<Vec<_>>();
+        assert_eq!(notes(&voices[0]), vec![C4, A4, G4]);
+        assert_eq!(notes(&voices[3]), vec![G5], "only Am7 has a fourth note");
+        assert_eq!(voices[0].slots[2], PatternSlot::Rest);
+    }
+}
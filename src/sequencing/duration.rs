@@ -96,8 +96,11 @@ impl Duration {
     /// Convert this duration to integer ticks
     /// ppq = pulses per quarter note (standard MIDI timing resolution)
     /// Formula: ticks = (numerator * 4 * ppq) / denominator
+    ///
+    /// Computed in 64 bits, so long durations at high PPQ don't overflow.
+    /// Rounds down when the duration isn't a whole number of ticks.
     pub fn to_ticks(&self, ppq: u32) -> u32 {
-        (self.numerator * 4 * ppq) / self.denominator
+        (self.numerator as u64 * 4 * ppq as u64 / self.denominator as u64) as u32
     }
 
     /// Add two durations (finds common denominator)
@@ -0,0 +1,329 @@
+/*
+Pattern Visualization
+=====================
+
+Draws a pattern as a piano-roll grid: one row per note (highest at the top),
+one column per cell of the finest subdivision in the bar. Useful for docs,
+for checking what a dense `pattern!` really does, and for sharing a groove
+outside the TUI.
+
+Text grid (`Pattern::to_grid_string`):
+--------------------------------------
+
+  pattern!(4/4 => [C4, [E4, G4], _, (C5, 64)])
+
+       |1 |2 |3 |4 |
+    C5 |..|..|..|5-|
+    G4 |..|.7|..|..|
+    E4 |..|7.|..|..|
+    C4 |7-|..|..|..|
+
+- `|` separates top-level slots (numbered in the header)
+- A digit marks a note-on: its velocity scaled to 1-9 (100 → 7, 64 → 5)
+- `-` continues the note through its slot; `.` is silence
+
+Columns come from the greatest common divisor of every slot and sub-slot
+boundary, so a bar of straight eighths gets 2 columns per slot and a
+triplet gets 3. Mixing the two gives 6. Weighted subdivisions can produce
+odd tick counts; the columns per slot are capped so the grid stays readable,
+and notes then land on the nearest cell to their left.
+
+SVG (`Pattern::to_svg`):
+------------------------
+
+The same layout drawn as rectangles: note length is the rectangle width and
+velocity is its opacity. Slot boundaries are solid lines and subdivision
+boundaries are dashed. The SVG has no external styles or fonts beyond a
+generic monospace, so it embeds directly in Markdown or HTML.
+*/
+
+use super::notes::note_name;
+use super::pattern::{share, Pattern, PatternSlot};
+use super::SequenceEvent;
+
+/// Ticks per quarter note used for layout (divides evenly by 2, 3, 4, 5, 6, 8)
+const LAYOUT_PPQ: u32 = 960;
+
+/// Upper bound on grid columns per top-level slot
+const MAX_COLUMNS_PER_SLOT: u32 = 32;
+
+/// SVG cell size in pixels
+const CELL_WIDTH: u32 = 16;
+const ROW_HEIGHT: u32 = 14;
+/// Width of the note-name column in the SVG
+const LABEL_WIDTH: u32 = 36;
+
+/// Where everything in a pattern lands on the grid
+struct GridLayout {
+    slot_count: usize,
+    bar_ticks: u32,
+    columns_per_slot: u32,
+    /// Start tick of every sub-slot (rests included) that isn't a slot boundary
+    subdivisions: Vec<u32>,
+    events: Vec<SequenceEvent>,
+    /// Distinct notes, highest first
+    rows: Vec<u8>,
+}
+
+impl GridLayout {
+    fn new(pattern: &Pattern) -> Self {
+        let sequence = pattern.to_sequence(LAYOUT_PPQ);
+        let slot_count = pattern.slot_count() as usize;
+        let bar_ticks = sequence.total_ticks;
+        let slot_start = |i: usize| share(bar_ticks, i as u32, slot_count as u32);
+
+        let mut subdivisions = Vec::new();
+        let mut position = 0;
+        for slot in &pattern.slots {
+            let start = slot_start(position);
+            position += slot.span() as usize;
+            collect_subdivisions(slot, start, slot_start(position) - start, &mut subdivisions);
+        }
+        let step = subdivisions.iter().fold(bar_ticks, |step, &tick| gcd(step, tick));
+        let columns_per_slot = (bar_ticks.checked_div(step).unwrap_or(1) / slot_count.max(1) as u32)
+            .clamp(1, MAX_COLUMNS_PER_SLOT);
+
+        let slot_starts: Vec<u32> = (0..slot_count).map(slot_start).collect();
+        subdivisions.retain(|tick| !slot_starts.contains(tick));
+        subdivisions.sort_unstable();
+        subdivisions.dedup();
+
+        let mut rows: Vec<u8> = sequence.events.iter().filter_map(|e| e.note).collect();
+        rows.sort_unstable_by(|a, b| b.cmp(a));
+        rows.dedup();
+
+        Self {
+            slot_count,
+            bar_ticks,
+            columns_per_slot,
+            subdivisions,
+            events: sequence.events,
+            rows,
+        }
+    }
+
+    fn total_columns(&self) -> usize {
+        self.slot_count * self.columns_per_slot as usize
+    }
+
+    /// Grid column containing `tick` (rounding down)
+    fn column(&self, tick: u32) -> usize {
+        share(tick, self.total_columns() as u32, self.bar_ticks) as usize
+    }
+
+    /// First and one-past-last column an event covers (always at least one)
+    fn span(&self, event: &SequenceEvent) -> (usize, usize) {
+        let start = self.column(event.tick_offset).min(self.total_columns().saturating_sub(1));
+        let end = self
+            .column(event.tick_offset + event.duration_ticks)
+            .clamp(start + 1, self.total_columns().max(start + 1));
+        (start, end)
+    }
+
+    /// Column position of `tick` without rounding (for drawing)
+    fn x(&self, tick: u32) -> f32 {
+        tick as f32 * self.total_columns() as f32 / self.bar_ticks.max(1) as f32
+    }
+
+    /// Tick where top-level slot `index` starts
+    fn slot_start(&self, index: usize) -> u32 {
+        share(self.bar_ticks, index as u32, self.slot_count as u32)
+    }
+}
+
+/// Walk a slot the same way `Pattern::to_sequence` does, recording where each sub-slot starts
+fn collect_subdivisions(slot: &PatternSlot, start_tick: u32, duration: u32, out: &mut Vec<u32>) {
+    out.push(start_tick);
+    if let PatternSlot::Subdivision(sub_slots) | PatternSlot::Tuplet { slots: sub_slots, .. } = slot {
+        let total_weight: u32 = sub_slots.iter().map(PatternSlot::weight).sum();
+        if total_weight == 0 {
+            return;
+        }
+
+        let mut weight_before = 0;
+        for sub_slot in sub_slots {
+            let start = share(duration, weight_before, total_weight);
+            let end = share(duration, weight_before + sub_slot.weight(), total_weight);
+            collect_subdivisions(sub_slot, start_tick + start, end - start, out);
+            weight_before += sub_slot.weight();
+        }
+    }
+}
+
+pub(crate) fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Velocity 0-127 as a single digit 1-9
+fn velocity_digit(velocity: u8) -> char {
+    let level = ((velocity as u32 * 9 + 63) / 127).clamp(1, 9);
+    char::from_digit(level, 10).unwrap_or('9')
+}
+
+impl Pattern {
+    /// Render the pattern as a text piano-roll grid
+    ///
+    /// One row per note, `|` between slots, velocity digits at note-ons.
+    /// See the module docs for the full legend.
+    pub fn to_grid_string(&self) -> String {
+        let layout = GridLayout::new(self);
+        let label_width = layout
+            .rows
+            .iter()
+            .map(|&note| note_name(note).len())
+            .max()
+            .unwrap_or(0);
+        let columns_per_slot = layout.columns_per_slot as usize;
+
+        let mut out = String::new();
+
+        // Header: slot numbers
+        out.push_str(&" ".repeat(label_width + 1));
+        out.push('|');
+        for slot in 1..=layout.slot_count {
+            let number = slot.to_string();
+            if number.len() <= columns_per_slot {
+                out.push_str(&format!("{number:<columns_per_slot$}"));
+            } else {
+                out.push_str(&" ".repeat(columns_per_slot));
+            }
+            out.push('|');
+        }
+        out.push('\n');
+
+        for &note in &layout.rows {
+            let mut cells = vec!['.'; layout.total_columns()];
+            for event in layout.events.iter().filter(|e| e.note == Some(note)) {
+                let (start, end) = layout.span(event);
+                cells[start] = velocity_digit(event.velocity);
+                for cell in &mut cells[start + 1..end] {
+                    if *cell == '.' {
+                        *cell = '-';
+                    }
+                }
+            }
+
+            out.push_str(&format!("{:<label_width$} |", note_name(note)));
+            for slot in cells.chunks(columns_per_slot) {
+                out.extend(slot);
+                out.push('|');
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// Render the pattern as a standalone SVG piano roll
+    ///
+    /// Note length is rectangle width, velocity is opacity; slot boundaries
+    /// are solid lines and subdivision boundaries dashed.
+    pub fn to_svg(&self) -> String {
+        let layout = GridLayout::new(self);
+        let header = ROW_HEIGHT;
+        let grid_width = layout.total_columns() as u32 * CELL_WIDTH;
+        let grid_height = layout.rows.len() as u32 * ROW_HEIGHT;
+        let width = LABEL_WIDTH + grid_width + 1;
+        let height = header + grid_height + 1;
+        let x = |tick: u32| LABEL_WIDTH as f32 + layout.x(tick) * CELL_WIDTH as f32;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"10\">\n"
+        );
+        svg.push_str(&format!(
+            "  <rect x=\"{LABEL_WIDTH}\" y=\"{header}\" width=\"{grid_width}\" height=\"{grid_height}\" fill=\"#f4f4f4\"/>\n"
+        ));
+
+        // Row labels
+        for (row, &note) in layout.rows.iter().enumerate() {
+            let y = header + row as u32 * ROW_HEIGHT + ROW_HEIGHT - 3;
+            svg.push_str(&format!("  <text x=\"2\" y=\"{y}\">{}</text>\n", note_name(note)));
+        }
+
+        // Subdivision boundaries (dashed), then slot boundaries and numbers (solid)
+        for &tick in &layout.subdivisions {
+            let x = x(tick);
+            svg.push_str(&format!(
+                "  <line x1=\"{x}\" y1=\"{header}\" x2=\"{x}\" y2=\"{}\" stroke=\"#bbb\" stroke-dasharray=\"2,2\"/>\n",
+                header + grid_height
+            ));
+        }
+        for slot in 0..=layout.slot_count {
+            let x = x(layout.slot_start(slot));
+            svg.push_str(&format!(
+                "  <line x1=\"{x}\" y1=\"{header}\" x2=\"{x}\" y2=\"{}\" stroke=\"#555\"/>\n",
+                header + grid_height
+            ));
+            if slot < layout.slot_count {
+                svg.push_str(&format!(
+                    "  <text x=\"{}\" y=\"{}\">{}</text>\n",
+                    x + 2.0,
+                    header - 3,
+                    slot + 1
+                ));
+            }
+        }
+
+        // Notes
+        for event in &layout.events {
+            let Some(row) = layout.rows.iter().position(|&n| Some(n) == event.note) else {
+                continue;
+            };
+            let left = x(event.tick_offset);
+            let right = x(event.tick_offset + event.duration_ticks);
+            let y = header + row as u32 * ROW_HEIGHT + 1;
+            svg.push_str(&format!(
+                "  <rect x=\"{}\" y=\"{y}\" width=\"{}\" height=\"{}\" fill=\"#3b6fd8\" fill-opacity=\"{:.2}\"/>\n",
+                left + 1.0,
+                (right - left - 2.0).max(1.0),
+                ROW_HEIGHT - 2,
+                (event.velocity.max(1) as f32 / 127.0).min(1.0)
+            ));
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pattern;
+    use crate::sequencing::*;
+
+    #[test]
+    fn grid_shows_slots_subdivisions_and_velocity() {
+        let p = pattern!(4/4 => [C4, [E4, G4], _, (C5, 64)]);
+        let expected = concat!(
+            "   |1 |2 |3 |4 |\n",
+            "C5 |..|..|..|5-|\n",
+            "G4 |..|.7|..|..|\n",
+            "E4 |..|7.|..|..|\n",
+            "C4 |7-|..|..|..|\n",
+        );
+        assert_eq!(p.to_grid_string(), expected);
+    }
+
+    #[test]
+    fn grid_resolves_mixed_triplets_and_eighths() {
+        let p = pattern!(4/4 => [[C4, C4, C4], [C4, C4]]);
+        let grid = p.to_grid_string();
+        // 3 and 2 per slot need 6 columns each
+        assert!(grid.ends_with("C4 |7-7-7-|7--7--|\n"), "{grid}");
+    }
+
+    #[test]
+    fn svg_has_one_rect_per_note() {
+        let p = pattern!(4/4 => [C4, [E4, _], _, G4]);
+        let svg = p.to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("fill=\"#3b6fd8\"").count(), 3);
+        assert_eq!(svg.matches("stroke-dasharray").count(), 1);
+    }
+}
@@ -0,0 +1,181 @@
+#![allow(non_upper_case_globals)]
+
+/*
+Keys and Scales
+===============
+
+A scale is a pattern of intervals above a root note; a key is a scale
+anchored on a particular root:
+
+    C major:  C  D  E  F  G  A  B      intervals 0 2 4 5 7 9 11
+    A minor:  A  B  C  D  E  F  G      intervals 0 2 3 5 7 8 10
+
+Writing Patterns "in C"
+-----------------------
+
+Patterns are written with C as the tonic. `Saavy::key` then does two things
+to every note:
+
+1. TRANSPOSE: move the tonic from C to the key's root, taking the shorter
+   way round (C → A is 3 semitones down, not 9 up), so basslines stay in
+   their register.
+
+2. QUANTIZE (scale lock): snap any note that isn't in the scale to the
+   nearest one that is. Ties go down. This is what turns a riff written in
+   C major into A *minor* rather than A major:
+
+       written:      C4  E4  G4       (C major triad)
+       transposed:   A3  C#4 E4       (A major triad)
+       locked to Am: A3  C4  E4       (C# isn't in A minor → C)
+
+So the same patterns can be moved to any key - and any mode - in one line:
+
+  Saavy::new().key(Key::Am)
+  Saavy::new().key(Key::new(D4, Scale::Dorian))
+
+Drum tracks usually shouldn't move: mark them with `Saavy::unpitched`.
+*/
+
+/// A scale: the intervals (in semitones) above the root
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scale {
+    Major,
+    /// Natural minor (Aeolian)
+    Minor,
+    HarmonicMinor,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    MajorPentatonic,
+    MinorPentatonic,
+    /// All twelve notes - transposes without quantizing
+    Chromatic,
+}
+
+impl Scale {
+    /// Semitones above the root, ascending from 0
+    pub const fn intervals(self) -> &'static [u8] {
+        match self {
+            Scale::Major => &[0, 2, 4, 5, 7, 9, 11],
+            Scale::Minor => &[0, 2, 3, 5, 7, 8, 10],
+            Scale::HarmonicMinor => &[0, 2, 3, 5, 7, 8, 11],
+            Scale::Dorian => &[0, 2, 3, 5, 7, 9, 10],
+            Scale::Phrygian => &[0, 1, 3, 5, 7, 8, 10],
+            Scale::Lydian => &[0, 2, 4, 6, 7, 9, 11],
+            Scale::Mixolydian => &[0, 2, 4, 5, 7, 9, 10],
+            Scale::MajorPentatonic => &[0, 2, 4, 7, 9],
+            Scale::MinorPentatonic => &[0, 3, 5, 7, 10],
+            Scale::Chromatic => &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        }
+    }
+}
+
+/// A root pitch class and a scale
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Key {
+    /// Pitch class of the root (0 = C, 1 = C#, ... 11 = B)
+    pub root: u8,
+    pub scale: Scale,
+}
+
+impl Key {
+    /// Key on `root` (any octave - only the pitch class matters)
+    pub const fn new(root: u8, scale: Scale) -> Self {
+        Self {
+            root: root % 12,
+            scale,
+        }
+    }
+
+    pub const C: Key = Key::new(0, Scale::Major);
+    pub const Db: Key = Key::new(1, Scale::Major);
+    pub const D: Key = Key::new(2, Scale::Major);
+    pub const Eb: Key = Key::new(3, Scale::Major);
+    pub const E: Key = Key::new(4, Scale::Major);
+    pub const F: Key = Key::new(5, Scale::Major);
+    pub const Fs: Key = Key::new(6, Scale::Major);
+    pub const G: Key = Key::new(7, Scale::Major);
+    pub const Ab: Key = Key::new(8, Scale::Major);
+    pub const A: Key = Key::new(9, Scale::Major);
+    pub const Bb: Key = Key::new(10, Scale::Major);
+    pub const B: Key = Key::new(11, Scale::Major);
+
+    pub const Cm: Key = Key::new(0, Scale::Minor);
+    pub const Csm: Key = Key::new(1, Scale::Minor);
+    pub const Dm: Key = Key::new(2, Scale::Minor);
+    pub const Ebm: Key = Key::new(3, Scale::Minor);
+    pub const Em: Key = Key::new(4, Scale::Minor);
+    pub const Fm: Key = Key::new(5, Scale::Minor);
+    pub const Fsm: Key = Key::new(6, Scale::Minor);
+    pub const Gm: Key = Key::new(7, Scale::Minor);
+    pub const Gsm: Key = Key::new(8, Scale::Minor);
+    pub const Am: Key = Key::new(9, Scale::Minor);
+    pub const Bbm: Key = Key::new(10, Scale::Minor);
+    pub const Bm: Key = Key::new(11, Scale::Minor);
+
+    /// Semitones that move C to this key's root, the shorter way (-6..=5)
+    pub fn transposition(&self) -> i32 {
+        let root = self.root as i32;
+        if root <= 5 {
+            root
+        } else {
+            root - 12
+        }
+    }
+
+    /// Snap `note` to the nearest note of the scale (ties resolve down)
+    pub fn quantize(&self, note: u8) -> u8 {
+        let degree = (note as i32 - self.root as i32).rem_euclid(12);
+
+        // Check each scale note in this octave and its neighbours
+        let shift = self
+            .scale
+            .intervals()
+            .iter()
+            .flat_map(|&i| [i as i32 - 12, i as i32, i as i32 + 12])
+            .map(|candidate| candidate - degree)
+            .min_by_key(|&d| (d.abs(), d > 0))
+            .unwrap_or(0);
+
+        (note as i32 + shift).clamp(0, 127) as u8
+    }
+
+    /// Move a note written in C into this key: transpose, then quantize
+    pub fn apply(&self, note: u8) -> u8 {
+        self.quantize((note as i32 + self.transposition()).clamp(0, 127) as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequencing::notes::*;
+
+    #[test]
+    fn c_major_riff_moves_to_a_minor() {
+        let key = Key::Am;
+        let moved: Vec<u8> = [C4, E4, G4, C5].iter().map(|&n| key.apply(n)).collect();
+        assert_eq!(moved, vec![A3, C4, E4, A4]);
+    }
+
+    #[test]
+    fn quantize_snaps_to_nearest_ties_down() {
+        let key = Key::C;
+        assert_eq!(key.quantize(Cs4), C4); // between C and D: down
+        assert_eq!(key.quantize(Fs4), F4);
+        assert_eq!(key.quantize(E4), E4);
+
+        // Pentatonic gaps wrap to the next octave's root when closer
+        let pentatonic = Key::new(C4, Scale::MajorPentatonic);
+        assert_eq!(pentatonic.quantize(B4), C5);
+        assert_eq!(pentatonic.quantize(F4), E4);
+    }
+
+    #[test]
+    fn transposition_takes_the_short_way() {
+        assert_eq!(Key::D.transposition(), 2);
+        assert_eq!(Key::Am.transposition(), -3);
+        assert_eq!(Key::Fs.transposition(), -6);
+    }
+}
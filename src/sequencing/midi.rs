@@ -0,0 +1,247 @@
+/*
+Standard MIDI File Export
+=========================
+
+Writes sequences as a Standard MIDI File (SMF) so a pattern sketched here can
+be dragged into a DAW and continued there.
+
+File Layout (format 1):
+-----------------------
+
+  MThd  header: format, track count, ticks per quarter note (our PPQ)
+  MTrk  track 0: tempo + time signature (the "conductor" track)
+  MTrk  track 1..n: one per sequence, note on/off events
+
+Each event inside a track is prefixed by a delta time: ticks since the
+previous event, written as a variable-length quantity (VLQ). A VLQ stores 7
+bits per byte, most significant group first, with the high bit set on every
+byte except the last:
+
+  0x00000040  →  40
+  0x00000080  →  81 00
+  0x00003FFF  →  FF 7F
+
+Because our sequences already count time in PPQ ticks, no rescaling is
+needed - the header's division field is simply set to the sequence PPQ.
+
+Example usage:
+  let bytes = midi::to_smf(&[("lead", &lead_seq), ("bass", &bass_seq)], 120.0, 480);
+  std::fs::write("jam.mid", bytes)?;
+*/
+
+use super::Sequence;
+
+/// Channels tracks are assigned round-robin - every channel but 10
+/// (index 9), which General MIDI reserves for drums
+const PITCHED_CHANNELS: [u8; 15] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 10, 11, 12, 13, 14, 15];
+
+/// Encode tracks as a format 1 Standard MIDI File.
+///
+/// Each `(name, sequence)` pair becomes its own MIDI track, on channels
+/// assigned round-robin skipping the General MIDI drum channel. Tempo and
+/// the first sequence's time signature are written to a leading conductor
+/// track. Microtiming offsets are applied, so swing and humanization
+/// survive the round trip.
+pub fn to_smf(tracks: &[(&str, &Sequence)], bpm: f64, ppq: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    // Header chunk
+    bytes.extend_from_slice(b"MThd");
+    bytes.extend_from_slice(&6u32.to_be_bytes());
+    bytes.extend_from_slice(&1u16.to_be_bytes()); // Format 1: simultaneous tracks
+    bytes.extend_from_slice(&(tracks.len() as u16 + 1).to_be_bytes());
+    // Division: high bit must be clear for ticks-per-quarter timing
+    bytes.extend_from_slice(&(ppq.min(0x7FFF) as u16).to_be_bytes());
+
+    // Conductor track: tempo and time signature
+    let mut conductor = Vec::new();
+    let micros_per_quarter = (60_000_000.0 / bpm.max(1.0)).round() as u32;
+    write_vlq(&mut conductor, 0);
+    conductor.extend_from_slice(&[0xFF, 0x51, 0x03]);
+    conductor.extend_from_slice(&micros_per_quarter.to_be_bytes()[1..]);
+
+    if let Some((_, first)) = tracks.first() {
+        let ts = first.time_signature;
+        // Denominator is stored as a power of two (4 → 2, 8 → 3)
+        let denominator_pow = ts.denominator.max(1).trailing_zeros() as u8;
+        write_vlq(&mut conductor, 0);
+        conductor.extend_from_slice(&[0xFF, 0x58, 0x04, ts.numerator, denominator_pow, 24, 8]);
+    }
+    write_end_of_track(&mut conductor);
+    write_chunk(&mut bytes, &conductor);
+
+    // One MIDI track per sequence
+    for (index, (name, sequence)) in tracks.iter().enumerate() {
+        let channel = PITCHED_CHANNELS[index % PITCHED_CHANNELS.len()];
+        let track = encode_track(name, sequence, channel);
+        write_chunk(&mut bytes, &track);
+    }
+
+    bytes
+}
+
+/// Encode a single sequence as the body of an MTrk chunk
+fn encode_track(name: &str, sequence: &Sequence, channel: u8) -> Vec<u8> {
+    let mut track = Vec::new();
+
+    // Track name meta event
+    write_vlq(&mut track, 0);
+    track.extend_from_slice(&[0xFF, 0x03]);
+    write_vlq(&mut track, name.len() as u32);
+    track.extend_from_slice(name.as_bytes());
+
+    // Collect (tick, is_note_on, note, velocity)
+    let mut events: Vec<(u32, bool, u8, u8)> = Vec::with_capacity(sequence.events.len() * 2);
+    for event in &sequence.events {
+        if let Some(note) = event.note {
+            let start = event.tick_offset.saturating_add_signed(event.offset_ticks);
+            let end = start.saturating_add(event.duration_ticks);
+            // Velocity 0 would be read as a note-off, so clamp to 1
+            let velocity = event.velocity.clamp(1, 127);
+            events.push((start, true, note.min(127), velocity));
+            events.push((end, false, note.min(127), 0));
+        }
+    }
+
+    // Note-offs sort before note-ons on the same tick so repeated notes retrigger
+    events.sort_by_key(|&(tick, is_on, _, _)| (tick, is_on));
+
+    let mut last_tick = 0;
+    for (tick, is_on, note, velocity) in events {
+        write_vlq(&mut track, tick - last_tick);
+        last_tick = tick;
+        let status = if is_on { 0x90 } else { 0x80 };
+        track.extend_from_slice(&[status | channel, note, velocity]);
+    }
+
+    write_end_of_track(&mut track);
+    track
+}
+
+/// Append an MTrk chunk (header + length + body)
+fn write_chunk(bytes: &mut Vec<u8>, body: &[u8]) {
+    bytes.extend_from_slice(b"MTrk");
+    bytes.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(body);
+}
+
+/// Append the mandatory end-of-track meta event
+fn write_end_of_track(track: &mut Vec<u8>) {
+    write_vlq(track, 0);
+    track.extend_from_slice(&[0xFF, 0x2F, 0x00]);
+}
+
+/// Append a variable-length quantity (7 bits per byte, MSB first)
+fn write_vlq(bytes: &mut Vec<u8>, mut value: u32) {
+    let mut buffer = [0u8; 5];
+    let mut len = 0;
+    loop {
+        buffer[len] = (value & 0x7F) as u8;
+        len += 1;
+        value >>= 7;
+        if value == 0 {
+            break;
+        }
+    }
+    for i in (0..len).rev() {
+        let continuation = if i > 0 { 0x80 } else { 0x00 };
+        bytes.push(buffer[i] | continuation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequencing::{notes::*, Pattern, PatternSlot};
+
+    const PPQ: u32 = 480;
+
+    fn vlq(value: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_vlq(&mut bytes, value);
+        bytes
+    }
+
+    #[test]
+    fn vlq_encoding_matches_spec() {
+        assert_eq!(vlq(0x00), vec![0x00]);
+        assert_eq!(vlq(0x40), vec![0x40]);
+        assert_eq!(vlq(0x7F), vec![0x7F]);
+        assert_eq!(vlq(0x80), vec![0x81, 0x00]);
+        assert_eq!(vlq(0x3FFF), vec![0xFF, 0x7F]);
+        assert_eq!(vlq(0x4000), vec![0x81, 0x80, 0x00]);
+    }
+
+    #[test]
+    fn header_describes_tracks_and_ppq() {
+        let seq = Pattern::four_four(vec![C4.into(), E4.into(), G4.into(), C5.into()]).to_sequence(PPQ);
+        let bytes = to_smf(&[("lead", &seq)], 120.0, PPQ);
+
+        assert_eq!(&bytes[0..4], b"MThd");
+        assert_eq!(u16::from_be_bytes([bytes[8], bytes[9]]), 1); // format 1
+        assert_eq!(u16::from_be_bytes([bytes[10], bytes[11]]), 2); // conductor + lead
+        assert_eq!(u16::from_be_bytes([bytes[12], bytes[13]]), PPQ as u16);
+    }
+
+    #[test]
+    fn tempo_is_written_in_microseconds_per_quarter() {
+        let seq = Pattern::four_four(vec![C4.into()]).to_sequence(PPQ);
+        let bytes = to_smf(&[("lead", &seq)], 120.0, PPQ);
+
+        // 120 BPM = 500_000 µs per quarter = 0x07A120
+        let tempo = bytes.windows(6).position(|w| w == [0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20]);
+        assert!(tempo.is_some(), "tempo meta event missing");
+    }
+
+    #[test]
+    fn notes_become_on_off_pairs() {
+        let seq = Pattern::four_four(vec![
+            C4.into(),
+            PatternSlot::Rest,
+            G4.into(),
+            PatternSlot::Rest,
+        ])
+        .to_sequence(PPQ);
+        let track = encode_track("lead", &seq, 0);
+
+        let note_ons = track.windows(2).filter(|w| w[0] == 0x90 && (w[1] == C4 || w[1] == G4)).count();
+        let note_offs = track.windows(2).filter(|w| w[0] == 0x80 && (w[1] == C4 || w[1] == G4)).count();
+        assert_eq!(note_ons, 2);
+        assert_eq!(note_offs, 2);
+        assert!(track.ends_with(&[0x00, 0xFF, 0x2F, 0x00]));
+    }
+
+    #[test]
+    fn repeated_notes_release_before_retrigger() {
+        let seq = Pattern::four_four(vec![C4.into(), C4.into(), C4.into(), C4.into()]).to_sequence(PPQ);
+        let track = encode_track("kick", &seq, 9);
+
+        // Second event pair at tick 480: note-off (delta 480) then note-on (delta 0)
+        let off = [0x83, 0x60, 0x89, C4, 0x00];
+        let on = [0x00, 0x99, C4];
+        let off_pos = track.windows(off.len()).position(|w| w == off).expect("note-off missing");
+        assert_eq!(&track[off_pos + off.len()..off_pos + off.len() + on.len()], &on);
+    }
+
+    #[test]
+    fn tracks_are_assigned_channels() {
+        let seq = Pattern::four_four(vec![C4.into()]).to_sequence(PPQ);
+        let a = encode_track("a", &seq, 0);
+        let b = encode_track("b", &seq, 1);
+
+        assert!(a.contains(&0x90));
+        assert!(b.contains(&0x91));
+    }
+
+    #[test]
+    fn tenth_track_skips_the_drum_channel() {
+        let seq = Pattern::four_four(vec![C4.into()]).to_sequence(PPQ);
+        let names: Vec<String> = (0..10).map(|i| format!("t{i}")).collect();
+        let tracks: Vec<(&str, &Sequence)> = names.iter().map(|name| (name.as_str(), &seq)).collect();
+        let smf = to_smf(&tracks, 120.0, PPQ);
+
+        // Channel 11 (0x9A) instead of the drum channel (0x99)
+        assert!(smf.windows(2).any(|w| w == [0x9A, C4]));
+        assert!(!smf.windows(2).any(|w| w == [0x99, C4]));
+    }
+}
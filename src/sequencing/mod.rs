@@ -1,11 +1,17 @@
+pub mod chord;
 pub mod duration;
+pub mod grid;
+pub mod key;
+pub mod midi;
 pub mod notes;
 pub mod pattern;
 pub mod sequence;
 pub mod time_signature;
 
+pub use chord::{Chord, ChordError, Voicing};
 pub use duration::Duration;
+pub use key::{Key, Scale};
 pub use notes::*;
 pub use pattern::{NoteSlot, Pattern, PatternChain, PatternSlot};
-pub use sequence::{Sequence, SequenceBuilder, SequenceError, SequenceEvent};
+pub use sequence::{Expression, Sequence, SequenceBuilder, SequenceError, SequenceEvent, Slide};
 pub use time_signature::TimeSignature;
@@ -198,6 +198,12 @@ pub const As8: u8 = 118;
 pub const Bb8: u8 = 118;
 pub const B8: u8 = 119;
 
+/// Display name of a MIDI note, e.g. 60 → "C4", 61 → "C#4"
+pub fn note_name(note: u8) -> String {
+    const NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+    format!("{}{}", NAMES[note as usize % 12], note as i32 / 12 - 1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -226,6 +232,13 @@ mod tests {
         assert_eq!(As4, Bb4);
     }
 
+    #[test]
+    fn note_names_use_sharps() {
+        assert_eq!(note_name(C4), "C4");
+        assert_eq!(note_name(Db4), "C#4");
+        assert_eq!(note_name(0), "C-1");
+    }
+
     #[test]
     fn chromatic_scale() {
         // C4 chromatic scale
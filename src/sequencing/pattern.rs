@@ -14,17 +14,35 @@ Example mental model:
     [C4, [E4, G4], _, _] = quarter, 2 eighths, 2 quarter rests
     [[C4, E4, G4], ...]  = triplet (3 notes in 1 beat)
 
+Tuplets Across Beats
+--------------------
+
+Brackets only ever divide ONE slot, so a quarter-note triplet (3 notes
+spread over 2 beats) can't be written with them. A tuplet marker spreads a
+group over several slots instead:
+
+    [3:[C4, E4, G4], C5, _]  = quarter-note triplet over beats 1-2, then C5
+
+`N:[...]` spans the largest power of two below N - the number of notes the
+tuplet replaces: 3 → 2 slots, 5/6/7 → 4 slots. Inside, the group divides
+like any subdivision (weights, nesting and rests all work). For any other
+span use `slot::tuplet(span, slots)`.
+
 This module provides:
-- `PatternSlot` - A single slot that can be a note, rest, or subdivision
+- `PatternSlot` - A single slot that can be a note, rest, subdivision or tuplet
 - `Pattern` - A collection of slots with a time signature
 - Conversion to the low-level `Sequence` type for playback
 */
 
 use super::time_signature::TimeSignature;
-use super::{Sequence, SequenceEvent};
+use super::{Expression, Sequence, SequenceEvent, Slide};
 
-/// A slot in a pattern - can be a note, rest, or subdivision
+/// A slot in a pattern - can be a note, rest, subdivision or tuplet
+///
+/// Non-exhaustive: new slot kinds (as `Tuplet` was) can be added without
+/// breaking downstream matches.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum PatternSlot {
     /// A single note (MIDI note number)
     Note(NoteSlot),
@@ -32,6 +50,27 @@ pub enum PatternSlot {
     Rest,
     /// Subdivide this slot into smaller parts
     Subdivision(Vec<PatternSlot>),
+    /// Spread these parts evenly over `span` slots (e.g. a triplet over 2 beats)
+    Tuplet { span: u8, slots: Vec<PatternSlot> },
+}
+
+impl PatternSlot {
+    /// Share of its parent this slot takes: its weight, or its span for a tuplet
+    pub(crate) fn weight(&self) -> u32 {
+        match self {
+            PatternSlot::Note(n) => n.weight as u32,
+            PatternSlot::Tuplet { .. } => self.span(),
+            _ => 1,
+        }
+    }
+
+    /// Top-level slots this occupies (more than one only for tuplets)
+    pub fn span(&self) -> u32 {
+        match self {
+            PatternSlot::Tuplet { span, .. } => (*span as u32).max(1),
+            _ => 1,
+        }
+    }
 }
 
 /// A note with optional weight for swing/uneven subdivisions
@@ -44,6 +83,10 @@ pub struct NoteSlot {
     /// Weight for uneven subdivisions (default 1)
     /// In a subdivision like [C4@2, E4], C4 gets 2/3 of the time
     pub weight: u8,
+    /// Glide to this note over the slot's duration (303-style slide)
+    pub slide_to: Option<u8>,
+    /// Pressure ramp over the slot's duration (0-127 at start and end)
+    pub expression: Option<Expression>,
 }
 
 impl NoteSlot {
@@ -52,6 +95,8 @@ impl NoteSlot {
             note,
             velocity: 100,
             weight: 1,
+            slide_to: None,
+            expression: None,
         }
     }
 
@@ -64,6 +109,20 @@ impl NoteSlot {
         self.weight = weight;
         self
     }
+
+    pub fn with_slide(mut self, target: u8) -> Self {
+        self.slide_to = Some(target);
+        self
+    }
+
+    /// Ramp pressure from `start` to `end` (0-127) over the slot
+    pub fn with_expression(mut self, start: u8, end: u8) -> Self {
+        self.expression = Some(Expression {
+            start: start.min(127),
+            end: end.min(127),
+        });
+        self
+    }
 }
 
 /// Convenient conversion from u8 (MIDI note) to PatternSlot
@@ -73,6 +132,20 @@ impl From<u8> for PatternSlot {
     }
 }
 
+/// `(note, velocity)` shorthand, e.g. `(C4, 64)`
+impl From<(u8, u8)> for PatternSlot {
+    fn from((note, velocity): (u8, u8)) -> Self {
+        PatternSlot::Note(NoteSlot::new(note).with_velocity(velocity))
+    }
+}
+
+/// `(note, velocity, weight)` shorthand, e.g. `(C4, 64, 2)`
+impl From<(u8, u8, u8)> for PatternSlot {
+    fn from((note, velocity, weight): (u8, u8, u8)) -> Self {
+        PatternSlot::Note(NoteSlot::new(note).with_velocity(velocity).with_weight(weight))
+    }
+}
+
 /// A pattern is one cycle of music with a time signature
 #[derive(Debug, Clone)]
 pub struct Pattern {
@@ -120,34 +193,38 @@ impl Pattern {
         }
     }
 
+    /// Number of equal divisions of the bar (a tuplet counts its span)
+    pub fn slot_count(&self) -> u32 {
+        self.slots.iter().map(PatternSlot::span).sum()
+    }
+
     /// Convert to a low-level Sequence for playback
     pub fn to_sequence(&self, ppq: u32) -> Sequence {
         let bar_ticks = self.time_signature.bar_ticks(ppq);
-        let slot_count = self.slots.len() as u32;
+        let slot_count = self.slot_count();
 
         // Handle empty pattern - return empty sequence
         if slot_count == 0 {
             return Sequence {
-                time_signature: self.time_signature.clone(),
+                time_signature: self.time_signature,
                 ppq,
                 events: Vec::new(),
                 total_ticks: bar_ticks,
             };
         }
 
-        // Each top-level slot gets an equal portion of the bar
-        let ticks_per_slot = bar_ticks / slot_count;
-
+        // Each top-level slot gets an equal portion of the bar (see `share`)
         let mut events = Vec::new();
-        let mut cursor = 0u32;
-
+        let mut position = 0;
         for slot in &self.slots {
-            Self::expand_slot(slot, cursor, ticks_per_slot, &mut events);
-            cursor += ticks_per_slot;
+            let start = share(bar_ticks, position, slot_count);
+            position += slot.span();
+            let end = share(bar_ticks, position, slot_count);
+            Self::expand_slot(slot, start, end - start, &mut events);
         }
 
         Sequence {
-            time_signature: self.time_signature.clone(),
+            time_signature: self.time_signature,
             ppq,
             events,
             total_ticks: bar_ticks,
@@ -164,42 +241,52 @@ impl Pattern {
                     note: Some(note_slot.note),
                     velocity: note_slot.velocity,
                     offset_ticks: 0,
+                    slide: note_slot.slide_to.map(|target| Slide {
+                        target,
+                        glide_ticks: duration,
+                    }),
+                    expression: note_slot.expression,
                 });
             }
             PatternSlot::Rest => {
                 // Rests don't create events, just consume time
             }
-            PatternSlot::Subdivision(sub_slots) => {
+            PatternSlot::Subdivision(sub_slots) | PatternSlot::Tuplet { slots: sub_slots, .. } => {
                 assert!(
                     !sub_slots.is_empty(),
                     "Empty subdivision is not allowed - use PatternSlot::Rest for silence"
                 );
 
                 // Calculate total weight
-                let total_weight: u32 = sub_slots
-                    .iter()
-                    .map(|s| match s {
-                        PatternSlot::Note(n) => n.weight as u32,
-                        _ => 1,
-                    })
-                    .sum();
+                let total_weight: u32 = sub_slots.iter().map(PatternSlot::weight).sum();
 
                 // Distribute time according to weights
-                let mut sub_cursor = start_tick;
+                let mut weight_before = 0;
                 for sub_slot in sub_slots {
-                    let weight = match sub_slot {
-                        PatternSlot::Note(n) => n.weight as u32,
-                        _ => 1,
-                    };
-                    let sub_duration = (duration * weight) / total_weight;
-                    Self::expand_slot(sub_slot, sub_cursor, sub_duration, events);
-                    sub_cursor += sub_duration;
+                    let weight = sub_slot.weight();
+                    let start = share(duration, weight_before, total_weight);
+                    let end = share(duration, weight_before + weight, total_weight);
+                    Self::expand_slot(sub_slot, start_tick + start, end - start, events);
+                    weight_before += weight;
                 }
             }
         }
     }
 }
 
+/// Tick where `part` of `whole` equal shares of `ticks` ends
+///
+/// Slot boundaries are placed by rounding each *cumulative* position rather
+/// than adding up rounded slot lengths. 5 slots in a 1920-tick bar at
+/// 480 PPQ divide evenly, but at 96 PPQ (384 ticks) each is 76.8 ticks:
+/// adding 76 five times ends the last slot 4 ticks early and the error
+/// grows with every nesting level. Rounding the boundaries instead makes
+/// some slots a tick longer than others, but every slot starts within a
+/// tick of its exact position and the last one always ends on the bar line.
+pub(crate) fn share(ticks: u32, part: u32, whole: u32) -> u32 {
+    (ticks as u64 * part as u64 / whole.max(1) as u64) as u32
+}
+
 /// A chain of patterns to be played in sequence
 #[derive(Debug, Clone)]
 pub struct PatternChain {
@@ -247,7 +334,7 @@ impl PatternChain {
         let time_signature = self
             .patterns
             .first()
-            .map(|p| p.time_signature.clone())
+            .map(|p| p.time_signature)
             .unwrap_or(TimeSignature::FOUR_FOUR);
 
         for pattern in &self.patterns {
@@ -294,55 +381,75 @@ impl PatternChain {
 ///
 /// // 6/8 compound meter
 /// let waltz = pattern!(6/8 => [C4, G4]);
+///
+/// // Velocity: scale the default (100) with `*`, or give it exactly in a tuple
+/// let accents = pattern!(4/4 => [C4*1.2, C4*0.5, (C4, 90), [(C4, 40, 2), C4*0.3]]);
 /// ```
 #[macro_export]
 macro_rules! pattern {
     // 4/4 time signature
-    (4/4 => [$($slot:tt),* $(,)?]) => {
+    (4/4 => [$($slots:tt)*]) => {
         $crate::sequencing::Pattern::new(
             $crate::sequencing::TimeSignature::FOUR_FOUR,
-            vec![$($crate::pattern!(@slot $slot)),*]
+            $crate::pattern!(@slots $($slots)*)
         )
     };
 
     // 3/4 time signature
-    (3/4 => [$($slot:tt),* $(,)?]) => {
+    (3/4 => [$($slots:tt)*]) => {
         $crate::sequencing::Pattern::new(
             $crate::sequencing::TimeSignature::THREE_FOUR,
-            vec![$($crate::pattern!(@slot $slot)),*]
+            $crate::pattern!(@slots $($slots)*)
         )
     };
 
     // 6/8 time signature
-    (6/8 => [$($slot:tt),* $(,)?]) => {
+    (6/8 => [$($slots:tt)*]) => {
         $crate::sequencing::Pattern::new(
             $crate::sequencing::TimeSignature::SIX_EIGHT,
-            vec![$($crate::pattern!(@slot $slot)),*]
+            $crate::pattern!(@slots $($slots)*)
         )
     };
 
     // 2/4 time signature
-    (2/4 => [$($slot:tt),* $(,)?]) => {
+    (2/4 => [$($slots:tt)*]) => {
         $crate::sequencing::Pattern::new(
             $crate::sequencing::TimeSignature::TWO_FOUR,
-            vec![$($crate::pattern!(@slot $slot)),*]
+            $crate::pattern!(@slots $($slots)*)
         )
     };
 
+    // Slot list. A slot is one token plus an optional `* scale` (`C4*0.5`)
+    // or `: [...]` (`3:[C4, E4, G4]`), so plain repetition covers it
+    (@slots $($head:tt $(* $scale:tt)? $(: $group:tt)?),* $(,)?) => {
+        vec![$($crate::pattern!(@slot $head $(* $scale)? $(: $group)?)),*]
+    };
+
     // Rest slot
     (@slot _) => {
         $crate::sequencing::PatternSlot::Rest
     };
 
     // Subdivision slot (brackets)
-    (@slot [$($inner:tt),* $(,)?]) => {
-        $crate::sequencing::PatternSlot::Subdivision(
-            vec![$($crate::pattern!(@slot $inner)),*]
+    (@slot [$($inner:tt)*]) => {
+        $crate::sequencing::PatternSlot::Subdivision($crate::pattern!(@slots $($inner)*))
+    };
+
+    // Tuplet spanning several slots (`3:[C4, E4, G4]`)
+    (@slot $count:literal : [$($inner:tt)*]) => {
+        $crate::sequencing::pattern::slot::tuplet(
+            $crate::sequencing::pattern::slot::tuplet_span($count),
+            $crate::pattern!(@slots $($inner)*)
         )
     };
 
-    // Note slot (any other identifier/expression)
-    (@slot $note:expr) => {
+    // Note with scaled velocity (`C4*0.5`)
+    (@slot $note:tt * $scale:tt) => {
+        $crate::sequencing::pattern::slot::note_scaled($note, $scale as f32)
+    };
+
+    // Note slot (identifier, literal or tuple)
+    (@slot $note:tt) => {
         $crate::sequencing::PatternSlot::from($note)
     };
 }
@@ -364,11 +471,30 @@ pub mod slot {
         PatternSlot::Note(NoteSlot::new(midi_note).with_velocity(velocity))
     }
 
+    /// Create a note slot with the default velocity (100) scaled by `factor`
+    ///
+    /// This is what `C4*0.5` means inside `pattern!`. The result is clamped
+    /// to 1..=127 so a scaled note never turns into a silent note-on.
+    pub fn note_scaled(midi_note: u8, factor: f32) -> PatternSlot {
+        let velocity = (NoteSlot::new(midi_note).velocity as f32 * factor).round().clamp(1.0, 127.0);
+        note_vel(midi_note, velocity as u8)
+    }
+
     /// Create a note slot with weight (for swing)
     pub fn note_weight(midi_note: u8, weight: u8) -> PatternSlot {
         PatternSlot::Note(NoteSlot::new(midi_note).with_weight(weight))
     }
 
+    /// Create a note that glides to `target` over its whole slot
+    pub fn slide(midi_note: u8, target: u8) -> PatternSlot {
+        PatternSlot::Note(NoteSlot::new(midi_note).with_slide(target))
+    }
+
+    /// Create a note whose pressure ramps from `start` to `end` over its slot
+    pub fn swell(midi_note: u8, start: u8, end: u8) -> PatternSlot {
+        PatternSlot::Note(NoteSlot::new(midi_note).with_expression(start, end))
+    }
+
     /// Create a rest slot
     pub fn rest() -> PatternSlot {
         PatternSlot::Rest
@@ -378,6 +504,22 @@ pub mod slot {
     pub fn sub(slots: Vec<PatternSlot>) -> PatternSlot {
         PatternSlot::Subdivision(slots)
     }
+
+    /// Spread `slots` evenly over `span` top-level slots
+    pub fn tuplet(span: u8, slots: Vec<PatternSlot>) -> PatternSlot {
+        PatternSlot::Tuplet { span, slots }
+    }
+
+    /// Slots an N-tuplet replaces: the largest power of two below N
+    ///
+    /// 3 → 2 (triplet), 5, 6 or 7 → 4. This is what `N:[...]` spans in `pattern!`.
+    pub const fn tuplet_span(count: u32) -> u8 {
+        let mut span = 1u32;
+        while span * 2 < count {
+            span *= 2;
+        }
+        span as u8
+    }
 }
 
 #[cfg(test)]
@@ -652,6 +794,32 @@ mod tests {
         assert_eq!(p.slots.len(), 4);
     }
 
+    #[test]
+    fn test_uneven_slots_do_not_drift() {
+        // 7 slots in 1920 ticks: 274.28... each
+        let pattern = crate::pattern!(4/4 => [C4, C4, C4, C4, C4, C4, C4]);
+        let seq = pattern.to_sequence(PPQ);
+
+        let starts: Vec<u32> = seq.events.iter().map(|e| e.tick_offset).collect();
+        assert_eq!(starts, vec![0, 274, 548, 822, 1097, 1371, 1645]);
+        let last = seq.events.last().unwrap();
+        assert_eq!(last.tick_offset + last.duration_ticks, 1920, "last slot ends on the bar line");
+    }
+
+    #[test]
+    fn test_nested_quintuplets_fill_their_slot() {
+        // A quintuplet inside each beat at 96 PPQ: 19.2 ticks per note
+        let pattern = crate::pattern!(4/4 => [[C4, C4, C4, C4, C4], [C4, C4, C4, C4, C4], _, _]);
+        let seq = pattern.to_sequence(96);
+
+        let total: u32 = seq.events.iter().map(|e| e.duration_ticks).sum();
+        assert_eq!(total, 192);
+        assert_eq!(seq.events[5].tick_offset, 96, "second quintuplet starts on beat 2");
+        for pair in seq.events.windows(2) {
+            assert_eq!(pair[0].tick_offset + pair[0].duration_ticks, pair[1].tick_offset, "no gaps");
+        }
+    }
+
     // Edge case tests
     #[test]
     fn test_empty_pattern() {
@@ -675,4 +843,105 @@ mod tests {
         ]);
         let _ = p.to_sequence(PPQ); // This should panic
     }
+
+    #[test]
+    fn test_tuple_slots_set_velocity_and_weight() {
+        let pattern = Pattern::four_four(vec![
+            (C4, 64).into(),
+            PatternSlot::Subdivision(vec![(E4, 90, 2).into(), G4.into()]),
+        ]);
+
+        let seq = pattern.to_sequence(PPQ);
+        assert_eq!(seq.events[0].velocity, 64);
+        assert_eq!(seq.events[1].velocity, 90);
+        assert_eq!(seq.events[1].duration_ticks, 640); // 2/3 of a half-bar slot
+        assert_eq!(seq.events[2].velocity, 100);
+    }
+
+    #[test]
+    fn test_macro_velocity_shorthand() {
+        let pattern = crate::pattern!(4/4 => [C4*0.5, (E4, 30), [G4*1.2, (C5, 20, 2)], C4*2]);
+        let velocities: Vec<u8> = pattern.to_sequence(PPQ).events.iter().map(|e| e.velocity).collect();
+
+        assert_eq!(velocities, vec![50, 30, 120, 20, 127]);
+    }
+
+    #[test]
+    fn test_macro_still_accepts_plain_slots() {
+        let pattern = crate::pattern!(4/4 => [C4, _, [E4, G4], _,]);
+        // Well past the default macro recursion limit of 128
+        let long = crate::pattern!(4/4 => [
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+            C4, _, C4*0.5, _, [E4, G4], _, 3:[C4, E4, G4], (C4, 90), C4, _, C4, _, C4, _, C4, _,
+        ]);
+        assert_eq!(long.slots.len(), 256);
+        assert_eq!(long.slots[2], slot::note_scaled(C4, 0.5));
+        assert_eq!(long.slots[6].span(), 2);
+        assert_eq!(
+            pattern.slots,
+            vec![
+                C4.into(),
+                PatternSlot::Rest,
+                PatternSlot::Subdivision(vec![E4.into(), G4.into()]),
+                PatternSlot::Rest,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slide_spans_its_slot() {
+        let pattern = Pattern::four_four(vec![slot::slide(C3, C4), C3.into()]);
+        let seq = pattern.to_sequence(PPQ);
+
+        assert_eq!(seq.events[0].slide, Some(Slide { target: C4, glide_ticks: 960 }));
+        assert_eq!(seq.events[1].slide, None);
+    }
+
+    #[test]
+    fn test_quarter_triplet_spans_two_beats() {
+        let pattern = crate::pattern!(4/4 => [3:[C4, E4, G4], C5, _]);
+        assert_eq!(pattern.slot_count(), 4);
+
+        let seq = pattern.to_sequence(PPQ);
+        let timing: Vec<(u32, u32)> = seq.events.iter().map(|e| (e.tick_offset, e.duration_ticks)).collect();
+        // 3 notes in the time of 2 quarters (960 ticks), then C5 on beat 3
+        assert_eq!(timing, vec![(0, 320), (320, 320), (640, 320), (960, 480)]);
+    }
+
+    #[test]
+    fn test_tuplet_spans_and_nesting() {
+        assert_eq!(slot::tuplet_span(3), 2);
+        assert_eq!(slot::tuplet_span(5), 4);
+        assert_eq!(slot::tuplet_span(7), 4);
+
+        // A quintuplet over the whole bar, with a nested pair in its last note
+        let pattern = crate::pattern!(4/4 => [5:[C4, E4, G4, _, [C5, E5]]]);
+        let starts: Vec<u32> = pattern.to_sequence(PPQ).events.iter().map(|e| e.tick_offset).collect();
+        assert_eq!(starts, vec![0, 384, 768, 1536, 1728]);
+    }
+
+    #[test]
+    fn test_zero_span_tuplet_weighs_one_slot() {
+        let tuplet = slot::tuplet(0, vec![C4.into(), E4.into(), G4.into()]);
+        assert_eq!(tuplet.weight(), tuplet.span());
+
+        // Nested beside a note, it takes half the beat like any other slot
+        let pattern = Pattern::four_four(vec![PatternSlot::Subdivision(vec![tuplet, C5.into()])]);
+        let seq = pattern.to_sequence(PPQ);
+        assert_eq!(seq.events.last().unwrap().tick_offset, 960);
+    }
 }
@@ -1,4 +1,5 @@
 use super::duration::Duration;
+use super::key::Key;
 use super::time_signature::TimeSignature;
 
 /// A single event in a sequence (note or rest)
@@ -15,6 +16,32 @@ pub struct SequenceEvent {
     /// Microtiming offset in ticks (for swing/humanization)
     /// Can be negative to rush, positive to drag
     pub offset_ticks: i32,
+    /// Optional pitch slide while the note sounds (303-style glide)
+    pub slide: Option<Slide>,
+    /// Optional pressure ramp over the note (aftertouch-style expression)
+    pub expression: Option<Expression>,
+}
+
+/// Glide from an event's note toward another pitch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slide {
+    /// MIDI note the pitch glides to
+    pub target: u8,
+    /// Time to reach the target (in ticks from note-on)
+    pub glide_ticks: u32,
+}
+
+/// Pressure ramp across an event's duration, like MIDI aftertouch
+///
+/// The track ramps its pressure (0-127) linearly from `start` at note-on to
+/// `end` at the event's end. Voices read it as `RenderCtx::pressure`, usually
+/// through `PressureNode` into `.modulate()` or `.amplify()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Expression {
+    /// Pressure at note-on (0-127)
+    pub start: u8,
+    /// Pressure at the end of the event (0-127)
+    pub end: u8,
 }
 
 /// A musical sequence with time signature and events
@@ -32,6 +59,7 @@ pub struct Sequence {
 
 impl Sequence {
     /// Create a new sequence builder with default 4/4 time signature
+    #[allow(clippy::new_ret_no_self)] // Public entry point to the builder; renaming would break callers
     pub fn new(ppq: u32) -> SequenceBuilder {
         SequenceBuilder::new(TimeSignature::FOUR_FOUR, ppq)
     }
@@ -53,6 +81,80 @@ impl Sequence {
         })
     }
 
+    /// Delay every off-beat of `base` to give the sequence a swing feel
+    ///
+    /// `amount` is the share of each pair of `base` notes taken by the first:
+    /// 0.5 is straight, ~0.67 a triplet shuffle, 0.75 a dotted feel (clamped
+    /// to 0.5-0.75). Events on the second `base` of each pair move later via
+    /// `offset_ticks` and are shortened by the same amount, so they still end
+    /// where the next note begins. Events off that grid are untouched.
+    pub fn apply_swing(&mut self, amount: f32, base: Duration) {
+        let base_ticks = base.to_ticks(self.ppq);
+        if base_ticks == 0 {
+            return;
+        }
+        let pair_ticks = base_ticks * 2;
+        let delay = ((amount.clamp(0.5, 0.75) - 0.5) * pair_ticks as f32).round() as u32;
+
+        for event in &mut self.events {
+            if event.tick_offset % pair_ticks == base_ticks {
+                event.offset_ticks = event.offset_ticks.saturating_add(delay as i32);
+                event.duration_ticks = event.duration_ticks.saturating_sub(delay).max(1);
+            }
+        }
+    }
+
+    /// Move every note (and slide target) from C into `key`
+    ///
+    /// Transposes the tonic to the key's root, then snaps out-of-scale
+    /// notes to the scale; see `Key::apply`.
+    pub fn apply_key(&mut self, key: Key) {
+        for event in &mut self.events {
+            event.note = event.note.map(|note| key.apply(note));
+            if let Some(slide) = &mut event.slide {
+                slide.target = key.apply(slide.target);
+            }
+        }
+    }
+
+    /// Repeat this sequence, ending every `every_n_bars` bars with `fill`
+    ///
+    /// The result is one cycle: `self` loops until the fill is due (a note
+    /// running into the fill is cut short), then `fill` plays to the end.
+    /// With a one-bar fill and `every_n_bars = 4`, that's three bars of
+    /// groove and a fill - looped by the sequencer like any other sequence.
+    pub fn with_fill(&self, fill: &Sequence, every_n_bars: u32) -> Sequence {
+        let cycle_ticks = (self.bar_ticks() * every_n_bars.max(1)).max(fill.total_ticks);
+        let fill_start = cycle_ticks - fill.total_ticks;
+
+        let mut events = Vec::new();
+        let mut cursor = 0;
+        while cursor < fill_start && self.total_ticks > 0 {
+            for event in &self.events {
+                let start = cursor + event.tick_offset;
+                if start < fill_start {
+                    events.push(SequenceEvent {
+                        tick_offset: start,
+                        duration_ticks: event.duration_ticks.min(fill_start - start),
+                        ..event.clone()
+                    });
+                }
+            }
+            cursor += self.total_ticks;
+        }
+        events.extend(fill.events.iter().map(|event| SequenceEvent {
+            tick_offset: fill_start + event.tick_offset,
+            ..event.clone()
+        }));
+
+        Sequence {
+            time_signature: self.time_signature,
+            ppq: self.ppq,
+            events,
+            total_ticks: cycle_ticks,
+        }
+    }
+
     /// Get the total duration of this sequence in ticks
     pub fn duration_ticks(&self) -> u32 {
         self.total_ticks
@@ -69,7 +171,12 @@ pub struct SequenceBuilder {
     time_signature: TimeSignature,
     ppq: u32,
     events: Vec<SequenceEvent>,
-    cursor_ticks: u32, // Current position in ticks
+    /// Current position as an exact fraction of a whole note
+    ///
+    /// Kept as a `Duration` rather than ticks so tuplets that don't divide
+    /// the PPQ (e.g. 7 in a bar) round each note's position, not its length,
+    /// and never drift.
+    cursor: Duration,
     allow_anacrusis: bool,
     num_bars: u32,
 }
@@ -81,7 +188,10 @@ impl SequenceBuilder {
             time_signature,
             ppq,
             events: Vec::new(),
-            cursor_ticks: 0,
+            cursor: Duration {
+                numerator: 0,
+                denominator: 1,
+            },
             allow_anacrusis: false,
             num_bars: 1,
         }
@@ -101,22 +211,23 @@ impl SequenceBuilder {
 
     /// Add a note with the specified duration
     pub fn note(mut self, duration: Duration) -> Self {
-        let ticks = duration.to_ticks(self.ppq);
+        let start = self.cursor.to_ticks(self.ppq);
+        self.cursor = self.cursor.add(duration);
         self.events.push(SequenceEvent {
-            tick_offset: self.cursor_ticks,
-            duration_ticks: ticks,
+            tick_offset: start,
+            duration_ticks: self.cursor.to_ticks(self.ppq) - start,
             note: Some(60), // Default to middle C
             velocity: 100,
             offset_ticks: 0,
+            slide: None,
+            expression: None,
         });
-        self.cursor_ticks += ticks;
         self
     }
 
     /// Add a rest (silence) with the specified duration
     pub fn rest(mut self, duration: Duration) -> Self {
-        let ticks = duration.to_ticks(self.ppq);
-        self.cursor_ticks += ticks; // Just advance time without adding an event
+        self.cursor = self.cursor.add(duration); // Just advance time without adding an event
         self
     }
 
@@ -149,19 +260,20 @@ impl SequenceBuilder {
     pub fn build(self) -> Result<Sequence, SequenceError> {
         let bar_ticks = self.time_signature.bar_ticks(self.ppq);
         let expected_ticks = bar_ticks * self.num_bars;
+        let cursor_ticks = self.cursor.to_ticks(self.ppq);
 
         // Validate bar boundaries
-        if self.cursor_ticks > expected_ticks {
+        if cursor_ticks > expected_ticks {
             return Err(SequenceError::OverflowsBar {
                 expected: expected_ticks,
-                actual: self.cursor_ticks,
+                actual: cursor_ticks,
             });
         }
 
-        if !self.allow_anacrusis && self.cursor_ticks < expected_ticks {
+        if !self.allow_anacrusis && cursor_ticks < expected_ticks {
             return Err(SequenceError::UnderflowsBar {
                 expected: expected_ticks,
-                actual: self.cursor_ticks,
+                actual: cursor_ticks,
             });
         }
 
@@ -354,4 +466,57 @@ mod tests {
         assert_eq!(seq.events[0].offset_ticks, 20);
         assert_eq!(seq.events[1].offset_ticks, -10);
     }
+
+    #[test]
+    fn test_septuplets_fill_the_bar_exactly() {
+        // 7 in the time of 4 quarters: 1920 / 7 = 274.28... ticks each
+        let septuplet = Duration::QUARTER.tuplet(4, 7);
+        let seq = (0..7)
+            .fold(Sequence::new(PPQ), |builder, _| builder.note(septuplet))
+            .build()
+            .expect("seven septuplet quarters fill a 4/4 bar");
+
+        let starts: Vec<u32> = seq.events.iter().map(|e| e.tick_offset).collect();
+        assert_eq!(starts, vec![0, 274, 548, 822, 1097, 1371, 1645]);
+        assert_eq!(seq.events[6].tick_offset + seq.events[6].duration_ticks, 1920);
+    }
+
+    #[test]
+    fn test_swing_delays_off_beats() {
+        let mut seq = Sequence::new(PPQ)
+            .note(Duration::EIGHTH)
+            .note(Duration::EIGHTH)
+            .note(Duration::SIXTEENTH)
+            .note(Duration::SIXTEENTH)
+            .note(Duration::EIGHTH)
+            .note(Duration::HALF)
+            .build()
+            .unwrap();
+        seq.apply_swing(0.6, Duration::EIGHTH);
+
+        // 8th pairs span 480 ticks: 60% puts the off-beat 48 ticks late
+        let offsets: Vec<i32> = seq.events.iter().map(|e| e.offset_ticks).collect();
+        assert_eq!(offsets, vec![0, 48, 0, 0, 48, 0]);
+        assert_eq!(seq.events[1].duration_ticks, 192);
+        // The second 16th (tick 600) is off the 8th grid
+        assert_eq!(seq.events[3].tick_offset, 600);
+    }
+
+    #[test]
+    fn test_fill_replaces_last_bar_of_cycle() {
+        let groove = Sequence::new(PPQ).note(Duration::HALF).note(Duration::HALF).build().unwrap();
+        let fill = Sequence::new(PPQ)
+            .note(Duration::QUARTER)
+            .note(Duration::QUARTER)
+            .note(Duration::QUARTER)
+            .note(Duration::QUARTER)
+            .build()
+            .unwrap();
+
+        let seq = groove.with_fill(&fill, 4);
+        let starts: Vec<u32> = seq.events.iter().map(|e| e.tick_offset).collect();
+
+        assert_eq!(seq.total_ticks, 4 * 1920);
+        assert_eq!(starts, vec![0, 960, 1920, 2880, 3840, 4800, 5760, 6240, 6720, 7200]);
+    }
 }
@@ -88,7 +88,7 @@ impl TimeSignature {
     pub fn tactus_beats_per_bar(&self) -> u8 {
         // Only valid for compound meters where numerator is divisible by tactus_group
         // For simple meters (like 3/4), just return the numerator
-        if self.numerator % self.tactus_group == 0 {
+        if self.numerator.is_multiple_of(self.tactus_group) {
             self.numerator / self.tactus_group
         } else {
             self.numerator
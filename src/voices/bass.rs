@@ -20,15 +20,41 @@
 
 use crate::graph::{envelope::EnvNode, extensions::NodeExt, filter::FilterNode, oscillator::OscNode};
 
+/// Tunable parameters for `bass_with`. `Default` is the `bass()` preset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BassParams {
+    /// Low-pass cutoff (Hz) - higher lets more growl through
+    pub cutoff: f32,
+    /// Filter resonance (0.0 = none, 1.0 = squelchy)
+    pub resonance: f32,
+    /// Level held after the decay (0.0 = plucky, 1.0 = organ-like)
+    pub sustain: f32,
+}
+
+impl Default for BassParams {
+    fn default() -> Self {
+        Self {
+            cutoff: 500.0,
+            resonance: 0.0,
+            sustain: 0.7,
+        }
+    }
+}
+
 /// Create a bass voice.
 ///
 /// Returns a node graph configured for deep, punchy bass sounds.
 /// Responds to note pitch for playing bass lines.
 pub fn bass() -> impl crate::graph::GraphNode {
+    bass_with(BassParams::default())
+}
+
+/// Create a bass voice with custom parameters.
+pub fn bass_with(params: BassParams) -> impl crate::graph::GraphNode {
     // Square wave for hollow, woody character
     OscNode::square()
         // Snappy envelope for rhythmic bass
-        .amplify(EnvNode::adsr(0.01, 0.1, 0.7, 0.15))
+        .amplify(EnvNode::adsr(0.01, 0.1, params.sustain, 0.15))
         // Low-pass filter keeps it deep
-        .through(FilterNode::lowpass(500.0))
+        .through(FilterNode::lowpass(params.cutoff).with_resonance(params.resonance))
 }
@@ -0,0 +1,49 @@
+//! Brass voice - synth brass section.
+//!
+//! The classic analog "brass" patch: a sawtooth through a low-pass filter
+//! whose cutoff is driven by its own envelope. A real horn gets brighter
+//! as the player blows harder; the filter envelope imitates that swell.
+//!
+//! # How It Works
+//!
+//! 1. Two sawtooths detuned ±6 cents for a section rather than a soloist
+//! 2. Low-pass filter starting dark (~400Hz)
+//! 3. Filter envelope with a SLOW attack (~80ms) opens the cutoff by up to
+//!    2.5kHz, then settles - the "bwaah" swell
+//! 4. Amplitude envelope with a slightly faster attack so the note is
+//!    already sounding when the brightness arrives
+//!
+//! The delay between loudness and brightness is what makes it read as
+//! brass instead of a plain saw lead.
+//!
+//! # Variations
+//!
+//! - Faster filter attack = punchy "stab" brass
+//! - Deeper filter depth = brighter, more aggressive
+//! - Higher resonance = 80s synth-pop brass
+
+use crate::graph::{
+    envelope::EnvNode,
+    extensions::NodeExt,
+    filter::{FilterNode, FilterParam},
+    oscillator::OscNode,
+};
+
+/// Create a brass voice.
+///
+/// Returns a node graph configured for swelling synth-brass sounds.
+/// Responds to note pitch for melodic/harmonic use.
+pub fn brass() -> impl crate::graph::GraphNode {
+    // Slow filter swell: opens +2.5kHz over 80ms, settles at 60%
+    let filter_env = EnvNode::adsr(0.08, 0.3, 0.6, 0.3);
+
+    OscNode::sawtooth()
+        .with_detune(-6.0)
+        .mix(OscNode::sawtooth().with_detune(6.0), 0.5)
+        .through(
+            FilterNode::lowpass(400.0)
+                .with_resonance(0.2)
+                .modulate(filter_env, FilterParam::Cutoff, 2500.0),
+        )
+        .amplify(EnvNode::adsr(0.04, 0.2, 0.8, 0.25))
+}
@@ -6,10 +6,10 @@
 //! # How It Works
 //!
 //! 1. White noise source
-//! 2. Bandpass filter focuses the frequency range (~1.5kHz center)
-//! 3. Quick envelope with slight attack (5ms) for "clap" feel
-//! 4. Short decay, no sustain for punchy character
-//! 5. Boosted gain to cut through the mix
+//! 2. Burst envelope: several sharp hits a few ms apart (the "hands"),
+//!    then a longer tail (the room)
+//! 3. Bandpass filter focuses the frequency range (~1.5kHz center)
+//! 4. Boosted gain to cut through the mix
 //!
 //! The bandpass filter is key - it removes both the low rumble and
 //! ultra-high hiss, leaving the characteristic "crack" frequencies.
@@ -18,26 +18,56 @@
 //!
 //! - Higher bandpass center = thinner, more "crack"
 //! - Lower bandpass center = fuller, more "thwack"
+//! - Wider spacing = looser, more hands
 //! - Longer decay = more reverberant room feel
-//! - Add slight reverb/delay for depth
 
-use crate::graph::{
-    envelope::EnvNode,
-    extensions::NodeExt,
-    filter::FilterNode,
-    oscillator::OscNode,
-};
+use crate::graph::{burst::BurstNode, extensions::NodeExt, filter::FilterNode, oscillator::OscNode};
+
+/// Tunable parameters for `clap_with`. `Default` is the `clap()` preset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClapParams {
+    /// Number of hand hits before the tail
+    pub hits: u8,
+    /// Time between hits (seconds)
+    pub spacing: f32,
+    /// Center of the band-pass (Hz) - higher is brighter
+    pub tone: f32,
+    /// Tail decay time (seconds to fall 60 dB)
+    pub decay: f32,
+}
+
+impl Default for ClapParams {
+    fn default() -> Self {
+        Self {
+            hits: 4,
+            spacing: 0.010,
+            tone: 1500.0,
+            decay: 0.2,
+        }
+    }
+}
 
 /// Create a clap voice.
 ///
 /// Returns a node graph configured for punchy clap sounds.
 /// The note pitch is ignored - claps are unpitched percussion.
 pub fn clap() -> impl crate::graph::GraphNode {
+    clap_with(ClapParams::default())
+}
+
+/// Create a clap voice with custom parameters.
+///
+/// # Example
+/// ```ignore
+/// // Loose, dark clap with a long room
+/// let clap = voices::clap_with(ClapParams { spacing: 0.02, tone: 1000.0, decay: 0.4, ..Default::default() });
+/// ```
+pub fn clap_with(params: ClapParams) -> impl crate::graph::GraphNode {
     OscNode::noise()
+        // Each hit falls 60 dB in one spacing, so the hits stay distinct
+        .amplify(BurstNode::new(params.hits, params.spacing, params.spacing, params.decay))
         // Bandpass focuses on the "crack" frequencies
-        .through(FilterNode::bandpass(1500.0))
-        // Quick envelope with slight attack for clap character
-        .amplify(EnvNode::adsr(0.005, 0.08, 0.0, 0.1))
+        .through(FilterNode::bandpass(params.tone))
         // Boost to cut through mix
         .gain(1.5)
 }
@@ -0,0 +1,33 @@
+//! Cowbell voice.
+//!
+//! The famous TR-808 cowbell: two square waves at a clashing, non-harmonic
+//! interval, bandpassed into a metallic "clank".
+//!
+//! # How It Works
+//!
+//! 1. Two square oscillators at ~540Hz and ~800Hz (fixed, ignores note pitch)
+//! 2. Their ratio (~1.48) is NOT a simple musical interval, so the result
+//!    sounds like one inharmonic metal object instead of a chord
+//! 3. Bandpass filter around 800Hz removes the buzzy extremes
+//! 4. Short attack, medium decay for the clank and ring
+//!
+//! # Variations
+//!
+//! - Wider interval = more clangorous, bell-like
+//! - Longer decay = "more cowbell"
+//! - Higher frequencies = agogo bell
+
+use crate::graph::{envelope::EnvNode, extensions::NodeExt, filter::FilterNode, oscillator::OscNode};
+
+/// Create a cowbell voice.
+///
+/// Returns a node graph configured for metallic 808-style cowbell.
+/// The note pitch is ignored - the cowbell is fixed-pitch percussion.
+pub fn cowbell() -> impl crate::graph::GraphNode {
+    OscNode::square()
+        .with_frequency(540.0)
+        .mix(OscNode::square().with_frequency(800.0), 0.5)
+        .through(FilterNode::bandpass(800.0).with_resonance(0.3))
+        // Clank and ring
+        .amplify(EnvNode::adsr(0.001, 0.25, 0.0, 0.1))
+}
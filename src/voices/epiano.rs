@@ -0,0 +1,52 @@
+//! Electric piano voice - bell-like tine piano.
+//!
+//! The DX7 "E. Piano" is the textbook FM sound: a sine modulating another
+//! sine at audio rate adds a bright, bell-like partial that dies away
+//! quickly, leaving a soft, round tone underneath.
+//!
+//! # How It Works
+//!
+//! Two FM pairs (`OscNode::with_fm`, modulated every sample), as in the
+//! DX7 patch:
+//!
+//! 1. Body: a sine modulated by a sine at the same pitch. The modulator's
+//!    envelope sets the index, so the tone starts reedy and mellows as it
+//!    decays toward a low sustain
+//! 2. Tine: a sine modulated by a sine 14x up with a very fast decay -
+//!    the metallic "ding" of the strike, gone within ~100ms
+//! 3. Gentle low-pass rounds off the highest sidebands
+//!
+//! The key idea is that the modulation index falls FASTER than the
+//! carriers' level, so the bright partials die away before the body.
+//!
+//! # Variations
+//!
+//! - Louder/longer tine = glassier, "DX" character
+//! - Remove tine = soft Rhodes-style tone
+//! - Add chorus or tremolo = classic 70s/80s ballad sound
+
+use crate::graph::{envelope::EnvNode, extensions::NodeExt, filter::FilterNode, oscillator::OscNode};
+
+/// Create an electric piano voice.
+///
+/// Returns a node graph configured for bell-like e-piano sounds.
+/// Responds to note pitch for melodic/harmonic use.
+pub fn epiano() -> impl crate::graph::GraphNode {
+    // Body: 1:1 pair, index falling from ~1.5 to ~0.3
+    let body_modulator = OscNode::sine().amplify(EnvNode::adsr(0.001, 0.8, 0.2, 0.3));
+    let body = OscNode::sine()
+        .with_fm(body_modulator, 1.5)
+        .amplify(EnvNode::adsr(0.002, 1.2, 0.3, 0.4));
+
+    // Tine: 1:14 pair (4569 cents up), index ~0.5 that rings only briefly
+    let tine_modulator = OscNode::sine()
+        .with_detune(4569.0)
+        .amplify(EnvNode::adsr(0.001, 0.08, 0.0, 0.05));
+    let tine = OscNode::sine()
+        .with_fm(tine_modulator, 7.0)
+        .amplify(EnvNode::adsr(0.001, 0.5, 0.0, 0.2));
+
+    body.mix(tine, 0.3)
+        .through(FilterNode::lowpass(6000.0))
+        .gain(0.9)
+}
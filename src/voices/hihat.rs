@@ -17,11 +17,34 @@
 
 use crate::graph::{envelope::EnvNode, extensions::NodeExt, filter::FilterNode, oscillator::OscNode};
 
+/// Tunable parameters for `hihat_with`. `Default` is the `hihat()` preset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HihatParams {
+    /// High-pass cutoff (Hz) - lower is darker and jazzier
+    pub tone: f32,
+    /// Decay time (seconds) - longer heads toward an open hat
+    pub decay: f32,
+}
+
+impl Default for HihatParams {
+    fn default() -> Self {
+        Self {
+            tone: 7000.0,
+            decay: 0.05,
+        }
+    }
+}
+
 /// Create a closed hi-hat voice.
 ///
 /// Returns a node graph configured for tight, bright hi-hat sounds.
 pub fn hihat() -> impl crate::graph::GraphNode {
+    hihat_with(HihatParams::default())
+}
+
+/// Create a closed hi-hat voice with custom parameters.
+pub fn hihat_with(params: HihatParams) -> impl crate::graph::GraphNode {
     OscNode::noise()
-        .amplify(EnvNode::adsr(0.001, 0.05, 0.0, 0.03))
-        .through(FilterNode::highpass(7000.0))
+        .amplify(EnvNode::adsr(0.001, params.decay, 0.0, 0.03))
+        .through(FilterNode::highpass(params.tone))
 }
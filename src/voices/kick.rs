@@ -7,7 +7,7 @@
 //! # How It Works
 //!
 //! 1. Sine oscillator with fixed base frequency (ignores note pitch)
-//! 2. Pitch envelope: starts ~150Hz, drops to ~50Hz over ~80ms
+//! 2. Pitch envelope: starts ~150Hz, drops exponentially to ~50Hz over ~80ms
 //! 3. Amplitude envelope with instant attack, quick decay
 //! 4. Low-pass filter removes any harshness
 //!
@@ -23,28 +23,80 @@
 //! - Add noise burst at start = more acoustic character
 
 use crate::graph::{
+    distortion::DistortionNode,
     envelope::EnvNode,
     extensions::NodeExt,
     filter::FilterNode,
-    oscillator::{OscNode, OscParam},
+    node::GraphNode,
+    oscillator::OscNode,
 };
 
+/// Tunable parameters for `kick_with`. `Default` is the `kick()` preset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KickParams {
+    /// Fundamental the pitch sweep settles to (Hz). The sweep starts at 3x this.
+    pub pitch: f32,
+    /// Amplitude decay time (seconds)
+    pub decay: f32,
+    /// Noise click on the attack (0.0 = none, 1.0 = loud)
+    pub click: f32,
+    /// Soft-clip saturation (0.0 = clean, 1.0 = heavily driven)
+    pub drive: f32,
+}
+
+impl Default for KickParams {
+    fn default() -> Self {
+        Self {
+            pitch: 50.0,
+            decay: 0.15,
+            click: 0.0,
+            drive: 0.0,
+        }
+    }
+}
+
 /// Create a kick drum voice.
 ///
 /// Returns a node graph configured for punchy electronic kick sounds.
 /// The note pitch is ignored - kicks use a fixed frequency with pitch envelope.
 pub fn kick() -> impl crate::graph::GraphNode {
-    // Pitch envelope: fast attack, ~80ms decay to sweep frequency down
-    // Output goes 0 -> 1 (attack) -> 0 (decay), scaled by depth
-    let pitch_env = EnvNode::adsr(0.001, 0.08, 0.0, 0.0);
+    kick_with(KickParams::default())
+}
+
+/// Create a kick drum voice with custom parameters.
+///
+/// # Example
+/// ```ignore
+/// // Lower, longer, with a beater click and some grit
+/// let kick = voices::kick_with(KickParams { pitch: 42.0, decay: 0.4, click: 0.3, drive: 0.5 });
+/// ```
+pub fn kick_with(params: KickParams) -> impl crate::graph::GraphNode {
+    let click = params.click.clamp(0.0, 1.0);
+    let drive = params.drive.clamp(0.0, 1.0);
+
+    // Sine wave with a per-sample pitch drop: 3x the fundamental down to it
+    // over ~80ms, most of the way in the first 20ms
+    let mut kick: Box<dyn GraphNode> = Box::new(
+        OscNode::sine()
+            .with_frequency(params.pitch)
+            .pitch_env(3.0, 1.0, 0.08)
+            .with_curve(5.0)
+            // Amplitude envelope: instant attack, ~150ms decay by default
+            .amplify(EnvNode::adsr(0.001, params.decay, 0.0, 0.05))
+            // Low-pass to keep it smooth
+            .through(FilterNode::lowpass(200.0)),
+    );
 
-    // Sine wave with fixed frequency + pitch modulation
-    // Base: 50Hz (fundamental), Depth: +100Hz (so starts at 150Hz)
-    OscNode::sine()
-        .with_frequency(50.0)
-        .modulate(pitch_env, OscParam::Frequency, 100.0)
-        // Amplitude envelope: instant attack, ~150ms decay
-        .amplify(EnvNode::adsr(0.001, 0.15, 0.0, 0.05))
-        // Low-pass to keep it smooth
-        .through(FilterNode::lowpass(200.0))
+    // Click and drive are only built when used
+    if click > 0.0 {
+        // Beater click: a few milliseconds of bright noise
+        let beater = OscNode::noise()
+            .amplify(EnvNode::adsr(0.0005, 0.005, 0.0, 0.005))
+            .through(FilterNode::highpass(3000.0));
+        kick = Box::new(kick.mix(beater, 0.5 * click));
+    }
+    if drive > 0.0 {
+        kick = Box::new(kick.through(DistortionNode::soft(1.0 + 4.0 * drive, drive)));
+    }
+    kick
 }
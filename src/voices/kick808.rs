@@ -0,0 +1,50 @@
+//! 808 bass drum voice.
+//!
+//! The deep, booming kick from the Roland TR-808: a sine that starts a
+//! little sharp, slides down to a low fundamental, and rings for a long
+//! time. Where `kick` is a short punch, the 808 is as much a bass note as
+//! a drum.
+//!
+//! # How It Works
+//!
+//! 1. Sine oscillator with fixed base frequency (ignores note pitch)
+//! 2. Phase reset on every hit, so each one starts at the zero crossing
+//!    (no random click, identical attack every time)
+//! 3. Slow pitch envelope: ~90Hz sliding to ~45Hz over ~150ms
+//! 4. Long amplitude decay (~900ms) for the characteristic "boom"
+//!
+//! Resetting the phase matters more here than on a short kick: the body
+//! is a near-pure sine, so any difference in starting phase is audible
+//! as a different click on each hit.
+//!
+//! # Variations
+//!
+//! - Shorter amplitude decay = tighter, "punchy 808"
+//! - Deeper pitch sweep = more pronounced "drop"
+//! - Add distortion = modern trap 808
+
+use crate::dsp::oscillator::PhaseMode;
+use crate::graph::{
+    envelope::EnvNode,
+    extensions::NodeExt,
+    filter::FilterNode,
+    oscillator::{OscNode, OscParam},
+};
+
+/// Create an 808-style bass drum voice.
+///
+/// Returns a node graph configured for long, deep kicks.
+/// The note pitch is ignored - the 808 uses a fixed frequency with pitch envelope.
+pub fn kick808() -> impl crate::graph::GraphNode {
+    // Slow pitch envelope for the gradual 808 "drop"
+    let pitch_env = EnvNode::adsr(0.001, 0.15, 0.0, 0.0);
+
+    // Base: 45Hz, Depth: +45Hz (so starts at 90Hz)
+    OscNode::sine()
+        .with_frequency(45.0)
+        .with_phase(PhaseMode::Reset)
+        .modulate(pitch_env, OscParam::Frequency, 45.0)
+        // Long decay: the boom
+        .amplify(EnvNode::adsr(0.001, 0.9, 0.0, 0.2))
+        .through(FilterNode::lowpass(150.0))
+}
@@ -20,15 +20,41 @@
 
 use crate::graph::{envelope::EnvNode, extensions::NodeExt, filter::FilterNode, oscillator::OscNode};
 
+/// Tunable parameters for `lead_with`. `Default` is the `lead()` preset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LeadParams {
+    /// Low-pass cutoff (Hz) - brightness
+    pub cutoff: f32,
+    /// Attack time (seconds) - raise for swelling, violin-like leads
+    pub attack: f32,
+    /// Release time (seconds) - how long notes ring after key-up
+    pub release: f32,
+}
+
+impl Default for LeadParams {
+    fn default() -> Self {
+        Self {
+            cutoff: 2500.0,
+            attack: 0.01,
+            release: 0.2,
+        }
+    }
+}
+
 /// Create a lead voice.
 ///
 /// Returns a node graph configured for bright, singing lead sounds.
 /// Responds to note pitch for playing melodies.
 pub fn lead() -> impl crate::graph::GraphNode {
+    lead_with(LeadParams::default())
+}
+
+/// Create a lead voice with custom parameters.
+pub fn lead_with(params: LeadParams) -> impl crate::graph::GraphNode {
     // Sawtooth for bright, harmonically rich sound
     OscNode::sawtooth()
         // Envelope with sustain for held notes
-        .amplify(EnvNode::adsr(0.01, 0.1, 0.6, 0.2))
+        .amplify(EnvNode::adsr(params.attack, 0.1, 0.6, params.release))
         // Filter to tame brightness while keeping presence
-        .through(FilterNode::lowpass(2500.0))
+        .through(FilterNode::lowpass(params.cutoff))
 }
@@ -15,36 +15,118 @@
 //! let openhat = voices::openhat();
 //! let clap = voices::clap();
 //! let tom = voices::tom();
+//! let boom = voices::kick808();
+//! let rim = voices::rimshot();
+//! let bell = voices::cowbell();
+//! let shaker = voices::shaker();
 //!
 //! // Melodic
 //! let bass = voices::bass();
 //! let lead = voices::lead();
 //! let pad = voices::pad();
 //! let pluck = voices::pluck();
+//! let organ = voices::organ();
+//! let epiano = voices::epiano();
+//! let strings = voices::strings();
+//! let brass = voices::brass();
+//!
+//! // Tweaked presets: override only what you need
+//! let boomy = voices::kick_with(voices::KickParams { decay: 0.4, ..Default::default() });
 //! ```
 
 mod bass;
+mod brass;
 mod clap;
+mod cowbell;
 mod crash;
+mod epiano;
 mod hihat;
 mod kick;
+mod kick808;
 mod lead;
 mod openhat;
+mod organ;
 mod pad;
 mod pluck;
 mod ride;
+mod rimshot;
+mod shaker;
 mod snare;
+mod strings;
 mod tom;
 
-pub use bass::bass;
-pub use clap::clap;
+pub use bass::{bass, bass_with, BassParams};
+pub use brass::brass;
+pub use clap::{clap, clap_with, ClapParams};
+pub use cowbell::cowbell;
 pub use crash::crash;
-pub use hihat::hihat;
-pub use kick::kick;
-pub use lead::lead;
+pub use epiano::epiano;
+pub use hihat::{hihat, hihat_with, HihatParams};
+pub use kick::{kick, kick_with, KickParams};
+pub use kick808::kick808;
+pub use lead::{lead, lead_with, LeadParams};
 pub use openhat::openhat;
+pub use organ::organ;
 pub use pad::pad;
 pub use pluck::pluck;
 pub use ride::ride;
-pub use snare::snare;
+pub use rimshot::rimshot;
+pub use shaker::shaker;
+pub use snare::{snare, snare_with, SnareParams};
+pub use strings::strings;
 pub use tom::tom;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::analysis::snapshot::{assert_snapshot, render_note, Fingerprint};
+    use crate::graph::GraphNode;
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    /// Render a short note and compare against `src/voices/snapshots/<name>.snap`
+    fn check(name: &str, voice: impl GraphNode, note: u8) {
+        let audio = render_note(voice, SAMPLE_RATE, note, 0.3, 0.5);
+        let path = format!("{}/src/voices/snapshots/{name}.snap", env!("CARGO_MANIFEST_DIR"));
+        assert_snapshot(path, &Fingerprint::of(&audio, SAMPLE_RATE));
+    }
+
+    #[test]
+    fn drum_snapshots() {
+        check("kick", kick(), 36);
+        check("snare", snare(), 38);
+        check("hihat", hihat(), 42);
+        check("openhat", openhat(), 46);
+        check("clap", clap(), 39);
+        check("tom", tom(), 45);
+        check("crash", crash(), 49);
+        check("ride", ride(), 51);
+        check("kick808", kick808(), 35);
+        check("rimshot", rimshot(), 37);
+        check("cowbell", cowbell(), 56);
+        check("shaker", shaker(), 70);
+    }
+
+    #[test]
+    fn melodic_snapshots() {
+        check("bass", bass(), 36);
+        check("lead", lead(), 60);
+        check("pad", pad(), 60);
+        check("pluck", pluck(), 60);
+        check("organ", organ(), 60);
+        check("epiano", epiano(), 60);
+        check("strings", strings(), 60);
+        check("brass", brass(), 60);
+    }
+
+    #[test]
+    fn params_shape_the_preset() {
+        let fingerprint = |voice| Fingerprint::of(&render_note(voice, SAMPLE_RATE, 36, 0.3, 0.5), SAMPLE_RATE);
+        let plain = fingerprint(kick_with(KickParams::default()));
+        let long = fingerprint(kick_with(KickParams { decay: 0.5, ..Default::default() }));
+        let driven = fingerprint(kick_with(KickParams { drive: 1.0, ..Default::default() }));
+
+        assert!(long.rms_db > plain.rms_db + 3.0, "decay {} vs {}", long.rms_db, plain.rms_db);
+        assert!(driven.rms_db > plain.rms_db, "drive {} vs {}", driven.rms_db, plain.rms_db);
+    }
+}
@@ -0,0 +1,49 @@
+//! Organ voice - drawbar tonewheel organ.
+//!
+//! A Hammond-style organ built by ADDITIVE synthesis: instead of filtering
+//! a bright waveform down (subtractive), we stack pure sines at chosen
+//! harmonics and set each one's level, just like pulling drawbars.
+//!
+//! # How It Works
+//!
+//! 1. Four sine "drawbars", each a fixed pitch offset from the note:
+//!
+//!    | Drawbar | Offset      | Harmonic |
+//!    |---------|-------------|----------|
+//!    | 16'     | -1200 cents | 0.5      |
+//!    | 8'      | 0           | 1        |
+//!    | 4'      | +1200 cents | 2        |
+//!    | 2 2/3'  | +1902 cents | ~3       |
+//!
+//! 2. Nested mixes set the levels (8' loudest, 2 2/3' quietest)
+//! 3. Organ envelope: instant on, full sustain, instant off - a tonewheel
+//!    organ is a switch, not a struck string
+//!
+//! 1902 cents is the equal-tempered fifth above the octave; a real
+//! tonewheel is also slightly off the pure 3rd harmonic, which is part of
+//! its sound.
+//!
+//! # Variations
+//!
+//! - More 4' and 2 2/3' = brighter, "gospel" registration
+//! - Only 16' and 8' = soft, flute-like
+//! - `.through(RotaryNode::new())` = the classic Leslie cabinet
+
+use crate::graph::{envelope::EnvNode, extensions::NodeExt, oscillator::OscNode};
+
+/// Create a drawbar organ voice.
+///
+/// Returns a node graph configured for sustained organ tones.
+/// Responds to note pitch for melodic/harmonic use.
+pub fn organ() -> impl crate::graph::GraphNode {
+    // 16' + 8' (fundamental-heavy), then 4', then a little 2 2/3'
+    let low = OscNode::sine().with_detune(-1200.0).mix(OscNode::sine(), 0.6);
+    let drawbars = low
+        .mix(OscNode::sine().with_detune(1200.0), 0.3)
+        .mix(OscNode::sine().with_detune(1902.0), 0.15);
+
+    drawbars
+        // Switch-like envelope (tiny ramps only to avoid clicks)
+        .amplify(EnvNode::adsr(0.005, 0.01, 1.0, 0.02))
+        .gain(0.8)
+}
@@ -0,0 +1,40 @@
+//! Rimshot voice.
+//!
+//! The stick hitting the rim and head of the snare at once: a very short,
+//! high "tick" with a woody pitched ring. Common in bossa nova, reggae
+//! and as a lighter alternative to the snare on the backbeat.
+//!
+//! # How It Works
+//!
+//! 1. Triangle oscillator at ~1.7kHz for the pitched "ring"
+//! 2. Mixed with a little high-passed noise for the stick click
+//! 3. Extremely short envelope (~25ms) - the rimshot is all transient
+//! 4. Bandpass filter keeps it focused and woody
+//!
+//! The TR-808 rimshot is two resonant bandpass filters (~450Hz and ~1.7kHz)
+//! pinged by a pulse; a triangle at the upper resonance gets most of the way.
+//!
+//! # Variations
+//!
+//! - Lower oscillator (~500Hz) = "cross-stick" side stick
+//! - Longer decay = more ring, closer to a woodblock
+//! - More noise = harder, snappier hit
+
+use crate::graph::{envelope::EnvNode, extensions::NodeExt, filter::FilterNode, oscillator::OscNode};
+
+/// Create a rimshot voice.
+///
+/// Returns a node graph configured for short, woody rim clicks.
+/// The note pitch is ignored - rimshots are fixed-pitch percussion.
+pub fn rimshot() -> impl crate::graph::GraphNode {
+    // Pitched ring plus a touch of stick noise
+    let click = OscNode::noise().through(FilterNode::highpass(5000.0));
+
+    OscNode::triangle()
+        .with_frequency(1700.0)
+        .mix(click, 0.3)
+        .through(FilterNode::bandpass(1700.0))
+        // All transient: ~25ms decay
+        .amplify(EnvNode::adsr(0.0005, 0.025, 0.0, 0.02))
+        .gain(2.0)
+}
@@ -0,0 +1,30 @@
+//! Shaker voice.
+//!
+//! Seeds or beads rattling in a shell: a soft, bright swish with a
+//! gentle attack. Shakers fill in the 16th notes between hi-hats.
+//!
+//! # How It Works
+//!
+//! 1. White noise for the "many tiny impacts" texture
+//! 2. Slow-ish attack (~15ms) - the beads take time to hit the shell,
+//!    which is what separates a shaker from a hi-hat
+//! 3. Short decay with no sustain
+//! 4. High-pass filter keeps only the bright rattle
+//!
+//! # Variations
+//!
+//! - Longer attack = lazier, "swung" shake
+//! - Lower filter = maraca / seed pod
+//! - Alternate velocities = realistic back-and-forth motion
+
+use crate::graph::{envelope::EnvNode, extensions::NodeExt, filter::FilterNode, oscillator::OscNode};
+
+/// Create a shaker voice.
+///
+/// Returns a node graph configured for soft, bright shaker sounds.
+pub fn shaker() -> impl crate::graph::GraphNode {
+    OscNode::noise()
+        .amplify(EnvNode::adsr(0.015, 0.06, 0.0, 0.04))
+        .through(FilterNode::highpass(6000.0))
+        .gain(0.7)
+}
@@ -20,15 +20,41 @@
 
 use crate::graph::{envelope::EnvNode, extensions::NodeExt, filter::FilterNode, oscillator::OscNode};
 
+/// Tunable parameters for `snare_with`. `Default` is the `snare()` preset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnareParams {
+    /// Center of the rattle's band-pass (Hz) - higher is brighter
+    pub tone: f32,
+    /// Rattle decay time (seconds)
+    pub decay: f32,
+    /// Rattle vs body balance (0.0 = all body, 1.0 = all rattle)
+    pub snappy: f32,
+}
+
+impl Default for SnareParams {
+    fn default() -> Self {
+        Self {
+            tone: 3000.0,
+            decay: 0.12,
+            snappy: 0.7,
+        }
+    }
+}
+
 /// Create a snare drum voice.
 ///
 /// Returns a node graph configured for snappy electronic snare sounds.
 /// Combines tonal body with noise for the characteristic snare rattle.
 pub fn snare() -> impl crate::graph::GraphNode {
+    snare_with(SnareParams::default())
+}
+
+/// Create a snare drum voice with custom parameters.
+pub fn snare_with(params: SnareParams) -> impl crate::graph::GraphNode {
     // Noise for the snare rattle, band-pass filtered
     let rattle = OscNode::noise()
-        .amplify(EnvNode::adsr(0.001, 0.12, 0.0, 0.08))
-        .through(FilterNode::bandpass(3000.0));
+        .amplify(EnvNode::adsr(0.001, params.decay, 0.0, 0.08))
+        .through(FilterNode::bandpass(params.tone));
 
     // Triangle for the tonal body
     let body = OscNode::triangle()
@@ -36,5 +62,5 @@ pub fn snare() -> impl crate::graph::GraphNode {
         .through(FilterNode::lowpass(400.0));
 
     // Mix body and rattle (more rattle than body for snare character)
-    body.mix(rattle, 0.7)
+    body.mix(rattle, params.snappy.clamp(0.0, 1.0))
 }
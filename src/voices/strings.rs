@@ -0,0 +1,48 @@
+//! Strings voice - string ensemble.
+//!
+//! A synth string section in the style of the Solina and the Juno string
+//! patches: several slightly detuned sawtooths, a slow bow-like attack,
+//! and chorus to turn a few oscillators into "many players".
+//!
+//! # How It Works
+//!
+//! 1. Three sawtooth oscillators at -10, 0 and +10 cents. No two players in
+//!    a section are perfectly in tune; the beating between them is the
+//!    ensemble sound
+//! 2. Slow attack (~400ms) and long release like a bow on a string
+//! 3. Low-pass filter removes the raw saw fizz
+//! 4. Chorus adds movement and width
+//!
+//! Compare with `pad`: same ingredients, but more voices, more detune
+//! and chorus - the difference between "a synth" and "a section".
+//!
+//! # Variations
+//!
+//! - Faster attack (50ms) = staccato / pizzicato-ish
+//! - More detune = lusher, more "vintage"
+//! - Higher cutoff = brighter violins; lower = cellos
+
+use crate::graph::{
+    chorus::ChorusNode,
+    envelope::EnvNode,
+    extensions::NodeExt,
+    filter::FilterNode,
+    oscillator::OscNode,
+};
+
+/// Create a string ensemble voice.
+///
+/// Returns a node graph configured for lush, bowed string sounds.
+/// Responds to note pitch for melodic/harmonic use.
+pub fn strings() -> impl crate::graph::GraphNode {
+    // Three slightly out-of-tune "players"
+    let section = OscNode::sawtooth()
+        .with_detune(-10.0)
+        .mix(OscNode::sawtooth().with_detune(10.0), 0.5)
+        .mix(OscNode::sawtooth(), 0.33);
+
+    section
+        .amplify(EnvNode::adsr(0.4, 0.2, 0.85, 0.8)) // Bowed: slow in, slow out
+        .through(FilterNode::lowpass(3500.0))
+        .through(ChorusNode::new(0.6, 3.0, 0.5))
+}
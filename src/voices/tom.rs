@@ -6,7 +6,7 @@
 //! # How It Works
 //!
 //! 1. Sine oscillator with fixed frequency (ignores note pitch)
-//! 2. Pitch envelope: starts ~350Hz, drops to ~150Hz over ~60ms
+//! 2. Pitch envelope: starts ~350Hz, drops exponentially to ~150Hz over ~60ms
 //! 3. Amplitude envelope with instant attack, medium decay
 //! 4. Low-pass filter smooths the sound
 //!
@@ -24,7 +24,7 @@ use crate::graph::{
     envelope::EnvNode,
     extensions::NodeExt,
     filter::FilterNode,
-    oscillator::{OscNode, OscParam},
+    oscillator::OscNode,
 };
 
 /// Create a tom drum voice.
@@ -32,14 +32,12 @@ use crate::graph::{
 /// Returns a node graph configured for pitched tom sounds.
 /// The note pitch is ignored - toms use a fixed frequency with pitch envelope.
 pub fn tom() -> impl crate::graph::GraphNode {
-    // Pitch envelope: fast attack, ~60ms decay for pitch sweep
-    let pitch_env = EnvNode::adsr(0.001, 0.06, 0.0, 0.0);
-
-    // Sine wave with fixed frequency + pitch modulation
-    // Base: 150Hz, Depth: +200Hz (so starts at 350Hz)
+    // Sine wave with a per-sample pitch drop
+    // Base: 150Hz, starting 7/3 higher (350Hz)
     OscNode::sine()
         .with_frequency(150.0)
-        .modulate(pitch_env, OscParam::Frequency, 200.0)
+        .pitch_env(350.0 / 150.0, 1.0, 0.06)
+        .with_curve(4.0)
         // Amplitude envelope: instant attack, ~120ms decay
         .amplify(EnvNode::adsr(0.001, 0.12, 0.0, 0.05))
         // Low-pass to keep it smooth